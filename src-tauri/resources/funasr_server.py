@@ -58,11 +58,20 @@ import funasr.tokenizer.sentencepiece_tokenizer  # noqa: F401, E402
 from hf_cache_utils import MODEL_REPOS
 
 VAD_MAX_SEGMENT_MS = 30000
+# SenseVoiceSmall + FSMN-VAD 的显存估算（GB），只有一档
+SENSEVOICE_CUDA_VRAM_TIERS = [("float32", 1.2)]
 
 
 class FunASRServer(BaseASRServer):
+    # SenseVoice 的 use_itn 同时控制数字规整和标点，关掉后模型不再输出标点
+    supports_itn = True
+    supports_reload = True
+    supported_languages = ("zh", "en", "yue", "ja", "ko")
+
     def __init__(self):
         super().__init__(engine="sensevoice", logger=logger)
+        if self.device == "cuda" and self._pick_cuda_tier(SENSEVOICE_CUDA_VRAM_TIERS) is None:
+            self.device = "cpu"
         self.asr_model = None
         self._torch = None
         self._rich_postprocess = None
@@ -71,6 +80,15 @@ class FunASRServer(BaseASRServer):
     def _get_model_repos(self) -> list:
         return MODEL_REPOS
 
+    def _release_model(self) -> None:
+        self.asr_model = None
+
+    def _effective_cpu_threads(self):
+        # 模型加载后 AutoModel 已调用 torch.set_num_threads，以 torch 为准
+        if self._torch is not None:
+            return self._torch.get_num_threads()
+        return super()._effective_cpu_threads()
+
     def _load_asr_model(self):
         """加载ASR模型（SenseVoiceSmall + fsmn-vad）"""
         try:
@@ -85,6 +103,8 @@ class FunASRServer(BaseASRServer):
                     hub="hf",
                     disable_update=True,
                     device=self.device,
+                    # AutoModel 默认 ncpu=4 会覆盖 OMP_NUM_THREADS，用户指定时才传入
+                    **({"ncpu": self.cpu_threads} if self.cpu_threads else {}),
                 )
             import torch
             from funasr.utils.postprocess_utils import rich_transcription_postprocess
@@ -106,7 +126,7 @@ class FunASRServer(BaseASRServer):
     def initialize(self):
         """初始化 FunASR 模型"""
         if self.initialized:
-            return {"success": True, "message": "模型已初始化"}
+            return {"success": True, "message": "模型已初始化", "model_loaded": True}
 
         try:
             import time
@@ -144,6 +164,9 @@ class FunASRServer(BaseASRServer):
             logger.error(traceback.format_exc())
             return {"success": False, "error": error_msg, "type": "init_error", "engine": self.engine}
 
+    def _model_emits_punctuation(self):
+        return self.use_itn
+
     def _warmup_inference(self) -> None:
         """首次推理会懒加载 CUDA kernel / 计算图，冷启动 2-4s。
         加载后立刻用一段 1s 低幅噪声跑一次 dummy generate，把这笔成本摊到
@@ -250,8 +273,8 @@ class FunASRServer(BaseASRServer):
             generate_kwargs = dict(
                 input=audio_input,
                 cache={},
-                language="auto",
-                use_itn=True,
+                language=self.language or "auto",
+                use_itn=self.use_itn,
                 batch_size_s=60,
                 merge_vad=True,
                 merge_length_s=15,
@@ -259,8 +282,11 @@ class FunASRServer(BaseASRServer):
             if hotword_str:
                 generate_kwargs["hotword"] = hotword_str
 
-            with self.stdout_suppressor.suppress(), self._torch.inference_mode():
-                asr_result = self.asr_model.generate(**generate_kwargs)
+            progress_total = max(duration, 0.0)
+            with self.progress_heartbeat(progress_total, "识别中"):
+                with self.stdout_suppressor.suppress(), self._torch.inference_mode():
+                    asr_result = self.asr_model.generate(**generate_kwargs)
+            self.emit_progress(progress_total, progress_total)
             asr_elapsed = time.time() - asr_start
 
             # 提取识别文本并进行富文本后处理（去除 <|zh|><|NEUTRAL|> 等标签）
@@ -276,6 +302,7 @@ class FunASRServer(BaseASRServer):
                     final_text = str(asr_result[0])
             else:
                 final_text = str(asr_result)
+            final_text = self._apply_output_formatting(final_text)
 
             logger.info(f"ASR识别完成，耗时: {asr_elapsed:.2f}秒，文本: {final_text[:100]}...")
 
@@ -371,6 +398,7 @@ class FunASRServer(BaseASRServer):
                 "model_loaded": model_loaded,
                 "models": models,
                 **device_info,
+                **self._formatting_info(),
             }
         except ImportError as e:
             error_msg = f"FunASR 依赖加载失败: {e}"
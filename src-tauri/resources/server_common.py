@@ -8,6 +8,7 @@ import contextlib
 import io
 import logging
 import os
+import re
 import struct
 import sys
 import tempfile
@@ -199,8 +200,25 @@ import signal
 import traceback
 
 
+# 服务端协议版本，初始化握手时上报给 Rust 端（get_server_version）。
+# 2：支持 request_id 回显和内存音频输入。修改协议时递增。
+SERVER_VERSION = "2"
+
 CLEANUP_EVERY_N = 20
 
+# 长推理期间补发进度行的间隔，须明显短于宿主等待响应的超时（60 秒）
+PROGRESS_HEARTBEAT_INTERVAL_SECS = 10.0
+
+# 关闭标点时去掉的字符。ASCII 的 . , 夹在数字中间时（3.14、1,000）保留。
+_CJK_PUNCTUATION_RE = re.compile(r"[，。、；：？！…「」『』（）《》〈〉“”‘’]")
+_ASCII_PUNCTUATION_RE = re.compile(r"(?<!\d)[.,]|[.,](?!\d)|[;:?!\"()]")
+
+
+def strip_punctuation(text: str) -> str:
+    text = _CJK_PUNCTUATION_RE.sub("", text)
+    text = _ASCII_PUNCTUATION_RE.sub("", text)
+    return re.sub(r"\s{2,}", " ", text).strip()
+
 
 class BaseASRServer:
     """Base class for ASR server implementations.
@@ -214,8 +232,22 @@ class BaseASRServer:
       - _get_model_repos() -> list[str]  (repo IDs to check before init)
     """
 
+    # 模型是否支持关闭逆文本正则化（ITN）
+    supports_itn = False
+    # 说话人分离：子类接入相应模型后置 True，并在转写结果的 segments 里带 speaker
+    supports_diarization = False
+    # 能否在进程内释放并重新加载模型（reload_model 命令），子类实现 _release_model 后置 True
+    supports_reload = False
+    # 可以通过 configure 指定的识别语言；为空表示只支持自动检测
+    supported_languages: tuple = ()
+
     def __init__(self, engine: str, logger: logging.Logger) -> None:
         self.engine = engine
+        self.use_itn = True
+        self.use_punctuation = True
+        self.use_diarization = False
+        # 识别语言提示，None 为自动检测
+        self.language = None
         self.logger = logger
         self.initialized = False
         self.running = True
@@ -223,6 +255,10 @@ class BaseASRServer:
         self.total_audio_duration = 0.0
         self.device = self._detect_device()
         self.stdout_suppressor = StdoutSuppressor()
+        # 进度行需要绕过 StdoutSuppressor 写到真正的协议通道
+        self._protocol_stdout = sys.stdout
+        self._progress_lock = threading.Lock()
+        self.active_request_id = None
 
         signal.signal(signal.SIGTERM, self._signal_handler)
         signal.signal(signal.SIGINT, self._signal_handler)
@@ -232,6 +268,43 @@ class BaseASRServer:
     # Shared helpers
     # ------------------------------------------------------------------
 
+    def emit_progress(self, current: float, total: float, message: Optional[str] = None) -> None:
+        """输出转写中间进度，Rust 端转发为 transcription-progress 事件。
+
+        协议：{"type": "progress", "request_id": N, "current": x, "total": y, "message": "..."}
+        不带 success 字段，宿主不会把它当成最终响应。
+        """
+        payload = {"type": "progress", "current": current, "total": total}
+        if self.active_request_id is not None:
+            payload["request_id"] = self.active_request_id
+        if message:
+            payload["message"] = message
+        try:
+            with self._progress_lock:
+                self._protocol_stdout.write(json.dumps(payload, ensure_ascii=False) + "\n")
+                self._protocol_stdout.flush()
+        except Exception as e:
+            self.logger.debug(f"写入进度行失败: {e}")
+
+    @contextlib.contextmanager
+    def progress_heartbeat(self, total: float, message: Optional[str] = None):
+        """单次推理内部没有分段回调时，先报一次起点，之后每隔
+        PROGRESS_HEARTBEAT_INTERVAL_SECS 补发同一进度，宿主据此重置等待超时。"""
+        stop = threading.Event()
+
+        def beat():
+            while not stop.wait(PROGRESS_HEARTBEAT_INTERVAL_SECS):
+                self.emit_progress(0.0, total, message)
+
+        self.emit_progress(0.0, total, message)
+        thread = threading.Thread(target=beat, name="progress-heartbeat", daemon=True)
+        thread.start()
+        try:
+            yield
+        finally:
+            stop.set()
+            thread.join()
+
     def _detect_device(self) -> str:
         """Detect inference device. Override for engine-specific detection."""
         try:
@@ -252,10 +325,31 @@ class BaseASRServer:
             return "cpu"
 
     def _setup_runtime_environment(self) -> None:
+        # 宿主通过 LIGHT_WHISPER_THREADS 传入用户指定的 CPU 推理线程数，
+        # LIGHT_WHISPER_MAX_VRAM_GB 传入显存预算
+        self.cpu_threads = None
+        self.max_vram_gb = None
         try:
             os.environ["HF_HUB_OFFLINE"] = "1"
             cpu_count = os.cpu_count() or 4
             thread_count = max(4, cpu_count - 2)
+            override = os.environ.get("LIGHT_WHISPER_THREADS", "").strip()
+            if override:
+                try:
+                    requested = int(override)
+                    if requested > 0:
+                        self.cpu_threads = requested
+                        thread_count = requested
+                except ValueError:
+                    self.logger.warning(f"忽略无效的 LIGHT_WHISPER_THREADS: {override}")
+            vram_override = os.environ.get("LIGHT_WHISPER_MAX_VRAM_GB", "").strip()
+            if vram_override:
+                try:
+                    budget = float(vram_override)
+                    if budget > 0:
+                        self.max_vram_gb = budget
+                except ValueError:
+                    self.logger.warning(f"忽略无效的 LIGHT_WHISPER_MAX_VRAM_GB: {vram_override}")
             os.environ["OMP_NUM_THREADS"] = str(thread_count)
             self.logger.info(
                 f"运行时环境变量设置完成，HF_HUB_OFFLINE=1, OMP_NUM_THREADS={thread_count} (CPU核心数: {cpu_count})"
@@ -263,6 +357,80 @@ class BaseASRServer:
         except Exception as e:
             self.logger.warning(f"环境设置失败: {e}")
 
+    def configure(self, command: dict) -> dict:
+        """运行时配置：日志级别、输出格式（ITN / 标点）与识别语言，只影响当前进程。
+
+        未出现的字段保持当前值；language 为 null 时恢复自动检测。
+        """
+        result = {"success": True}
+        if command.get("log_level") is not None:
+            level_name = str(command.get("log_level")).upper()
+            level = logging.getLevelName(level_name)
+            if not isinstance(level, int):
+                return {"success": False, "error": f"未知的日志级别: {command.get('log_level')}"}
+            # basicConfig 的 handler 挂在 root logger 上，两处都要调
+            logging.getLogger().setLevel(level)
+            self.logger.setLevel(level)
+            self.logger.info(f"日志级别已切换为 {level_name}")
+            result["log_level"] = level_name.lower()
+        if command.get("itn") is not None:
+            if self.supports_itn:
+                self.use_itn = bool(command["itn"])
+            else:
+                self.logger.info(f"{self.engine} 不支持切换 ITN，忽略")
+        if command.get("punctuation") is not None:
+            self.use_punctuation = bool(command["punctuation"])
+        if command.get("diarization") is not None:
+            if self.supports_diarization:
+                self.use_diarization = bool(command["diarization"])
+            else:
+                self.logger.info(f"{self.engine} 不支持说话人分离，忽略")
+        if "language" in command:
+            language = command.get("language") or None
+            if language is not None and language not in self.supported_languages:
+                return {"success": False, "error": f"{self.engine} 不支持语言: {language}"}
+            self.language = language
+            self.logger.info(f"识别语言: {self.language or '自动检测'}")
+        if "itn" in command or "punctuation" in command or "diarization" in command:
+            self.logger.info(f"输出格式: {self._formatting_info()}")
+        result.update(self._formatting_info())
+        result["language"] = self.language
+        return result
+
+    def _model_emits_punctuation(self) -> bool:
+        """模型在当前设置下是否输出标点，子类按模型行为覆盖。"""
+        return True
+
+    def _formatting_info(self) -> dict:
+        """实际生效的输出格式；不支持切换 ITN 的引擎 itn 为 None。"""
+        return {
+            "itn": self.use_itn if self.supports_itn else None,
+            "punctuation": self.use_punctuation and self._model_emits_punctuation(),
+            "diarization": self.use_diarization if self.supports_diarization else None,
+        }
+
+    def _strip_speakers_unless_enabled(self, result) -> None:
+        """未开启说话人分离时去掉分段里的 speaker，避免子类漏判时把标签带出去。"""
+        if self.use_diarization or not isinstance(result, dict):
+            return
+        for segment in result.get("segments") or []:
+            if isinstance(segment, dict):
+                segment.pop("speaker", None)
+
+    def _apply_output_formatting(self, text: str) -> str:
+        if not self.use_punctuation:
+            return strip_punctuation(text)
+        return text
+
+    def _effective_cpu_threads(self) -> Optional[int]:
+        """当前推理实际使用的 CPU 线程数，子类可按推理库覆盖。"""
+        if self.cpu_threads:
+            return self.cpu_threads
+        try:
+            return int(os.environ.get("OMP_NUM_THREADS", ""))
+        except ValueError:
+            return None
+
     def _signal_handler(self, signum, frame) -> None:
         self.logger.info(f"收到信号 {signum}，准备退出...")
         self.running = False
@@ -290,9 +458,28 @@ class BaseASRServer:
             threading.Thread(target=self._cleanup_memory, daemon=True).start()
             self.logger.info(f"已完成 {self.transcription_count} 次转录，后台执行内存清理")
 
+    def _pick_cuda_tier(self, tiers: list) -> Optional[str]:
+        """按显存预算从 [(compute_type, vram_gb), ...]（从大到小）里挑一档。
+
+        返回 None 表示预算内放不下任何 CUDA 档位，调用方应改用 CPU。
+        档位表与 Rust 端 funasr_service::MODEL_VRAM_ESTIMATES 保持一致。
+        """
+        if self.max_vram_gb is None:
+            return tiers[0][0]
+        for compute_type, vram_gb in tiers:
+            if vram_gb <= self.max_vram_gb:
+                if compute_type != tiers[0][0]:
+                    self.logger.warning(
+                        f"显存预算 {self.max_vram_gb}GB 低于 {tiers[0][0]} 所需约 {tiers[0][1]}GB，"
+                        f"改用 {compute_type}"
+                    )
+                return compute_type
+        self.logger.warning(f"显存预算 {self.max_vram_gb}GB 不足以在 GPU 上加载模型，改用 CPU 推理")
+        return None
+
     def _get_gpu_device_info(self) -> dict:
         """Return device/gpu_name/gpu_memory_total dict for status responses."""
-        info = {"device": self.device}
+        info = {"device": self.device, "cpu_threads": self._effective_cpu_threads()}
         if self.device == "cuda":
             try:
                 import torch
@@ -304,10 +491,34 @@ class BaseASRServer:
                 pass
         return info
 
+    def _capabilities(self) -> list:
+        """初始化握手里声明的可选能力，Rust 端据此决定是否走新命令。
+        支持的识别语言以 `language:<code>` 的形式一并声明，Rust 端不再另存一份列表。"""
+        capabilities = ["reload_model"] if self.supports_reload else []
+        capabilities.extend(f"language:{code}" for code in self.supported_languages)
+        return capabilities
+
+    def reload_model(self) -> dict:
+        """释放当前模型并在本进程内重新初始化，保留已导入的依赖。"""
+        if not self.supports_reload:
+            return {"success": False, "error": f"{self.engine} 不支持重新加载模型"}
+        self.logger.info("正在重新加载模型...")
+        self._release_model()
+        self.initialized = False
+        self._cleanup_memory()
+        result = self.initialize()
+        if isinstance(result, dict):
+            result.setdefault("capabilities", self._capabilities())
+        return result
+
     # ------------------------------------------------------------------
     # Hooks for subclasses
     # ------------------------------------------------------------------
 
+    def _release_model(self) -> None:
+        """丢弃已加载的模型引用，supports_reload 为 True 的子类必须实现。"""
+        raise NotImplementedError
+
     def _get_model_repos(self) -> list:
         """Return list of HF repo IDs to check before auto-init."""
         raise NotImplementedError
@@ -359,6 +570,9 @@ class BaseASRServer:
                 "engine": self.engine,
             }
 
+        if isinstance(init_result, dict):
+            init_result.setdefault("server_version", SERVER_VERSION)
+            init_result.setdefault("capabilities", self._capabilities())
         print(json.dumps(init_result, ensure_ascii=False))
         sys.stdout.flush()
 
@@ -388,6 +602,7 @@ class BaseASRServer:
                     rid = command.get("request_id")
                     if isinstance(rid, int):
                         request_id = rid
+                self.active_request_id = request_id
 
                 action = command.get("action")
                 if action == "transcribe":
@@ -399,10 +614,15 @@ class BaseASRServer:
                         audio_format=command.get("audio_format"),
                         sample_rate=command.get("sample_rate"),
                     )
+                    self._strip_speakers_unless_enabled(result)
                 elif action == "status":
                     result = self.check_status()
                 elif action == "stats":
                     result = {"success": True, "stats": self.get_performance_stats()}
+                elif action == "configure":
+                    result = self.configure(command)
+                elif action == "reload_model":
+                    result = self.reload_model()
                 elif action == "cleanup":
                     self._cleanup_memory()
                     result = {"success": True, "message": "内存清理完成"}
@@ -22,17 +22,35 @@ logger = setup_rotating_logger(__name__, "whisper_server.log", "Whisper服务器
 
 from hf_cache_utils import WHISPER_MODEL_REPOS
 
+# large-v3-turbo 在各 CUDA 精度下的显存估算（GB），从大到小
+WHISPER_CUDA_VRAM_TIERS = [("float16", 1.7), ("int8_float16", 1.0)]
+
 
 class WhisperServer(BaseASRServer):
+    supports_reload = True
+    supported_languages = (
+        "zh", "en", "yue", "ja", "ko", "de", "fr", "es", "it", "pt", "ru",
+        "ar", "hi", "th", "vi", "id", "ms", "tr", "nl", "pl", "sv", "uk",
+    )
+
     def __init__(self):
         super().__init__(engine="whisper", logger=logger)
         self.model = None
-        self.compute_type = "float16" if self.device == "cuda" else "int8"
+        self.compute_type = "int8"
+        if self.device == "cuda":
+            compute_type = self._pick_cuda_tier(WHISPER_CUDA_VRAM_TIERS)
+            if compute_type:
+                self.compute_type = compute_type
+            else:
+                self.device = "cpu"
         self._last_load_error = None
 
     def _get_model_repos(self) -> list:
         return WHISPER_MODEL_REPOS
 
+    def _release_model(self) -> None:
+        self.model = None
+
     def _detect_device(self) -> str:
         """Whisper-specific device detection: also checks CTranslate2 CUDA support."""
         try:
@@ -66,6 +84,7 @@ class WhisperServer(BaseASRServer):
                     "deepdml/faster-whisper-large-v3-turbo-ct2",
                     device=self.device,
                     compute_type=self.compute_type,
+                    cpu_threads=self.cpu_threads or 0,
                 )
             self._last_load_error = None
             logger.info(f"Faster Whisper 模型加载完成 (device={self.device})")
@@ -84,7 +103,7 @@ class WhisperServer(BaseASRServer):
     def initialize(self):
         """初始化 Faster Whisper 模型"""
         if self.initialized:
-            return {"success": True, "message": "模型已初始化"}
+            return {"success": True, "message": "模型已初始化", "model_loaded": True}
 
         try:
             import time
@@ -227,16 +246,27 @@ class WhisperServer(BaseASRServer):
             with self.stdout_suppressor.suppress():
                 segments, info = self.model.transcribe(
                     audio_input,
-                    language=None,
+                    language=self.language,
                     initial_prompt=initial_prompt,
                     condition_on_previous_text=False,
                     vad_filter=True,
                     vad_parameters={"min_silence_duration_ms": 500},
                 )
-                text_parts = [segment.text for segment in segments]
+                total = float(getattr(info, "duration", 0.0) or duration or 0.0)
+                text_parts = []
+                result_segments = []
+                for segment in segments:
+                    text_parts.append(segment.text)
+                    result_segments.append({
+                        "start": float(segment.start),
+                        "end": float(segment.end),
+                        "text": segment.text.strip(),
+                    })
+                    if total > 0:
+                        self.emit_progress(min(segment.end, total), total)
             asr_elapsed = time.time() - asr_start
 
-            final_text = "".join(text_parts).strip()
+            final_text = self._apply_output_formatting("".join(text_parts).strip())
             detected_language = info.language if info else "unknown"
 
             logger.info(f"Whisper识别完成，耗时: {asr_elapsed:.2f}秒，语言: {detected_language}，文本: {final_text[:100]}...")
@@ -254,6 +284,7 @@ class WhisperServer(BaseASRServer):
                 "language": detected_language,
                 "model_type": "ctranslate2",
                 "input_mode": input_mode,
+                "segments": result_segments,
             }
 
             self._maybe_cleanup(duration)
@@ -308,6 +339,7 @@ class WhisperServer(BaseASRServer):
                     "punc": True,  # 内置标点
                 },
                 **device_info,
+                **self._formatting_info(),
             }
         except ImportError as e:
             error_msg = f"Faster Whisper 依赖加载失败: {e}"
@@ -3,18 +3,40 @@ use std::sync::{
     Arc,
 };
 
-use tauri::Emitter;
+use tauri::{Emitter, Manager};
 
-use crate::services::audio_service;
+use crate::services::{audio_service, profile_service};
 use crate::state::{
-    AppState, PendingRecordingSession, RecordingMode, RecordingOutcomeKind, RecordingPhase,
-    RecordingSession, RecordingSlot, RecordingSnapshot, RecordingTrigger,
+    AppState, LanguageInputMethods, PendingRecordingSession, RecordingMode, RecordingOutcomeKind,
+    RecordingPause, RecordingPhase, RecordingSession, RecordingSlot, RecordingSnapshot,
+    RecordingTrigger, TaskStatusSnapshot,
 };
 use crate::utils::AppError;
 
 pub(crate) const RECORDING_NOT_READY_ERROR: &str = "语音识别服务尚未就绪，请等待初始化完成";
 pub(crate) const RECORDING_ALREADY_ACTIVE_ERROR: &str = "已有录音正在进行中";
+pub(crate) const ENGINE_COMPARISON_ACTIVE_ERROR: &str = "正在对比引擎，请稍后再录音";
 pub(crate) const RECORDING_START_CANCELLED_ERROR: &str = "录音启动已取消";
+const RECORDING_NOT_ACTIVE_ERROR: &str = "当前没有正在进行的录音";
+const MAX_RECORDING_MERGE_WINDOW_MS: u64 = 10_000;
+/// 录音缓冲区预分配时长。预分配越长，短句听写白占的内存越多（单声道 i16，
+/// 16kHz 下每秒约 31KB、48kHz 下约 94KB）；越短，长录音中途扩容拷贝越频繁。
+pub const DEFAULT_RECORDING_BUFFER_PREALLOC_SECS: u64 = 30;
+pub const MIN_RECORDING_BUFFER_PREALLOC_SECS: u64 = 5;
+pub const MAX_RECORDING_BUFFER_PREALLOC_SECS: u64 = 600;
+
+pub fn effective_recording_buffer_prealloc_secs(configured: Option<u64>) -> u64 {
+    configured
+        .unwrap_or(DEFAULT_RECORDING_BUFFER_PREALLOC_SECS)
+        .clamp(
+            MIN_RECORDING_BUFFER_PREALLOC_SECS,
+            MAX_RECORDING_BUFFER_PREALLOC_SECS,
+        )
+}
+
+fn recording_buffer_capacity(prealloc_secs: u64, sample_rate: u32) -> usize {
+    (prealloc_secs as usize).saturating_mul(sample_rate as usize)
+}
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum CaptureStartErrorResolution {
@@ -36,6 +58,109 @@ fn resolve_capture_start_error(
     }
 }
 
+// ---------- 连续录音合并窗口 ----------
+//
+// 听写停止后不立即收尾，而是把会话暂存为 merge_candidate；窗口内用同一触发方式
+// 再次开始录音时，新会话把上一段音频拼在自己前面，最终只转写、只粘贴一次。
+// 上一段的字幕在窗口内保持"识别中"，随后被新会话的字幕接管；窗口到期或新录音
+// 启动失败时，上一段照常走 finalize_recording，不会丢字。开启后每次粘贴都会
+// 相应延后这段时间。
+
+fn recording_merge_window_ms(state: &AppState, trigger: RecordingTrigger) -> u64 {
+    if trigger.mode() != RecordingMode::Dictation {
+        return 0;
+    }
+    state
+        .with_profile(|profile| profile.recording_merge_window_ms)
+        .min(MAX_RECORDING_MERGE_WINDOW_MS)
+}
+
+fn spawn_finalize(app_handle: tauri::AppHandle, session: RecordingSession) {
+    tokio::spawn(async move {
+        audio_service::finalize_recording(app_handle, session).await;
+    });
+}
+
+fn park_merge_candidate(
+    app_handle: tauri::AppHandle,
+    state: &AppState,
+    session: RecordingSession,
+    window_ms: u64,
+) {
+    let session_id = session.session_id;
+    let replaced = state.recording.merge_candidate.lock().replace(session);
+    if let Some(previous) = replaced {
+        spawn_finalize(app_handle.clone(), previous);
+    }
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(window_ms)).await;
+        let expired = {
+            let state = app_handle.state::<AppState>();
+            let mut guard = state.recording.merge_candidate.lock();
+            if guard.as_ref().is_some_and(|s| s.session_id == session_id) {
+                guard.take()
+            } else {
+                None
+            }
+        };
+        if let Some(session) = expired {
+            audio_service::finalize_recording(app_handle, session).await;
+        }
+    });
+}
+
+fn take_merge_candidate(state: &AppState, trigger: RecordingTrigger) -> Option<RecordingSession> {
+    let mut guard = state.recording.merge_candidate.lock();
+    if guard.as_ref().is_some_and(|s| s.trigger == trigger) {
+        guard.take()
+    } else {
+        None
+    }
+}
+
+fn finalize_unmerged(app_handle: &tauri::AppHandle, previous: Option<RecordingSession>) {
+    if let Some(previous) = previous {
+        log::info!(
+            "新录音未能接续，上一段按原样收尾 (session {})",
+            previous.session_id
+        );
+        spawn_finalize(app_handle.clone(), previous);
+    }
+}
+
+/// 回收上一段会话的采集线程和 interim 任务，把它的采样拼到 `samples` 最前面。
+/// 返回上一段的前台应用与选中文本抓取，合并后的会话沿用最初那次开始时的上下文。
+async fn absorb_merge_candidate(
+    previous: RecordingSession,
+    samples: &parking_lot::Mutex<Vec<i16>>,
+) -> (
+    Option<crate::utils::foreground::ForegroundApp>,
+    Option<tokio::task::JoinHandle<Option<String>>>,
+) {
+    if let Some(h) = previous.audio_thread {
+        let _ = tokio::task::spawn_blocking(move || {
+            let _ = h.join();
+        })
+        .await;
+    }
+    if let Some(t) = previous.interim_task {
+        t.abort();
+    }
+    let mut merged = std::mem::take(&mut *previous.samples.lock());
+    let prefix_len = merged.len();
+    {
+        let mut guard = samples.lock();
+        merged.extend_from_slice(&guard);
+        *guard = merged;
+    }
+    log::info!(
+        "已合并上一段录音 (session {}, {} 个采样)",
+        previous.session_id,
+        prefix_len
+    );
+    (previous.foreground_app, previous.edit_grab)
+}
+
 fn emit_recording_state(
     app_handle: &tauri::AppHandle,
     snapshot: &RecordingSnapshot,
@@ -57,6 +182,7 @@ fn emit_recording_state(
         payload["error"] = serde_json::json!(error);
     }
     let _ = app_handle.emit("recording-state", payload);
+    crate::commands::window::reassert_subtitle_topmost(app_handle);
 }
 
 fn emit_start_error(app_handle: &tauri::AppHandle, snapshot: &RecordingSnapshot, error: &str) {
@@ -78,6 +204,8 @@ pub(crate) async fn start_recording_inner(
     app_handle: tauri::AppHandle,
     state: &AppState,
     trigger: RecordingTrigger,
+    // 仅本次录音使用的设备名；None 时使用已保存的设备选择
+    device_override: Option<String>,
     // 调用方（如 hotkey 路径）在 start 之前已 spawn 的选中文本抓取任务，
     // 会被存进最终创建的 RecordingSession，由 finalize/discard 路径负责回收。
     // 本函数如果在任何路径上提前失败，这个本地 Option 会自然 drop，JoinHandle 被 detach。
@@ -98,6 +226,10 @@ pub(crate) async fn start_recording_inner(
         if guard.is_some() {
             return Err(AppError::Audio(RECORDING_ALREADY_ACTIVE_ERROR.into()));
         }
+        // 持有录音槽锁时检查，与 compare_engines 的“先置位再查录音槽”配对
+        if state.engine.comparison_active.load(Ordering::SeqCst) {
+            return Err(AppError::Audio(ENGINE_COMPARISON_ACTIVE_ERROR.into()));
+        }
         let session_id = state
             .recording
             .session_counter
@@ -132,6 +264,8 @@ pub(crate) async fn start_recording_inner(
             .expect("new recording session must own the latest snapshot");
         (session_id, show_gen, stop_flag, stop_notify, snapshot)
     };
+    // Starting 槽位已占住，计时任务此后拿不到候选会话也不会和这里抢着收尾。
+    let mut merge_from = take_merge_candidate(state, trigger);
 
     emit_recording_state(&app_handle, &starting_snapshot, true, false, false, None);
 
@@ -146,20 +280,69 @@ pub(crate) async fn start_recording_inner(
         })
     };
 
+    // 设备采样率要等采集启动后才知道，先按 16kHz 预分配
+    let prealloc_secs = effective_recording_buffer_prealloc_secs(
+        state.with_profile(|profile| profile.recording_buffer_prealloc_secs),
+    );
     let samples: Arc<parking_lot::Mutex<Vec<i16>>> =
-        Arc::new(parking_lot::Mutex::new(Vec::with_capacity(16000 * 30)));
+        Arc::new(parking_lot::Mutex::new(Vec::with_capacity(
+            recording_buffer_capacity(prealloc_secs, audio_service::TARGET_SAMPLE_RATE),
+        )));
     let interim_cache: Arc<parking_lot::Mutex<Option<crate::state::InterimCache>>> =
         Arc::new(parking_lot::Mutex::new(None));
 
+    let pause_flag = Arc::new(AtomicBool::new(false));
+    let level_meter = Arc::new(audio_service::AudioLevelMeter::default());
+    let requested_device = device_override
+        .filter(|name| !name.trim().is_empty())
+        .or_else(|| state.selected_input_device_name());
+
     let capture_task = {
         let capture_stop = stop_flag.clone();
+        let capture_pause = pause_flag.clone();
         let capture_samples = samples.clone();
-        let selected_device = state.selected_input_device_name();
+        let device_sample_rates = state.device_sample_rates();
+        let preferred_format = state.preferred_sample_format();
+        let lost_app = app_handle.clone();
+        let on_device_lost: audio_service::DeviceLostCallback =
+            Box::new(move |device_name: &str| {
+                log::warn!("录音设备已断开 (session {}): {}", session_id, device_name);
+                let _ = lost_app.emit(
+                    "audio-device-lost",
+                    serde_json::json!({ "sessionId": session_id, "deviceName": device_name }),
+                );
+                // 设备没了之后不会再有数据，直接按停止收尾，转写断开前录到的部分
+                tauri::async_runtime::spawn(async move {
+                    let state = lost_app.state::<AppState>();
+                    if let Err(err) = stop_recording_inner(
+                        lost_app.clone(),
+                        state.inner(),
+                        Some((session_id, trigger)),
+                    )
+                    .await
+                    {
+                        log::warn!(
+                            "录音设备断开后停止录音失败 (session {}): {}",
+                            session_id,
+                            err
+                        );
+                    }
+                });
+            });
+        let requested_device = requested_device.clone();
+        let capture_level_meter = level_meter.clone();
         tokio::task::spawn_blocking(move || {
             audio_service::spawn_audio_capture_thread(
                 capture_stop,
+                capture_pause,
                 capture_samples,
-                selected_device,
+                requested_device,
+                device_sample_rates,
+                preferred_format,
+                audio_service::CaptureHooks {
+                    on_device_lost: Some(on_device_lost),
+                    level_meter: Some(capture_level_meter),
+                },
             )
         })
     };
@@ -177,7 +360,7 @@ pub(crate) async fn start_recording_inner(
     // Cancellation wins over a simultaneous capture error. This keeps a
     // quick tap from being presented as a microphone failure.
     if stop_flag.load(Ordering::Acquire) {
-        if let Ok((audio_thread, actual_sample_rate)) = capture_result {
+        if let Ok((audio_thread, actual_sample_rate, device_name)) = capture_result {
             audio_service::discard_recording(RecordingSession {
                 session_id,
                 subtitle_show_gen: show_gen,
@@ -186,14 +369,17 @@ pub(crate) async fn start_recording_inner(
                 stop_notify,
                 samples,
                 sample_rate: actual_sample_rate,
+                device_name,
                 audio_thread: Some(audio_thread),
                 interim_task: None,
                 interim_cache,
                 foreground_app: foreground_app.clone(),
                 edit_grab: edit_grab.take(),
+                pause: RecordingPause::with_flag(pause_flag),
             })
             .await;
         }
+        finalize_unmerged(&app_handle, merge_from.take());
         crate::commands::window::schedule_subtitle_hide(
             &app_handle,
             session_id,
@@ -204,9 +390,10 @@ pub(crate) async fn start_recording_inner(
         return Err(AppError::Audio(RECORDING_START_CANCELLED_ERROR.into()));
     }
 
-    let (audio_thread, actual_sample_rate) = match capture_result {
+    let (audio_thread, actual_sample_rate, device_name) = match capture_result {
         Ok(result) => result,
         Err(error) => {
+            finalize_unmerged(&app_handle, merge_from.take());
             let detail = error.to_string();
             let (resolution, outcome_snapshot) = {
                 let mut guard = state.recording.recording.lock();
@@ -254,6 +441,46 @@ pub(crate) async fn start_recording_inner(
         }
     };
 
+    let device_fallback = requested_device
+        .as_deref()
+        .is_some_and(|requested| requested != device_name);
+    if device_fallback {
+        log::warn!("指定麦克风不可用，本次录音改用默认设备: {}", device_name);
+    }
+    let _ = app_handle.emit(
+        "recording-device",
+        serde_json::json!({
+            "sessionId": session_id,
+            "deviceName": device_name,
+            "requestedDeviceName": requested_device,
+            "fallback": device_fallback,
+        }),
+    );
+
+    // 设备按更高采样率采集时趁缓冲区还几乎是空的补足容量，此时扩容几乎不用拷贝
+    if actual_sample_rate > audio_service::TARGET_SAMPLE_RATE {
+        let mut buffer = samples.lock();
+        let additional = recording_buffer_capacity(prealloc_secs, actual_sample_rate)
+            .saturating_sub(buffer.len());
+        buffer.reserve(additional);
+    }
+
+    let mut foreground_app = foreground_app;
+    let merged = match merge_from.take() {
+        Some(previous) if previous.sample_rate == actual_sample_rate => {
+            let (previous_app, previous_grab) = absorb_merge_candidate(previous, &samples).await;
+            foreground_app = previous_app;
+            if let Some(grab) = std::mem::replace(&mut edit_grab, previous_grab) {
+                grab.abort();
+            }
+            true
+        }
+        previous => {
+            finalize_unmerged(&app_handle, previous);
+            false
+        }
+    };
+
     let interim_task = audio_service::spawn_interim_loop(
         app_handle.clone(),
         session_id,
@@ -271,6 +498,22 @@ pub(crate) async fn start_recording_inner(
         samples.clone(),
         actual_sample_rate,
     );
+    audio_service::spawn_audio_level_emitter(
+        app_handle.clone(),
+        session_id,
+        stop_flag.clone(),
+        level_meter,
+    );
+    crate::commands::window::spawn_subtitle_topmost_guard(app_handle.clone(), stop_flag.clone());
+    if state.with_profile(|profile| profile.waveform_stream_enabled) {
+        audio_service::spawn_waveform_stream_emitter(
+            app_handle.clone(),
+            session_id,
+            stop_flag.clone(),
+            samples.clone(),
+            actual_sample_rate,
+        );
+    }
 
     let mut session = Some(RecordingSession {
         session_id,
@@ -280,11 +523,13 @@ pub(crate) async fn start_recording_inner(
         stop_notify,
         samples,
         sample_rate: actual_sample_rate,
+        device_name: device_name.clone(),
         audio_thread: Some(audio_thread),
         interim_task: Some(interim_task),
         interim_cache,
         foreground_app,
         edit_grab: edit_grab.take(),
+        pause: RecordingPause::with_flag(pause_flag),
     });
 
     let (cancelled, recording_snapshot) = {
@@ -298,13 +543,16 @@ pub(crate) async fn start_recording_inner(
                     .as_ref()
                     .expect("active slot was just installed")
                     .snapshot(0);
-                let snapshot = state.recording.transition_snapshot_while_recording_locked(
-                    session_id,
-                    slot_snapshot.phase,
-                    slot_snapshot.mode,
-                    None,
-                    None,
-                );
+                let snapshot = state
+                    .recording
+                    .transition_snapshot_with_device_while_recording_locked(
+                        session_id,
+                        slot_snapshot.phase,
+                        slot_snapshot.mode,
+                        None,
+                        None,
+                        slot_snapshot.device_name,
+                    );
                 (None, snapshot)
             }
             _ => (session.take(), None),
@@ -314,7 +562,12 @@ pub(crate) async fn start_recording_inner(
     if let Some(s) = cancelled {
         s.stop_flag.store(true, Ordering::Relaxed);
         s.stop_notify.notify_waiters();
-        audio_service::discard_recording(s).await;
+        if merged {
+            // 已经吸收了上一段的音频，丢弃会连带丢掉上一段；改为正常收尾
+            spawn_finalize(app_handle.clone(), s);
+        } else {
+            audio_service::discard_recording(s).await;
+        }
         crate::commands::window::schedule_subtitle_hide(
             &app_handle,
             session_id,
@@ -336,10 +589,11 @@ pub(crate) async fn start_recording_inner(
         }
     }
     log::info!(
-        "录音已开始 (session {}, {}Hz, mode={})",
+        "录音已开始 (session {}, {}Hz, mode={}, 设备={})",
         session_id,
         actual_sample_rate,
-        trigger.mode().as_str()
+        trigger.mode().as_str(),
+        device_name
     );
     Ok(session_id)
 }
@@ -430,9 +684,12 @@ pub(crate) async fn stop_recording_inner(
         emit_recording_state(&app_handle, snapshot, false, false, true, None);
     }
 
-    tokio::spawn(async move {
-        audio_service::finalize_recording(app_handle, session).await;
-    });
+    let merge_window_ms = recording_merge_window_ms(state, session.trigger);
+    if merge_window_ms > 0 {
+        park_merge_candidate(app_handle, state, session, merge_window_ms);
+    } else {
+        spawn_finalize(app_handle, session);
+    }
 
     Ok(Some(session_id))
 }
@@ -441,11 +698,13 @@ pub(crate) async fn stop_recording_inner(
 pub async fn start_recording(
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
+    device_name: Option<String>,
 ) -> Result<u64, AppError> {
     start_recording_inner(
         app_handle,
         state.inner(),
         RecordingTrigger::DictationOriginal,
+        device_name,
         None,
     )
     .await
@@ -460,19 +719,340 @@ pub async fn stop_recording(
     Ok(())
 }
 
+/// force_stop_recording、reset_audio_subsystem 与取消热键共用的拆除逻辑。
+pub(crate) async fn force_stop_active_recording(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+) -> Option<u64> {
+    let (recording, transition) = {
+        let mut guard = state.recording.recording.lock();
+        let recording = guard.take();
+        let transition = recording.as_ref().and_then(|slot| {
+            match slot {
+                RecordingSlot::Starting(pending) => {
+                    pending.stop_flag.store(true, Ordering::Release);
+                    pending.stop_notify.notify_waiters();
+                }
+                RecordingSlot::Active(session) => {
+                    session.stop_flag.store(true, Ordering::Release);
+                    session.stop_notify.notify_waiters();
+                }
+            }
+            state.recording.transition_snapshot_while_recording_locked(
+                slot.session_id(),
+                RecordingPhase::Idle,
+                slot.trigger().mode(),
+                None,
+                None,
+            )
+        });
+        (recording, transition)
+    };
+
+    let Some(slot) = recording else {
+        log::info!("强制停止录音: 没有活跃的录音会话");
+        return None;
+    };
+    let session_id = slot.session_id();
+    let show_gen = slot.subtitle_show_gen();
+    let mode = slot.trigger().mode();
+    log::warn!("强制停止录音 (session {})，跳过转写与粘贴", session_id);
+    if let Some(snapshot) = transition.as_ref() {
+        emit_recording_state(app_handle, snapshot, false, false, false, None);
+    }
+    // Starting 阶段的会话由 start_recording_inner 发现槽位被取走后自行丢弃
+    if let RecordingSlot::Active(session) = slot {
+        audio_service::force_teardown_recording(state, session).await;
+    }
+    crate::commands::window::schedule_subtitle_hide(app_handle, session_id, show_gen, mode, 0);
+    Some(session_id)
+}
+
+/// 录音链路卡死时的恢复手段：立即拆除当前会话，跳过转写与粘贴。
+/// 与 stop_recording（会转写）和用户取消不同，这里不产生任何结果；
+/// 已停止、等待合并的上一段不受影响，照常收尾。返回被拆除的会话 ID。
+#[tauri::command]
+pub async fn force_stop_recording(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<u64>, AppError> {
+    Ok(force_stop_active_recording(&app_handle, state.inner()).await)
+}
+
+/// 热插拔音频设备或 cpal 报设备错误后，不重启应用只重置录音子系统：
+/// 拆除活跃录音和麦克风预览流，清掉缓存的设备选择，再用新建的 cpal host
+/// 重新枚举设备。不在录音时调用也安全。
+#[tauri::command]
+pub async fn reset_audio_subsystem(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<audio_service::AudioSubsystemResetPayload, AppError> {
+    let stopped_session_id = force_stop_active_recording(&app_handle, state.inner()).await;
+    let monitor_stopped = state.recording.microphone_level_monitor.lock().is_some();
+    audio_service::stop_microphone_level_monitor(state.inner());
+    let cleared_device_name = state.selected_input_device_name();
+    state.set_selected_input_device_name(None);
+    audio_service::clear_negotiated_input_config();
+
+    let (hosts, listing) = tokio::task::spawn_blocking(|| {
+        audio_service::list_input_devices_sync(None)
+            .map(|listing| (audio_service::list_audio_hosts(), listing))
+    })
+    .await
+    .map_err(|e| AppError::Audio(format!("设备枚举任务失败: {}", e)))??;
+    log::info!(
+        "录音子系统已重置：后端 {:?}，可用输入设备 {} 个，清除的设备选择 {:?}",
+        hosts,
+        listing.devices.len(),
+        cleared_device_name
+    );
+    Ok(audio_service::AudioSubsystemResetPayload {
+        stopped_session_id,
+        monitor_stopped,
+        cleared_device_name,
+        hosts,
+        devices: listing.devices,
+    })
+}
+
+fn set_recording_paused(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+    paused: bool,
+) -> Result<u64, AppError> {
+    let (session_id, paused_ms, changed) = {
+        let mut guard = state.recording.recording.lock();
+        let Some(RecordingSlot::Active(session)) = guard.as_mut() else {
+            return Err(AppError::Audio(RECORDING_NOT_ACTIVE_ERROR.into()));
+        };
+        let now = std::time::Instant::now();
+        let changed = if paused {
+            session.pause.pause(now)
+        } else {
+            session.pause.resume(now)
+        };
+        (
+            session.session_id,
+            session.pause.total_paused(now).as_millis() as u64,
+            changed,
+        )
+    };
+    if changed {
+        log::info!(
+            "录音已{} (session {}，累计暂停 {}ms)",
+            if paused { "暂停" } else { "恢复" },
+            session_id,
+            paused_ms
+        );
+        let _ = app_handle.emit(
+            "recording-paused",
+            serde_json::json!({
+                "sessionId": session_id,
+                "paused": paused,
+                "pausedMs": paused_ms,
+            }),
+        );
+    }
+    Ok(session_id)
+}
+
+/// 暂停当前录音但不收尾，恢复后继续追加到同一段音频。暂停的时间不计入
+/// 录音时长和录音上限。已暂停时重复调用无副作用。返回会话 ID。
+#[tauri::command]
+pub async fn pause_recording(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<u64, AppError> {
+    set_recording_paused(&app_handle, state.inner(), true)
+}
+
+#[tauri::command]
+pub async fn resume_recording(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<u64, AppError> {
+    set_recording_paused(&app_handle, state.inner(), false)
+}
+
+/// 暂停热键：录音中暂停、已暂停则恢复。返回切换后是否处于暂停。
+pub(crate) fn toggle_recording_pause(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+) -> Result<bool, AppError> {
+    let paused = match state.recording.recording.lock().as_ref() {
+        Some(RecordingSlot::Active(session)) => session.pause.is_paused(),
+        _ => return Err(AppError::Audio(RECORDING_NOT_ACTIVE_ERROR.into())),
+    };
+    set_recording_paused(app_handle, state, !paused)?;
+    Ok(!paused)
+}
+
 #[tauri::command]
 pub fn get_recording_snapshot(state: tauri::State<'_, AppState>) -> Option<RecordingSnapshot> {
     state.recording.snapshot()
 }
 
+/// 统计最近这么长的音频算实时电平
+const RECORDING_STATS_LEVEL_WINDOW_SEC: f64 = 0.1;
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingStats {
+    pub recording: bool,
+    pub session_id: Option<u64>,
+    pub paused: bool,
+    pub sample_rate: u32,
+    pub sample_count: usize,
+    /// 按已采到的样本数计算，暂停的时间不计入
+    pub elapsed_sec: f64,
+    /// 最近 100ms 的 RMS，归一化到 0..1
+    pub rms_level: f32,
+}
+
+fn recording_stats_from_samples(
+    session_id: u64,
+    paused: bool,
+    samples: &[i16],
+    sample_rate: u32,
+) -> RecordingStats {
+    let window = ((sample_rate as f64 * RECORDING_STATS_LEVEL_WINDOW_SEC) as usize).max(1);
+    let recent = &samples[samples.len().saturating_sub(window)..];
+    let rms_level = if recent.is_empty() {
+        0.0
+    } else {
+        let mean_square = recent
+            .iter()
+            .map(|&s| {
+                let v = s as f64 / i16::MAX as f64;
+                v * v
+            })
+            .sum::<f64>()
+            / recent.len() as f64;
+        mean_square.sqrt().min(1.0) as f32
+    };
+    RecordingStats {
+        recording: true,
+        session_id: Some(session_id),
+        paused,
+        sample_rate,
+        sample_count: samples.len(),
+        elapsed_sec: samples.len() as f64 / sample_rate.max(1) as f64,
+        rms_level,
+    }
+}
+
+/// 拉取式的录音计时和电平，作为录音事件之外的补充。启动阶段还没有样本，
+/// 计数为 0；空闲时 `recording` 为 false。
+#[tauri::command]
+pub fn get_recording_stats(state: tauri::State<'_, AppState>) -> RecordingStats {
+    let (session_id, paused, samples, sample_rate) = match state.recording.recording.lock().as_ref()
+    {
+        Some(RecordingSlot::Active(session)) => (
+            session.session_id,
+            session.pause.is_paused(),
+            session.samples.clone(),
+            session.sample_rate,
+        ),
+        Some(RecordingSlot::Starting(pending)) => {
+            return RecordingStats {
+                recording: true,
+                session_id: Some(pending.session_id),
+                ..Default::default()
+            };
+        }
+        None => return RecordingStats::default(),
+    };
+    let samples = samples.lock();
+    recording_stats_from_samples(session_id, paused, &samples, sample_rate)
+}
+
+/// 调试用：汇总录音会话、引擎启动、下载、麦克风监听等后台任务的当前状态。
+#[tauri::command]
+pub fn get_task_status(state: tauri::State<'_, AppState>) -> TaskStatusSnapshot {
+    state.task_status_snapshot()
+}
+
 #[tauri::command]
 pub async fn test_microphone(state: tauri::State<'_, AppState>) -> Result<String, AppError> {
     let name = state.selected_input_device_name();
-    tokio::task::spawn_blocking(move || audio_service::test_microphone_sync(name))
+    let rates = state.device_sample_rates();
+    let format = state.preferred_sample_format();
+    tokio::task::spawn_blocking(move || audio_service::test_microphone_sync(name, rates, format))
         .await
         .map_err(|e| AppError::Audio(format!("麦克风测试任务失败: {}", e)))?
 }
 
+/// 当前平台可用的 cpal 音频后端，以及默认和用户选择的后端。
+#[tauri::command]
+pub async fn list_audio_hosts(
+    state: tauri::State<'_, AppState>,
+) -> Result<audio_service::AudioHostListPayload, AppError> {
+    Ok(audio_service::AudioHostListPayload {
+        hosts: audio_service::list_audio_hosts(),
+        default_host: audio_service::default_audio_host_name(),
+        selected_host: state.with_profile(|profile| profile.audio_host.clone()),
+    })
+}
+
+/// 选择录音使用的音频后端，None 恢复平台默认；下次录音或枚举设备时生效。
+/// 返回规范化后的后端名。
+#[tauri::command]
+pub async fn set_audio_host(
+    state: tauri::State<'_, AppState>,
+    host: Option<String>,
+) -> Result<Option<String>, AppError> {
+    let host = match host.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+        Some(name) => {
+            let id = audio_service::find_audio_host(name).ok_or_else(|| {
+                AppError::Audio(format!(
+                    "当前平台不支持音频后端 {}，可用: {}",
+                    name,
+                    audio_service::list_audio_hosts().join(", ")
+                ))
+            })?;
+            Some(id.name().to_string())
+        }
+        None => None,
+    };
+    audio_service::set_audio_host_preference(host.clone());
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.audio_host = host.clone();
+    });
+    log::info!(
+        "音频后端已设置为: {}",
+        host.as_deref().unwrap_or("系统默认")
+    );
+    Ok(host)
+}
+
+/// 建一条输入流后立即关掉，提前完成设备初始化和配置协商，让第一次录音更快开始。
+#[tauri::command]
+pub async fn prewarm_audio(
+    state: tauri::State<'_, AppState>,
+) -> Result<audio_service::AudioPrewarmReport, AppError> {
+    if state.recording.recording.lock().is_some() {
+        return Err(AppError::Audio(RECORDING_ALREADY_ACTIVE_ERROR.into()));
+    }
+    let name = state.selected_input_device_name();
+    let rates = state.device_sample_rates();
+    let format = state.preferred_sample_format();
+    tokio::task::spawn_blocking(move || audio_service::prewarm_audio_sync(name, rates, format))
+        .await
+        .map_err(|e| AppError::Audio(format!("录音预热任务失败: {}", e)))?
+}
+
+/// 启动时是否自动预热录音链路；预热会短暂打开麦克风，默认关闭。
+#[tauri::command]
+pub async fn set_audio_prewarm_enabled(
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.audio_prewarm_enabled = enabled;
+    });
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn list_input_devices(
     state: tauri::State<'_, AppState>,
@@ -483,6 +1063,68 @@ pub async fn list_input_devices(
         .map_err(|e| AppError::Audio(format!("设备枚举任务失败: {}", e)))?
 }
 
+/// 导出当前输入设备支持的全部配置和实际选用的那一项，用于排查采样率/格式问题。
+#[tauri::command]
+pub async fn get_device_capabilities(
+    state: tauri::State<'_, AppState>,
+) -> Result<audio_service::DeviceCapabilitiesPayload, AppError> {
+    let name = state.selected_input_device_name();
+    let rates = state.device_sample_rates();
+    let format = state.preferred_sample_format();
+    tokio::task::spawn_blocking(move || {
+        audio_service::get_device_capabilities_sync(name, rates, format)
+    })
+    .await
+    .map_err(|e| AppError::Audio(format!("设备能力查询任务失败: {}", e)))?
+}
+
+/// 当前麦克风支持的采样率/声道摘要，界面用来说明是否需要重采样。
+#[tauri::command]
+pub async fn get_microphone_info(
+    state: tauri::State<'_, AppState>,
+) -> Result<audio_service::MicrophoneInfoPayload, AppError> {
+    let name = state.selected_input_device_name();
+    let rates = state.device_sample_rates();
+    let format = state.preferred_sample_format();
+    tokio::task::spawn_blocking(move || {
+        audio_service::get_microphone_info_sync(name, rates, format)
+    })
+    .await
+    .map_err(|e| AppError::Audio(format!("麦克风信息查询任务失败: {}", e)))?
+}
+
+/// 单独测量重采样 + WAV 编码的耗时，不涉及模型，用来评估弱 CPU 上的预处理开销。
+#[tauri::command]
+pub async fn benchmark_audio_pipeline(
+    duration_secs: f64,
+    source_sample_rate: Option<u32>,
+) -> Result<audio_service::AudioPipelineBenchmark, AppError> {
+    tokio::task::spawn_blocking(move || {
+        audio_service::benchmark_audio_pipeline_sync(duration_secs, source_sample_rate)
+    })
+    .await
+    .map_err(|e| AppError::Audio(format!("音频基准测试任务失败: {}", e)))?
+}
+
+/// 录几秒环境声（期间不要说话），返回底噪统计和建议的静音阈值。
+#[tauri::command]
+pub async fn measure_noise_floor(
+    state: tauri::State<'_, AppState>,
+    secs: f64,
+) -> Result<audio_service::NoiseFloorReport, AppError> {
+    if state.recording.recording.lock().is_some() {
+        return Err(AppError::Audio(RECORDING_ALREADY_ACTIVE_ERROR.into()));
+    }
+    let name = state.selected_input_device_name();
+    let rates = state.device_sample_rates();
+    let format = state.preferred_sample_format();
+    tokio::task::spawn_blocking(move || {
+        audio_service::measure_noise_floor_sync(name, rates, format, secs)
+    })
+    .await
+    .map_err(|e| AppError::Audio(format!("底噪测量任务失败: {}", e)))?
+}
+
 #[tauri::command]
 pub async fn set_input_device(
     state: tauri::State<'_, AppState>,
@@ -492,6 +1134,74 @@ pub async fn set_input_device(
     Ok(())
 }
 
+/// 录音期间额外推送 "audio-waveform" 滚动波形数据，下次录音生效。
+#[tauri::command]
+pub async fn set_waveform_stream_enabled(
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.waveform_stream_enabled = enabled;
+    });
+    Ok(())
+}
+
+pub(crate) const MIN_DEVICE_SAMPLE_RATE: u32 = 8000;
+pub(crate) const MAX_DEVICE_SAMPLE_RATE: u32 = 384_000;
+
+#[tauri::command]
+pub async fn get_device_sample_rates(
+    state: tauri::State<'_, AppState>,
+) -> Result<audio_service::DeviceSampleRates, AppError> {
+    Ok(state.device_sample_rates())
+}
+
+/// 为某个输入设备记住采集采样率（None 清除），下次用该设备录音时生效；
+/// 设备不支持该采样率时仍按自动规则选择。
+#[tauri::command]
+pub async fn set_device_sample_rate(
+    state: tauri::State<'_, AppState>,
+    device_name: String,
+    sample_rate: Option<u32>,
+) -> Result<(), AppError> {
+    let device_name = device_name.trim().to_string();
+    if device_name.is_empty() {
+        return Err(AppError::Audio("设备名不能为空".into()));
+    }
+    if let Some(rate) = sample_rate {
+        if !(MIN_DEVICE_SAMPLE_RATE..=MAX_DEVICE_SAMPLE_RATE).contains(&rate) {
+            return Err(AppError::Audio(format!(
+                "采样率需在 {}-{}Hz 之间",
+                MIN_DEVICE_SAMPLE_RATE, MAX_DEVICE_SAMPLE_RATE
+            )));
+        }
+    }
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        match sample_rate {
+            Some(rate) => {
+                profile.device_sample_rates.insert(device_name, rate);
+            }
+            None => {
+                profile.device_sample_rates.remove(&device_name);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// 设置排在最前面的采集采样格式（None 恢复默认 I16 > F32 > U16），下次打开
+/// 设备时生效；设备不提供该格式时按默认顺序选择，选择结果见设备能力诊断。
+#[tauri::command]
+pub async fn set_preferred_sample_format(
+    state: tauri::State<'_, AppState>,
+    format: Option<crate::state::user_profile::InputSampleFormat>,
+) -> Result<(), AppError> {
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.preferred_sample_format = format;
+    });
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn start_microphone_level_monitor(
     app_handle: tauri::AppHandle,
@@ -517,30 +1227,383 @@ pub async fn set_sound_enabled(
     Ok(())
 }
 
+pub(crate) fn validate_min_audio_duration(secs: f64) -> Result<u64, AppError> {
+    let range =
+        audio_service::MIN_AUDIO_DURATION_LOWER_SEC..=audio_service::MIN_AUDIO_DURATION_UPPER_SEC;
+    if !secs.is_finite() || !range.contains(&secs) {
+        return Err(AppError::Other(format!(
+            "最短录音时长需在 {}-{} 秒之间",
+            range.start(),
+            range.end()
+        )));
+    }
+    Ok((secs * 1000.0).round() as u64)
+}
+
+/// 设置最短录音时长：更短的录音视为误按、不转写，下次停止录音时生效并保存到画像。
 #[tauri::command]
-pub async fn set_input_method(
+pub async fn set_min_audio_duration(
     state: tauri::State<'_, AppState>,
-    method: String,
+    secs: f64,
 ) -> Result<(), AppError> {
-    // 仅允许这两个取值。clipboard.rs 的 paste_text_impl 把 "clipboard" 单独
-    // 分支处理，其余值都走 SendInput，所以"任意 String"等于把所有未知值悄悄
-    // 解释为 sendInput。这里在入口卡死，避免 UI 错位/typo 写入静默退化。
-    match method.as_str() {
-        "sendInput" | "clipboard" => {
-            *state.ui.input_method.lock() = method;
-            Ok(())
+    let ms = validate_min_audio_duration(secs)?;
+    state
+        .recording
+        .min_audio_duration_ms
+        .store(ms, Ordering::Relaxed);
+    profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.min_audio_duration_ms = Some(ms);
+    });
+    log::info!("最短录音时长已设为 {}ms", ms);
+    Ok(())
+}
+
+fn validate_recording_merge_window_ms(window_ms: u64) -> Result<(), AppError> {
+    if window_ms > MAX_RECORDING_MERGE_WINDOW_MS {
+        return Err(AppError::Other(format!(
+            "录音合并窗口不能超过 {} 毫秒",
+            MAX_RECORDING_MERGE_WINDOW_MS
+        )));
+    }
+    Ok(())
+}
+
+/// 听写停止后在 `window_ms` 毫秒内重新开始，两段录音合并成一次转写；0 关闭。
+#[tauri::command]
+pub async fn set_recording_merge_window(
+    state: tauri::State<'_, AppState>,
+    window_ms: u64,
+) -> Result<(), AppError> {
+    validate_recording_merge_window_ms(window_ms)?;
+    profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.recording_merge_window_ms = window_ms;
+    });
+    Ok(())
+}
+
+/// 录音期间待粘贴队列的段数上限；None 恢复默认。超出时最早的段转存到恢复文件。
+#[tauri::command]
+pub async fn set_pending_paste_limit(
+    state: tauri::State<'_, AppState>,
+    limit: Option<u32>,
+) -> Result<(), AppError> {
+    if let Some(limit) = limit {
+        if limit == 0 || limit > audio_service::MAX_PENDING_PASTE_LIMIT {
+            return Err(AppError::Other(format!(
+                "待粘贴队列上限必须在 1 到 {} 之间",
+                audio_service::MAX_PENDING_PASTE_LIMIT
+            )));
+        }
+    }
+    profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.pending_paste_limit = limit;
+    });
+    Ok(())
+}
+
+/// 设置中间转写的耗时阈值；`auto_calibrate` 打开时每次录音按实测耗时重新推算。
+#[tauri::command]
+pub async fn set_interim_cost_settings(
+    state: tauri::State<'_, AppState>,
+    heavy_cost_ms: u64,
+    light_cost_ms: u64,
+    auto_calibrate: bool,
+) -> Result<(), AppError> {
+    profile_service::validate_interim_cost_thresholds(heavy_cost_ms, light_cost_ms)
+        .map_err(AppError::Other)?;
+    profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.interim_cost_settings = crate::state::user_profile::InterimCostSettings {
+            heavy_cost_ms,
+            light_cost_ms,
+            auto_calibrate,
+        };
+    });
+    Ok(())
+}
+
+/// 设置识别结果的文本清理（连续空格合并、大小写），中间与最终结果同时生效。
+#[tauri::command]
+pub async fn set_output_cleanup(
+    state: tauri::State<'_, AppState>,
+    settings: crate::state::user_profile::OutputCleanupSettings,
+) -> Result<(), AppError> {
+    profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.output_cleanup = settings;
+    });
+    Ok(())
+}
+
+/// 设置录音缓冲区预分配时长（秒）；None 恢复默认。返回生效值，下一次录音生效。
+#[tauri::command]
+pub async fn set_recording_buffer_prealloc_secs(
+    state: tauri::State<'_, AppState>,
+    secs: Option<u64>,
+) -> Result<u64, AppError> {
+    if let Some(secs) = secs.filter(|secs| {
+        !(MIN_RECORDING_BUFFER_PREALLOC_SECS..=MAX_RECORDING_BUFFER_PREALLOC_SECS).contains(secs)
+    }) {
+        return Err(AppError::Other(format!(
+            "录音缓冲区预分配时长必须在 {}-{} 秒之间（当前 {} 秒）",
+            MIN_RECORDING_BUFFER_PREALLOC_SECS, MAX_RECORDING_BUFFER_PREALLOC_SECS, secs
+        )));
+    }
+    profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.recording_buffer_prealloc_secs = secs;
+    });
+    Ok(effective_recording_buffer_prealloc_secs(secs))
+}
+
+/// 切换批量 / 流式转写，下一次录音生效。取舍见 `TranscriptionMode`。
+#[tauri::command]
+pub async fn set_transcription_mode(
+    state: tauri::State<'_, AppState>,
+    mode: crate::state::user_profile::TranscriptionMode,
+) -> Result<(), AppError> {
+    profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.transcription_mode = mode;
+    });
+    Ok(())
+}
+
+/// 分别设置听写结果在字幕上显示和粘贴的版本（润色后 / 识别原文）。
+/// 助手与编辑模式不受影响。
+#[tauri::command]
+pub async fn set_text_variants(
+    state: tauri::State<'_, AppState>,
+    subtitle: crate::state::user_profile::TextVariant,
+    paste: crate::state::user_profile::TextVariant,
+) -> Result<(), AppError> {
+    profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.subtitle_text_variant = subtitle;
+        profile.paste_text_variant = paste;
+    });
+    Ok(())
+}
+
+/// 设置从识别结果开头去掉的引导语，返回整理后的列表（去空、去重、截断）。
+#[tauri::command]
+pub async fn set_strip_prefix(
+    state: tauri::State<'_, AppState>,
+    phrases: Vec<String>,
+    fuzzy: bool,
+) -> Result<Vec<String>, AppError> {
+    if phrases.len() > audio_service::MAX_STRIP_PREFIXES {
+        return Err(AppError::Other(format!(
+            "引导语最多 {} 条",
+            audio_service::MAX_STRIP_PREFIXES
+        )));
+    }
+    if phrases
+        .iter()
+        .any(|phrase| phrase.trim().chars().count() > audio_service::MAX_STRIP_PREFIX_CHARS)
+    {
+        return Err(AppError::Other(format!(
+            "单条引导语不能超过 {} 个字符",
+            audio_service::MAX_STRIP_PREFIX_CHARS
+        )));
+    }
+    let phrases = audio_service::normalize_strip_prefixes(phrases);
+    profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.strip_prefix = phrases.clone();
+        profile.strip_prefix_fuzzy = fuzzy;
+    });
+    Ok(phrases)
+}
+
+/// 开关最终识别前的响度归一化；`target_lufs` 省略时保持当前目标。
+#[tauri::command]
+pub async fn set_loudness_normalization(
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+    target_lufs: Option<f64>,
+) -> Result<(), AppError> {
+    if let Some(target) = target_lufs {
+        profile_service::validate_loudness_target(target).map_err(AppError::Other)?;
+    }
+    profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.loudness_settings.enabled = enabled;
+        if let Some(target) = target_lufs {
+            profile.loudness_settings.target_lufs = target;
         }
+    });
+    Ok(())
+}
+
+/// 设置最终识别前开头补的静音时长；None 或 0 关闭。
+#[tauri::command]
+pub async fn set_leading_silence_pad(
+    state: tauri::State<'_, AppState>,
+    pad_ms: Option<u32>,
+) -> Result<(), AppError> {
+    if let Some(pad_ms) = pad_ms.filter(|value| *value > audio_service::MAX_LEADING_SILENCE_MS) {
+        return Err(AppError::Other(format!(
+            "开头补静音不能超过 {}ms（当前 {}ms）",
+            audio_service::MAX_LEADING_SILENCE_MS,
+            pad_ms
+        )));
+    }
+    profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.leading_silence_ms = pad_ms.filter(|value| *value > 0);
+    });
+    Ok(())
+}
+
+pub(crate) fn validate_input_method(method: &str) -> Result<(), AppError> {
+    // 仅允许这三个取值。clipboard.rs 的 paste_text_impl 把 "clipboard" / "copyOnly"
+    // 单独分支处理，其余值都走 SendInput，所以"任意 String"等于把所有未知值悄悄
+    // 解释为 sendInput。这里在入口卡死，避免 UI 错位/typo 写入静默退化。
+    match method {
+        "sendInput" | "clipboard" | crate::commands::clipboard::INPUT_METHOD_COPY_ONLY => Ok(()),
         other => Err(AppError::Other(format!(
-            "未知的输入方式: {}，可选值: sendInput, clipboard",
+            "未知的输入方式: {}，可选值: sendInput, clipboard, copyOnly",
             other
         ))),
     }
 }
 
+#[tauri::command]
+pub async fn set_input_method(
+    state: tauri::State<'_, AppState>,
+    method: String,
+) -> Result<(), AppError> {
+    validate_input_method(&method)?;
+    *state.ui.input_method.lock() = method;
+    Ok(())
+}
+
+/// 只覆盖下一次注入的输入方式，用完（或 2 分钟后）恢复默认；None 取消。
+#[tauri::command]
+pub async fn set_next_input_method(
+    state: tauri::State<'_, AppState>,
+    method: Option<String>,
+) -> Result<(), AppError> {
+    let next = match method.filter(|value| !value.trim().is_empty()) {
+        Some(method) => {
+            validate_input_method(&method)?;
+            Some(crate::state::NextInputMethod {
+                method,
+                expires_at: std::time::Instant::now()
+                    + crate::state::app_state::NEXT_INPUT_METHOD_TTL,
+            })
+        }
+        None => None,
+    };
+    *state.ui.next_input_method.lock() = next;
+    Ok(())
+}
+
+/// 主输入方式失败后依次尝试的兜底链；None 恢复平台默认（Windows: clipboard → copyOnly）。
+#[tauri::command]
+pub async fn set_input_method_fallback(
+    state: tauri::State<'_, AppState>,
+    methods: Option<Vec<String>>,
+) -> Result<Vec<String>, AppError> {
+    let methods = match methods {
+        Some(methods) => {
+            let mut deduped: Vec<String> = Vec::with_capacity(methods.len());
+            for method in methods {
+                validate_input_method(&method)?;
+                if !deduped.contains(&method) {
+                    deduped.push(method);
+                }
+            }
+            Some(deduped)
+        }
+        None => None,
+    };
+    let effective = methods
+        .clone()
+        .unwrap_or_else(crate::commands::clipboard::default_input_method_fallback);
+    profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.input_method_fallback = methods;
+    });
+    Ok(effective)
+}
+
+/// 按识别结果语种覆盖注入方式。`language` 取 "cjk" / "latin"，
+/// `method` 为 None 时清除覆盖、回到全局输入方式。
+#[tauri::command]
+pub async fn set_language_input_method(
+    state: tauri::State<'_, AppState>,
+    language: String,
+    method: Option<String>,
+) -> Result<(), AppError> {
+    let method = method.filter(|value| !value.trim().is_empty());
+    if let Some(ref value) = method {
+        validate_input_method(value)?;
+    }
+    if !matches!(language.as_str(), "cjk" | "latin") {
+        return Err(AppError::Other(format!(
+            "未知的语种: {}，可选值: cjk, latin",
+            language
+        )));
+    }
+    profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        let overrides = &mut profile.language_input_methods;
+        if language == "cjk" {
+            overrides.cjk = method;
+        } else {
+            overrides.latin = method;
+        }
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_language_input_methods(
+    state: tauri::State<'_, AppState>,
+) -> Result<LanguageInputMethods, AppError> {
+    Ok(state.with_profile(|profile| profile.language_input_methods.clone()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn min_audio_duration_is_limited_to_supported_range() {
+        assert_eq!(validate_min_audio_duration(0.1).unwrap(), 100);
+        assert_eq!(validate_min_audio_duration(0.25).unwrap(), 250);
+        assert_eq!(validate_min_audio_duration(5.0).unwrap(), 5000);
+        assert!(validate_min_audio_duration(0.05).is_err());
+        assert!(validate_min_audio_duration(5.5).is_err());
+        assert!(validate_min_audio_duration(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn recording_buffer_prealloc_defaults_and_clamps() {
+        assert_eq!(
+            effective_recording_buffer_prealloc_secs(None),
+            DEFAULT_RECORDING_BUFFER_PREALLOC_SECS
+        );
+        assert_eq!(
+            effective_recording_buffer_prealloc_secs(Some(1)),
+            MIN_RECORDING_BUFFER_PREALLOC_SECS
+        );
+        assert_eq!(
+            effective_recording_buffer_prealloc_secs(Some(100_000)),
+            MAX_RECORDING_BUFFER_PREALLOC_SECS
+        );
+        assert_eq!(recording_buffer_capacity(30, 16_000), 480_000);
+    }
+
+    #[test]
+    fn recording_stats_use_sample_count_and_recent_level() {
+        let mut samples = vec![0i16; 16_000];
+        samples.resize(17_600, i16::MAX / 2);
+        let stats = recording_stats_from_samples(7, false, &samples, 16_000);
+        assert_eq!(stats.session_id, Some(7));
+        assert_eq!(stats.sample_count, 17_600);
+        assert!((stats.elapsed_sec - 1.1).abs() < 1e-9);
+        assert!((stats.rms_level - 0.5).abs() < 0.01);
+
+        let silent = recording_stats_from_samples(7, true, &[], 48_000);
+        assert!(silent.recording && silent.paused);
+        assert_eq!(silent.elapsed_sec, 0.0);
+        assert_eq!(silent.rms_level, 0.0);
+        assert!(!RecordingStats::default().recording);
+    }
+
     #[test]
     fn capture_error_after_stop_is_cancelled() {
         assert_eq!(
@@ -561,6 +1624,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn merge_window_is_bounded() {
+        assert!(validate_recording_merge_window_ms(0).is_ok());
+        assert!(validate_recording_merge_window_ms(MAX_RECORDING_MERGE_WINDOW_MS).is_ok());
+        assert!(validate_recording_merge_window_ms(MAX_RECORDING_MERGE_WINDOW_MS + 1).is_err());
+    }
+
     #[test]
     fn superseded_capture_error_is_stale() {
         assert_eq!(
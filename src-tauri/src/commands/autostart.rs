@@ -0,0 +1,95 @@
+use serde::Serialize;
+use tauri_plugin_autostart::ManagerExt;
+
+use crate::state::AppState;
+use crate::utils::AppError;
+
+// ---------- 开机自启动 ----------
+//
+// 系统里的自启动项可能被用户在系统设置或安全软件里删掉，应用自己并不知道。
+// 这里同时报告系统实际状态和应用记录的意图，前端据此提示并修复不一致。
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutostartStatus {
+    /// 系统里是否注册了自启动；查询失败时为 None
+    pub os_enabled: Option<bool>,
+    /// 用户最后一次在应用里的选择；从未设置过时为 None
+    pub intended: Option<bool>,
+    pub in_sync: bool,
+    pub error: Option<String>,
+}
+
+/// 没有记录过意图时不算不一致（旧版本直接调用插件，没有留下记录）。
+fn autostart_in_sync(os_enabled: Option<bool>, intended: Option<bool>) -> bool {
+    match (os_enabled, intended) {
+        (Some(os_enabled), Some(intended)) => os_enabled == intended,
+        _ => true,
+    }
+}
+
+fn autostart_status(app_handle: &tauri::AppHandle, intended: Option<bool>) -> AutostartStatus {
+    let (os_enabled, error) = match app_handle.autolaunch().is_enabled() {
+        Ok(enabled) => (Some(enabled), None),
+        Err(err) => {
+            log::warn!("查询开机自启动状态失败: {}", err);
+            (None, Some(err.to_string()))
+        }
+    };
+    AutostartStatus {
+        os_enabled,
+        intended,
+        in_sync: autostart_in_sync(os_enabled, intended),
+        error,
+    }
+}
+
+#[tauri::command]
+pub async fn get_autostart_status(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<AutostartStatus, AppError> {
+    let intended = state.with_profile(|profile| profile.autostart_enabled);
+    let status = autostart_status(&app_handle, intended);
+    if !status.in_sync {
+        log::warn!(
+            "开机自启动与设置不一致：系统 {:?}，设置 {:?}",
+            status.os_enabled,
+            status.intended
+        );
+    }
+    Ok(status)
+}
+
+/// 注册或移除系统自启动项并记下用户意图，返回操作后的实际状态。
+#[tauri::command]
+pub async fn set_autostart(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+) -> Result<AutostartStatus, AppError> {
+    let autolaunch = app_handle.autolaunch();
+    let result = if enabled {
+        autolaunch.enable()
+    } else {
+        autolaunch.disable()
+    };
+    result.map_err(|e| AppError::Other(format!("设置开机自启动失败: {}", e)))?;
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.autostart_enabled = Some(enabled);
+    });
+    Ok(autostart_status(&app_handle, Some(enabled)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::autostart_in_sync;
+
+    #[test]
+    fn drift_is_reported_only_when_intent_is_known() {
+        assert!(autostart_in_sync(Some(true), Some(true)));
+        assert!(!autostart_in_sync(Some(false), Some(true)));
+        assert!(autostart_in_sync(Some(false), None));
+        assert!(autostart_in_sync(None, Some(true)));
+    }
+}
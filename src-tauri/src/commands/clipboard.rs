@@ -31,6 +31,18 @@ fn make_key_input(vk: u16, scan: u16, flags: u32) -> INPUT {
 
 #[cfg(target_os = "windows")]
 const CLIPBOARD_RESTORE_DELAY_MS: u64 = 200;
+/// 只写剪贴板、不模拟粘贴的输入方式
+pub const INPUT_METHOD_COPY_ONLY: &str = "copyOnly";
+/// 首个字符（或 Ctrl+V）前的额外停顿，给刚获得焦点的目标窗口一点准备时间。
+/// 与收尾阶段的 PASTE_DELAY_MS 叠加，不替代它。
+pub const DEFAULT_FIRST_CHAR_DELAY_MS: u64 = 15;
+pub const MAX_FIRST_CHAR_DELAY_MS: u64 = 1000;
+/// 写剪贴板失败后的重试次数。Windows 上别的程序短暂占着剪贴板锁时写入会失败，
+/// 通常几十毫秒内就会释放；默认 3 次重试共等待约 175ms。
+pub const DEFAULT_CLIPBOARD_WRITE_RETRIES: u32 = 3;
+pub const MAX_CLIPBOARD_WRITE_RETRIES: u32 = 10;
+const CLIPBOARD_RETRY_BASE_DELAY_MS: u64 = 25;
+const CLIPBOARD_RETRY_MAX_DELAY_MS: u64 = 400;
 
 #[cfg(any(target_os = "windows", test))]
 fn try_all_then_standard<T, E, F>(mut capture: F) -> Result<(T, Option<E>), (E, E)>
@@ -360,18 +372,192 @@ pub async fn copy_to_clipboard(
     app_handle: tauri::AppHandle,
     text: String,
 ) -> Result<String, AppError> {
-    write_text_to_clipboard(&app_handle, &text)?;
-    log::info!("已复制 {} 个字符到剪贴板", text.len());
+    let len = text.len();
+    write_text_to_clipboard_async(&app_handle, text).await?;
+    log::info!("已复制 {} 个字符到剪贴板", len);
     Ok("已复制到剪贴板".to_string())
 }
 
+/// 读取剪贴板里文件管理器复制的文件列表（CF_HDROP）；没有文件或剪贴板被占用时为空。
+#[cfg(target_os = "windows")]
+pub fn read_clipboard_file_paths() -> Vec<std::path::PathBuf> {
+    use std::os::windows::ffi::OsStringExt;
+    use windows_sys::Win32::System::DataExchange::{
+        CloseClipboard, GetClipboardData, IsClipboardFormatAvailable, OpenClipboard,
+    };
+    use windows_sys::Win32::System::Ole::CF_HDROP;
+    use windows_sys::Win32::UI::Shell::DragQueryFileW;
+
+    let mut paths = Vec::new();
+    unsafe {
+        if IsClipboardFormatAvailable(CF_HDROP as u32) == 0
+            || OpenClipboard(std::ptr::null_mut()) == 0
+        {
+            return paths;
+        }
+        let hdrop = GetClipboardData(CF_HDROP as u32);
+        if !hdrop.is_null() {
+            let count = DragQueryFileW(hdrop, u32::MAX, std::ptr::null_mut(), 0);
+            for index in 0..count {
+                let len = DragQueryFileW(hdrop, index, std::ptr::null_mut(), 0);
+                if len == 0 {
+                    continue;
+                }
+                let mut buf = vec![0u16; len as usize + 1];
+                let copied = DragQueryFileW(hdrop, index, buf.as_mut_ptr(), buf.len() as u32);
+                paths.push(std::path::PathBuf::from(std::ffi::OsString::from_wide(
+                    &buf[..copied as usize],
+                )));
+            }
+        }
+        CloseClipboard();
+    }
+    paths
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn read_clipboard_file_paths() -> Vec<std::path::PathBuf> {
+    Vec::new()
+}
+
+/// 用当前输入方式重新粘贴上一次的转写结果；返回是否有可重贴的文本。
+#[tauri::command]
+pub async fn repaste_last(app_handle: tauri::AppHandle) -> Result<bool, AppError> {
+    let repasted = crate::services::audio_service::repaste_last(&app_handle).await?;
+    if !repasted {
+        log::info!("没有可重新粘贴的文本");
+    }
+    Ok(repasted)
+}
+
+/// 确认粘贴等待确认的听写结果（review_before_paste）；返回是否有结果被注入。
+#[tauri::command]
+pub async fn confirm_paste(
+    app_handle: tauri::AppHandle,
+    id: Option<u64>,
+) -> Result<bool, AppError> {
+    crate::services::audio_service::confirm_paste_review(&app_handle, id).await
+}
+
+/// 丢弃等待确认的听写结果；返回是否有结果被丢弃。
+#[tauri::command]
+pub async fn discard_paste(
+    app_handle: tauri::AppHandle,
+    id: Option<u64>,
+) -> Result<bool, AppError> {
+    Ok(crate::services::audio_service::discard_paste_review(
+        &app_handle,
+        id,
+    ))
+}
+
+/// 设置听写结果是否先等待确认再粘贴。关闭时丢弃当前待确认的结果。
+#[tauri::command]
+pub async fn set_review_before_paste(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, crate::state::AppState>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.review_before_paste = enabled;
+    });
+    if !enabled {
+        crate::services::audio_service::discard_paste_review(&app_handle, None);
+    }
+    Ok(())
+}
+
+pub fn effective_clipboard_write_retries(configured: Option<u32>) -> u32 {
+    configured
+        .unwrap_or(DEFAULT_CLIPBOARD_WRITE_RETRIES)
+        .min(MAX_CLIPBOARD_WRITE_RETRIES)
+}
+
+/// 第 `retry` 次重试（从 0 开始）前的等待：25ms 起逐次翻倍，封顶 400ms。
+fn clipboard_retry_delay_ms(retry: u32) -> u64 {
+    CLIPBOARD_RETRY_BASE_DELAY_MS
+        .saturating_mul(1 << retry.min(8))
+        .min(CLIPBOARD_RETRY_MAX_DELAY_MS)
+}
+
+/// 失败后按退避间隔重试 `retries` 次，全部失败才返回最后一次的错误。
+fn write_clipboard_with_retry<E: std::fmt::Display>(
+    retries: u32,
+    mut write: impl FnMut() -> Result<(), E>,
+) -> Result<(), AppError> {
+    let mut retry = 0;
+    loop {
+        match write() {
+            Ok(()) => {
+                if retry > 0 {
+                    log::info!("写入剪贴板重试 {} 次后成功", retry);
+                }
+                return Ok(());
+            }
+            Err(err) if retry < retries => {
+                let delay_ms = clipboard_retry_delay_ms(retry);
+                log::debug!("写入剪贴板失败，{}ms 后重试: {}", delay_ms, err);
+                std::thread::sleep(std::time::Duration::from_millis(delay_ms));
+                retry += 1;
+            }
+            Err(err) if retries > 0 => {
+                return Err(AppError::Other(format!(
+                    "写入剪贴板失败（已重试 {} 次）: {}",
+                    retries, err
+                )))
+            }
+            Err(err) => return Err(AppError::Other(format!("写入剪贴板失败: {}", err))),
+        }
+    }
+}
+
+fn clipboard_write_retries(app_handle: &tauri::AppHandle) -> u32 {
+    use tauri::Manager;
+
+    effective_clipboard_write_retries(
+        app_handle
+            .state::<crate::state::AppState>()
+            .with_profile(|profile| profile.clipboard_write_retries),
+    )
+}
+
+/// 失败重试时会阻塞当前线程等待退避，只在粘贴路径这类已经在阻塞线程上的地方直接用；
+/// async 命令用 `write_text_to_clipboard_async`。
 pub fn write_text_to_clipboard(app_handle: &tauri::AppHandle, text: &str) -> Result<(), AppError> {
     use tauri_plugin_clipboard_manager::ClipboardExt;
 
-    app_handle
-        .clipboard()
-        .write_text(text)
-        .map_err(|e| AppError::Other(format!("写入剪贴板失败: {}", e)))
+    write_clipboard_with_retry(clipboard_write_retries(app_handle), || {
+        app_handle.clipboard().write_text(text)
+    })
+}
+
+/// 把带重试的写入放到阻塞线程里，退避等待不占 tokio worker。
+pub async fn write_text_to_clipboard_async(
+    app_handle: &tauri::AppHandle,
+    text: String,
+) -> Result<(), AppError> {
+    let app_handle = app_handle.clone();
+    tokio::task::spawn_blocking(move || write_text_to_clipboard(&app_handle, &text))
+        .await
+        .map_err(|e| AppError::Other(format!("写入剪贴板任务异常结束: {}", e)))?
+}
+
+/// 设置写剪贴板失败后的重试次数；None 恢复默认，0 不重试。返回生效值。
+#[tauri::command]
+pub async fn set_clipboard_write_retries(
+    state: tauri::State<'_, crate::state::AppState>,
+    retries: Option<u32>,
+) -> Result<u32, AppError> {
+    if let Some(retries) = retries.filter(|retries| *retries > MAX_CLIPBOARD_WRITE_RETRIES) {
+        return Err(AppError::Other(format!(
+            "剪贴板重试次数不能超过 {}（当前 {}）",
+            MAX_CLIPBOARD_WRITE_RETRIES, retries
+        )));
+    }
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.clipboard_write_retries = retries;
+    });
+    Ok(effective_clipboard_write_retries(retries))
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -442,6 +628,31 @@ pub fn replace_raw_paste_suffix_if_unchanged(
     }
 }
 
+/// 用户设置的首字符延迟，0 表示不额外等待。
+pub fn effective_first_char_delay_ms(configured: Option<u64>) -> u64 {
+    configured
+        .unwrap_or(DEFAULT_FIRST_CHAR_DELAY_MS)
+        .min(MAX_FIRST_CHAR_DELAY_MS)
+}
+
+/// 设置首字符延迟；None 恢复默认。返回生效值。
+#[tauri::command]
+pub async fn set_first_char_delay_ms(
+    state: tauri::State<'_, crate::state::AppState>,
+    ms: Option<u64>,
+) -> Result<u64, AppError> {
+    if let Some(ms) = ms.filter(|ms| *ms > MAX_FIRST_CHAR_DELAY_MS) {
+        return Err(AppError::Other(format!(
+            "首字符延迟不能超过 {}ms（当前 {}ms）",
+            MAX_FIRST_CHAR_DELAY_MS, ms
+        )));
+    }
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.first_char_delay_ms = ms;
+    });
+    Ok(effective_first_char_delay_ms(ms))
+}
+
 #[tauri::command]
 pub async fn paste_text(
     app_handle: tauri::AppHandle,
@@ -457,9 +668,23 @@ pub async fn paste_text_impl(
     text: &str,
     method: &str,
 ) -> Result<String, AppError> {
+    if method == INPUT_METHOD_COPY_ONLY {
+        // 只放进剪贴板，不模拟按键：任何平台都可用，作为兜底让用户手动粘贴
+        write_text_to_clipboard_async(app_handle, text.to_string()).await?;
+        log::info!("已复制 {} 个字符到剪贴板（未自动粘贴）", text.len());
+        return Ok("已复制".to_string());
+    }
+
     #[cfg(target_os = "windows")]
     {
+        use tauri::Manager;
+
         let use_clipboard = method == "clipboard";
+        let first_char_delay = std::time::Duration::from_millis(effective_first_char_delay_ms(
+            app_handle
+                .state::<crate::state::AppState>()
+                .with_profile(|profile| profile.first_char_delay_ms),
+        ));
 
         if use_clipboard {
             use tauri_plugin_clipboard_manager::ClipboardExt;
@@ -475,16 +700,18 @@ pub async fn paste_text_impl(
 
             let clipboard_snapshot = capture_clipboard_snapshot()?;
 
-            app_handle
-                .clipboard()
-                .write_text(text)
-                .map_err(|e| AppError::Other(format!("写入剪贴板失败: {}", e)))?;
+            write_clipboard_with_retry(clipboard_write_retries(app_handle), || {
+                app_handle.clipboard().write_text(text)
+            })?;
 
             let paste_result = (|| -> Result<(), AppError> {
                 std::thread::sleep(std::time::Duration::from_millis(10));
 
                 release_stuck_modifiers()?;
                 std::thread::sleep(std::time::Duration::from_millis(20));
+                if !first_char_delay.is_zero() {
+                    std::thread::sleep(first_char_delay);
+                }
 
                 const VK_CONTROL: u16 = 0x11;
                 const VK_V: u16 = 0x56;
@@ -574,6 +801,9 @@ pub async fn paste_text_impl(
                 }
             }
             let send_result = if !inputs.is_empty() {
+                if !first_char_delay.is_zero() {
+                    tokio::time::sleep(first_char_delay).await;
+                }
                 send_inputs(&inputs)
             } else {
                 Ok(())
@@ -606,13 +836,155 @@ pub async fn paste_text_impl(
     Ok("已输入".to_string())
 }
 
+/// 主输入方式失败后依次尝试的默认兜底链。非 Windows 没有按键注入，只能复制。
+pub fn default_input_method_fallback() -> Vec<String> {
+    #[cfg(target_os = "windows")]
+    {
+        vec!["clipboard".to_string(), INPUT_METHOD_COPY_ONLY.to_string()]
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        vec![INPUT_METHOD_COPY_ONLY.to_string()]
+    }
+}
+
+/// 主输入方式在前，兜底链按顺序接在后面，重复项只保留第一次出现。
+pub fn input_method_chain(primary: &str, fallback: &[String]) -> Vec<String> {
+    let mut chain = vec![primary.to_string()];
+    for method in fallback {
+        if !chain.iter().any(|existing| existing == method) {
+            chain.push(method.clone());
+        }
+    }
+    chain
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasteMethodUsedPayload {
+    pub method: String,
+    /// 是否因前面的方式失败而用了兜底
+    pub fallback: bool,
+    pub failed_methods: Vec<String>,
+}
+
+/// 按 `chain` 顺序尝试注入，第一个成功的方式通过 "paste-method-used" 事件告知前端。
+/// 全部失败时返回最后一个错误。
+///
+/// SendInput 被 UIPI 拦截时一个事件都发不出去，失败后换方式不会重复输入文本。
+pub async fn paste_text_with_fallback(
+    app_handle: &tauri::AppHandle,
+    text: &str,
+    chain: &[String],
+) -> Result<String, AppError> {
+    use tauri::Emitter;
+
+    let mut failed_methods = Vec::new();
+    let mut last_error = None;
+    for method in chain {
+        match paste_text_impl(app_handle, text, method).await {
+            Ok(_) => {
+                if !failed_methods.is_empty() {
+                    log::info!(
+                        "输入方式 {} 兜底成功（已失败: {:?}）",
+                        method,
+                        failed_methods
+                    );
+                }
+                let _ = app_handle.emit(
+                    "paste-method-used",
+                    PasteMethodUsedPayload {
+                        method: method.clone(),
+                        fallback: !failed_methods.is_empty(),
+                        failed_methods,
+                    },
+                );
+                return Ok(method.clone());
+            }
+            Err(e) => {
+                log::warn!("输入方式 {} 失败: {}", method, e);
+                failed_methods.push(method.clone());
+                last_error = Some(e);
+            }
+        }
+    }
+    Err(last_error.unwrap_or_else(|| AppError::Other("没有可用的输入方式".to_string())))
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        replacement_value_if_raw_suffix_unchanged, should_restore_clipboard_after_paste,
-        try_all_then_standard,
+        clipboard_retry_delay_ms, effective_clipboard_write_retries, effective_first_char_delay_ms,
+        input_method_chain, replacement_value_if_raw_suffix_unchanged,
+        should_restore_clipboard_after_paste, try_all_then_standard, write_clipboard_with_retry,
+        DEFAULT_CLIPBOARD_WRITE_RETRIES, DEFAULT_FIRST_CHAR_DELAY_MS, MAX_CLIPBOARD_WRITE_RETRIES,
+        MAX_FIRST_CHAR_DELAY_MS,
     };
 
+    #[test]
+    fn first_char_delay_defaults_and_is_capped() {
+        assert_eq!(
+            effective_first_char_delay_ms(None),
+            DEFAULT_FIRST_CHAR_DELAY_MS
+        );
+        assert_eq!(effective_first_char_delay_ms(Some(0)), 0);
+        assert_eq!(
+            effective_first_char_delay_ms(Some(10_000)),
+            MAX_FIRST_CHAR_DELAY_MS
+        );
+    }
+
+    #[test]
+    fn clipboard_write_retries_with_backoff_until_success() {
+        assert_eq!(
+            effective_clipboard_write_retries(None),
+            DEFAULT_CLIPBOARD_WRITE_RETRIES
+        );
+        assert_eq!(
+            effective_clipboard_write_retries(Some(99)),
+            MAX_CLIPBOARD_WRITE_RETRIES
+        );
+        assert_eq!(
+            (0..5).map(clipboard_retry_delay_ms).collect::<Vec<_>>(),
+            vec![25, 50, 100, 200, 400]
+        );
+
+        let mut calls = 0;
+        let result = write_clipboard_with_retry(3, || {
+            calls += 1;
+            if calls < 3 {
+                Err("locked")
+            } else {
+                Ok(())
+            }
+        });
+        assert!(result.is_ok());
+        assert_eq!(calls, 3);
+
+        let mut calls = 0;
+        let result = write_clipboard_with_retry(0, || {
+            calls += 1;
+            Err("locked")
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn input_method_chain_puts_primary_first_and_dedups() {
+        let fallback = vec![
+            "clipboard".to_string(),
+            "sendInput".to_string(),
+            "copyOnly".to_string(),
+            "clipboard".to_string(),
+        ];
+        assert_eq!(
+            input_method_chain("sendInput", &fallback),
+            vec!["sendInput", "clipboard", "copyOnly"]
+        );
+        assert_eq!(input_method_chain("copyOnly", &[]), vec!["copyOnly"]);
+    }
+
     #[test]
     fn clipboard_snapshot_keeps_full_formats_when_they_are_readable() {
         let mut attempts = Vec::new();
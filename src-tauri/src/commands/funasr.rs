@@ -1,7 +1,9 @@
 use tauri::Emitter;
 
+use crate::services::download_service::DownloadSpaceReport;
 use crate::services::funasr_service;
 use crate::services::llm_provider;
+use crate::state::user_profile::AsrFormattingSettings;
 use crate::state::AppState;
 use crate::utils::{paths, AppError};
 
@@ -51,6 +53,171 @@ pub async fn transcribe_audio(
     funasr_service::transcribe(state.inner(), audio_data, &app_handle).await
 }
 
+const MIN_TRANSCRIBE_PCM_SAMPLE_RATE: u32 = 8000;
+const MAX_TRANSCRIBE_PCM_SAMPLE_RATE: u32 = 384_000;
+/// 与 WAV 入口同一份字节上限，按 16-bit 采样折算
+const MAX_TRANSCRIBE_PCM_SAMPLES: usize = MAX_TRANSCRIBE_AUDIO_BYTES / 2;
+
+fn validate_transcribe_pcm(sample_count: usize, sample_rate: u32) -> Result<(), AppError> {
+    if !(MIN_TRANSCRIBE_PCM_SAMPLE_RATE..=MAX_TRANSCRIBE_PCM_SAMPLE_RATE).contains(&sample_rate) {
+        return Err(AppError::Audio(format!(
+            "不支持的采样率: {}Hz，可选范围 {}-{}Hz",
+            sample_rate, MIN_TRANSCRIBE_PCM_SAMPLE_RATE, MAX_TRANSCRIBE_PCM_SAMPLE_RATE
+        )));
+    }
+    if sample_count > MAX_TRANSCRIBE_PCM_SAMPLES {
+        return Err(AppError::Asr(format!(
+            "音频过大：{} 个采样点超过上限 {} 个",
+            sample_count, MAX_TRANSCRIBE_PCM_SAMPLES
+        )));
+    }
+    let duration_sec = sample_count as f64 / sample_rate as f64;
+    if duration_sec < crate::services::audio_service::MIN_AUDIO_DURATION_SEC {
+        return Err(AppError::Audio(format!(
+            "音频太短：{:.2}s，至少需要 {}s",
+            duration_sec,
+            crate::services::audio_service::MIN_AUDIO_DURATION_SEC
+        )));
+    }
+    Ok(())
+}
+
+/// 直接转写单声道 16-bit PCM（例如前端 Web Audio 自行采集），调用方不必自己封装 WAV。
+#[tauri::command]
+pub async fn transcribe_pcm(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    samples: Vec<i16>,
+    sample_rate: u32,
+) -> Result<funasr_service::TranscriptionResult, AppError> {
+    use crate::services::audio_service;
+
+    validate_transcribe_pcm(samples.len(), sample_rate)?;
+    let wav = tokio::task::spawn_blocking(move || {
+        let pcm = audio_service::resample_to_16k(&samples, sample_rate).map_err(AppError::Audio)?;
+        audio_service::encode_wav(&pcm, audio_service::TARGET_SAMPLE_RATE)
+    })
+    .await
+    .map_err(|e| AppError::Audio(format!("PCM 预处理任务失败: {}", e)))??;
+    funasr_service::transcribe(state.inner(), wav, &app_handle).await
+}
+
+/// 本地引擎经 Python 端解码，可接受常见压缩格式；在线引擎只收 WAV。
+const CLIPBOARD_AUDIO_EXTENSIONS: [&str; 8] =
+    ["wav", "mp3", "flac", "m4a", "aac", "ogg", "opus", "webm"];
+
+/// 剪贴板里没有文件列表（CF_HDROP）时，从文本里取出文件路径：兼容文件管理器
+/// 写入的 `file://` URI 列表、GNOME 的 `x-special/gnome-copied-files` 头以及
+/// 带引号的路径，只取第一项。
+fn clipboard_text_to_file_path(text: &str) -> Option<std::path::PathBuf> {
+    text.lines()
+        .map(|line| line.trim().trim_matches('"'))
+        .filter(|line| {
+            !line.is_empty() && !line.starts_with("x-special/") && !matches!(*line, "copy" | "cut")
+        })
+        .find_map(|line| match line.strip_prefix("file://") {
+            Some(uri) => {
+                let decoded = percent_decode(uri.strip_prefix("localhost").unwrap_or(uri))?;
+                // file:///C:/x → C:/x
+                let bytes = decoded.as_bytes();
+                let decoded = if bytes.len() > 2 && bytes[0] == b'/' && bytes[2] == b':' {
+                    decoded[1..].to_string()
+                } else {
+                    decoded
+                };
+                Some(std::path::PathBuf::from(decoded))
+            }
+            None => Some(std::path::PathBuf::from(line)),
+        })
+}
+
+fn percent_decode(input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = input.get(i + 1..i + 3)?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+/// 转写剪贴板里复制的音频文件（文件管理器中 "复制" 文件后直接调用）。
+#[tauri::command]
+pub async fn transcribe_clipboard_file(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<funasr_service::TranscriptionResult, AppError> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    let copied_file = crate::commands::clipboard::read_clipboard_file_paths()
+        .into_iter()
+        .next();
+    let path = match copied_file {
+        Some(path) => path,
+        None => {
+            let text = app_handle
+                .clipboard()
+                .read_text()
+                .map_err(|_| AppError::Asr("剪贴板中没有文件".to_string()))?;
+            clipboard_text_to_file_path(&text)
+                .ok_or_else(|| AppError::Asr("剪贴板中没有文件".to_string()))?
+        }
+    };
+    if !path.is_file() {
+        return Err(AppError::Asr("剪贴板内容不是可访问的文件路径".to_string()));
+    }
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    if !CLIPBOARD_AUDIO_EXTENSIONS.contains(&extension.as_str()) {
+        return Err(AppError::Asr(format!(
+            "不支持的文件类型: {}",
+            path.display()
+        )));
+    }
+    let engine = paths::read_engine_config();
+    if paths::is_online_engine(&engine) && extension != "wav" {
+        return Err(AppError::Asr("在线引擎仅支持转写 WAV 文件".to_string()));
+    }
+
+    let size = tokio::fs::metadata(&path)
+        .await
+        .map_err(|e| AppError::Asr(format!("读取音频文件失败: {}", e)))?
+        .len();
+    if size > MAX_TRANSCRIBE_AUDIO_BYTES as u64 {
+        return Err(AppError::Asr(format!(
+            "音频过大：{} 字节超过上限 {} 字节",
+            size, MAX_TRANSCRIBE_AUDIO_BYTES
+        )));
+    }
+    let audio_data = tokio::fs::read(&path)
+        .await
+        .map_err(|e| AppError::Asr(format!("读取音频文件失败: {}", e)))?;
+    log::info!(
+        "转写剪贴板音频文件: {} ({} 字节)",
+        path.display(),
+        audio_data.len()
+    );
+
+    crate::services::audio_service::transcribe_audio_file(
+        &app_handle,
+        state.inner(),
+        &engine,
+        audio_data,
+        &extension,
+    )
+    .await
+}
+
 #[tauri::command]
 pub async fn check_funasr_status(
     app_handle: tauri::AppHandle,
@@ -59,6 +226,73 @@ pub async fn check_funasr_status(
     funasr_service::check_status(state.inner(), &app_handle).await
 }
 
+/// 只读诊断：下一次启动引擎时子进程会拿到的可执行文件、参数和环境变量。
+#[tauri::command]
+pub async fn get_server_environment(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<funasr_service::ServerEnvironmentReport, AppError> {
+    Ok(funasr_service::describe_server_environment(&app_handle, state.inner()).await)
+}
+
+/// 服务端脚本的协议版本：运行中引擎的握手上报值 + 磁盘脚本里的版本常量。
+#[tauri::command]
+pub async fn get_server_version(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<funasr_service::ServerVersionInfo, AppError> {
+    Ok(funasr_service::server_version_info(&app_handle, state.inner()).await)
+}
+
+/// 最近一次引擎启动耗时；本次运行还没启动成功过时为 None。
+#[tauri::command]
+pub async fn get_last_startup_time(
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<funasr_service::EngineStartupTiming>, AppError> {
+    Ok(state.engine.last_startup.lock().clone())
+}
+
+/// Python 引擎 stderr 日志（funasr_stderr.log）的最后 `lines` 行，省略时取默认行数。
+#[tauri::command]
+pub async fn get_server_stderr(
+    lines: Option<usize>,
+) -> Result<funasr_service::ServerStderrTail, AppError> {
+    let lines = lines.unwrap_or(funasr_service::DEFAULT_SERVER_STDERR_LINES);
+    if lines == 0 || lines > funasr_service::MAX_SERVER_STDERR_LINES {
+        return Err(AppError::Other(format!(
+            "行数必须在 1-{} 之间",
+            funasr_service::MAX_SERVER_STDERR_LINES
+        )));
+    }
+    tokio::task::spawn_blocking(move || funasr_service::server_stderr_tail(lines))
+        .await
+        .map_err(|e| AppError::Other(format!("读取引擎日志失败: {}", e)))?
+}
+
+/// 崩溃遗留的临时音频（`light_whisper_audio_*.wav`）的数量和总大小。
+#[tauri::command]
+pub async fn get_temp_usage() -> Result<funasr_service::TempAudioUsage, AppError> {
+    tokio::task::spawn_blocking(funasr_service::temp_audio_usage)
+        .await
+        .map_err(|e| AppError::Other(format!("统计临时音频失败: {}", e)))
+}
+
+/// 删除遗留的临时音频，只处理本应用命名格式的文件。
+#[tauri::command]
+pub async fn clear_temp_audio() -> Result<funasr_service::TempAudioCleanupReport, AppError> {
+    tokio::task::spawn_blocking(funasr_service::clear_temp_audio_files)
+        .await
+        .map_err(|e| AppError::Other(format!("清理临时音频失败: {}", e)))
+}
+
+/// 应用自身与引擎子进程的常驻内存，引擎未运行时只报告应用部分。
+#[tauri::command]
+pub async fn get_resource_usage(
+    state: tauri::State<'_, AppState>,
+) -> Result<crate::services::resource_service::ResourceUsage, AppError> {
+    Ok(crate::services::resource_service::resource_usage(state.inner()).await)
+}
+
 #[tauri::command]
 pub async fn check_model_files() -> Result<funasr_service::ModelCheckResult, AppError> {
     funasr_service::check_model_files().await
@@ -72,6 +306,33 @@ pub async fn download_models(
     crate::services::download_service::run_download(&app_handle, state.inner()).await
 }
 
+/// 下载前检查模型目录所在磁盘的剩余空间是否够当前引擎缺失的模型。
+#[tauri::command]
+pub async fn check_download_space() -> Result<DownloadSpaceReport, AppError> {
+    Ok(crate::services::download_service::check_download_space())
+}
+
+/// 设置模型下载的最大尝试次数和无响应超时，下次下载生效。
+#[tauri::command]
+pub async fn set_download_retry_settings(
+    state: tauri::State<'_, AppState>,
+    max_attempts: u32,
+    stall_timeout_secs: u64,
+) -> Result<(), AppError> {
+    crate::services::profile_service::validate_download_retry_settings(
+        max_attempts,
+        stall_timeout_secs,
+    )
+    .map_err(AppError::Other)?;
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.download_retry_settings = crate::state::user_profile::DownloadRetrySettings {
+            max_attempts,
+            stall_timeout_secs,
+        };
+    });
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn cancel_model_download(state: tauri::State<'_, AppState>) -> Result<String, AppError> {
     let cancellation = {
@@ -117,6 +378,37 @@ pub async fn restart_funasr(
     Ok("FunASR 服务器已重启".to_string())
 }
 
+/// 只重新加载模型、保留已预热的进程，比 restart_funasr 快；服务端不支持时
+/// 改为完整重启。
+#[tauri::command]
+pub async fn reload_model(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, AppError> {
+    if paths::is_online_engine(&paths::read_engine_config()) {
+        return restart_funasr(app_handle, state).await;
+    }
+    if state.recording.recording.lock().is_some() {
+        return Err(AppError::Audio(
+            crate::commands::audio::RECORDING_ALREADY_ACTIVE_ERROR.into(),
+        ));
+    }
+    if state.engine.is_funasr_starting() {
+        log::info!("FunASR 正在启动中，跳过本次重新加载请求");
+        return Ok("FunASR 正在启动中，跳过重新加载".to_string());
+    }
+
+    let reloaded = {
+        let _lifecycle_guard = state.engine.funasr_lifecycle_op.lock().await;
+        funasr_service::reload_model(&app_handle, state.inner()).await?
+    };
+    if reloaded {
+        return Ok("模型已重新加载".to_string());
+    }
+    log::info!("引擎不支持原地重新加载模型，改为完整重启");
+    restart_funasr(app_handle, state).await
+}
+
 #[tauri::command]
 pub async fn get_engine() -> Result<String, AppError> {
     Ok(paths::read_engine_config())
@@ -186,12 +478,18 @@ pub async fn set_engine(
     state: tauri::State<'_, AppState>,
     engine: String,
 ) -> Result<String, AppError> {
-    const VALID: &[&str] = &["sensevoice", "whisper", "glm-asr", "alibaba-asr"];
-    if !VALID.contains(&engine.as_str()) {
+    if !paths::SUPPORTED_ENGINES.contains(&engine.as_str()) {
         return Err(AppError::Other(format!(
             "不支持的引擎类型: {}，可选值: {}",
             engine,
-            VALID.join(", ")
+            paths::SUPPORTED_ENGINES.join(", ")
+        )));
+    }
+    if let Some(forced) = paths::engine_env_override().filter(|forced| *forced != engine) {
+        return Err(AppError::Other(format!(
+            "引擎已由环境变量 {} 固定为 {}，需取消该变量后再切换",
+            paths::ENGINE_ENV_VAR,
+            forced
         )));
     }
 
@@ -226,6 +524,261 @@ pub async fn set_engine(
     Ok(engine)
 }
 
+/// 参与对比的本地引擎
+const COMPARE_ENGINES: [&str; 2] = ["sensevoice", "whisper"];
+/// 未指定样本时合成的音频时长
+const COMPARE_SYNTHETIC_SAMPLE_SEC: f64 = 3.0;
+
+/// 引擎切换热键的下一个引擎：只在模型齐全的本地引擎之间轮换（在线引擎需要
+/// API Key，仍在设置里切换）。当前是在线引擎时从第一个可用的本地引擎开始。
+fn next_cycle_engine(current: &str, available: impl Fn(&str) -> bool) -> Option<&'static str> {
+    let start = COMPARE_ENGINES
+        .iter()
+        .position(|engine| *engine == current)
+        .map_or(0, |index| index + 1);
+    (0..COMPARE_ENGINES.len())
+        .map(|offset| COMPARE_ENGINES[(start + offset) % COMPARE_ENGINES.len()])
+        .find(|engine| *engine != current && available(engine))
+}
+
+/// 切到下一个本地引擎并返回它；原引擎在运行时新引擎一并启动。录音中拒绝切换。
+pub(crate) async fn cycle_engine(
+    app_handle: &tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, AppError> {
+    if state.recording.recording.lock().is_some() {
+        return Err(AppError::Other("录音中不能切换引擎".to_string()));
+    }
+    let current = paths::read_engine_config();
+    let next = next_cycle_engine(&current, |engine| {
+        funasr_service::inspect_model_files_for_engine(engine).all_present
+    })
+    .ok_or_else(|| AppError::Other("没有其他模型齐全的本地引擎可切换".to_string()))?;
+    let was_ready = state.is_funasr_ready();
+    let engine = set_engine(app_handle.clone(), state.clone(), next.to_string()).await?;
+    let _ = app_handle.emit(
+        "engine-cycled",
+        serde_json::json!({ "from": current, "to": &engine }),
+    );
+    if was_ready {
+        funasr_service::start_server(app_handle, state.inner()).await?;
+    }
+    Ok(engine)
+}
+
+/// 当前引擎排在最前，少切换一次。
+fn compare_engine_order(current: &str) -> Vec<&'static str> {
+    let mut order = COMPARE_ENGINES.to_vec();
+    order.sort_by_key(|engine| *engine != current);
+    order
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineComparisonEntry {
+    engine: &'static str,
+    text: Option<String>,
+    /// 从发出转写到拿到结果的耗时
+    latency_ms: Option<u64>,
+    /// 需要启动引擎时的启动耗时；引擎本来就在运行时为 None
+    startup_ms: Option<u64>,
+    missing_models: Vec<String>,
+    error: Option<String>,
+}
+
+impl EngineComparisonEntry {
+    fn failed(engine: &'static str, error: String) -> Self {
+        Self {
+            engine,
+            text: None,
+            latency_ms: None,
+            startup_ms: None,
+            missing_models: Vec::new(),
+            error: Some(error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineComparison {
+    /// "file" | "synthetic"
+    source: &'static str,
+    results: Vec<EngineComparisonEntry>,
+    /// 对比结束后恢复的引擎
+    restored_engine: String,
+    /// 恢复原引擎失败的原因
+    restore_error: Option<String>,
+}
+
+async fn compare_one_engine(
+    app_handle: &tauri::AppHandle,
+    state: &tauri::State<'_, AppState>,
+    engine: &'static str,
+    audio: &[u8],
+) -> EngineComparisonEntry {
+    let models = funasr_service::inspect_model_files_for_engine(engine);
+    if !models.all_present {
+        return EngineComparisonEntry {
+            missing_models: models.missing_models,
+            ..EngineComparisonEntry::failed(engine, "模型文件未下载，已跳过".to_string())
+        };
+    }
+    if paths::read_engine_config() != engine {
+        if let Err(err) = set_engine(app_handle.clone(), state.clone(), engine.to_string()).await {
+            return EngineComparisonEntry::failed(engine, format!("切换引擎失败: {}", err));
+        }
+    }
+
+    let mut startup_ms = None;
+    if !state.is_funasr_ready() {
+        let started = std::time::Instant::now();
+        if let Err(err) = funasr_service::start_server(app_handle, state.inner()).await {
+            return EngineComparisonEntry::failed(engine, format!("启动引擎失败: {}", err));
+        }
+        if !state.is_funasr_ready() {
+            return EngineComparisonEntry::failed(engine, "引擎未就绪".to_string());
+        }
+        startup_ms = Some(started.elapsed().as_millis() as u64);
+    }
+
+    let started = std::time::Instant::now();
+    let result = funasr_service::transcribe(state.inner(), audio.to_vec(), app_handle).await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    match result {
+        Ok(result) if result.success => EngineComparisonEntry {
+            engine,
+            text: Some(result.text),
+            latency_ms: Some(latency_ms),
+            startup_ms,
+            missing_models: Vec::new(),
+            error: None,
+        },
+        Ok(result) => EngineComparisonEntry {
+            latency_ms: Some(latency_ms),
+            startup_ms,
+            ..EngineComparisonEntry::failed(
+                engine,
+                result.error.unwrap_or_else(|| "转写失败".to_string()),
+            )
+        },
+        Err(err) => EngineComparisonEntry {
+            startup_ms,
+            ..EngineComparisonEntry::failed(engine, err.to_string())
+        },
+    }
+}
+
+/// 对比结束（含提前返回）时清掉 `comparison_active`
+struct ComparisonActiveGuard<'a>(&'a std::sync::atomic::AtomicBool);
+
+impl Drop for ComparisonActiveGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+/// 用同一段音频依次跑 SenseVoice 和 Whisper，返回两边的文本和耗时，
+/// 结束后切回原引擎：原引擎在运行时一并重新启动，对比前没在运行的引擎会被停掉。
+/// 对比期间拒绝开始录音。`sample_path` 为空时使用合成音频，只适合比较延迟。
+#[tauri::command]
+pub async fn compare_engines(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    sample_path: Option<String>,
+) -> Result<EngineComparison, AppError> {
+    use crate::services::audio_service;
+    use std::sync::atomic::Ordering;
+
+    if state
+        .engine
+        .comparison_active
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return Err(AppError::Other("引擎对比正在进行中".to_string()));
+    }
+    let _comparison_guard = ComparisonActiveGuard(&state.engine.comparison_active);
+    if state.recording.recording.lock().is_some() {
+        return Err(AppError::Audio(
+            crate::commands::audio::RECORDING_ALREADY_ACTIVE_ERROR.into(),
+        ));
+    }
+    if let Some(forced) = paths::engine_env_override() {
+        return Err(AppError::Other(format!(
+            "引擎已由环境变量 {} 固定为 {}，无法对比",
+            paths::ENGINE_ENV_VAR,
+            forced
+        )));
+    }
+    let (source, audio) = match sample_path.as_deref().map(str::trim) {
+        Some(path) if !path.is_empty() => {
+            let size = tokio::fs::metadata(path)
+                .await
+                .map_err(|e| AppError::Asr(format!("读取音频文件失败: {}", e)))?
+                .len();
+            if size > MAX_TRANSCRIBE_AUDIO_BYTES as u64 {
+                return Err(AppError::Asr(format!(
+                    "音频过大：{} 字节超过上限 {} 字节",
+                    size, MAX_TRANSCRIBE_AUDIO_BYTES
+                )));
+            }
+            let audio = tokio::fs::read(path)
+                .await
+                .map_err(|e| AppError::Asr(format!("读取音频文件失败: {}", e)))?;
+            ("file", audio)
+        }
+        _ => (
+            "synthetic",
+            audio_service::synthetic_sample_wav(COMPARE_SYNTHETIC_SAMPLE_SEC)?,
+        ),
+    };
+
+    let original_engine = paths::read_engine_config();
+    let original_ready = state.is_funasr_ready();
+    log::info!("开始引擎对比（{}），当前引擎: {}", source, original_engine);
+    let mut results = Vec::with_capacity(COMPARE_ENGINES.len());
+    for engine in compare_engine_order(&original_engine) {
+        let entry = compare_one_engine(&app_handle, &state, engine, &audio).await;
+        if let Some(err) = &entry.error {
+            log::warn!("引擎对比：{} 失败: {}", engine, err);
+        }
+        results.push(entry);
+    }
+
+    let mut restore_error = None;
+    if paths::read_engine_config() != original_engine {
+        match set_engine(app_handle.clone(), state.clone(), original_engine.clone()).await {
+            Ok(_) if original_ready && !paths::is_online_engine(&original_engine) => {
+                if let Err(err) = funasr_service::start_server(&app_handle, state.inner()).await {
+                    restore_error = Some(format!("重新启动原引擎失败: {}", err));
+                }
+            }
+            Ok(_) => {}
+            Err(err) => restore_error = Some(format!("切回原引擎失败: {}", err)),
+        }
+    }
+    // 原引擎对比前没在运行、却被对比拉起来了（另一个引擎缺模型被跳过时）
+    if restore_error.is_none()
+        && !original_ready
+        && !paths::is_online_engine(&original_engine)
+        && state.is_funasr_ready()
+    {
+        if let Err(err) = funasr_service::stop_server(state.inner()).await {
+            restore_error = Some(format!("停止对比时启动的引擎失败: {}", err));
+        }
+    }
+    if let Some(err) = &restore_error {
+        log::error!("引擎对比结束后{}", err);
+    }
+    Ok(EngineComparison {
+        source,
+        results,
+        restored_engine: paths::read_engine_config(),
+        restore_error,
+    })
+}
+
 #[tauri::command]
 pub async fn set_online_asr_api_key(
     app_handle: tauri::AppHandle,
@@ -271,6 +824,23 @@ pub async fn get_online_asr_api_key(state: tauri::State<'_, AppState>) -> Result
     Ok(state.read_online_asr_api_key())
 }
 
+/// 用一段合成音频测试 OpenAI 兼容转写接口的地址和 Key，不保存任何设置。
+#[tauri::command]
+pub async fn test_remote_endpoint(
+    state: tauri::State<'_, AppState>,
+    url: String,
+    api_key: String,
+    model: Option<String>,
+) -> Result<crate::services::remote_asr_service::RemoteEndpointTestReport, AppError> {
+    crate::services::remote_asr_service::test_endpoint(
+        state.inner(),
+        &url,
+        &api_key,
+        model.as_deref(),
+    )
+    .await
+}
+
 #[tauri::command]
 pub async fn get_online_asr_endpoint() -> Result<serde_json::Value, AppError> {
     Ok(serde_json::json!({
@@ -472,6 +1042,290 @@ fn models_dir_update_result(
     }
 }
 
+/// 调试开关：Windows 上让 Python 引擎显示控制台窗口，下次启动引擎时生效。
+#[tauri::command]
+pub async fn set_show_python_console(
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.show_python_console = enabled;
+    });
+    log::info!(
+        "Python 调试控制台已{}，重启引擎后生效",
+        if enabled { "开启" } else { "关闭" }
+    );
+    Ok(())
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataDirUpdateResult {
+    message: String,
+    /// 数据目录在进程启动时解析，切换后需重启应用
+    restart_required: bool,
+    /// 当前数据目录已有数据：不会自动复制，需用户自行迁移
+    current_has_data: bool,
+    /// 目标目录已有数据：重启后将直接使用目标目录里的设置和历史
+    target_has_data: bool,
+}
+
+#[tauri::command]
+pub async fn get_data_dir() -> Result<serde_json::Value, AppError> {
+    let custom = paths::read_custom_data_dir();
+    let env_override = std::env::var_os("LIGHT_WHISPER_DATA_DIR").is_some();
+    Ok(serde_json::json!({
+        "path": paths::strip_win_prefix(paths::get_data_dir()),
+        "configured": custom.as_deref().map(paths::strip_win_prefix),
+        "is_custom": custom.is_some(),
+        "env_override": env_override,
+        "startup_error": paths::data_dir_issue().map(|issue| issue.error.clone()),
+        "using_fallback": paths::data_dir_issue().is_some_and(|issue| issue.fallback.is_some()),
+    }))
+}
+
+/// 诊断用：列出数据目录、HF 缓存、脚本、日志等实际解析到的路径及是否存在。
+#[tauri::command]
+pub async fn get_paths(app_handle: tauri::AppHandle) -> Result<Vec<paths::ResolvedPath>, AppError> {
+    Ok(paths::resolved_paths(&app_handle))
+}
+
+/// 切换数据目录（设置、历史、引擎配置、日志；未自定义模型目录时也包括 HF 缓存）。
+/// 目标写入系统配置目录下的 bootstrap.json，重启后生效。
+#[tauri::command]
+pub async fn set_data_dir(path: Option<String>) -> Result<DataDirUpdateResult, AppError> {
+    let target = path
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(std::path::PathBuf::from);
+    let current = paths::get_data_dir().clone();
+
+    if let Some(dir) = &target {
+        if !dir.is_absolute() {
+            return Err(AppError::Other("数据目录必须是绝对路径".to_string()));
+        }
+        // 创建并试写一次，避免重启后才发现目录不可用
+        let dir = dir.clone();
+        tokio::task::spawn_blocking(move || -> std::io::Result<()> {
+            std::fs::create_dir_all(&dir)?;
+            let probe = dir.join(".light-whisper-write-test");
+            std::fs::write(&probe, b"ok")?;
+            std::fs::remove_file(&probe)
+        })
+        .await
+        .map_err(|e| AppError::Other(format!("检查数据目录失败: {}", e)))?
+        .map_err(|e| AppError::Other(format!("数据目录不可写: {}", e)))?;
+    }
+
+    let effective_target = target.clone().unwrap_or_else(paths::get_default_data_dir);
+    let canon = |p: &std::path::Path| std::fs::canonicalize(p).unwrap_or_else(|_| p.to_path_buf());
+    let unchanged = canon(&current) == canon(&effective_target);
+
+    paths::write_custom_data_dir(target.as_deref())
+        .map_err(|e| AppError::Other(format!("写入配置失败: {}", e)))?;
+
+    if std::env::var_os("LIGHT_WHISPER_DATA_DIR").is_some() {
+        log::warn!("LIGHT_WHISPER_DATA_DIR 环境变量已设置，自定义数据目录不会生效");
+    }
+    if unchanged {
+        return Ok(DataDirUpdateResult {
+            message: "路径未变化".to_string(),
+            restart_required: false,
+            current_has_data: false,
+            target_has_data: false,
+        });
+    }
+
+    let current_has_data = paths::dir_has_app_data(&current);
+    let target_has_data = paths::dir_has_app_data(&effective_target);
+    log::info!(
+        "数据目录将在重启后切换: {} -> {}（当前目录有数据: {}，目标目录有数据: {}）",
+        current.display(),
+        effective_target.display(),
+        current_has_data,
+        target_has_data
+    );
+    Ok(DataDirUpdateResult {
+        message: if target.is_none() {
+            "已恢复默认数据目录，重启后生效".to_string()
+        } else {
+            "数据目录已更新，重启后生效".to_string()
+        },
+        restart_required: true,
+        current_has_data,
+        target_has_data,
+    })
+}
+
+/// 运行时切换本地引擎的 Python 日志级别（debug/info/warning/error），无需重启。
+#[tauri::command]
+pub async fn set_server_log_level(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    level: String,
+) -> Result<String, AppError> {
+    funasr_service::set_server_log_level(&app_handle, state.inner(), &level).await
+}
+
+/// 设置本地引擎的 ITN / 标点开关；引擎运行中时立即下发，否则在下次启动后生效。
+#[tauri::command]
+pub async fn set_asr_formatting(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    itn: bool,
+    punctuation: bool,
+) -> Result<(), AppError> {
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.asr_formatting = AsrFormattingSettings { itn, punctuation };
+    });
+    if state.is_funasr_ready() {
+        funasr_service::apply_asr_formatting(&app_handle, state.inner(), false).await?;
+    }
+    Ok(())
+}
+
+/// 开关说话人分离，返回引擎实际生效的值；None 表示引擎未运行或不支持（设置仍会保存）。
+#[tauri::command]
+pub async fn set_diarization(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+) -> Result<Option<bool>, AppError> {
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.diarization_enabled = enabled;
+    });
+    if !state.is_funasr_ready() {
+        return Ok(None);
+    }
+    funasr_service::apply_diarization(&app_handle, state.inner()).await
+}
+
+/// 保存语言切换热键的轮换列表，按正在运行的引擎声明的语言校验；返回规范化后的列表。
+#[tauri::command]
+pub async fn set_language_cycle(
+    state: tauri::State<'_, AppState>,
+    languages: Vec<String>,
+) -> Result<Vec<String>, AppError> {
+    let engine = crate::utils::paths::read_engine_config();
+    let supported = funasr_service::server_supported_languages(state.inner());
+    let cycle = funasr_service::normalize_language_cycle(&engine, supported.as_deref(), languages)
+        .map_err(AppError::Other)?;
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.language_cycle = cycle.clone();
+    });
+    Ok(cycle)
+}
+
+/// 切换到轮换列表里的下一种识别语言；返回新的语言，None 表示自动检测。
+#[tauri::command]
+pub async fn cycle_language(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<String>, AppError> {
+    funasr_service::cycle_language(&app_handle, state.inner()).await
+}
+
+/// 开启后，启动时若配置的本地引擎缺模型而另一个本地引擎模型齐全，自动改用后者。
+#[tauri::command]
+pub async fn set_auto_fallback_engine(
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.auto_fallback_engine = enabled;
+    });
+    Ok(())
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CpuThreadsInfo {
+    /// 用户设置；None 表示自动
+    configured: Option<u32>,
+    available: u32,
+}
+
+#[tauri::command]
+pub async fn get_cpu_threads(
+    state: tauri::State<'_, AppState>,
+) -> Result<CpuThreadsInfo, AppError> {
+    Ok(CpuThreadsInfo {
+        configured: state.with_profile(|profile| profile.cpu_threads),
+        available: funasr_service::available_cpu_threads(),
+    })
+}
+
+/// 设置 CPU 推理线程数（None 恢复自动），重启引擎后生效。
+#[tauri::command]
+pub async fn set_cpu_threads(
+    state: tauri::State<'_, AppState>,
+    threads: Option<u32>,
+) -> Result<String, AppError> {
+    if let Some(threads) = threads {
+        funasr_service::validate_cpu_threads(threads)?;
+    }
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.cpu_threads = threads;
+    });
+    Ok("推理线程数已更新，重启引擎后生效".to_string())
+}
+
+/// 设置引擎命令失败后的确认窗口：窗口内进程仍存活就不判定崩溃，写入失败
+/// 重试一次；None 恢复默认，0 关闭。返回生效值。
+#[tauri::command]
+pub async fn set_server_crash_grace_ms(
+    state: tauri::State<'_, AppState>,
+    ms: Option<u64>,
+) -> Result<u64, AppError> {
+    if let Some(ms) = ms.filter(|ms| *ms > funasr_service::MAX_SERVER_CRASH_GRACE_MS) {
+        return Err(AppError::Other(format!(
+            "崩溃确认窗口不能超过 {}ms（当前 {}ms）",
+            funasr_service::MAX_SERVER_CRASH_GRACE_MS,
+            ms
+        )));
+    }
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.server_crash_grace_ms = ms;
+    });
+    Ok(funasr_service::effective_crash_grace_ms(ms))
+}
+
+fn current_vram_budget_report(state: &AppState) -> funasr_service::VramBudgetReport {
+    funasr_service::vram_budget_report(
+        &paths::read_engine_config(),
+        state.with_profile(|profile| profile.max_vram_gb),
+        *state.engine.gpu_memory_total.lock(),
+    )
+}
+
+#[tauri::command]
+pub async fn get_vram_budget(
+    state: tauri::State<'_, AppState>,
+) -> Result<funasr_service::VramBudgetReport, AppError> {
+    Ok(current_vram_budget_report(state.inner()))
+}
+
+/// 设置显存预算（None 不限制），重启引擎后生效。返回按新预算会选用的档位，
+/// 当前模型放不下时附带警告。
+#[tauri::command]
+pub async fn set_max_vram_gb(
+    state: tauri::State<'_, AppState>,
+    max_vram_gb: Option<f64>,
+) -> Result<funasr_service::VramBudgetReport, AppError> {
+    if let Some(gb) = max_vram_gb {
+        funasr_service::validate_max_vram_gb(gb)?;
+    }
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.max_vram_gb = max_vram_gb;
+    });
+    let report = current_vram_budget_report(state.inner());
+    if let Some(warning) = &report.warning {
+        log::warn!("{}", warning);
+    }
+    Ok(report)
+}
+
 #[tauri::command]
 pub async fn pick_folder() -> Result<Option<String>, AppError> {
     let result = tokio::task::spawn_blocking(|| rfd::FileDialog::new().pick_folder())
@@ -807,3 +1661,74 @@ mod model_dir_migration_tests {
         let _ = std::fs::remove_dir_all(root);
     }
 }
+
+#[cfg(test)]
+mod compare_engine_tests {
+    use super::{compare_engine_order, next_cycle_engine};
+
+    #[test]
+    fn cycle_engine_skips_engines_without_models() {
+        assert_eq!(next_cycle_engine("sensevoice", |_| true), Some("whisper"));
+        assert_eq!(next_cycle_engine("whisper", |_| true), Some("sensevoice"));
+        assert_eq!(
+            next_cycle_engine("glm-asr", |engine| engine == "whisper"),
+            Some("whisper")
+        );
+        assert_eq!(next_cycle_engine("sensevoice", |e| e == "sensevoice"), None);
+    }
+
+    #[test]
+    fn current_local_engine_runs_first() {
+        assert_eq!(compare_engine_order("whisper"), ["whisper", "sensevoice"]);
+        assert_eq!(
+            compare_engine_order("sensevoice"),
+            ["sensevoice", "whisper"]
+        );
+        assert_eq!(compare_engine_order("glm-asr"), ["sensevoice", "whisper"]);
+    }
+}
+
+#[cfg(test)]
+mod clipboard_file_tests {
+    use super::clipboard_text_to_file_path;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_plain_and_uri_clipboard_paths() {
+        assert_eq!(
+            clipboard_text_to_file_path("  \"/tmp/a b.wav\"  "),
+            Some(PathBuf::from("/tmp/a b.wav"))
+        );
+        assert_eq!(
+            clipboard_text_to_file_path(
+                "x-special/gnome-copied-files\ncopy\nfile:///tmp/a%20b.wav\n"
+            ),
+            Some(PathBuf::from("/tmp/a b.wav"))
+        );
+        assert_eq!(
+            clipboard_text_to_file_path("file:///C:/Users/me/%E5%BD%95%E9%9F%B3.mp3"),
+            Some(PathBuf::from("C:/Users/me/录音.mp3"))
+        );
+    }
+
+    #[test]
+    fn rejects_empty_or_malformed_clipboard_text() {
+        assert_eq!(clipboard_text_to_file_path("  \n "), None);
+        assert_eq!(clipboard_text_to_file_path("file:///tmp/%zz.wav"), None);
+    }
+}
+
+#[cfg(test)]
+mod pcm_input_tests {
+    use super::validate_transcribe_pcm;
+
+    #[test]
+    fn pcm_input_rejects_bad_rate_and_length() {
+        assert!(validate_transcribe_pcm(16000, 16000).is_ok());
+        assert!(validate_transcribe_pcm(48000, 48000).is_ok());
+        assert!(validate_transcribe_pcm(16000, 0).is_err());
+        assert!(validate_transcribe_pcm(16000, 400_000).is_err());
+        assert!(validate_transcribe_pcm(4000, 16000).is_err());
+        assert!(validate_transcribe_pcm(super::MAX_TRANSCRIBE_PCM_SAMPLES + 1, 16000).is_err());
+    }
+}
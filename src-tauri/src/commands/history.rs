@@ -3,7 +3,8 @@ use std::time::Instant;
 use tauri::Emitter;
 
 use crate::services::{
-    ai_polish_service, alibaba_asr_service, funasr_service, glm_asr_service, history_service,
+    ai_polish_service, audio_service, funasr_service, history_service, recording_archive_service,
+    subtitle_export_service, usage_stats_service,
 };
 use crate::state::AppState;
 use crate::utils::{foreground, paths};
@@ -24,6 +25,38 @@ pub async fn get_transcription_history_stats() -> Result<history_service::Histor
     history_service::stats().await
 }
 
+/// 开关录音存档：打开后每次听写另存 WAV 和转写文本到数据目录的 recordings/，
+/// 识别失败和没识别到内容的录音也会存。设置保存到画像。
+#[tauri::command]
+pub async fn set_save_recordings(
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), String> {
+    state
+        .recording
+        .save_recordings
+        .store(enabled, std::sync::atomic::Ordering::Relaxed);
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.save_recordings = enabled;
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_recordings() -> Result<Vec<recording_archive_service::SavedRecording>, String> {
+    recording_archive_service::list().await
+}
+
+#[tauri::command]
+pub async fn get_usage_stats() -> Result<usage_stats_service::UsageStatsReport, String> {
+    usage_stats_service::report().await
+}
+
+#[tauri::command]
+pub async fn reset_usage_stats() -> Result<usage_stats_service::UsageStatsReport, String> {
+    usage_stats_service::reset().await
+}
+
 #[tauri::command]
 pub async fn delete_transcription_history(
     app_handle: tauri::AppHandle,
@@ -36,6 +69,52 @@ pub async fn delete_transcription_history(
     Ok(removed)
 }
 
+async fn history_text(id: i64) -> Result<String, String> {
+    let record = history_service::get(id)
+        .await?
+        .map(|stored| stored.record)
+        .ok_or_else(|| "找不到这条历史记录".to_string())?;
+    if record.text.trim().is_empty() {
+        return Err("这条记录没有可用的文本".into());
+    }
+    Ok(record.text)
+}
+
+/// 用当前输入方式重新注入一条历史文本；录音进行中时与自动粘贴一样排入待粘贴队列。
+#[tauri::command]
+pub async fn repaste_history(app_handle: tauri::AppHandle, id: i64) -> Result<(), String> {
+    let text = history_text(id).await?;
+    audio_service::repaste_text(&app_handle, &text)
+        .await
+        .map_err(|error| format!("重新粘贴历史文本失败: {error}"))
+}
+
+#[tauri::command]
+pub async fn copy_history(app_handle: tauri::AppHandle, id: i64) -> Result<(), String> {
+    let text = history_text(id).await?;
+    crate::commands::clipboard::write_text_to_clipboard_async(&app_handle, text)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+/// 修正一条历史记录的最终文本，原始 ASR 保留不变。
+#[tauri::command]
+pub async fn update_history(
+    app_handle: tauri::AppHandle,
+    id: i64,
+    text: String,
+) -> Result<bool, String> {
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Err("历史文本不能为空".into());
+    }
+    let updated = history_service::update_text(id, text).await?;
+    if updated {
+        emit_history_updated(&app_handle, Some(id));
+    }
+    Ok(updated)
+}
+
 fn export_markdown(records: &[history_service::HistoryRecord]) -> String {
     let mut output = String::from("# 轻语 Whisper 转写历史\n\n");
     for record in records {
@@ -125,19 +204,84 @@ pub async fn export_transcription_history(format: String) -> Result<Option<Strin
     Ok(Some(paths::strip_win_prefix(&path)))
 }
 
+/// 把一条历史记录导出为 SRT / WebVTT 字幕。`segments` 为带时间戳的分段，
+/// 缺省时整段文本作为一条字幕覆盖整个时长。`path` 缺省时弹出保存对话框；
+/// 用户取消返回 None。
+#[tauri::command]
+pub async fn export_transcription(
+    id: i64,
+    format: String,
+    path: Option<String>,
+    segments: Option<Vec<subtitle_export_service::SubtitleCue>>,
+) -> Result<Option<String>, String> {
+    let format = subtitle_export_service::SubtitleFormat::parse(&format)?;
+    let record = history_service::get(id)
+        .await?
+        .map(|stored| stored.record)
+        .ok_or_else(|| "找不到要导出的历史记录".to_string())?;
+    let cues = match segments.filter(|segments| !segments.is_empty()) {
+        Some(segments) => segments,
+        None => {
+            if record.text.trim().is_empty() {
+                return Err("这条记录没有可导出的文本".into());
+            }
+            vec![subtitle_export_service::single_cue(
+                &record.text,
+                record.duration_sec,
+            )]
+        }
+    };
+    let data = subtitle_export_service::render(format, &cues);
+
+    let path = match path.map(|value| value.trim().to_string()) {
+        Some(value) if !value.is_empty() => std::path::PathBuf::from(value),
+        _ => {
+            let extension = format.extension();
+            let filter_label = format.label();
+            let file_name = format!("light-whisper-{id}.{extension}");
+            let selected = tokio::task::spawn_blocking(move || {
+                let mut dialog = rfd::FileDialog::new()
+                    .add_filter(filter_label, &[extension])
+                    .set_file_name(file_name);
+                if let Some(directory) = dirs::download_dir() {
+                    dialog = dialog.set_directory(directory);
+                }
+                dialog.save_file()
+            })
+            .await
+            .map_err(|error| format!("选择字幕导出路径失败: {error}"))?;
+            let Some(path) = selected else {
+                return Ok(None);
+            };
+            path
+        }
+    };
+    tokio::fs::write(&path, data)
+        .await
+        .map_err(|error| format!("写入字幕文件失败: {error}"))?;
+    Ok(Some(paths::strip_win_prefix(&path)))
+}
+
 async fn transcribe_saved_audio(
     app_handle: &tauri::AppHandle,
     state: &AppState,
     audio_file: &str,
 ) -> Result<funasr_service::TranscriptionResult, String> {
-    let audio = history_service::read_audio(audio_file).await?;
-    let engine = paths::read_engine_config();
-    let result = match engine.as_str() {
-        "alibaba-asr" => alibaba_asr_service::transcribe(state, audio).await,
-        "glm-asr" => glm_asr_service::transcribe(state, audio).await,
-        _ => funasr_service::transcribe(state, audio, app_handle).await,
+    let mut audio = history_service::read_audio(audio_file).await?;
+    if audio_service::is_ogg_stream(&audio) {
+        // Ogg/Opus 历史音频先解回 16kHz PCM，再按 WAV 交给各引擎
+        audio = tokio::task::spawn_blocking(move || {
+            let pcm = audio_service::decode_ogg_opus(&audio)?;
+            audio_service::encode_wav(&pcm, audio_service::TARGET_SAMPLE_RATE)
+        })
+        .await
+        .map_err(|error| format!("解码历史音频任务失败: {error}"))?
+        .map_err(|error| format!("解码历史音频失败: {error}"))?;
     }
-    .map_err(|error| format!("重新识别失败: {error}"))?;
+    let engine = paths::read_engine_config();
+    let result = audio_service::transcribe_audio_file(app_handle, state, &engine, audio, "wav")
+        .await
+        .map_err(|error| format!("重新识别失败: {error}"))?;
     if result.success {
         Ok(result)
     } else {
@@ -2,6 +2,7 @@ use crate::commands::audio::{
     start_recording_inner, stop_recording_inner, RECORDING_ALREADY_ACTIVE_ERROR,
     RECORDING_NOT_READY_ERROR, RECORDING_START_CANCELLED_ERROR,
 };
+use crate::state::user_profile::UserProfile;
 use crate::state::{AppState, RecordingSlot, RecordingTrigger};
 use crate::utils::AppError;
 use std::sync::{
@@ -33,6 +34,13 @@ use windows_sys::Win32::UI::WindowsAndMessaging::{
 };
 
 const HOTKEY_REPRESS_DEBOUNCE_MS: u64 = 180;
+/// 序列热键（如 `Ctrl+K, D`）相邻两步之间允许的最长间隔
+const HOTKEY_SEQUENCE_TIMEOUT_DEFAULT_MS: u64 = 1000;
+const HOTKEY_SEQUENCE_TIMEOUT_MIN_MS: u64 = 200;
+const HOTKEY_SEQUENCE_TIMEOUT_MAX_MS: u64 = 5000;
+const HOTKEY_SEQUENCE_MAX_STEPS: usize = 4;
+
+static HOTKEY_SEQUENCE_TIMEOUT_MS: AtomicU64 = AtomicU64::new(HOTKEY_SEQUENCE_TIMEOUT_DEFAULT_MS);
 
 fn is_ignorable_start_audio_error(message: &str) -> bool {
     message == RECORDING_NOT_READY_ERROR
@@ -64,6 +72,8 @@ fn classify_backend(spec: &HotkeySpec) -> HotkeyBackend {
         match spec {
             HotkeySpec::ModifierOnly { .. } => HotkeyBackend::LowLevelHook,
             HotkeySpec::Standard { .. } => HotkeyBackend::RegisterHotKey,
+            // RegisterHotKey 只能识别单个组合，序列需要 LLKH 逐步跟踪
+            HotkeySpec::Sequence { .. } => HotkeyBackend::LowLevelHook,
         }
     } else {
         // Hold mode always needs LLKH for key-up detection
@@ -91,23 +101,32 @@ fn dispatch_channel() -> &'static std::sync::mpsc::Sender<DispatchEvent> {
             .spawn(move || {
                 for event in rx {
                     match event {
-                        DispatchEvent::Press(state, msg) => {
-                            dispatch_hotkey_press(
+                        DispatchEvent::Press(state, msg) => match state.action {
+                            HookAction::Record(trigger) => dispatch_hotkey_press(
                                 &state.app_handle,
                                 &state.gate,
-                                state.trigger,
+                                trigger,
                                 &msg,
                                 state.spec.label(),
-                            );
-                        }
-                        DispatchEvent::Release(state, msg) => {
-                            dispatch_hotkey_release(
+                            ),
+                            HookAction::Command(action) => dispatch_command_press(
                                 &state.app_handle,
                                 &state.gate,
-                                state.trigger,
-                                &msg,
+                                action,
                                 state.spec.label(),
-                            );
+                            ),
+                        },
+                        DispatchEvent::Release(state, msg) => {
+                            // 非录音动作只在按下时触发，松开无事可做
+                            if let HookAction::Record(trigger) = state.action {
+                                dispatch_hotkey_release(
+                                    &state.app_handle,
+                                    &state.gate,
+                                    trigger,
+                                    &msg,
+                                    state.spec.label(),
+                                );
+                            }
                         }
                     }
                 }
@@ -147,7 +166,7 @@ fn probe_system_hotkey_conflict(spec: &HotkeySpec) -> Option<String> {
             (m, *main_vk as u32)
         }
         // Modifier-only combos can't be probed via RegisterHotKey
-        HotkeySpec::ModifierOnly { .. } => return None,
+        HotkeySpec::ModifierOnly { .. } | HotkeySpec::Sequence { .. } => return None,
     };
 
     // Attempt to register — success means no conflict
@@ -180,6 +199,12 @@ enum HotkeySpec {
         modifiers: ShortcutModifiers,
         main_vk: u16,
     },
+    /// Several combos pressed one after another (e.g. `Ctrl+K, D`).
+    /// Only the last step starts/stops recording; earlier steps just arm it.
+    Sequence {
+        label: String,
+        steps: Vec<HotkeySpec>,
+    },
 }
 
 impl HotkeySpec {
@@ -187,6 +212,15 @@ impl HotkeySpec {
         match self {
             Self::ModifierOnly { label, .. } => label,
             Self::Standard { label, .. } => label,
+            Self::Sequence { label, .. } => label,
+        }
+    }
+
+    /// The combo that actually drives press/release — the last step of a sequence.
+    fn trigger_step(&self) -> &HotkeySpec {
+        match self {
+            Self::Sequence { steps, .. } => steps.last().unwrap_or(self),
+            _ => self,
         }
     }
 }
@@ -283,6 +317,12 @@ fn hotkey_kind_label(kind: HotkeyKind) -> &'static str {
         HotkeyKind::Dictation => "说话",
         HotkeyKind::Translation => "翻译",
         HotkeyKind::Assistant => "助手",
+        HotkeyKind::LanguageCycle => "语言切换",
+        HotkeyKind::ToggleWindow => "显示/隐藏窗口",
+        HotkeyKind::Pause => "暂停",
+        HotkeyKind::Cancel => "取消",
+        HotkeyKind::CycleEngine => "引擎切换",
+        HotkeyKind::FlushPaste => "立即粘贴",
     }
 }
 
@@ -297,11 +337,7 @@ fn ensure_hotkey_not_conflicting(
             Ok(g) => g,
             Err(p) => p.into_inner(),
         };
-        for (other_kind, state) in [
-            (HotkeyKind::Dictation, guard.dictation.as_ref()),
-            (HotkeyKind::Translation, guard.translation.as_ref()),
-            (HotkeyKind::Assistant, guard.assistant.as_ref()),
-        ] {
+        for (other_kind, state) in guard.slots() {
             if other_kind == kind {
                 continue;
             }
@@ -371,6 +407,7 @@ fn handle_hotkey_start(
             app_handle.clone(),
             state.inner(),
             trigger,
+            None,
             Some(grab_handle),
         )
         .await
@@ -617,6 +654,72 @@ fn dispatch_hotkey_release(
     }
 }
 
+/// 非录音动作的热键（语言切换、显示窗口、暂停等）：按下时执行一次，不进入录音流程。
+#[cfg(target_os = "windows")]
+fn dispatch_command_press(
+    app_handle: &tauri::AppHandle,
+    gate: &HotkeyEventGate,
+    action: HotkeyAction,
+    shortcut_label: &str,
+) {
+    let now_ms = now_unix_ms();
+    let last_press_ms = gate.last_release_ms.swap(now_ms, Ordering::AcqRel);
+    if now_ms.saturating_sub(last_press_ms) < HOTKEY_REPRESS_DEBOUNCE_MS {
+        log::debug!("忽略{}热键 {} 的连按抖动", action.label(), shortcut_label);
+        return;
+    }
+    log::info!("{}热键 {} 按下", action.label(), shortcut_label);
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(err) = run_hotkey_command(&app_handle, action).await {
+            log::warn!("{}热键执行失败: {}", action.label(), err);
+            let (event, payload) = match action {
+                HotkeyAction::LanguageCycle => (
+                    "language-change-error",
+                    serde_json::json!({ "message": err.to_string() }),
+                ),
+                _ => (
+                    "hotkey-action-error",
+                    serde_json::json!({ "action": action, "message": err.to_string() }),
+                ),
+            };
+            let _ = app_handle.emit(event, payload);
+        }
+    });
+}
+
+/// 执行一个非录音动作；录音类动作走 dispatch_hotkey_press，这里不处理。
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+async fn run_hotkey_command(
+    app_handle: &tauri::AppHandle,
+    action: HotkeyAction,
+) -> Result<(), AppError> {
+    let state = app_handle.state::<AppState>();
+    match action {
+        HotkeyAction::LanguageCycle => {
+            crate::services::funasr_service::cycle_language(app_handle, state.inner()).await?;
+        }
+        HotkeyAction::ToggleWindow => crate::commands::window::toggle_main_window(app_handle)?,
+        HotkeyAction::Pause => {
+            crate::commands::audio::toggle_recording_pause(app_handle, state.inner())?;
+        }
+        HotkeyAction::Cancel => {
+            if crate::commands::audio::force_stop_active_recording(app_handle, state.inner())
+                .await
+                .is_none()
+            {
+                log::info!("取消热键：没有进行中的录音");
+            }
+        }
+        HotkeyAction::CycleEngine => {
+            crate::commands::funasr::cycle_engine(app_handle, state).await?;
+        }
+        HotkeyAction::FlushPaste => crate::services::audio_service::flush_pending_paste(app_handle),
+        HotkeyAction::Dictation | HotkeyAction::Translation | HotkeyAction::Assistant => {}
+    }
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Unified low-level keyboard hook (Windows)
 // ---------------------------------------------------------------------------
@@ -625,7 +728,7 @@ fn dispatch_hotkey_release(
 struct UnifiedHookState {
     app_handle: tauri::AppHandle,
     spec: HotkeySpec,
-    trigger: RecordingTrigger,
+    action: HookAction,
     gate: HotkeyEventGate,
     backend: HotkeyBackend,
     /// Per-VK key-down tracking for modifier-only mode
@@ -636,6 +739,12 @@ struct UnifiedHookState {
     tainted: AtomicBool,
     /// A modifier key leaked to OS before the combo was complete
     modifier_leaked: AtomicBool,
+    /// Sequence mode: number of prefix steps already matched
+    sequence_progress: std::sync::atomic::AtomicUsize,
+    /// Sequence mode: timestamp of the last matched step
+    sequence_step_at_ms: AtomicU64,
+    /// Sequence mode: VK that completed the last step (ignored on auto-repeat)
+    sequence_last_vk: std::sync::atomic::AtomicU32,
 }
 
 #[cfg(target_os = "windows")]
@@ -644,12 +753,49 @@ struct UnifiedHookBundle {
     dictation: Option<Arc<UnifiedHookState>>,
     translation: Option<Arc<UnifiedHookState>>,
     assistant: Option<Arc<UnifiedHookState>>,
+    language_cycle: Option<Arc<UnifiedHookState>>,
+    toggle_window: Option<Arc<UnifiedHookState>>,
+    pause: Option<Arc<UnifiedHookState>>,
+    cancel: Option<Arc<UnifiedHookState>>,
+    cycle_engine: Option<Arc<UnifiedHookState>>,
+    flush_paste: Option<Arc<UnifiedHookState>>,
 }
 
 #[cfg(target_os = "windows")]
 impl UnifiedHookBundle {
+    fn slots(&self) -> [(HotkeyKind, Option<&Arc<UnifiedHookState>>); 9] {
+        HotkeyKind::ALL.map(|kind| {
+            let slot = match kind {
+                HotkeyKind::Dictation => &self.dictation,
+                HotkeyKind::Translation => &self.translation,
+                HotkeyKind::Assistant => &self.assistant,
+                HotkeyKind::LanguageCycle => &self.language_cycle,
+                HotkeyKind::ToggleWindow => &self.toggle_window,
+                HotkeyKind::Pause => &self.pause,
+                HotkeyKind::Cancel => &self.cancel,
+                HotkeyKind::CycleEngine => &self.cycle_engine,
+                HotkeyKind::FlushPaste => &self.flush_paste,
+            };
+            (kind, slot.as_ref())
+        })
+    }
+
+    fn slot_mut(&mut self, kind: HotkeyKind) -> &mut Option<Arc<UnifiedHookState>> {
+        match kind {
+            HotkeyKind::Dictation => &mut self.dictation,
+            HotkeyKind::Translation => &mut self.translation,
+            HotkeyKind::Assistant => &mut self.assistant,
+            HotkeyKind::LanguageCycle => &mut self.language_cycle,
+            HotkeyKind::ToggleWindow => &mut self.toggle_window,
+            HotkeyKind::Pause => &mut self.pause,
+            HotkeyKind::Cancel => &mut self.cancel,
+            HotkeyKind::CycleEngine => &mut self.cycle_engine,
+            HotkeyKind::FlushPaste => &mut self.flush_paste,
+        }
+    }
+
     fn is_empty(&self) -> bool {
-        self.dictation.is_none() && self.translation.is_none() && self.assistant.is_none()
+        self.slots().iter().all(|(_, state)| state.is_none())
     }
 }
 
@@ -658,6 +804,37 @@ enum HotkeyKind {
     Dictation,
     Translation,
     Assistant,
+    LanguageCycle,
+    ToggleWindow,
+    Pause,
+    Cancel,
+    CycleEngine,
+    FlushPaste,
+}
+
+#[cfg(target_os = "windows")]
+impl HotkeyKind {
+    const ALL: [HotkeyKind; 9] = [
+        Self::Dictation,
+        Self::Translation,
+        Self::Assistant,
+        Self::LanguageCycle,
+        Self::ToggleWindow,
+        Self::Pause,
+        Self::Cancel,
+        Self::CycleEngine,
+        Self::FlushPaste,
+    ];
+}
+
+/// What a hook slot does when its hotkey fires: drive a recording, or
+/// run a one-shot command (language/engine cycle, window, pause, ...)
+/// without touching the recorder.
+#[cfg(target_os = "windows")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum HookAction {
+    Record(RecordingTrigger),
+    Command(HotkeyAction),
 }
 
 #[cfg(target_os = "windows")]
@@ -686,12 +863,7 @@ fn set_unified_hook_state(
         Ok(g) => g,
         Err(p) => p.into_inner(),
     };
-    let slot = match kind {
-        HotkeyKind::Dictation => &mut guard.dictation,
-        HotkeyKind::Translation => &mut guard.translation,
-        HotkeyKind::Assistant => &mut guard.assistant,
-    };
-    let previous = std::mem::replace(slot, state);
+    let previous = std::mem::replace(guard.slot_mut(kind), state);
     publish_unified_hook_state_snapshot(&guard);
     previous
 }
@@ -761,7 +933,7 @@ fn is_alt_vk(vk: u32) -> bool {
 /// Returns None if the VK is not one of the required VKs.
 #[cfg(target_os = "windows")]
 fn vk_to_required_index(spec: &HotkeySpec, vk: u32) -> Option<usize> {
-    if let HotkeySpec::ModifierOnly { required_vks, .. } = spec {
+    if let HotkeySpec::ModifierOnly { required_vks, .. } = spec.trigger_step() {
         required_vks
             .iter()
             .position(|&required| vk_matches_required(vk, required))
@@ -869,14 +1041,11 @@ unsafe extern "system" fn unified_low_level_keyboard_proc(
     let vk = keyboard.vkCode;
 
     let mut swallow = false;
-    for state in [
-        bundle.dictation.as_ref(),
-        bundle.translation.as_ref(),
-        bundle.assistant.as_ref(),
-    ]
-    .into_iter()
-    .flatten()
-    .filter(|s| s.backend == HotkeyBackend::LowLevelHook)
+    for state in bundle
+        .slots()
+        .into_iter()
+        .filter_map(|(_, state)| state)
+        .filter(|s| s.backend == HotkeyBackend::LowLevelHook)
     {
         swallow |= match &state.spec {
             HotkeySpec::ModifierOnly {
@@ -888,6 +1057,9 @@ unsafe extern "system" fn unified_low_level_keyboard_proc(
                 modifiers,
                 main_vk,
             } => handle_standard_event(state, vk, is_key_down, label, modifiers, *main_vk),
+            HotkeySpec::Sequence { label, steps } => {
+                handle_sequence_event(state, vk, is_key_down, label, steps)
+            }
         };
     }
 
@@ -1073,6 +1245,154 @@ fn handle_standard_event(
     currently_activated && is_main_key
 }
 
+/// Whether the left or right variant of a canonical modifier VK is held.
+#[cfg(target_os = "windows")]
+fn is_required_vk_down(required_vk: u16) -> bool {
+    let right = match required_vk {
+        x if x == VK_LCONTROL => VK_RCONTROL,
+        x if x == VK_LMENU => VK_RMENU,
+        x if x == VK_LSHIFT => VK_RSHIFT,
+        x if x == VK_LWIN => VK_RWIN,
+        other => other,
+    };
+    is_key_physically_down(required_vk) || is_key_physically_down(right)
+}
+
+/// Does this key-down complete `step`? The current key is not yet reflected in
+/// GetAsyncKeyState, so it is excluded from the physical check.
+#[cfg(target_os = "windows")]
+fn sequence_step_matches(step: &HotkeySpec, vk: u32) -> bool {
+    match step {
+        HotkeySpec::Standard {
+            modifiers, main_vk, ..
+        } => vk as u16 == *main_vk && all_modifiers_down(modifiers),
+        HotkeySpec::ModifierOnly { required_vks, .. } => {
+            required_vks
+                .iter()
+                .any(|&required| vk_matches_required(vk, required))
+                && required_vks.iter().all(|&required| {
+                    vk_matches_required(vk, required) || is_required_vk_down(required)
+                })
+        }
+        HotkeySpec::Sequence { .. } => false,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn reset_sequence_progress(state: &UnifiedHookState) {
+    state.sequence_progress.store(0, Ordering::Release);
+    state.sequence_last_vk.store(0, Ordering::Release);
+    for key in &state.key_down {
+        key.store(false, Ordering::Release);
+    }
+    state.tainted.store(false, Ordering::Release);
+}
+
+/// Returns `true` if the event should be swallowed.
+/// Prefix steps only advance `sequence_progress`; once every prefix step has
+/// matched within the timeout, the last step is handed to the regular
+/// Standard / ModifierOnly handler so hold/toggle semantics stay identical.
+#[cfg(target_os = "windows")]
+fn handle_sequence_event(
+    state: &Arc<UnifiedHookState>,
+    vk: u32,
+    is_key_down: bool,
+    label: &str,
+    steps: &[HotkeySpec],
+) -> bool {
+    let Some((last_step, prefix)) = steps.split_last() else {
+        return false;
+    };
+
+    if !state.activated.load(Ordering::Acquire) {
+        let mut progress = state.sequence_progress.load(Ordering::Acquire);
+        let now_ms = now_unix_ms();
+        let timeout_ms = HOTKEY_SEQUENCE_TIMEOUT_MS.load(Ordering::Relaxed);
+        if progress > 0
+            && now_ms.saturating_sub(state.sequence_step_at_ms.load(Ordering::Acquire)) > timeout_ms
+        {
+            reset_sequence_progress(state);
+            progress = 0;
+        }
+
+        let is_repeat = progress > 0 && state.sequence_last_vk.load(Ordering::Acquire) == vk;
+
+        if progress < prefix.len() {
+            if !is_key_down {
+                return false;
+            }
+            if is_repeat {
+                // Auto-repeat of the key that completed the previous step
+                return !is_modifier_vk(vk);
+            }
+            let step = &prefix[progress];
+            if sequence_step_matches(step, vk) {
+                state
+                    .sequence_progress
+                    .store(progress + 1, Ordering::Release);
+                state.sequence_step_at_ms.store(now_ms, Ordering::Release);
+                state.sequence_last_vk.store(vk, Ordering::Release);
+                return matches!(step, HotkeySpec::Standard { .. });
+            }
+            if progress > 0 && !is_modifier_vk(vk) {
+                // Unrelated key breaks the sequence
+                reset_sequence_progress(state);
+            }
+            return false;
+        }
+
+        // Armed: all prefix steps matched, waiting for the last step.
+        if is_key_down && !is_modifier_vk(vk) {
+            let is_last_main_key = matches!(
+                last_step,
+                HotkeySpec::Standard { main_vk, .. } if vk as u16 == *main_vk
+            );
+            if is_repeat && !is_last_main_key {
+                return true;
+            }
+            if !is_last_main_key {
+                reset_sequence_progress(state);
+                return false;
+            }
+        }
+
+        // Modifiers of the last step may already be held from the prefix;
+        // seed their key-down flags so the ModifierOnly handler sees them.
+        if is_key_down {
+            if let HotkeySpec::ModifierOnly { required_vks, .. } = last_step {
+                if required_vks
+                    .iter()
+                    .any(|&required| vk_matches_required(vk, required))
+                {
+                    for (idx, &required) in required_vks.iter().enumerate() {
+                        if is_required_vk_down(required) {
+                            state.key_down[idx].store(true, Ordering::Release);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let swallow = match last_step {
+        HotkeySpec::ModifierOnly { required_vks, .. } => {
+            handle_modifier_only_event(state, vk, is_key_down, label, required_vks)
+        }
+        HotkeySpec::Standard {
+            modifiers, main_vk, ..
+        } => handle_standard_event(state, vk, is_key_down, label, modifiers, *main_vk),
+        HotkeySpec::Sequence { .. } => false,
+    };
+
+    // The sequence is consumed once the last step fires; the next trigger has
+    // to start over from the first step.
+    if state.activated.load(Ordering::Acquire) {
+        state.sequence_progress.store(0, Ordering::Release);
+    }
+
+    swallow
+}
+
 // ---------------------------------------------------------------------------
 // RegisterHotKey backend — dedicated thread with message pump
 // ---------------------------------------------------------------------------
@@ -1125,6 +1445,12 @@ fn hotkey_kind_to_reg_id(kind: HotkeyKind) -> i32 {
         HotkeyKind::Dictation => 1,
         HotkeyKind::Translation => 2,
         HotkeyKind::Assistant => 3,
+        HotkeyKind::LanguageCycle => 4,
+        HotkeyKind::ToggleWindow => 5,
+        HotkeyKind::Pause => 6,
+        HotkeyKind::Cancel => 7,
+        HotkeyKind::CycleEngine => 8,
+        HotkeyKind::FlushPaste => 9,
     }
 }
 
@@ -1476,13 +1802,15 @@ fn ensure_unified_hotkey_monitor(_app_handle: tauri::AppHandle) -> Result<(), Ap
 #[cfg(target_os = "windows")]
 fn force_release_hotkey(state: &UnifiedHookState) {
     let label = state.spec.label();
-    dispatch_hotkey_release(
-        &state.app_handle,
-        &state.gate,
-        state.trigger,
-        &format!("{} 监听结束，补发松开事件", label),
-        label,
-    );
+    if let HookAction::Record(trigger) = state.action {
+        dispatch_hotkey_release(
+            &state.app_handle,
+            &state.gate,
+            trigger,
+            &format!("{} 监听结束，补发松开事件", label),
+            label,
+        );
+    }
     reset_hotkey_event_gate(&state.gate);
 }
 
@@ -1497,28 +1825,28 @@ fn ensure_unified_hotkey_monitor(_app_handle: tauri::AppHandle) -> Result<(), Ap
 fn build_hook_state(
     app_handle: tauri::AppHandle,
     spec: HotkeySpec,
-    trigger: RecordingTrigger,
+    action: HookAction,
 ) -> Arc<UnifiedHookState> {
     let backend = classify_backend(&spec);
-    build_hook_state_with_backend(app_handle, spec, trigger, backend)
+    build_hook_state_with_backend(app_handle, spec, action, backend)
 }
 
 fn build_hook_state_with_backend(
     app_handle: tauri::AppHandle,
     spec: HotkeySpec,
-    trigger: RecordingTrigger,
+    action: HookAction,
     backend: HotkeyBackend,
 ) -> Arc<UnifiedHookState> {
-    let key_down_count = match &spec {
+    let key_down_count = match spec.trigger_step() {
         HotkeySpec::ModifierOnly { required_vks, .. } => required_vks.len(),
-        HotkeySpec::Standard { .. } => 0,
+        HotkeySpec::Standard { .. } | HotkeySpec::Sequence { .. } => 0,
     };
 
     Arc::new(UnifiedHookState {
         app_handle,
         backend,
         spec,
-        trigger,
+        action,
         gate: HotkeyEventGate::default(),
         key_down: (0..key_down_count)
             .map(|_| AtomicBool::new(false))
@@ -1526,6 +1854,9 @@ fn build_hook_state_with_backend(
         activated: AtomicBool::new(false),
         tainted: AtomicBool::new(false),
         modifier_leaked: AtomicBool::new(false),
+        sequence_progress: std::sync::atomic::AtomicUsize::new(0),
+        sequence_step_at_ms: AtomicU64::new(0),
+        sequence_last_vk: std::sync::atomic::AtomicU32::new(0),
     })
 }
 
@@ -1536,14 +1867,11 @@ fn sync_hotkey_monitor_lifecycle(app_handle: tauri::AppHandle) -> Result<(), App
             Ok(g) => g,
             Err(p) => p.into_inner(),
         };
-        let has_llkh = [
-            guard.dictation.as_ref(),
-            guard.translation.as_ref(),
-            guard.assistant.as_ref(),
-        ]
-        .iter()
-        .flatten()
-        .any(|s| s.backend == HotkeyBackend::LowLevelHook);
+        let has_llkh = guard
+            .slots()
+            .iter()
+            .filter_map(|(_, state)| *state)
+            .any(|s| s.backend == HotkeyBackend::LowLevelHook);
         let has_any = !guard.is_empty();
         (has_llkh, has_any)
     };
@@ -1628,7 +1956,42 @@ fn modifiers_to_required_vks(mods: &ShortcutModifiers) -> Vec<u16> {
 // normalize_shortcut → HotkeySpec
 // ---------------------------------------------------------------------------
 
+/// 把快捷键字符串切成序列的各个步骤。先收紧 `+` 两侧的空白（兼容 `Ctrl + K`），
+/// 再按逗号或空白切分，例如 `Ctrl+K, D` / `Ctrl+Alt D` 都得到两步。
+fn split_shortcut_sequence(raw: &str) -> Vec<String> {
+    let compact = raw.split('+').map(str::trim).collect::<Vec<_>>().join("+");
+    compact
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 fn normalize_shortcut(raw: &str) -> Result<HotkeySpec, AppError> {
+    let parts = split_shortcut_sequence(raw);
+    if parts.len() <= 1 {
+        return normalize_shortcut_combo(parts.first().map(String::as_str).unwrap_or(raw));
+    }
+    if parts.len() > HOTKEY_SEQUENCE_MAX_STEPS {
+        return Err(AppError::Other(format!(
+            "快捷键格式无效：序列最多支持 {} 步",
+            HOTKEY_SEQUENCE_MAX_STEPS
+        )));
+    }
+
+    let steps = parts
+        .iter()
+        .map(|part| normalize_shortcut_combo(part))
+        .collect::<Result<Vec<_>, _>>()?;
+    let label = steps
+        .iter()
+        .map(HotkeySpec::label)
+        .collect::<Vec<_>>()
+        .join(", ");
+    Ok(HotkeySpec::Sequence { label, steps })
+}
+
+fn normalize_shortcut_combo(raw: &str) -> Result<HotkeySpec, AppError> {
     let mut modifiers = ShortcutModifiers::default();
     let mut main_key: Option<String> = None;
 
@@ -1743,7 +2106,7 @@ fn register_on_chosen_backend(
                     let fallback_state = build_hook_state_with_backend(
                         hook_state.app_handle.clone(),
                         hook_state.spec.clone(),
-                        hook_state.trigger,
+                        hook_state.action,
                         HotkeyBackend::LowLevelHook,
                     );
                     set_unified_hook_state(kind, Some(fallback_state));
@@ -1795,7 +2158,7 @@ fn try_register_hotkey_backend(kind: HotkeyKind, state: &Arc<UnifiedHookState>)
             let fallback = build_hook_state_with_backend(
                 state.app_handle.clone(),
                 state.spec.clone(),
-                state.trigger,
+                state.action,
                 HotkeyBackend::LowLevelHook,
             );
             set_unified_hook_state(kind, Some(fallback));
@@ -1807,12 +2170,78 @@ fn try_register_hotkey_backend(kind: HotkeyKind, state: &Arc<UnifiedHookState>)
 // Tauri commands
 // ---------------------------------------------------------------------------
 
+/// 听写热键的出厂默认值；用户可在设置里改掉或整个关闭
+pub const DEFAULT_DICTATION_HOTKEY: &str = "F2";
+
 #[tauri::command]
 pub async fn register_custom_hotkey(
     app_handle: tauri::AppHandle,
     shortcut: String,
 ) -> Result<String, AppError> {
-    let spec = match normalize_shortcut(&shortcut) {
+    let label = register_dictation_hotkey_inner(app_handle.clone(), &shortcut)?;
+    let state = app_handle.state::<AppState>();
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.dictation_hotkey = Some(label.clone());
+        profile.dictation_hotkey_disabled = false;
+    });
+    Ok(format!("快捷键 {} 已注册", label))
+}
+
+/// 启动时按画像里保存的听写热键注册；用户关闭了默认热键时什么都不注册。
+/// 序列热键间隔也在这里从画像恢复。
+pub(crate) fn register_saved_dictation_hotkey(app_handle: &tauri::AppHandle) {
+    let state = app_handle.state::<AppState>();
+    apply_saved_sequence_timeout(state.inner());
+    let (shortcut, disabled) =
+        state.with_profile(|p| (p.dictation_hotkey.clone(), p.dictation_hotkey_disabled));
+    if disabled {
+        log::info!("听写热键已关闭，启动时不注册");
+        return;
+    }
+    let shortcut = shortcut
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_DICTATION_HOTKEY.to_string());
+    if let Err(err) = register_dictation_hotkey_inner(app_handle.clone(), &shortcut) {
+        log::warn!("注册听写热键 {} 失败: {}", shortcut, err);
+    }
+}
+
+/// 按当前画像重新套用三个热键（导入整机配置后调用）：翻译/助手未设置时注销，
+/// 听写热键按保存值或关闭状态处理。单个热键失败只记日志。
+pub(crate) async fn reapply_saved_hotkeys(app_handle: &tauri::AppHandle) {
+    let state = app_handle.state::<AppState>();
+    let (translation, assistant, dictation_disabled) = state.with_profile(|p| {
+        (
+            p.translation_hotkey.clone(),
+            p.assistant_hotkey.clone(),
+            p.dictation_hotkey_disabled,
+        )
+    });
+    let non_empty = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+    if let Err(err) = register_translation_hotkey_inner(app_handle.clone(), non_empty(translation))
+    {
+        log::warn!("重新注册翻译热键失败: {}", err);
+    }
+    if let Err(err) = register_assistant_hotkey_inner(app_handle.clone(), non_empty(assistant)) {
+        log::warn!("重新注册助手热键失败: {}", err);
+    }
+    register_saved_command_hotkeys(app_handle);
+    if dictation_disabled {
+        apply_saved_sequence_timeout(state.inner());
+        if let Err(err) = disable_dictation_hotkey(app_handle.clone(), state).await {
+            log::warn!("注销听写热键失败: {}", err);
+        }
+    } else {
+        register_saved_dictation_hotkey(app_handle);
+    }
+}
+
+/// 注册听写热键，返回规范化后的热键名。
+pub(crate) fn register_dictation_hotkey_inner(
+    app_handle: tauri::AppHandle,
+    shortcut: &str,
+) -> Result<String, AppError> {
+    let spec = match normalize_shortcut(shortcut) {
         Ok(spec) => spec,
         Err(err) => {
             let now_ms = now_unix_ms();
@@ -1854,7 +2283,7 @@ pub async fn register_custom_hotkey(
     let hook_state = build_hook_state(
         app_handle.clone(),
         spec,
-        RecordingTrigger::DictationOriginal,
+        HookAction::Record(RecordingTrigger::DictationOriginal),
     );
 
     #[cfg(target_os = "windows")]
@@ -1899,7 +2328,99 @@ pub async fn register_custom_hotkey(
     });
 
     log::info!("自定义快捷键 {} 已注册（{}）", label, backend_label);
-    Ok(format!("快捷键 {} 已注册", label))
+    Ok(label)
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyValidation {
+    /// 注册时实际使用的规范化写法，例如 `ctrl + Space` → `Ctrl+Space`
+    pub normalized: String,
+    /// "standard" 主键组合、"modifierOnly" 纯修饰键（如 Ctrl+Win）、"sequence" 多步序列
+    pub kind: &'static str,
+    pub warning: Option<String>,
+}
+
+fn describe_hotkey(shortcut: &str) -> Result<HotkeyValidation, AppError> {
+    let spec = normalize_shortcut(shortcut)?;
+    let kind = match spec {
+        HotkeySpec::Standard { .. } => "standard",
+        HotkeySpec::ModifierOnly { .. } => "modifierOnly",
+        HotkeySpec::Sequence { .. } => "sequence",
+    };
+    let normalized = spec.label().to_string();
+    Ok(HotkeyValidation {
+        warning: hotkey_warning_message(&normalized),
+        normalized,
+        kind,
+    })
+}
+
+/// 只解析不注册：设置页输入时实时校验，无任何全局副作用。
+#[tauri::command]
+pub async fn validate_hotkey(shortcut: String) -> Result<HotkeyValidation, AppError> {
+    describe_hotkey(&shortcut)
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DictationHotkeySetting {
+    /// 启动时会注册的热键：保存过的值，否则为默认 F2
+    pub shortcut: String,
+    /// 画像里是否已经保存过热键（旧版本只存在前端 localStorage）
+    pub saved: bool,
+    pub disabled: bool,
+}
+
+#[tauri::command]
+pub async fn get_dictation_hotkey_setting(
+    state: tauri::State<'_, AppState>,
+) -> Result<DictationHotkeySetting, AppError> {
+    Ok(state.with_profile(|p| {
+        let saved = p
+            .dictation_hotkey
+            .clone()
+            .filter(|value| !value.trim().is_empty());
+        DictationHotkeySetting {
+            saved: saved.is_some(),
+            shortcut: saved.unwrap_or_else(|| DEFAULT_DICTATION_HOTKEY.to_string()),
+            disabled: p.dictation_hotkey_disabled,
+        }
+    }))
+}
+
+/// 关闭听写热键：立即注销，之后启动也不再注册。重新调用 `register_custom_hotkey` 即可恢复。
+#[tauri::command]
+pub async fn disable_dictation_hotkey(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    #[cfg(target_os = "windows")]
+    {
+        unregister_via_reg_hotkey(HotkeyKind::Dictation);
+        if let Some(previous) = set_unified_hook_state(HotkeyKind::Dictation, None) {
+            force_release_hotkey(&previous);
+        }
+        sync_hotkey_monitor_lifecycle(app_handle.clone())?;
+    }
+
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.dictation_hotkey_disabled = true;
+    });
+
+    let now_ms = now_unix_ms();
+    update_hotkey_diagnostic(&app_handle, |diagnostic| {
+        diagnostic.shortcut = String::new();
+        diagnostic.registered = false;
+        diagnostic.is_pressed = false;
+        diagnostic.last_error = None;
+        diagnostic.warning = None;
+        diagnostic.system_conflict = None;
+        diagnostic.last_event = Some("unregistered".to_string());
+        diagnostic.last_event_at_ms = Some(now_ms);
+    });
+    log::info!("听写热键已关闭");
+    Ok(())
 }
 
 #[tauri::command]
@@ -1936,7 +2457,7 @@ pub(crate) fn register_translation_hotkey_inner(
             Some(build_hook_state(
                 app_handle.clone(),
                 spec,
-                RecordingTrigger::DictationTranslated,
+                HookAction::Record(RecordingTrigger::DictationTranslated),
             ))
         } else {
             None
@@ -2001,7 +2522,7 @@ pub(crate) fn register_assistant_hotkey_inner(
             Some(build_hook_state(
                 app_handle.clone(),
                 spec,
-                RecordingTrigger::Assistant,
+                HookAction::Record(RecordingTrigger::Assistant),
             ))
         } else {
             None
@@ -2032,24 +2553,296 @@ pub(crate) fn register_assistant_hotkey_inner(
     }
 }
 
+#[tauri::command]
+pub async fn register_language_cycle_hotkey(
+    app_handle: tauri::AppHandle,
+    shortcut: String,
+) -> Result<String, AppError> {
+    register_command_hotkey_inner(
+        app_handle,
+        HotkeyAction::LanguageCycle,
+        Some(shortcut.trim().to_string()).filter(|value| !value.is_empty()),
+    )
+}
+
+/// 注册（或用 None 注销）一个非录音动作的热键，只动该动作自己的槽位。
+pub(crate) fn register_command_hotkey_inner(
+    app_handle: tauri::AppHandle,
+    action: HotkeyAction,
+    shortcut: Option<String>,
+) -> Result<String, AppError> {
+    #[cfg(not(target_os = "windows"))]
+    {
+        if shortcut.is_some() {
+            ensure_unified_hotkey_monitor(app_handle)?;
+        }
+        return Ok(format!("{}热键已更新", action.label()));
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let kind = action.kind();
+        unregister_via_reg_hotkey(kind);
+
+        let next_state = if let Some(shortcut) = shortcut {
+            let spec = normalize_shortcut(&shortcut)?;
+            ensure_hotkey_not_conflicting(&app_handle, kind, spec.label())?;
+            Some(build_hook_state(
+                app_handle.clone(),
+                spec,
+                HookAction::Command(action),
+            ))
+        } else {
+            None
+        };
+
+        let previous_state = set_unified_hook_state(kind, next_state.clone());
+
+        if let Some(previous) = previous_state.as_ref() {
+            force_release_hotkey(previous);
+        }
+
+        if let Some(ref state) = next_state {
+            try_register_hotkey_backend(kind, state);
+        }
+
+        if let Err(err) = sync_hotkey_monitor_lifecycle(app_handle.clone()) {
+            let _ = set_unified_hook_state(kind, previous_state);
+            let _ = sync_hotkey_monitor_lifecycle(app_handle.clone());
+            return Err(err);
+        }
+
+        let label = next_state
+            .as_ref()
+            .map(|state| state.spec.label().to_string())
+            .unwrap_or_else(|| "未设置".to_string());
+        log::info!("{}热键已更新: {}", action.label(), label);
+        Ok(format!("{}热键已更新: {}", action.label(), label))
+    }
+}
+
+/// 按画像注册全部非录音动作的热键（启动和导入配置时调用）；未设置的槽位注销。
+pub(crate) fn register_saved_command_hotkeys(app_handle: &tauri::AppHandle) {
+    let state = app_handle.state::<AppState>();
+    for action in HotkeyAction::COMMANDS {
+        let shortcut = state.with_profile(|profile| saved_command_hotkey(profile, action));
+        if let Err(err) = register_command_hotkey_inner(app_handle.clone(), action, shortcut) {
+            log::warn!("注册{}热键失败: {}", action.label(), err);
+        }
+    }
+}
+
+/// 可以绑定全局热键的动作，一个动作对应钩子里的一个热键槽位，互不覆盖。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HotkeyAction {
+    Dictation,
+    Translation,
+    Assistant,
+    LanguageCycle,
+    /// 显示/隐藏主窗口
+    ToggleWindow,
+    /// 暂停/恢复当前录音
+    Pause,
+    /// 丢弃当前录音，不转写
+    Cancel,
+    /// 切到下一个模型齐全的本地引擎
+    CycleEngine,
+    /// 立即粘贴待粘贴队列
+    FlushPaste,
+}
+
+impl HotkeyAction {
+    const ALL: [HotkeyAction; 9] = [
+        Self::Dictation,
+        Self::Translation,
+        Self::Assistant,
+        Self::LanguageCycle,
+        Self::ToggleWindow,
+        Self::Pause,
+        Self::Cancel,
+        Self::CycleEngine,
+        Self::FlushPaste,
+    ];
+
+    /// 不进入录音流程、按下即执行一次的动作
+    const COMMANDS: [HotkeyAction; 6] = [
+        Self::LanguageCycle,
+        Self::ToggleWindow,
+        Self::Pause,
+        Self::Cancel,
+        Self::CycleEngine,
+        Self::FlushPaste,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Dictation => "听写",
+            Self::Translation => "翻译",
+            Self::Assistant => "助手",
+            Self::LanguageCycle => "语言切换",
+            Self::ToggleWindow => "显示/隐藏窗口",
+            Self::Pause => "暂停",
+            Self::Cancel => "取消",
+            Self::CycleEngine => "引擎切换",
+            Self::FlushPaste => "立即粘贴",
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn kind(self) -> HotkeyKind {
+        match self {
+            Self::Dictation => HotkeyKind::Dictation,
+            Self::Translation => HotkeyKind::Translation,
+            Self::Assistant => HotkeyKind::Assistant,
+            Self::LanguageCycle => HotkeyKind::LanguageCycle,
+            Self::ToggleWindow => HotkeyKind::ToggleWindow,
+            Self::Pause => HotkeyKind::Pause,
+            Self::Cancel => HotkeyKind::Cancel,
+            Self::CycleEngine => HotkeyKind::CycleEngine,
+            Self::FlushPaste => HotkeyKind::FlushPaste,
+        }
+    }
+}
+
+/// 画像里保存的非录音动作热键（未去空白，调用方按需过滤）
+fn saved_command_hotkey(profile: &UserProfile, action: HotkeyAction) -> Option<String> {
+    let value = match action {
+        HotkeyAction::LanguageCycle => &profile.language_cycle_hotkey,
+        HotkeyAction::ToggleWindow => &profile.toggle_window_hotkey,
+        HotkeyAction::Pause => &profile.pause_hotkey,
+        HotkeyAction::Cancel => &profile.cancel_hotkey,
+        HotkeyAction::CycleEngine => &profile.cycle_engine_hotkey,
+        HotkeyAction::FlushPaste => &profile.flush_paste_hotkey,
+        HotkeyAction::Dictation | HotkeyAction::Translation | HotkeyAction::Assistant => {
+            return None
+        }
+    };
+    value.clone().filter(|v| !v.trim().is_empty())
+}
+
+fn store_command_hotkey(profile: &mut UserProfile, action: HotkeyAction, value: Option<String>) {
+    let slot = match action {
+        HotkeyAction::LanguageCycle => &mut profile.language_cycle_hotkey,
+        HotkeyAction::ToggleWindow => &mut profile.toggle_window_hotkey,
+        HotkeyAction::Pause => &mut profile.pause_hotkey,
+        HotkeyAction::Cancel => &mut profile.cancel_hotkey,
+        HotkeyAction::CycleEngine => &mut profile.cycle_engine_hotkey,
+        HotkeyAction::FlushPaste => &mut profile.flush_paste_hotkey,
+        HotkeyAction::Dictation | HotkeyAction::Translation | HotkeyAction::Assistant => return,
+    };
+    *slot = value;
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyBinding {
+    pub action: HotkeyAction,
+    /// 启动时会注册的热键；None 表示该动作未绑定
+    pub shortcut: Option<String>,
+}
+
+/// 画像里保存的全部绑定。听写未保存过时用默认 F2，关闭后视为未绑定。
+fn saved_hotkey_bindings(profile: &UserProfile) -> Vec<HotkeyBinding> {
+    let non_empty = |value: &Option<String>| value.clone().filter(|v| !v.trim().is_empty());
+    HotkeyAction::ALL
+        .into_iter()
+        .map(|action| HotkeyBinding {
+            action,
+            shortcut: match action {
+                HotkeyAction::Dictation if profile.dictation_hotkey_disabled => None,
+                HotkeyAction::Dictation => Some(
+                    non_empty(&profile.dictation_hotkey)
+                        .unwrap_or_else(|| DEFAULT_DICTATION_HOTKEY.to_string()),
+                ),
+                HotkeyAction::Translation => non_empty(&profile.translation_hotkey),
+                HotkeyAction::Assistant => non_empty(&profile.assistant_hotkey),
+                command => saved_command_hotkey(profile, command),
+            },
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_hotkey_bindings(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<HotkeyBinding>, AppError> {
+    Ok(state.with_profile(saved_hotkey_bindings))
+}
+
+/// 绑定（或用 None 解绑）一个动作的热键：只重新注册该动作的槽位，成功后写入
+/// 画像，返回更新后的全部绑定。与其他动作冲突时拒绝，原有绑定保持不变。
+#[tauri::command]
+pub async fn set_hotkey_binding(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    action: HotkeyAction,
+    shortcut: Option<String>,
+) -> Result<Vec<HotkeyBinding>, AppError> {
+    let label = match shortcut.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+        Some(shortcut) => Some(normalize_shortcut(shortcut)?.label().to_string()),
+        None => None,
+    };
+
+    match action {
+        HotkeyAction::Dictation => match label.as_deref() {
+            Some(label) => {
+                register_dictation_hotkey_inner(app_handle.clone(), label)?;
+                crate::services::profile_service::update_profile_and_schedule(
+                    state.inner(),
+                    |profile| {
+                        profile.dictation_hotkey = Some(label.to_string());
+                        profile.dictation_hotkey_disabled = false;
+                    },
+                );
+            }
+            None => disable_dictation_hotkey(app_handle.clone(), state.clone()).await?,
+        },
+        HotkeyAction::Translation => {
+            register_translation_hotkey_inner(app_handle.clone(), label.clone())?;
+            crate::services::profile_service::update_profile_and_schedule(
+                state.inner(),
+                |profile| profile.translation_hotkey = label.clone(),
+            );
+        }
+        HotkeyAction::Assistant => {
+            register_assistant_hotkey_inner(app_handle.clone(), label.clone())?;
+            crate::services::profile_service::update_profile_and_schedule(
+                state.inner(),
+                |profile| profile.assistant_hotkey = label.clone(),
+            );
+        }
+        command => {
+            register_command_hotkey_inner(app_handle.clone(), command, label.clone())?;
+            crate::services::profile_service::update_profile_and_schedule(
+                state.inner(),
+                |profile| store_command_hotkey(profile, command, label.clone()),
+            );
+        }
+    }
+
+    log::info!(
+        "{}热键绑定已更新: {}",
+        action.label(),
+        label.as_deref().unwrap_or("未设置")
+    );
+    Ok(state.with_profile(saved_hotkey_bindings))
+}
+
 #[tauri::command]
 pub async fn unregister_all_hotkeys(app_handle: tauri::AppHandle) -> Result<String, AppError> {
     #[cfg(target_os = "windows")]
     {
         // Unregister from RegisterHotKey backend
-        unregister_via_reg_hotkey(HotkeyKind::Dictation);
-        unregister_via_reg_hotkey(HotkeyKind::Translation);
-        unregister_via_reg_hotkey(HotkeyKind::Assistant);
+        for kind in HotkeyKind::ALL {
+            unregister_via_reg_hotkey(kind);
+        }
 
         // Unregister from LLKH backend
-        if let Some(previous) = set_unified_hook_state(HotkeyKind::Dictation, None) {
-            force_release_hotkey(&previous);
-        }
-        if let Some(previous) = set_unified_hook_state(HotkeyKind::Translation, None) {
-            force_release_hotkey(&previous);
-        }
-        if let Some(previous) = set_unified_hook_state(HotkeyKind::Assistant, None) {
-            force_release_hotkey(&previous);
+        for kind in HotkeyKind::ALL {
+            if let Some(previous) = set_unified_hook_state(kind, None) {
+                force_release_hotkey(&previous);
+            }
         }
     }
     stop_unified_hotkey_monitor();
@@ -2071,13 +2864,14 @@ pub async fn unregister_all_hotkeys(app_handle: tauri::AppHandle) -> Result<Stri
 
 #[tauri::command]
 pub async fn set_recording_mode(
-    _app_handle: tauri::AppHandle,
+    app_handle: tauri::AppHandle,
     toggle: bool,
 ) -> Result<(), AppError> {
-    toggle_mode_flag().store(toggle, Ordering::Release);
-    // If switching from toggle→hold while toggle is active, stop recording
-    if !toggle {
-        let state = _app_handle.state::<AppState>();
+    let previous = toggle_mode_flag().swap(toggle, Ordering::AcqRel);
+    if previous != toggle {
+        // 按下/松开的语义变了：进行中的录音直接收尾（照常转写），否则按住模式下
+        // 松键会被当成切换模式的空操作，切换模式下的再次按下又会被当成新录音而忽略。
+        let state = app_handle.state::<AppState>();
         let active_session = state
             .recording
             .recording
@@ -2085,13 +2879,30 @@ pub async fn set_recording_mode(
             .as_ref()
             .map(|slot| (slot.session_id(), slot.trigger()));
         if let Some((session_id, trigger)) = active_session {
-            handle_hotkey_stop(
-                _app_handle.clone(),
-                "切换到按住模式，停止当前录音".to_string(),
-                trigger,
-                session_id,
-            );
+            match stop_recording_inner(
+                app_handle.clone(),
+                state.inner(),
+                Some((session_id, trigger)),
+            )
+            .await
+            {
+                Ok(Some(session_id)) => {
+                    log::info!("切换录音模式，已停止当前录音 (session {})", session_id)
+                }
+                Ok(None) => {}
+                Err(err) => log::warn!("切换录音模式时停止录音失败: {}", err),
+            }
         }
+        for trigger in [
+            RecordingTrigger::DictationOriginal,
+            RecordingTrigger::DictationTranslated,
+            RecordingTrigger::Assistant,
+        ] {
+            reset_hotkey_gate_for_trigger(trigger);
+        }
+        update_hotkey_diagnostic(&app_handle, |diagnostic| {
+            diagnostic.is_pressed = false;
+        });
     }
 
     // Mode change may require backend migration for all registered hotkeys.
@@ -2099,11 +2910,7 @@ pub async fn set_recording_mode(
     #[cfg(target_os = "windows")]
     {
         let bundle = get_unified_hook_states();
-        for (kind, state) in [
-            (HotkeyKind::Dictation, bundle.dictation.as_ref()),
-            (HotkeyKind::Translation, bundle.translation.as_ref()),
-            (HotkeyKind::Assistant, bundle.assistant.as_ref()),
-        ] {
+        for (kind, state) in bundle.slots() {
             if let Some(old_state) = state {
                 let new_backend = classify_backend(&old_state.spec);
                 if new_backend != old_state.backend {
@@ -2122,7 +2929,7 @@ pub async fn set_recording_mode(
                     let new_state = build_hook_state(
                         old_state.app_handle.clone(),
                         old_state.spec.clone(),
-                        old_state.trigger,
+                        old_state.action,
                     );
 
                     force_release_hotkey(old_state);
@@ -2132,13 +2939,47 @@ pub async fn set_recording_mode(
                 }
             }
         }
-        let _ = sync_hotkey_monitor_lifecycle(_app_handle.clone());
+        let _ = sync_hotkey_monitor_lifecycle(app_handle.clone());
     }
 
     log::info!("录音模式已设置为: {}", if toggle { "切换" } else { "按住" });
     Ok(())
 }
 
+fn validate_sequence_timeout_ms(timeout_ms: u64) -> Result<u64, AppError> {
+    if (HOTKEY_SEQUENCE_TIMEOUT_MIN_MS..=HOTKEY_SEQUENCE_TIMEOUT_MAX_MS).contains(&timeout_ms) {
+        Ok(timeout_ms)
+    } else {
+        Err(AppError::Other(format!(
+            "序列热键步骤间隔需在 {}-{}ms 之间",
+            HOTKEY_SEQUENCE_TIMEOUT_MIN_MS, HOTKEY_SEQUENCE_TIMEOUT_MAX_MS
+        )))
+    }
+}
+
+#[tauri::command]
+pub async fn set_hotkey_sequence_timeout(
+    state: tauri::State<'_, AppState>,
+    timeout_ms: u64,
+) -> Result<(), AppError> {
+    let timeout_ms = validate_sequence_timeout_ms(timeout_ms)?;
+    HOTKEY_SEQUENCE_TIMEOUT_MS.store(timeout_ms, Ordering::Relaxed);
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.hotkey_sequence_timeout_ms = Some(timeout_ms);
+    });
+    log::info!("序列热键步骤间隔已设置为 {}ms", timeout_ms);
+    Ok(())
+}
+
+/// 把画像里保存的序列热键间隔套用到运行时；未设置或超出范围时用默认值。
+fn apply_saved_sequence_timeout(state: &AppState) {
+    let timeout_ms = state
+        .with_profile(|p| p.hotkey_sequence_timeout_ms)
+        .and_then(|ms| validate_sequence_timeout_ms(ms).ok())
+        .unwrap_or(HOTKEY_SEQUENCE_TIMEOUT_DEFAULT_MS);
+    HOTKEY_SEQUENCE_TIMEOUT_MS.store(timeout_ms, Ordering::Relaxed);
+}
+
 #[tauri::command]
 pub async fn get_hotkey_diagnostic(
     state: tauri::State<'_, AppState>,
@@ -2148,7 +2989,94 @@ pub async fn get_hotkey_diagnostic(
 
 #[cfg(test)]
 mod tests {
-    use super::{is_ignorable_start_audio_error, RECORDING_START_CANCELLED_ERROR};
+    use super::{
+        describe_hotkey, is_ignorable_start_audio_error, normalize_shortcut, saved_hotkey_bindings,
+        split_shortcut_sequence, validate_sequence_timeout_ms, HotkeyAction, HotkeySpec,
+        DEFAULT_DICTATION_HOTKEY, RECORDING_START_CANCELLED_ERROR,
+    };
+    use crate::state::user_profile::UserProfile;
+
+    #[test]
+    fn validate_hotkey_reports_normalized_form_and_kind() {
+        let standard = describe_hotkey("ctrl + Space").expect("standard combo");
+        assert_eq!(standard.normalized, "Ctrl+Space");
+        assert_eq!(standard.kind, "standard");
+
+        let modifier_only = describe_hotkey("Win+Ctrl").expect("modifier-only combo");
+        assert_eq!(modifier_only.normalized, "Ctrl+Win");
+        assert_eq!(modifier_only.kind, "modifierOnly");
+        assert!(modifier_only.warning.is_some());
+
+        assert_eq!(describe_hotkey("Ctrl+K, D").unwrap().kind, "sequence");
+        assert!(describe_hotkey("Ctrl+A+B").is_err());
+    }
+
+    #[test]
+    fn single_combo_is_not_treated_as_sequence() {
+        assert_eq!(split_shortcut_sequence("Ctrl + Space"), vec!["Ctrl+Space"]);
+        let spec = normalize_shortcut("Ctrl + Space").expect("single combo");
+        assert!(matches!(spec, HotkeySpec::Standard { .. }));
+        assert_eq!(spec.label(), "Ctrl+Space");
+    }
+
+    #[test]
+    fn comma_or_space_separated_combos_become_a_sequence() {
+        assert_eq!(split_shortcut_sequence("Ctrl+Alt D"), vec!["Ctrl+Alt", "D"]);
+        let spec = normalize_shortcut("Ctrl+K, D").expect("sequence");
+        match &spec {
+            HotkeySpec::Sequence { steps, .. } => assert_eq!(steps.len(), 2),
+            other => panic!("expected sequence, got {:?}", other),
+        }
+        assert_eq!(spec.label(), "Ctrl+K, D");
+        assert_eq!(spec.trigger_step().label(), "D");
+    }
+
+    #[test]
+    fn invalid_sequences_are_rejected() {
+        assert!(normalize_shortcut("Ctrl+K, Ctrl++D").is_err());
+        assert!(normalize_shortcut("A B C D E").is_err());
+    }
+
+    #[test]
+    fn sequence_timeout_is_validated() {
+        assert!(validate_sequence_timeout_ms(100).is_err());
+        assert!(validate_sequence_timeout_ms(10_000).is_err());
+        assert_eq!(validate_sequence_timeout_ms(800).unwrap(), 800);
+    }
+
+    #[test]
+    fn hotkey_bindings_cover_every_action_with_dictation_default() {
+        let bindings = saved_hotkey_bindings(&UserProfile {
+            translation_hotkey: Some("Ctrl+Alt+T".to_string()),
+            assistant_hotkey: Some("  ".to_string()),
+            ..Default::default()
+        });
+        let shortcuts: Vec<_> = bindings
+            .iter()
+            .map(|binding| (binding.action, binding.shortcut.as_deref()))
+            .collect();
+        assert_eq!(
+            shortcuts,
+            vec![
+                (HotkeyAction::Dictation, Some(DEFAULT_DICTATION_HOTKEY)),
+                (HotkeyAction::Translation, Some("Ctrl+Alt+T")),
+                (HotkeyAction::Assistant, None),
+                (HotkeyAction::LanguageCycle, None),
+                (HotkeyAction::ToggleWindow, None),
+                (HotkeyAction::Pause, None),
+                (HotkeyAction::Cancel, None),
+                (HotkeyAction::CycleEngine, None),
+                (HotkeyAction::FlushPaste, None),
+            ]
+        );
+
+        let disabled = saved_hotkey_bindings(&UserProfile {
+            dictation_hotkey: Some("F4".to_string()),
+            dictation_hotkey_disabled: true,
+            ..Default::default()
+        });
+        assert_eq!(disabled[0].shortcut, None);
+    }
 
     #[test]
     fn quick_cancel_is_not_rebroadcast_as_a_start_error() {
@@ -0,0 +1,201 @@
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::Manager;
+
+use crate::state::AppState;
+use crate::utils::{foreground, AppError};
+
+// ---------- 文本注入自检 ----------
+//
+// 打开一个置顶的小窗口，里面只有一个获得焦点的文本框；用指定输入方式把文本
+// 注入进去，再由窗口回报文本框内容，比对是否一致。用来在不污染真实应用的前提下
+// 验证 SendInput / 剪贴板粘贴（含中日韩、emoji）是否可用。
+
+const INJECTION_TEST_WINDOW_LABEL: &str = "injection-test";
+const INJECTION_TEST_READY_TIMEOUT_MS: u64 = 5000;
+/// 窗口拿到焦点后再等一下，避免首个按键落在焦点切换途中
+const INJECTION_TEST_FOCUS_SETTLE_MS: u64 = 150;
+/// 注入完成后等待文本框内容与期望一致的最长时间
+const INJECTION_TEST_READBACK_TIMEOUT_MS: u64 = 1500;
+const INJECTION_TEST_MAX_CHARS: usize = 500;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InjectionTestResult {
+    pub method: String,
+    pub expected: String,
+    pub received: String,
+    pub matched: bool,
+    pub elapsed_ms: u64,
+}
+
+/// 文本框会把换行统一成 `\n`，比对时两边都按此归一。
+fn injection_matches(expected: &str, received: &str) -> bool {
+    expected.replace("\r\n", "\n") == received.replace("\r\n", "\n")
+}
+
+fn tauri_error(action: &str, err: impl std::fmt::Display) -> AppError {
+    AppError::Tauri(format!("{}: {}", action, err))
+}
+
+async fn wait_for_probe<F>(
+    rx: &mut tokio::sync::watch::Receiver<Option<String>>,
+    timeout_ms: u64,
+    mut done: F,
+) -> bool
+where
+    F: FnMut(&Option<String>) -> bool,
+{
+    tokio::time::timeout(Duration::from_millis(timeout_ms), async {
+        loop {
+            if done(&rx.borrow_and_update()) {
+                return true;
+            }
+            if rx.changed().await.is_err() {
+                return false;
+            }
+        }
+    })
+    .await
+    .unwrap_or(false)
+}
+
+async fn run_injection_test(
+    app_handle: &tauri::AppHandle,
+    rx: &mut tokio::sync::watch::Receiver<Option<String>>,
+    text: &str,
+    method: &str,
+) -> Result<String, AppError> {
+    let window = tauri::WebviewWindowBuilder::new(
+        app_handle,
+        INJECTION_TEST_WINDOW_LABEL,
+        tauri::WebviewUrl::App("/?window=injection-test".into()),
+    )
+    .title("注入测试")
+    .inner_size(420.0, 160.0)
+    .center()
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .resizable(false)
+    .focused(true)
+    .build()
+    .map_err(|e| tauri_error("创建注入测试窗口失败", e))?;
+
+    if !wait_for_probe(rx, INJECTION_TEST_READY_TIMEOUT_MS, |value| value.is_some()).await {
+        return Err(AppError::Tauri("注入测试窗口加载超时".into()));
+    }
+    if let Err(err) = window.set_focus() {
+        log::warn!("注入测试窗口获取焦点失败，继续测试: {}", err);
+    }
+    tokio::time::sleep(Duration::from_millis(INJECTION_TEST_FOCUS_SETTLE_MS)).await;
+
+    crate::commands::clipboard::paste_text_impl(app_handle, text, method).await?;
+
+    wait_for_probe(rx, INJECTION_TEST_READBACK_TIMEOUT_MS, |value| {
+        value
+            .as_deref()
+            .is_some_and(|received| injection_matches(text, received))
+    })
+    .await;
+    let received = rx.borrow().clone().unwrap_or_default();
+    Ok(received)
+}
+
+/// 在受控的测试窗口里验证文本注入。录音进行中时拒绝执行，避免与真实粘贴抢焦点。
+#[tauri::command]
+pub async fn test_injection(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    text: String,
+    method: Option<String>,
+) -> Result<InjectionTestResult, AppError> {
+    if text.is_empty() {
+        return Err(AppError::Other("测试文本不能为空".into()));
+    }
+    if text.chars().count() > INJECTION_TEST_MAX_CHARS {
+        return Err(AppError::Other(format!(
+            "测试文本不能超过 {} 个字符",
+            INJECTION_TEST_MAX_CHARS
+        )));
+    }
+    let method = method.unwrap_or_else(|| state.ui.input_method.lock().clone());
+    crate::commands::audio::validate_input_method(&method)?;
+    if state.recording.recording.lock().is_some() {
+        return Err(AppError::Audio(
+            crate::commands::audio::RECORDING_ALREADY_ACTIVE_ERROR.into(),
+        ));
+    }
+
+    let (tx, mut rx) = tokio::sync::watch::channel(None);
+    {
+        let mut probe = state.ui.injection_test_probe.lock();
+        if probe.is_some() {
+            return Err(AppError::Other("已有注入测试正在进行".into()));
+        }
+        *probe = Some(tx);
+    }
+
+    let started = Instant::now();
+    let result = run_injection_test(&app_handle, &mut rx, &text, &method).await;
+
+    *state.ui.injection_test_probe.lock() = None;
+    if let Some(window) = app_handle.get_webview_window(INJECTION_TEST_WINDOW_LABEL) {
+        if let Err(err) = window.destroy() {
+            log::warn!("关闭注入测试窗口失败: {}", err);
+        }
+    }
+
+    let received = result?;
+    let matched = injection_matches(&text, &received);
+    log::info!(
+        "注入测试完成: method={}, 匹配={}, 期望 {} 字符, 实际 {} 字符",
+        method,
+        matched,
+        text.chars().count(),
+        received.chars().count()
+    );
+    Ok(InjectionTestResult {
+        method,
+        expected: text,
+        received,
+        matched,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+/// 检查当前前台窗口能否接收注入的输入（Windows 上比较进程完整性级别）。
+#[tauri::command]
+pub async fn can_inject_here() -> Result<foreground::InjectTargetCheck, AppError> {
+    let check = tokio::task::spawn_blocking(foreground::check_foreground_inject_target)
+        .await
+        .map_err(|e| AppError::Other(format!("检查前台窗口失败: {}", e)))?;
+    if !check.injectable {
+        log::info!(
+            "前台窗口不可注入: {} ({})",
+            check.process_name.as_deref().unwrap_or("未知"),
+            check.reason.as_deref().unwrap_or_default()
+        );
+    }
+    Ok(check)
+}
+
+/// 测试窗口挂载时以空串调用一次，之后文本框每次变化都回报当前内容。
+#[tauri::command]
+pub fn report_injection_test_value(state: tauri::State<'_, AppState>, value: String) {
+    if let Some(tx) = state.ui.injection_test_probe.lock().as_ref() {
+        tx.send_replace(Some(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::injection_matches;
+
+    #[test]
+    fn injection_match_normalizes_line_endings() {
+        assert!(injection_matches("你好\r\n世界 🎉", "你好\n世界 🎉"));
+        assert!(!injection_matches("你好", "你"));
+        assert!(!injection_matches("abc", "abc "));
+    }
+}
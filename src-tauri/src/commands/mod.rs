@@ -1,11 +1,13 @@
 pub mod ai_polish;
 pub mod assistant;
 pub mod audio;
+pub mod autostart;
 pub mod clipboard;
 pub mod codex_oauth;
 pub mod funasr;
 pub mod history;
 pub mod hotkey;
+pub mod injection_test;
 pub mod profile;
 pub mod selection;
 pub mod updater;
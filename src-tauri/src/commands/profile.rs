@@ -3,7 +3,8 @@ use std::sync::atomic::Ordering;
 
 use crate::services::llm_client::{LlmRequestOptions, LlmUserInput};
 use crate::services::{
-    codex_oauth_service, history_service, llm_client, llm_provider, profile_service,
+    audio_service, codex_oauth_service, config_bundle_service, history_service, llm_client,
+    llm_provider, profile_service,
 };
 use crate::state::user_profile::*;
 use crate::state::AppState;
@@ -210,10 +211,16 @@ pub async fn set_history_settings(
     enabled: bool,
     save_audio: bool,
     retention_days: u32,
+    recording_format: Option<String>,
 ) -> Result<(), String> {
     if retention_days > 3650 {
         return Err("历史保留天数不能超过 3650 天".into());
     }
+    let recording_format = match recording_format.as_deref() {
+        None => state.with_profile(|profile| profile.history_settings.recording_format.clone()),
+        Some(format @ ("wav" | "opus")) => format.to_string(),
+        Some(other) => return Err(format!("未知的录音保存格式: {}，可选值: wav, opus", other)),
+    };
     // 清理是可能失败的 I/O；先完成它再提交内存/profile 状态，避免命令报错但
     // 设置实际上已经改变。
     history_service::cleanup(retention_days).await?;
@@ -222,6 +229,7 @@ pub async fn set_history_settings(
             enabled,
             save_audio: enabled && save_audio,
             retention_days,
+            recording_format,
         };
     });
     Ok(())
@@ -658,6 +666,43 @@ pub async fn set_custom_prompt(
     Ok(())
 }
 
+/// 设置听写结果的输出模板；传 None 或 `{text}` 恢复默认（原文粘贴）。
+#[tauri::command]
+pub async fn set_output_template(
+    state: tauri::State<'_, AppState>,
+    template: Option<String>,
+) -> Result<(), String> {
+    let template = template.filter(|s| !s.trim().is_empty());
+    if let Some(template) = template.as_deref() {
+        audio_service::validate_output_template(template)?;
+    }
+    let template = template.filter(|s| s != audio_service::DEFAULT_OUTPUT_TEMPLATE);
+    profile_service::update_profile_and_schedule(state.inner(), |p| {
+        p.output_template = template;
+    });
+    Ok(())
+}
+
+/// 设置听写完成后执行的外部程序；传 None 关闭。程序会拿到完整转写文本，
+/// 只应配置自己信任的程序。
+#[tauri::command]
+pub async fn set_post_hook(
+    state: tauri::State<'_, AppState>,
+    hook: Option<PostHookSettings>,
+) -> Result<(), String> {
+    let hook = hook.map(|mut hook| {
+        hook.program = hook.program.trim().to_string();
+        hook
+    });
+    if let Some(hook) = hook.as_ref() {
+        audio_service::validate_post_hook(hook)?;
+    }
+    profile_service::update_profile_and_schedule(state.inner(), |p| {
+        p.post_hook = hook;
+    });
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn export_user_profile(
     state: tauri::State<'_, AppState>,
@@ -705,6 +750,93 @@ pub async fn import_user_profile(
     Ok(())
 }
 
+/// 导出整机配置（画像 + 引擎 + 输入设置）到指定路径，返回实际写入的路径。
+#[tauri::command]
+pub async fn export_config(
+    state: tauri::State<'_, AppState>,
+    path: String,
+) -> Result<String, String> {
+    let input = config_bundle_service::ConfigInputSettings {
+        input_device: state.selected_input_device_name(),
+        input_method: Some(state.ui.input_method.lock().clone()),
+    };
+    let bundle = config_bundle_service::build_config_bundle(
+        state.snapshot_profile(),
+        paths::read_engine_config(),
+        input,
+    );
+    let data = serde_json::to_string_pretty(&bundle).map_err(|e| format!("序列化失败: {}", e))?;
+    let path = normalize_profile_export_path(PathBuf::from(path));
+    tokio::fs::write(&path, data)
+        .await
+        .map_err(|e| format!("写入配置文件失败: {}", e))?;
+    Ok(paths::strip_win_prefix(&path))
+}
+
+/// 从 `export_config` 导出的文件恢复配置：先完整校验，再一次性替换画像并落盘，
+/// 随后重新注册热键、切换输入设备和注入方式；引擎或推理线程数变化时切换/重启引擎。
+/// 返回实际生效的输入设置，前端需写回 localStorage，否则下次启动会被旧值覆盖。
+#[tauri::command]
+pub async fn import_config(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    path: String,
+) -> Result<config_bundle_service::ConfigInputSettings, String> {
+    let json = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("读取配置文件失败: {}", e))?;
+    let bundle = config_bundle_service::parse_config_bundle(&json)?;
+
+    let previous_runtime = state.with_profile(|p| (p.cpu_threads, p.max_vram_gb));
+    let (_, profile) = state.update_profile(|profile| {
+        *profile = config_bundle_service::merge_imported_profile(profile, bundle.profile);
+        profile_service::normalize_profile(profile);
+    });
+    profile_service::save_profile_async(&profile)
+        .await
+        .map_err(|e| format!("保存用户画像失败: {}", e))?;
+    llm_provider::sync_runtime_api_key(&app_handle, state.inner());
+    crate::commands::hotkey::reapply_saved_hotkeys(&app_handle).await;
+
+    let mut input = bundle.input;
+    if let Some(method) = input.input_method.take() {
+        match crate::commands::audio::validate_input_method(&method) {
+            Ok(()) => *state.ui.input_method.lock() = method,
+            Err(err) => log::warn!("导入配置里的注入方式无效，保持当前值: {}", err),
+        }
+    }
+    if input.input_device.is_some() {
+        state.set_selected_input_device_name(input.input_device.clone());
+    }
+    let applied_input = config_bundle_service::ConfigInputSettings {
+        input_device: state.selected_input_device_name(),
+        input_method: Some(state.ui.input_method.lock().clone()),
+    };
+
+    let current_engine = paths::read_engine_config();
+    match bundle.engine.filter(|engine| *engine != current_engine) {
+        Some(engine) => {
+            if let Err(err) =
+                crate::commands::funasr::set_engine(app_handle.clone(), state.clone(), engine).await
+            {
+                log::warn!("导入配置时切换引擎失败，保留当前引擎: {}", err);
+            }
+        }
+        None if (profile.cpu_threads, profile.max_vram_gb) != previous_runtime
+            && state.is_funasr_ready() =>
+        {
+            if let Err(err) =
+                crate::commands::funasr::restart_funasr(app_handle.clone(), state.clone()).await
+            {
+                log::warn!("导入配置后重启引擎失败: {}", err);
+            }
+        }
+        None => {}
+    }
+    log::info!("已导入配置文件: {}", path);
+    Ok(applied_input)
+}
+
 /// LLM 审核核心逻辑，供命令和定期任务共用
 pub async fn run_correction_validation(
     app_handle: &tauri::AppHandle,
@@ -192,7 +192,7 @@ pub fn get_selection_overlay_state() -> Option<selection_service::SelectionDetec
 
 #[tauri::command]
 pub async fn copy_selection(app_handle: tauri::AppHandle, text: String) -> Result<(), AppError> {
-    crate::commands::clipboard::write_text_to_clipboard(&app_handle, &text)
+    crate::commands::clipboard::write_text_to_clipboard_async(&app_handle, text).await
 }
 
 #[tauri::command]
@@ -1,5 +1,6 @@
 use std::sync::atomic::Ordering;
 
+use crate::state::user_profile::{SubtitleDisplay, SubtitlePosition};
 use crate::state::{AppState, RecordingMode, RecordingPhase};
 use crate::utils::AppError;
 use tauri::{Emitter, Manager};
@@ -33,6 +34,11 @@ fn force_window_topmost(window: &tauri::WebviewWindow) {
 
 const DEFAULT_SUBTITLE_WINDOW_WIDTH: f64 = 1280.0;
 const DEFAULT_SUBTITLE_WINDOW_HEIGHT: f64 = 720.0;
+/// 可交互模式下字幕窗口的逻辑尺寸：不再铺满显示器，否则整块屏都点不穿
+const INTERACTIVE_SUBTITLE_WIDTH: f64 = 960.0;
+const INTERACTIVE_SUBTITLE_HEIGHT: f64 = 420.0;
+/// 默认位置离显示器底边的逻辑距离
+const INTERACTIVE_SUBTITLE_BOTTOM_MARGIN: f64 = 48.0;
 
 fn tauri_error(action: &str, err: impl std::fmt::Display) -> AppError {
     AppError::Tauri(format!("{}: {}", action, err))
@@ -78,14 +84,138 @@ fn find_cursor_monitor(_app_handle: &tauri::AppHandle) -> Option<tauri::Monitor>
     None
 }
 
-fn resolve_subtitle_layout(app_handle: &tauri::AppHandle) -> (f64, f64, f64, f64) {
-    let monitor = find_cursor_monitor(app_handle)
-        .or_else(|| {
-            app_handle
-                .get_webview_window("main")
-                .and_then(|window| window.current_monitor().ok().flatten())
-        })
-        .or_else(|| app_handle.primary_monitor().ok().flatten())
+/// 字幕窗口覆盖的显示器区域，按物理像素保存。
+///
+/// 混合 DPI 多显示器下，Tauri 会用窗口 *当前* 所在显示器的缩放比把逻辑坐标换算成物理坐标，
+/// 字幕从 150% 的主屏挪到 100% 的副屏时尺寸和位置都会算错；所以真正落到窗口上的
+/// 始终是目标显示器的物理矩形，逻辑值只用于创建窗口时的初始参数。
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SubtitleLayout {
+    position: tauri::PhysicalPosition<i32>,
+    size: tauri::PhysicalSize<u32>,
+    scale_factor: f64,
+}
+
+impl SubtitleLayout {
+    fn from_monitor(monitor: &tauri::Monitor) -> Self {
+        Self {
+            position: *monitor.position(),
+            size: *monitor.size(),
+            scale_factor: monitor.scale_factor(),
+        }
+    }
+
+    fn fallback() -> Self {
+        Self {
+            position: tauri::PhysicalPosition::new(0, 0),
+            size: tauri::PhysicalSize::new(
+                DEFAULT_SUBTITLE_WINDOW_WIDTH as u32,
+                DEFAULT_SUBTITLE_WINDOW_HEIGHT as u32,
+            ),
+            scale_factor: 1.0,
+        }
+    }
+
+    /// 按目标显示器自己的缩放比换算的逻辑尺寸与位置 (width, height, x, y)
+    fn logical(&self) -> (f64, f64, f64, f64) {
+        let scale_factor = if self.scale_factor > 0.0 {
+            self.scale_factor
+        } else {
+            1.0
+        };
+        (
+            (self.size.width as f64 / scale_factor).max(1.0),
+            (self.size.height as f64 / scale_factor).max(1.0),
+            self.position.x as f64 / scale_factor,
+            self.position.y as f64 / scale_factor,
+        )
+    }
+}
+
+/// 最近一次把字幕窗口铺到的显示器原点，用来区分 "拖到了另一块屏" 和同屏内的移动。
+/// 可交互模式下就是程序设置的窗口位置，用来区分用户拖动和自己的 set_position。
+static SUBTITLE_MONITOR_ORIGIN: parking_lot::Mutex<Option<tauri::PhysicalPosition<i32>>> =
+    parking_lot::Mutex::new(None);
+
+fn preferred_subtitle_monitor(app_handle: &tauri::AppHandle) -> Option<tauri::Monitor> {
+    let main_window_monitor = || {
+        app_handle
+            .get_webview_window("main")
+            .and_then(|window| window.current_monitor().ok().flatten())
+    };
+    let primary_monitor = || app_handle.primary_monitor().ok().flatten();
+    let display = app_handle
+        .state::<AppState>()
+        .with_profile(|profile| profile.subtitle_display);
+    match display {
+        SubtitleDisplay::Cursor => find_cursor_monitor(app_handle)
+            .or_else(main_window_monitor)
+            .or_else(primary_monitor),
+        SubtitleDisplay::Primary => primary_monitor(),
+        SubtitleDisplay::MainWindow => main_window_monitor().or_else(primary_monitor),
+    }
+}
+
+fn subtitle_is_interactive(app_handle: &tauri::AppHandle) -> bool {
+    app_handle
+        .state::<AppState>()
+        .with_profile(|profile| profile.subtitle_interactive)
+}
+
+fn monitor_contains(monitor: &tauri::Monitor, position: SubtitlePosition) -> bool {
+    let origin = monitor.position();
+    let size = monitor.size();
+    position.x >= origin.x
+        && position.x < origin.x + size.width as i32
+        && position.y >= origin.y
+        && position.y < origin.y + size.height as i32
+}
+
+/// 可交互模式的字幕条：保存过的位置优先（夹回显示器范围内，防止拔掉副屏后找不回来），
+/// 否则放在显示器底部居中。
+fn interactive_subtitle_layout(
+    monitor: SubtitleLayout,
+    saved: Option<SubtitlePosition>,
+) -> SubtitleLayout {
+    let scale_factor = if monitor.scale_factor > 0.0 {
+        monitor.scale_factor
+    } else {
+        1.0
+    };
+    let width = ((INTERACTIVE_SUBTITLE_WIDTH * scale_factor) as u32).min(monitor.size.width);
+    let height = ((INTERACTIVE_SUBTITLE_HEIGHT * scale_factor) as u32).min(monitor.size.height);
+    let max_x = monitor.position.x + (monitor.size.width - width) as i32;
+    let max_y = monitor.position.y + (monitor.size.height - height) as i32;
+    let (x, y) = match saved {
+        Some(saved) => (saved.x, saved.y),
+        None => (
+            monitor.position.x + (monitor.size.width - width) as i32 / 2,
+            max_y - (INTERACTIVE_SUBTITLE_BOTTOM_MARGIN * scale_factor) as i32,
+        ),
+    };
+    SubtitleLayout {
+        position: tauri::PhysicalPosition::new(
+            x.clamp(monitor.position.x, max_x),
+            y.clamp(monitor.position.y, max_y),
+        ),
+        size: tauri::PhysicalSize::new(width, height),
+        scale_factor: monitor.scale_factor,
+    }
+}
+
+fn resolve_subtitle_layout(app_handle: &tauri::AppHandle) -> SubtitleLayout {
+    let (interactive, saved_position) = app_handle
+        .state::<AppState>()
+        .with_profile(|profile| (profile.subtitle_interactive, profile.subtitle_position));
+    let saved_monitor = saved_position.filter(|_| interactive).and_then(|position| {
+        app_handle
+            .available_monitors()
+            .ok()?
+            .into_iter()
+            .find(|monitor| monitor_contains(monitor, position))
+    });
+    let monitor = saved_monitor
+        .or_else(|| preferred_subtitle_monitor(app_handle))
         .or_else(|| {
             app_handle
                 .available_monitors()
@@ -93,55 +223,133 @@ fn resolve_subtitle_layout(app_handle: &tauri::AppHandle) -> (f64, f64, f64, f64
                 .and_then(|monitors| monitors.into_iter().next())
         });
 
-    if let Some(monitor) = monitor {
-        let screen_size = monitor.size();
-        let screen_pos = monitor.position();
-        let scale_factor = monitor.scale_factor();
-        let logical_width = (screen_size.width as f64 / scale_factor).max(1.0);
-        let logical_height = (screen_size.height as f64 / scale_factor).max(1.0);
-        let x = screen_pos.x as f64 / scale_factor;
-        let y = screen_pos.y as f64 / scale_factor;
-        (logical_width, logical_height, x, y)
+    let layout = match monitor {
+        Some(monitor) => SubtitleLayout::from_monitor(&monitor),
+        None => {
+            log::warn!("未获取到显示器信息，字幕窗口使用默认布局");
+            SubtitleLayout::fallback()
+        }
+    };
+    if interactive {
+        interactive_subtitle_layout(layout, saved_position)
     } else {
-        log::warn!("未获取到显示器信息，字幕窗口使用默认布局");
-        (
-            DEFAULT_SUBTITLE_WINDOW_WIDTH,
-            DEFAULT_SUBTITLE_WINDOW_HEIGHT,
-            0.0,
-            0.0,
-        )
+        layout
     }
 }
 
-fn apply_subtitle_layout(
-    app_handle: &tauri::AppHandle,
+fn apply_layout_to_window(
     window: &tauri::WebviewWindow,
+    layout: SubtitleLayout,
 ) -> Result<(), AppError> {
-    let (logical_width, logical_height, x, y) = resolve_subtitle_layout(app_handle);
-    window
-        .set_size(tauri::Size::Logical(tauri::LogicalSize::new(
-            logical_width,
-            logical_height,
-        )))
-        .map_err(|e| tauri_error("设置字幕窗口尺寸失败", e))?;
+    // 先移动再改尺寸：移到另一块屏后窗口的缩放比才会切换，物理尺寸不受其影响
     window
-        .set_position(tauri::Position::Logical(tauri::LogicalPosition::new(x, y)))
+        .set_position(tauri::Position::Physical(layout.position))
         .map_err(|e| tauri_error("设置字幕窗口位置失败", e))?;
+    window
+        .set_size(tauri::Size::Physical(layout.size))
+        .map_err(|e| tauri_error("设置字幕窗口尺寸失败", e))?;
+    *SUBTITLE_MONITOR_ORIGIN.lock() = Some(layout.position);
     Ok(())
 }
 
+fn apply_subtitle_layout(
+    app_handle: &tauri::AppHandle,
+    window: &tauri::WebviewWindow,
+) -> Result<(), AppError> {
+    apply_layout_to_window(window, resolve_subtitle_layout(app_handle))
+}
+
+fn subtitle_moved_to_other_monitor(
+    last_origin: Option<tauri::PhysicalPosition<i32>>,
+    monitor_origin: tauri::PhysicalPosition<i32>,
+) -> bool {
+    last_origin != Some(monitor_origin)
+}
+
+/// 字幕窗口被拖到另一块显示器，或所在显示器的缩放比变化时，重新铺满它当前所在的显示器。
+/// 同一块屏内的移动不处理，避免和拖动本身较劲。
+pub(crate) fn handle_subtitle_window_event(window: &tauri::Window, event: &tauri::WindowEvent) {
+    if subtitle_is_interactive(window.app_handle()) {
+        // 可交互模式下窗口只有字幕条大小，移动就是用户拖动：记下位置，不重新铺满
+        if let tauri::WindowEvent::Moved(position) = event {
+            if *SUBTITLE_MONITOR_ORIGIN.lock() == Some(*position) {
+                return;
+            }
+            let position = SubtitlePosition {
+                x: position.x,
+                y: position.y,
+            };
+            let state = window.app_handle().state::<AppState>();
+            if state.with_profile(|profile| profile.subtitle_position) != Some(position) {
+                crate::services::profile_service::update_profile_and_schedule(
+                    state.inner(),
+                    |profile| profile.subtitle_position = Some(position),
+                );
+            }
+        }
+        return;
+    }
+    let scale_changed = match event {
+        tauri::WindowEvent::ScaleFactorChanged { .. } => true,
+        tauri::WindowEvent::Moved(_) => false,
+        _ => return,
+    };
+    let Some(webview_window) = window.get_webview_window("subtitle") else {
+        return;
+    };
+    let Some(monitor) = webview_window.current_monitor().ok().flatten() else {
+        return;
+    };
+    let layout = SubtitleLayout::from_monitor(&monitor);
+    if !scale_changed
+        && !subtitle_moved_to_other_monitor(*SUBTITLE_MONITOR_ORIGIN.lock(), layout.position)
+    {
+        return;
+    }
+    log::debug!(
+        "字幕窗口所在显示器变化，按新显示器重新布局 ({}x{} @ {:.2}x)",
+        layout.size.width,
+        layout.size.height,
+        layout.scale_factor
+    );
+    if let Err(err) = apply_layout_to_window(&webview_window, layout) {
+        log::warn!("字幕窗口跨显示器重新布局失败: {}", err);
+    }
+}
+
+/// `interactive` 为 false 时恢复鼠标穿透；用户开启了可交互字幕时始终不穿透。
 pub(crate) fn set_subtitle_window_interactive(
     app_handle: &tauri::AppHandle,
     interactive: bool,
 ) -> Result<(), AppError> {
     if let Some(window) = app_handle.get_webview_window("subtitle") {
         window
-            .set_ignore_cursor_events(!interactive)
+            .set_ignore_cursor_events(!(interactive || subtitle_is_interactive(app_handle)))
             .map_err(|e| tauri_error("设置字幕窗口交互状态失败", e))?;
     }
     Ok(())
 }
 
+/// 切换主窗口：正在前台时隐藏，否则调出来并聚焦（供热键使用）。
+pub(crate) fn toggle_main_window(app_handle: &tauri::AppHandle) -> Result<(), AppError> {
+    let window = require_window(app_handle, "main", "主窗口不存在")?;
+    let in_front = window.is_visible().unwrap_or(false)
+        && window.is_focused().unwrap_or(false)
+        && !window.is_minimized().unwrap_or(false);
+    if in_front {
+        window
+            .hide()
+            .map_err(|e| tauri_error("隐藏主窗口失败", e))?;
+    } else {
+        window
+            .show()
+            .map_err(|e| tauri_error("显示主窗口失败", e))?;
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn hide_main_window(app_handle: tauri::AppHandle) -> Result<String, AppError> {
     let window = require_window(&app_handle, "main", "主窗口不存在")?;
@@ -151,12 +359,61 @@ pub async fn hide_main_window(app_handle: tauri::AppHandle) -> Result<String, Ap
     Ok("主窗口已隐藏".to_string())
 }
 
+/// 连续创建失败这么多次后放弃字幕窗口（多半是显卡/合成器问题），
+/// 避免每次录音都重试拖慢启动；录音和转写照常进行。
+const MAX_SUBTITLE_CREATE_FAILURES: u8 = 3;
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SubtitleDisabledPayload {
+    failures: u8,
+    error: String,
+}
+
+fn subtitle_creation_disabled(app_handle: &tauri::AppHandle) -> bool {
+    app_handle
+        .state::<AppState>()
+        .recording
+        .subtitle_create_failures
+        .load(Ordering::Acquire)
+        >= MAX_SUBTITLE_CREATE_FAILURES
+}
+
+fn record_subtitle_create_failure(app_handle: &tauri::AppHandle, error: &AppError) {
+    let failures = app_handle
+        .state::<AppState>()
+        .recording
+        .subtitle_create_failures
+        .fetch_add(1, Ordering::AcqRel)
+        .saturating_add(1);
+    if failures == MAX_SUBTITLE_CREATE_FAILURES {
+        log::error!(
+            "字幕窗口连续 {} 次创建失败，本次运行不再显示字幕: {}",
+            failures,
+            error
+        );
+        let _ = app_handle.emit(
+            "subtitle-disabled",
+            SubtitleDisabledPayload {
+                failures,
+                error: error.to_string(),
+            },
+        );
+    }
+}
+
 fn create_subtitle_window_unlocked(app_handle: &tauri::AppHandle) -> Result<String, AppError> {
     if app_handle.get_webview_window("subtitle").is_some() {
         return Ok("字幕窗口已存在".to_string());
     }
+    if subtitle_creation_disabled(app_handle) {
+        return Err(AppError::Other(
+            "字幕窗口多次创建失败，已在本次运行中禁用".to_string(),
+        ));
+    }
 
-    let (logical_width, logical_height, x, y) = resolve_subtitle_layout(app_handle);
+    let layout = resolve_subtitle_layout(app_handle);
+    let (logical_width, logical_height, x, y) = layout.logical();
 
     let window = tauri::WebviewWindowBuilder::new(
         app_handle,
@@ -175,11 +432,27 @@ fn create_subtitle_window_unlocked(app_handle: &tauri::AppHandle) -> Result<Stri
     .shadow(false)
     .visible(false)
     .build()
-    .map_err(|e| tauri_error("创建字幕窗口失败", e))?;
+    .map_err(|e| tauri_error("创建字幕窗口失败", e));
+    let window = match window {
+        Ok(window) => window,
+        Err(err) => {
+            record_subtitle_create_failure(app_handle, &err);
+            return Err(err);
+        }
+    };
+    app_handle
+        .state::<AppState>()
+        .recording
+        .subtitle_create_failures
+        .store(0, Ordering::Release);
 
-    if let Err(err) = window.set_ignore_cursor_events(true) {
+    if let Err(err) = window.set_ignore_cursor_events(!subtitle_is_interactive(app_handle)) {
         log::warn!("设置字幕窗口鼠标穿透失败，继续运行: {}", err);
     }
+    // 构建参数是逻辑值，按创建时的缩放比换算；建好后再按物理矩形校正一次
+    if let Err(err) = apply_layout_to_window(&window, layout) {
+        log::warn!("校正字幕窗口布局失败，继续运行: {}", err);
+    }
 
     Ok("字幕窗口已创建".to_string())
 }
@@ -187,7 +460,12 @@ fn create_subtitle_window_unlocked(app_handle: &tauri::AppHandle) -> Result<Stri
 pub async fn create_subtitle_window(app_handle: tauri::AppHandle) -> Result<String, AppError> {
     let state = app_handle.state::<AppState>();
     let _window_op = state.recording.subtitle_window_op.lock().await;
-    create_subtitle_window_unlocked(&app_handle)
+    let created = create_subtitle_window_unlocked(&app_handle)?;
+    if subtitle_is_persistent(&app_handle) {
+        // 常驻模式：启动即显示，之后的收尾隐藏都会被跳过
+        return show_subtitle_window_unlocked(&app_handle, None);
+    }
+    Ok(created)
 }
 
 pub(crate) fn reserve_subtitle_show_generation(app_handle: &tauri::AppHandle) -> u64 {
@@ -254,6 +532,10 @@ fn show_subtitle_window_unlocked(
     }
 
     if app_handle.get_webview_window("subtitle").is_none() {
+        if subtitle_creation_disabled(app_handle) {
+            // 已经提示过用户，这里静默跳过，不让字幕拖累录音
+            return Ok("字幕窗口已禁用".to_string());
+        }
         create_subtitle_window_unlocked(app_handle)?;
     }
 
@@ -290,6 +572,21 @@ fn show_subtitle_window_unlocked(
             .show()
             .map_err(|e| tauri_error("显示字幕窗口失败", e))?;
     }
+    let shown_gen = session.map_or_else(
+        || {
+            app_handle
+                .state::<AppState>()
+                .recording
+                .subtitle_show_gen
+                .load(Ordering::Acquire)
+        },
+        |(_, show_gen)| show_gen,
+    );
+    *app_handle
+        .state::<AppState>()
+        .recording
+        .subtitle_shown_at
+        .lock() = Some((shown_gen, std::time::Instant::now()));
 
     // 确保窗口在最顶层（Windows 上 hide/show 后可能丢失置顶状态）
     // 先用 Tauri API 置顶
@@ -308,6 +605,64 @@ fn show_subtitle_window_unlocked(
     Ok("字幕窗口已显示".to_string())
 }
 
+/// 设置字幕窗口出现在哪块显示器：跟随光标、主显示器或主窗口所在显示器。
+#[tauri::command]
+pub async fn set_subtitle_display(
+    state: tauri::State<'_, AppState>,
+    display: SubtitleDisplay,
+) -> Result<(), AppError> {
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.subtitle_display = display;
+    });
+    Ok(())
+}
+
+/// 开关可交互字幕：开启后字幕窗口不再鼠标穿透、缩成字幕条并可拖动，拖动后的位置会被记住。
+#[tauri::command]
+pub async fn set_subtitle_interactive(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.subtitle_interactive = enabled;
+    });
+    let _window_op = state.recording.subtitle_window_op.lock().await;
+    if let Some(window) = app_handle.get_webview_window("subtitle") {
+        set_subtitle_window_interactive(&app_handle, false)?;
+        apply_subtitle_layout(&app_handle, &window)?;
+    }
+    Ok(())
+}
+
+/// 清除拖动保存的字幕位置，回到显示器底部居中。
+#[tauri::command]
+pub async fn reset_subtitle_position(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.subtitle_position = None;
+    });
+    let _window_op = state.recording.subtitle_window_op.lock().await;
+    if let Some(window) = app_handle.get_webview_window("subtitle") {
+        apply_subtitle_layout(&app_handle, &window)?;
+    }
+    Ok(())
+}
+
+/// 字幕窗口里按下鼠标时由前端调用，交给系统拖动窗口；未开启可交互字幕时忽略。
+#[tauri::command]
+pub async fn start_subtitle_drag(app_handle: tauri::AppHandle) -> Result<(), AppError> {
+    if !subtitle_is_interactive(&app_handle) {
+        return Ok(());
+    }
+    let window = require_window(&app_handle, "subtitle", "字幕窗口不存在")?;
+    window
+        .start_dragging()
+        .map_err(|e| tauri_error("拖动字幕窗口失败", e))
+}
+
 #[tauri::command]
 pub async fn show_subtitle_window(app_handle: tauri::AppHandle) -> Result<String, AppError> {
     reserve_subtitle_show_generation(&app_handle);
@@ -345,11 +700,107 @@ pub(crate) async fn set_subtitle_window_interactive_for_session(
     Ok(true)
 }
 
+/// 录音期间重新置顶字幕窗口的间隔
+const SUBTITLE_TOPMOST_INTERVAL_MS: u64 = 1_000;
+
+/// 字幕窗口可见时重新置顶。窗口隐藏时什么都不做：Windows 上的强制置顶
+/// 带 SWP_SHOWWINDOW，会把已隐藏的字幕重新显示出来。
+pub(crate) fn reassert_subtitle_topmost(app_handle: &tauri::AppHandle) {
+    if app_handle
+        .state::<AppState>()
+        .with_profile(|profile| profile.subtitle_keep_on_top_disabled)
+    {
+        return;
+    }
+    let Some(window) = app_handle.get_webview_window("subtitle") else {
+        return;
+    };
+    if !window.is_visible().unwrap_or(false) {
+        return;
+    }
+    if let Err(err) = window.set_always_on_top(true) {
+        log::debug!("重新置顶字幕窗口失败: {}", err);
+        return;
+    }
+    #[cfg(target_os = "windows")]
+    force_window_topmost(&window);
+}
+
+/// 录音期间每秒重新置顶一次字幕窗口，录音结束（stop_flag 置位）后退出。
+/// 其他置顶窗口弹出或焦点切换后，Windows 会让字幕掉到它们下面。
+pub(crate) fn spawn_subtitle_topmost_guard(
+    app_handle: tauri::AppHandle,
+    stop_flag: std::sync::Arc<std::sync::atomic::AtomicBool>,
+) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_millis(
+                SUBTITLE_TOPMOST_INTERVAL_MS,
+            ))
+            .await;
+            if stop_flag.load(Ordering::Acquire) {
+                break;
+            }
+            reassert_subtitle_topmost(&app_handle);
+        }
+    });
+}
+
+/// 开关录音期间的字幕窗口定期置顶。
+#[tauri::command]
+pub async fn set_subtitle_keep_on_top(
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.subtitle_keep_on_top_disabled = !enabled;
+    });
+    Ok(())
+}
+
+fn subtitle_is_persistent(app_handle: &tauri::AppHandle) -> bool {
+    app_handle
+        .state::<AppState>()
+        .with_profile(|profile| profile.subtitle_persistent)
+}
+
+/// 常驻模式下不真正隐藏，只恢复鼠标穿透（助手面板可能打开过交互）。
+fn release_or_hide_subtitle_window(app_handle: &tauri::AppHandle) -> Result<String, AppError> {
+    if subtitle_is_persistent(app_handle) {
+        set_subtitle_window_interactive(app_handle, false)?;
+        return Ok("字幕窗口为常驻模式，保持显示".to_string());
+    }
+    hide_subtitle_window_inner(app_handle)
+}
+
 #[tauri::command]
 pub async fn hide_subtitle_window(app_handle: tauri::AppHandle) -> Result<String, AppError> {
     let state = app_handle.state::<AppState>();
     let _window_op = state.recording.subtitle_window_op.lock().await;
-    hide_subtitle_window_inner(&app_handle)
+    release_or_hide_subtitle_window(&app_handle)
+}
+
+/// 开关常驻字幕：开启时立即显示，关闭时若没有录音则立即隐藏。
+#[tauri::command]
+pub async fn set_subtitle_persistent(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.subtitle_persistent = enabled;
+    });
+    let _ = app_handle.emit(
+        "subtitle-persistent",
+        serde_json::json!({ "enabled": enabled }),
+    );
+    let _window_op = state.recording.subtitle_window_op.lock().await;
+    if enabled {
+        show_subtitle_window_unlocked(&app_handle, None)?;
+    } else if state.recording.recording.lock().is_none() {
+        hide_subtitle_window_inner(&app_handle)?;
+    }
+    Ok(())
 }
 
 pub fn hide_subtitle_window_inner(app_handle: &tauri::AppHandle) -> Result<String, AppError> {
@@ -364,6 +815,144 @@ pub fn hide_subtitle_window_inner(app_handle: &tauri::AppHandle) -> Result<Strin
     }
 }
 
+pub const DEFAULT_MIN_SUBTITLE_VISIBLE_MS: u64 = 600;
+pub const MAX_MIN_SUBTITLE_VISIBLE_MS: u64 = 5_000;
+
+/// 隐藏前实际要等的时间：不短于调用方给的延迟，也不早于"显示后最短可见时长"。
+/// 结果本身停留得够久（例如 2.5s 的阅读延迟）时不会再额外拖长。
+fn effective_hide_delay_ms(
+    delay_ms: u64,
+    min_visible_ms: u64,
+    shown_elapsed_ms: Option<u64>,
+) -> u64 {
+    let remaining = shown_elapsed_ms.map_or(0, |elapsed| min_visible_ms.saturating_sub(elapsed));
+    delay_ms.max(remaining)
+}
+
+fn subtitle_hide_delay_ms(app_handle: &tauri::AppHandle, show_gen: u64, delay_ms: u64) -> u64 {
+    let state = app_handle.state::<AppState>();
+    let min_visible_ms = state
+        .with_profile(|profile| profile.min_subtitle_visible_ms)
+        .unwrap_or(DEFAULT_MIN_SUBTITLE_VISIBLE_MS)
+        .min(MAX_MIN_SUBTITLE_VISIBLE_MS);
+    let shown_elapsed_ms = state
+        .recording
+        .subtitle_shown_at
+        .lock()
+        .filter(|(shown_gen, _)| *shown_gen == show_gen)
+        .map(|(_, shown_at)| shown_at.elapsed().as_millis() as u64);
+    effective_hide_delay_ms(delay_ms, min_visible_ms, shown_elapsed_ms)
+}
+
+/// 设置字幕显示后的最短可见时长，避免快速连续听写时窗口闪烁；None 恢复默认。
+#[tauri::command]
+pub async fn set_min_subtitle_visible_ms(
+    state: tauri::State<'_, AppState>,
+    ms: Option<u64>,
+) -> Result<u64, AppError> {
+    if let Some(ms) = ms.filter(|ms| *ms > MAX_MIN_SUBTITLE_VISIBLE_MS) {
+        return Err(AppError::Other(format!(
+            "字幕最短显示时长不能超过 {}ms（当前 {}ms）",
+            MAX_MIN_SUBTITLE_VISIBLE_MS, ms
+        )));
+    }
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.min_subtitle_visible_ms = ms;
+    });
+    Ok(ms.unwrap_or(DEFAULT_MIN_SUBTITLE_VISIBLE_MS))
+}
+
+pub const DEFAULT_SUBTITLE_MAX_CHARS: u32 = 60;
+pub const MAX_SUBTITLE_MAX_CHARS: u32 = 1_000;
+const SUBTITLE_ELLIPSIS: char = '…';
+
+/// 字符在单行字幕里占的列数：CJK、全角符号和常见 emoji 按 2 算，其余按 1。
+fn subtitle_char_width(ch: char) -> usize {
+    match ch as u32 {
+        0x1100..=0x115F
+        | 0x2E80..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// 超出 `max_width` 时保留末尾（最新说出的内容），开头补省略号；`max_width`
+/// 为 0 表示不截断。返回显示文本和是否截断。
+fn truncate_subtitle_tail(text: &str, max_width: usize) -> (String, bool) {
+    let total: usize = text.chars().map(subtitle_char_width).sum();
+    if max_width == 0 || total <= max_width {
+        return (text.to_string(), false);
+    }
+    let budget = max_width.saturating_sub(subtitle_char_width(SUBTITLE_ELLIPSIS));
+    let mut width = 0;
+    let start = text
+        .char_indices()
+        .rev()
+        .take_while(|(_, ch)| {
+            width += subtitle_char_width(*ch);
+            width <= budget
+        })
+        .last()
+        .map_or(text.len(), |(index, _)| index);
+    let tail = text[start..].trim_start();
+    (format!("{}{}", SUBTITLE_ELLIPSIS, tail), true)
+}
+
+fn subtitle_max_chars(app_handle: &tauri::AppHandle) -> u32 {
+    app_handle
+        .state::<AppState>()
+        .with_profile(|profile| profile.subtitle_max_chars)
+        .unwrap_or(DEFAULT_SUBTITLE_MAX_CHARS)
+        .min(MAX_SUBTITLE_MAX_CHARS)
+}
+
+/// 发出 "subtitle-text"：字幕窗口显示 `displayText`，`text` 仍是完整文本，
+/// 粘贴内容不受影响。中间结果和最终结果都走这里。
+pub(crate) fn emit_subtitle_text(
+    app_handle: &tauri::AppHandle,
+    session_id: u64,
+    text: &str,
+    interim: bool,
+) {
+    let (display_text, truncated) =
+        truncate_subtitle_tail(text, subtitle_max_chars(app_handle) as usize);
+    let _ = app_handle.emit(
+        "subtitle-text",
+        serde_json::json!({
+            "sessionId": session_id,
+            "text": text,
+            "displayText": display_text,
+            "truncated": truncated,
+            "interim": interim,
+        }),
+    );
+}
+
+/// 设置字幕最多显示的宽度，超出时只显示末尾；0 不截断，None 恢复默认。
+#[tauri::command]
+pub async fn set_subtitle_max_chars(
+    state: tauri::State<'_, AppState>,
+    max_chars: Option<u32>,
+) -> Result<u32, AppError> {
+    if let Some(max_chars) = max_chars.filter(|value| *value > MAX_SUBTITLE_MAX_CHARS) {
+        return Err(AppError::Other(format!(
+            "字幕最大显示长度不能超过 {}（当前 {}）",
+            MAX_SUBTITLE_MAX_CHARS, max_chars
+        )));
+    }
+    crate::services::profile_service::update_profile_and_schedule(state.inner(), |profile| {
+        profile.subtitle_max_chars = max_chars;
+    });
+    Ok(max_chars.unwrap_or(DEFAULT_SUBTITLE_MAX_CHARS))
+}
+
 pub(crate) fn schedule_subtitle_hide(
     app_handle: &tauri::AppHandle,
     session_id: u64,
@@ -372,6 +961,7 @@ pub(crate) fn schedule_subtitle_hide(
     delay_ms: u64,
 ) {
     let app = app_handle.clone();
+    let delay_ms = subtitle_hide_delay_ms(app_handle, show_gen, delay_ms);
     tauri::async_runtime::spawn(async move {
         tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
 
@@ -395,7 +985,7 @@ pub(crate) fn schedule_subtitle_hide(
             None,
             None,
         );
-        let hide_result = hide_subtitle_window_inner(&app);
+        let hide_result = release_or_hide_subtitle_window(&app);
         if hide_result.is_ok() {
             state
                 .recording
@@ -425,7 +1015,19 @@ pub(crate) fn schedule_subtitle_hide(
 
 #[cfg(test)]
 mod recording_window_guard_tests {
-    use super::{hide_guard_matches, show_guard_matches};
+    use super::{effective_hide_delay_ms, hide_guard_matches, show_guard_matches};
+
+    #[test]
+    fn hide_waits_for_minimum_visible_time_only_when_needed() {
+        // 刚显示 100ms 就要隐藏空结果：补足到 600ms
+        assert_eq!(effective_hide_delay_ms(360, 600, Some(100)), 500);
+        // 结果阅读延迟本身更长，不额外拖延
+        assert_eq!(effective_hide_delay_ms(2500, 600, Some(100)), 2500);
+        // 已经显示够久，或没有对应的显示记录
+        assert_eq!(effective_hide_delay_ms(360, 600, Some(5000)), 360);
+        assert_eq!(effective_hide_delay_ms(360, 600, None), 360);
+        assert_eq!(effective_hide_delay_ms(0, 0, Some(0)), 0);
+    }
 
     #[test]
     fn stale_show_cannot_overtake_a_new_session() {
@@ -441,3 +1043,99 @@ mod recording_window_guard_tests {
         assert!(hide_guard_matches(2, 2, false, 2, 2));
     }
 }
+
+#[cfg(test)]
+mod subtitle_text_tests {
+    use super::truncate_subtitle_tail;
+
+    #[test]
+    fn short_text_is_untouched_and_zero_disables_truncation() {
+        assert_eq!(
+            truncate_subtitle_tail("hello", 10),
+            ("hello".to_string(), false)
+        );
+        assert_eq!(
+            truncate_subtitle_tail("你好世界", 8),
+            ("你好世界".to_string(), false)
+        );
+        let long = "a".repeat(500);
+        assert_eq!(truncate_subtitle_tail(&long, 0), (long.clone(), false));
+    }
+
+    #[test]
+    fn long_text_keeps_the_tail_and_counts_cjk_as_double_width() {
+        assert_eq!(
+            truncate_subtitle_tail("one two three four", 10),
+            ("…hree four".to_string(), true)
+        );
+        // 省略号占 1 列，剩下 6 列只够 3 个汉字
+        assert_eq!(
+            truncate_subtitle_tail("今天天气很好我们出去玩", 7),
+            ("…出去玩".to_string(), true)
+        );
+        assert_eq!(
+            truncate_subtitle_tail("我说 hello world", 10),
+            ("…llo world".to_string(), true)
+        );
+    }
+}
+
+#[cfg(test)]
+mod subtitle_layout_tests {
+    use super::{
+        interactive_subtitle_layout, subtitle_moved_to_other_monitor, SubtitleLayout,
+        SubtitlePosition,
+    };
+    use tauri::{PhysicalPosition, PhysicalSize};
+
+    #[test]
+    fn interactive_layout_defaults_to_bottom_center_and_clamps_saved_position() {
+        let monitor = SubtitleLayout {
+            position: PhysicalPosition::new(0, 0),
+            size: PhysicalSize::new(3840, 2160),
+            scale_factor: 2.0,
+        };
+        let default = interactive_subtitle_layout(monitor, None);
+        assert_eq!(default.size, PhysicalSize::new(1920, 840));
+        assert_eq!(
+            default.position,
+            PhysicalPosition::new(960, 2160 - 840 - 96)
+        );
+
+        let saved = interactive_subtitle_layout(monitor, Some(SubtitlePosition { x: 100, y: 200 }));
+        assert_eq!(saved.position, PhysicalPosition::new(100, 200));
+
+        let off_screen =
+            interactive_subtitle_layout(monitor, Some(SubtitlePosition { x: 5000, y: -50 }));
+        assert_eq!(off_screen.position, PhysicalPosition::new(3840 - 1920, 0));
+    }
+
+    #[test]
+    fn logical_layout_uses_the_target_monitor_scale() {
+        // 150% 主屏左侧的 100% 副屏
+        let secondary = SubtitleLayout {
+            position: PhysicalPosition::new(-1920, 0),
+            size: PhysicalSize::new(1920, 1080),
+            scale_factor: 1.0,
+        };
+        assert_eq!(secondary.logical(), (1920.0, 1080.0, -1920.0, 0.0));
+
+        let primary = SubtitleLayout {
+            position: PhysicalPosition::new(0, 0),
+            size: PhysicalSize::new(3840, 2160),
+            scale_factor: 1.5,
+        };
+        assert_eq!(primary.logical(), (2560.0, 1440.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn only_crossing_monitors_triggers_relayout() {
+        let origin = PhysicalPosition::new(0, 0);
+        assert!(subtitle_moved_to_other_monitor(None, origin));
+        assert!(!subtitle_moved_to_other_monitor(Some(origin), origin));
+        assert!(subtitle_moved_to_other_monitor(
+            Some(PhysicalPosition::new(-1920, 0)),
+            origin
+        ));
+    }
+}
@@ -13,9 +13,9 @@ pub fn run() {
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
             Some(vec!["--minimized"]),
         ))
-        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
-            // 已有实例运行时，聚焦主窗口
-            focus_main_window(app);
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            // 已有实例运行时，新进程不会走到 setup，只在这里通知当前实例后退出
+            handle_second_instance(app, &args);
         }))
         .plugin(tauri_plugin_keyring::init())
         .plugin(
@@ -40,10 +40,12 @@ pub fn run() {
             }
 
             let app_handle = app.handle().clone();
-            log::info!(
-                "轻语 Whisper 应用启动，数据目录: {:?}",
-                utils::paths::get_data_dir()
-            );
+            match utils::paths::try_get_data_dir() {
+                Ok(data_dir) => log::info!("轻语 Whisper 应用启动，数据目录: {:?}", data_dir),
+                Err(err) => log::error!("轻语 Whisper 应用启动，{}", err),
+            }
+            emit_data_dir_issue(&app_handle);
+            check_resource_scripts_at_startup(&app_handle);
 
             // 启动时加载用户画像
             {
@@ -76,9 +78,20 @@ pub fn run() {
                         }
                     }
                 }
+                state
+                    .recording
+                    .save_recordings
+                    .store(loaded.save_recordings, std::sync::atomic::Ordering::Relaxed);
+                if let Some(ms) = loaded.min_audio_duration_ms {
+                    state
+                        .recording
+                        .min_audio_duration_ms
+                        .store(ms, std::sync::atomic::Ordering::Relaxed);
+                }
                 state.update_profile_mut(|profile| *profile = loaded);
                 log::info!("已加载用户画像");
             }
+            services::audio_service::restore_pending_paste_recovery(&app_handle);
 
             {
                 let app_handle = app_handle.clone();
@@ -96,7 +109,7 @@ pub fn run() {
                 });
             }
 
-            // 注册翻译/助手热键
+            // 注册翻译/助手热键，以及语言切换等非录音动作的热键
             {
                 type HotkeyRegisterFn =
                     dyn Fn(tauri::AppHandle, Option<String>) -> Result<String, utils::AppError>;
@@ -120,6 +133,30 @@ pub fn run() {
                         }
                     }
                 }
+                commands::hotkey::register_saved_command_hotkeys(&app_handle);
+            }
+
+            // 注册听写热键（保存的热键 / 默认 F2 / 用户关闭时不注册）
+            commands::hotkey::register_saved_dictation_hotkey(&app_handle);
+
+            // 按画像选择音频后端，再预热录音链路（用户开启时）
+            {
+                let state = app_handle.state::<AppState>();
+                services::audio_service::set_audio_host_preference(
+                    state.with_profile(|profile| profile.audio_host.clone()),
+                );
+                if state.with_profile(|profile| profile.audio_prewarm_enabled) {
+                    let name = state.selected_input_device_name();
+                    let rates = state.device_sample_rates();
+                    let format = state.preferred_sample_format();
+                    tauri::async_runtime::spawn_blocking(move || {
+                        if let Err(err) =
+                            services::audio_service::prewarm_audio_sync(name, rates, format)
+                        {
+                            log::warn!("启动时预热录音链路失败: {}", err);
+                        }
+                    });
+                }
             }
 
             // 启动时从系统密钥环加载 API Key
@@ -174,6 +211,7 @@ pub fn run() {
             spawn_funasr_startup(app_handle.clone());
             spawn_subtitle_prewarm(app_handle.clone());
             spawn_profile_maintenance(app_handle.clone());
+            spawn_temp_audio_cleanup();
             if let Err(error) = services::selection_service::create_selection_window(&app_handle) {
                 log::warn!("划词助手窗口预创建失败，首次使用时会重试: {error}");
             }
@@ -198,29 +236,67 @@ pub fn run() {
                     let _ = window.hide();
                 }
             }
+            if window.label() == "subtitle" {
+                commands::window::handle_subtitle_window_event(window, event);
+            }
         })
         .invoke_handler(tauri::generate_handler![
             commands::funasr::start_funasr,
             commands::funasr::transcribe_audio,
+            commands::funasr::transcribe_pcm,
+            commands::funasr::transcribe_clipboard_file,
             commands::funasr::check_funasr_status,
+            commands::funasr::get_server_environment,
+            commands::funasr::get_server_version,
+            commands::funasr::get_server_stderr,
+            commands::funasr::get_last_startup_time,
+            commands::funasr::get_temp_usage,
+            commands::funasr::clear_temp_audio,
+            commands::funasr::get_resource_usage,
             commands::funasr::check_model_files,
             commands::funasr::download_models,
+            commands::funasr::check_download_space,
             commands::funasr::cancel_model_download,
+            commands::funasr::set_download_retry_settings,
             commands::funasr::restart_funasr,
+            commands::funasr::reload_model,
             commands::funasr::get_engine,
             commands::funasr::set_engine,
+            commands::funasr::compare_engines,
             commands::funasr::set_online_asr_api_key,
             commands::funasr::get_online_asr_api_key,
             commands::funasr::get_online_asr_endpoint,
+            commands::funasr::test_remote_endpoint,
             commands::funasr::set_online_asr_endpoint,
             commands::funasr::get_alibaba_asr_config,
             commands::funasr::set_alibaba_asr_model,
             commands::funasr::list_alibaba_asr_models,
             commands::funasr::get_models_dir,
             commands::funasr::set_models_dir,
+            commands::funasr::get_data_dir,
+            commands::funasr::get_paths,
+            commands::funasr::set_data_dir,
+            commands::funasr::set_show_python_console,
+            commands::funasr::set_server_log_level,
+            commands::funasr::set_asr_formatting,
+            commands::funasr::set_diarization,
+            commands::funasr::set_language_cycle,
+            commands::funasr::cycle_language,
+            commands::funasr::set_auto_fallback_engine,
+            commands::funasr::get_cpu_threads,
+            commands::funasr::set_cpu_threads,
+            commands::funasr::set_server_crash_grace_ms,
+            commands::funasr::get_vram_budget,
+            commands::funasr::set_max_vram_gb,
             commands::funasr::pick_folder,
             commands::clipboard::copy_to_clipboard,
+            commands::clipboard::repaste_last,
+            commands::clipboard::confirm_paste,
+            commands::clipboard::discard_paste,
+            commands::clipboard::set_review_before_paste,
             commands::clipboard::paste_text,
+            commands::clipboard::set_first_char_delay_ms,
+            commands::clipboard::set_clipboard_write_retries,
             commands::codex_oauth::login_openai_codex_oauth,
             commands::codex_oauth::start_openai_codex_oauth_device_code,
             commands::codex_oauth::complete_openai_codex_oauth_device_code,
@@ -229,22 +305,73 @@ pub fn run() {
             commands::window::hide_main_window,
             commands::window::show_subtitle_window,
             commands::window::hide_subtitle_window,
+            commands::window::set_subtitle_display,
+            commands::window::set_subtitle_persistent,
+            commands::window::set_subtitle_keep_on_top,
+            commands::window::set_min_subtitle_visible_ms,
+            commands::window::set_subtitle_max_chars,
+            commands::window::set_subtitle_interactive,
+            commands::window::reset_subtitle_position,
+            commands::window::start_subtitle_drag,
             commands::hotkey::register_custom_hotkey,
+            commands::hotkey::get_dictation_hotkey_setting,
+            commands::hotkey::disable_dictation_hotkey,
+            commands::hotkey::validate_hotkey,
             commands::hotkey::register_translation_hotkey,
             commands::hotkey::register_assistant_hotkey,
+            commands::hotkey::register_language_cycle_hotkey,
+            commands::hotkey::get_hotkey_bindings,
+            commands::hotkey::set_hotkey_binding,
             commands::hotkey::unregister_all_hotkeys,
             commands::hotkey::set_recording_mode,
             commands::hotkey::get_hotkey_diagnostic,
+            commands::hotkey::set_hotkey_sequence_timeout,
+            commands::injection_test::test_injection,
+            commands::injection_test::report_injection_test_value,
+            commands::injection_test::can_inject_here,
             commands::audio::start_recording,
             commands::audio::stop_recording,
+            commands::audio::force_stop_recording,
+            commands::audio::pause_recording,
+            commands::audio::resume_recording,
             commands::audio::get_recording_snapshot,
+            commands::audio::get_recording_stats,
+            commands::audio::get_task_status,
             commands::audio::test_microphone,
+            commands::audio::prewarm_audio,
+            commands::audio::list_audio_hosts,
+            commands::audio::set_audio_host,
+            commands::audio::set_audio_prewarm_enabled,
             commands::audio::list_input_devices,
+            commands::audio::reset_audio_subsystem,
+            commands::audio::get_device_capabilities,
+            commands::audio::get_microphone_info,
+            commands::audio::benchmark_audio_pipeline,
+            commands::audio::measure_noise_floor,
             commands::audio::set_input_device,
+            commands::audio::set_waveform_stream_enabled,
+            commands::audio::get_device_sample_rates,
+            commands::audio::set_device_sample_rate,
+            commands::audio::set_preferred_sample_format,
             commands::audio::start_microphone_level_monitor,
             commands::audio::stop_microphone_level_monitor,
             commands::audio::set_input_method,
+            commands::audio::set_input_method_fallback,
+            commands::audio::set_next_input_method,
+            commands::audio::set_language_input_method,
+            commands::audio::set_recording_merge_window,
+            commands::audio::set_pending_paste_limit,
+            commands::audio::set_interim_cost_settings,
+            commands::audio::set_loudness_normalization,
+            commands::audio::set_leading_silence_pad,
+            commands::audio::set_output_cleanup,
+            commands::audio::set_strip_prefix,
+            commands::audio::set_recording_buffer_prealloc_secs,
+            commands::audio::set_transcription_mode,
+            commands::audio::set_text_variants,
+            commands::audio::get_language_input_methods,
             commands::audio::set_sound_enabled,
+            commands::audio::set_min_audio_duration,
             commands::ai_polish::set_ai_polish_config,
             commands::ai_polish::get_ai_polish_api_key,
             commands::ai_polish::set_ai_polish_screen_context_enabled,
@@ -260,10 +387,14 @@ pub fn run() {
             commands::profile::get_llm_reasoning_support,
             commands::profile::export_user_profile,
             commands::profile::import_user_profile,
+            commands::profile::export_config,
+            commands::profile::import_config,
             commands::profile::submit_user_correction,
             commands::profile::set_translation_target,
             commands::profile::set_translation_hotkey,
             commands::profile::set_custom_prompt,
+            commands::profile::set_output_template,
+            commands::profile::set_post_hook,
             commands::profile::set_openai_fast_mode,
             commands::profile::add_custom_provider,
             commands::profile::update_custom_provider,
@@ -271,6 +402,8 @@ pub fn run() {
             commands::profile::validate_corrections,
             commands::profile::set_correction_validation_config,
             commands::profile::remove_correction,
+            commands::autostart::get_autostart_status,
+            commands::autostart::set_autostart,
             commands::updater::check_app_update,
             commands::updater::open_app_release_page,
             commands::assistant::set_assistant_hotkey,
@@ -297,8 +430,16 @@ pub fn run() {
             commands::selection::cancel_selection_action,
             commands::history::list_transcription_history,
             commands::history::get_transcription_history_stats,
+            commands::history::get_usage_stats,
+            commands::history::reset_usage_stats,
+            commands::history::set_save_recordings,
+            commands::history::list_recordings,
             commands::history::delete_transcription_history,
+            commands::history::repaste_history,
+            commands::history::copy_history,
+            commands::history::update_history,
             commands::history::export_transcription_history,
+            commands::history::export_transcription,
             commands::history::reprocess_transcription_history,
         ])
         .run(tauri::generate_context!())
@@ -316,6 +457,65 @@ fn mark_setup_once() -> bool {
     first_run
 }
 
+/// 数据目录不可用时通知前端。setup 阶段窗口可能还没开始监听，
+/// 前端也可以通过 get_data_dir 的 startup_error 字段补查。
+fn emit_data_dir_issue(app_handle: &tauri::AppHandle) {
+    let Some(issue) = utils::paths::data_dir_issue() else {
+        return;
+    };
+    let message = match &issue.fallback {
+        Some(fallback) => format!(
+            "无法创建数据目录 {}，本次运行改用临时目录 {}，设置和历史可能在重启后丢失",
+            issue.requested.display(),
+            fallback.display()
+        ),
+        None => format!(
+            "无法创建数据目录 {}，设置和历史将无法保存",
+            issue.requested.display()
+        ),
+    };
+    let _ = app_handle.emit(
+        "startup-error",
+        serde_json::json!({
+            "kind": "data_dir",
+            "message": message,
+            "error": issue.error,
+            "requestedPath": utils::paths::strip_win_prefix(&issue.requested),
+            "fallbackPath": issue.fallback.as_deref().map(utils::paths::strip_win_prefix),
+        }),
+    );
+}
+
+/// 启动自检：打包脚本缺失时只告警不阻塞，等到真正启动引擎或下载模型时才会失败。
+fn check_resource_scripts_at_startup(app_handle: &tauri::AppHandle) {
+    let scripts = utils::paths::check_resource_scripts(app_handle);
+    let missing: Vec<&str> = scripts
+        .iter()
+        .filter(|script| !script.readable)
+        .map(|script| script.name.as_str())
+        .collect();
+    if missing.is_empty() {
+        log::info!("资源脚本自检通过");
+        return;
+    }
+    for script in scripts.iter().filter(|script| !script.readable) {
+        log::error!(
+            "资源脚本不可读 {} ({}): {}",
+            script.name,
+            script.path,
+            script.error.as_deref().unwrap_or("")
+        );
+    }
+    let _ = app_handle.emit(
+        "startup-error",
+        serde_json::json!({
+            "kind": "resource_scripts",
+            "message": format!("安装包缺少脚本: {}，请重新安装应用", missing.join(", ")),
+            "scripts": scripts,
+        }),
+    );
+}
+
 fn spawn_funasr_startup(app_handle: tauri::AppHandle) {
     tauri::async_runtime::spawn(async move {
         let engine = utils::paths::read_engine_config();
@@ -386,6 +586,12 @@ fn spawn_openai_codex_oauth_prewarm(app_handle: tauri::AppHandle) {
     });
 }
 
+fn spawn_temp_audio_cleanup() {
+    tauri::async_runtime::spawn_blocking(|| {
+        services::funasr_service::clear_temp_audio_files();
+    });
+}
+
 fn spawn_profile_maintenance(app_handle: tauri::AppHandle) {
     tauri::async_runtime::spawn(async move {
         const HOT_WORD_CLEANUP_INTERVAL_SECS: u64 = 24 * 60 * 60;
@@ -465,6 +671,27 @@ fn focus_main_window(app: &tauri::AppHandle) {
     }
 }
 
+/// 第二个进程启动时在当前实例里调用。手动启动就把主窗口调出来；开机自启动
+/// 带 `--minimized`，说明用户已经手动打开过，保持现状不抢焦点。
+fn handle_second_instance(app: &tauri::AppHandle, args: &[String]) {
+    let from_autostart = args.iter().any(|arg| arg == "--minimized");
+    log::info!(
+        "检测到重复启动（{}），已由当前实例接管，新进程退出",
+        if from_autostart {
+            "开机自启动"
+        } else {
+            "手动启动"
+        }
+    );
+    if !from_autostart {
+        focus_main_window(app);
+    }
+    let _ = app.emit(
+        "second-instance",
+        serde_json::json!({ "fromAutostart": from_autostart }),
+    );
+}
+
 fn hide_main_window(app: &tauri::AppHandle) {
     if let Some(window) = app.get_webview_window("main") {
         let _ = window.hide();
@@ -543,6 +770,7 @@ fn setup_system_tray(app_handle: &tauri::AppHandle) -> Result<(), Box<dyn std::e
             "hide" => hide_main_window(app),
             "quit" => {
                 log::info!("用户请求退出应用");
+                services::audio_service::preserve_pending_paste_on_exit(app);
                 stop_funasr_on_exit(app);
                 app.exit(0);
             }
@@ -116,6 +116,7 @@ async fn transcribe_via_dashscope_asr(
                         .unwrap_or_else(|| format!("DashScope ASR 错误: {}", code)),
                 ),
                 language: None,
+                segments: None,
             });
         }
     }
@@ -141,6 +142,7 @@ async fn transcribe_via_dashscope_asr(
         success: true,
         error: None,
         language: None,
+        segments: None,
     })
 }
 
@@ -264,6 +266,7 @@ async fn transcribe_via_omni_chat(
         success: true,
         error: None,
         language: None,
+        segments: None,
     })
 }
 
@@ -0,0 +1,181 @@
+use serde::Serialize;
+use std::time::Instant;
+
+use super::resample::ChunkedResampler;
+use super::wav::encode_wav;
+use super::TARGET_SAMPLE_RATE;
+use crate::utils::AppError;
+
+// ---------- 音频预处理基准测试 ----------
+//
+// 只测 "重采样 → WAV 编码" 这段纯 CPU 预处理，不涉及模型和麦克风，
+// 用来让弱 CPU 用户判断预处理本身占了多少实时预算。
+
+const DEFAULT_BENCHMARK_SOURCE_RATE: u32 = 48000;
+const MAX_BENCHMARK_DURATION_SEC: f64 = 120.0;
+/// 迭代次数按总音频量自适应：至少跑这么多秒的合成音频，避免短样本计时噪声过大。
+const BENCHMARK_TARGET_AUDIO_SEC: f64 = 60.0;
+const MIN_BENCHMARK_ITERATIONS: u32 = 3;
+const MAX_BENCHMARK_ITERATIONS: u32 = 200;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioPipelineBenchmark {
+    pub duration_secs: f64,
+    pub source_sample_rate: u32,
+    pub target_sample_rate: u32,
+    pub iterations: u32,
+    pub resample_ms_avg: f64,
+    pub encode_ms_avg: f64,
+    pub total_ms_avg: f64,
+    /// 每秒能处理的源采样点数
+    pub samples_per_sec: f64,
+    /// 预处理耗时 / 音频时长，越小越好；1.0 表示刚好实时
+    pub real_time_factor: f64,
+}
+
+/// 440Hz 正弦波叠加少量 1kHz 谐波，幅度约 -6dBFS，足够让 rubato 走完整插值路径。
+fn synthesize_signal(duration_secs: f64, sample_rate: u32) -> Vec<i16> {
+    let len = (duration_secs * sample_rate as f64).round() as usize;
+    let rate = sample_rate as f64;
+    (0..len)
+        .map(|i| {
+            let t = i as f64 / rate;
+            let v = 0.4 * (2.0 * std::f64::consts::PI * 440.0 * t).sin()
+                + 0.1 * (2.0 * std::f64::consts::PI * 1000.0 * t).sin();
+            (v * i16::MAX as f64) as i16
+        })
+        .collect()
+}
+
+/// 16kHz 合成样本 WAV，供引擎对比在没有真实录音时使用。纯音几乎不会被
+/// 识别出文本，只用来比较延迟。
+pub fn synthetic_sample_wav(duration_secs: f64) -> Result<Vec<u8>, AppError> {
+    encode_wav(
+        &synthesize_signal(duration_secs, TARGET_SAMPLE_RATE),
+        TARGET_SAMPLE_RATE,
+    )
+}
+
+fn benchmark_iterations(duration_secs: f64) -> u32 {
+    ((BENCHMARK_TARGET_AUDIO_SEC / duration_secs).ceil() as u32)
+        .clamp(MIN_BENCHMARK_ITERATIONS, MAX_BENCHMARK_ITERATIONS)
+}
+
+pub fn benchmark_audio_pipeline_sync(
+    duration_secs: f64,
+    source_sample_rate: Option<u32>,
+) -> Result<AudioPipelineBenchmark, AppError> {
+    if !duration_secs.is_finite() || duration_secs <= 0.0 {
+        return Err(AppError::Audio("基准测试时长必须大于 0 秒".to_string()));
+    }
+    if duration_secs > MAX_BENCHMARK_DURATION_SEC {
+        return Err(AppError::Audio(format!(
+            "基准测试时长不能超过 {} 秒",
+            MAX_BENCHMARK_DURATION_SEC
+        )));
+    }
+    let source_rate = source_sample_rate.unwrap_or(DEFAULT_BENCHMARK_SOURCE_RATE);
+    if !(8000..=384_000).contains(&source_rate) {
+        return Err(AppError::Audio(format!(
+            "不支持的源采样率: {}Hz",
+            source_rate
+        )));
+    }
+
+    let input = synthesize_signal(duration_secs, source_rate);
+    let iterations = benchmark_iterations(duration_secs);
+    let expected_len =
+        ((input.len() as f64 * TARGET_SAMPLE_RATE as f64 / source_rate as f64).ceil() as usize) + 8;
+
+    let mut resample_total = std::time::Duration::ZERO;
+    let mut encode_total = std::time::Duration::ZERO;
+
+    for _ in 0..iterations {
+        // 与 finalize 的 do_final_asr 保持同一条路径：整段 process_chunk + finish
+        let started = Instant::now();
+        let mut resampler = ChunkedResampler::new(source_rate).map_err(AppError::Audio)?;
+        let mut output = Vec::with_capacity(expected_len);
+        resampler
+            .process_chunk(&input, &mut output)
+            .and_then(|_| resampler.finish(&mut output))
+            .map_err(AppError::Audio)?;
+        resample_total += started.elapsed();
+
+        let asr_audio: &[i16] = if source_rate == TARGET_SAMPLE_RATE {
+            &input
+        } else {
+            &output
+        };
+        let started = Instant::now();
+        let wav = encode_wav(asr_audio, TARGET_SAMPLE_RATE)?;
+        encode_total += started.elapsed();
+        std::hint::black_box(wav);
+    }
+
+    let per_iter = |d: std::time::Duration| d.as_secs_f64() * 1000.0 / iterations as f64;
+    let resample_ms_avg = per_iter(resample_total);
+    let encode_ms_avg = per_iter(encode_total);
+    let total_ms_avg = resample_ms_avg + encode_ms_avg;
+    let total_secs = (resample_total + encode_total).as_secs_f64();
+    let samples_per_sec = if total_secs > 0.0 {
+        (input.len() as f64 * iterations as f64) / total_secs
+    } else {
+        f64::INFINITY
+    };
+
+    let result = AudioPipelineBenchmark {
+        duration_secs,
+        source_sample_rate: source_rate,
+        target_sample_rate: TARGET_SAMPLE_RATE,
+        iterations,
+        resample_ms_avg,
+        encode_ms_avg,
+        total_ms_avg,
+        samples_per_sec,
+        real_time_factor: total_ms_avg / (duration_secs * 1000.0),
+    };
+    log::info!(
+        "音频预处理基准: {:.1}s@{}Hz x{}，重采样 {:.2}ms，编码 {:.2}ms，RTF {:.4}",
+        duration_secs,
+        source_rate,
+        iterations,
+        resample_ms_avg,
+        encode_ms_avg,
+        result.real_time_factor
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{benchmark_audio_pipeline_sync, benchmark_iterations, synthesize_signal};
+
+    #[test]
+    fn synthetic_signal_has_expected_length() {
+        assert_eq!(synthesize_signal(0.5, 48000).len(), 24000);
+    }
+
+    #[test]
+    fn iterations_are_bounded() {
+        assert_eq!(benchmark_iterations(120.0), 3);
+        assert_eq!(benchmark_iterations(0.01), 200);
+        assert_eq!(benchmark_iterations(1.0), 60);
+    }
+
+    #[test]
+    fn rejects_invalid_duration_and_rate() {
+        assert!(benchmark_audio_pipeline_sync(0.0, None).is_err());
+        assert!(benchmark_audio_pipeline_sync(f64::NAN, None).is_err());
+        assert!(benchmark_audio_pipeline_sync(1.0, Some(0)).is_err());
+    }
+
+    #[test]
+    fn reports_positive_timings_for_short_signal() {
+        let result = benchmark_audio_pipeline_sync(0.2, Some(44100)).expect("benchmark");
+        assert_eq!(result.target_sample_rate, 16000);
+        assert!(result.iterations >= 3);
+        assert!(result.total_ms_avg >= result.resample_ms_avg);
+        assert!(result.real_time_factor >= 0.0);
+    }
+}
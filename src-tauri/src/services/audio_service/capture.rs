@@ -1,5 +1,6 @@
+use std::collections::HashMap;
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicU32, Ordering},
     mpsc, Arc,
 };
 
@@ -7,7 +8,11 @@ use tauri::Emitter;
 
 use super::resample::{f32_to_i16, u16_to_i16};
 use super::{AUDIO_CAPTURE_INIT_TIMEOUT_SECS, TARGET_SAMPLE_RATE};
-use crate::services::audio_service::{InputDeviceInfo, InputDeviceListPayload};
+use crate::services::audio_service::{
+    ChosenInputConfigInfo, DeviceCapabilitiesPayload, InputConfigRangeInfo, InputDeviceInfo,
+    InputDeviceListPayload, MicrophoneDefaultConfig, MicrophoneInfoPayload,
+};
+use crate::state::user_profile::InputSampleFormat;
 use crate::utils::AppError;
 
 /// 录音缓冲硬上限（单位：i16 样本，单声道，post mix-down）。即使 stop 信号
@@ -18,6 +23,9 @@ use crate::utils::AppError;
 pub(crate) const MAX_RECORD_SAMPLES: usize = 30 * 60 * 48_000;
 const AUDIO_CAPTURE_TIMEOUT_JOIN_MS: u64 = 500;
 
+/// 用户按设备名记住的采集采样率（设备名 → Hz），录音和诊断共用同一份。
+pub type DeviceSampleRates = HashMap<String, u32>;
+
 /// 一次性的"已触达录音缓冲硬上限"警告标志。仅在第一次撞上限时打日志。
 static RECORD_CAP_WARNED: AtomicBool = AtomicBool::new(false);
 
@@ -47,11 +55,46 @@ fn confirm_audio_thread_exit(handle: std::thread::JoinHandle<()>, wait: std::tim
 
 // ---------- cpal 设备管理 ----------
 
+/// 用户选择的 cpal 后端名（如 "ASIO"、"JACK"）；None 使用平台默认后端。
+/// 启动时和 `set_audio_host` 时从画像同步过来。
+static AUDIO_HOST_PREFERENCE: parking_lot::Mutex<Option<String>> = parking_lot::Mutex::new(None);
+
+pub fn set_audio_host_preference(host: Option<String>) {
+    *AUDIO_HOST_PREFERENCE.lock() = host;
+    // 设备名在不同后端下可能相同，换后端后不能复用之前协商的配置
+    clear_negotiated_input_config();
+}
+
+/// 按名字（忽略大小写）查找当前平台可用的后端。
+pub fn find_audio_host(name: &str) -> Option<cpal::HostId> {
+    cpal::available_hosts()
+        .into_iter()
+        .find(|id| id.name().eq_ignore_ascii_case(name.trim()))
+}
+
+/// 录音、设备枚举和诊断共用的后端：所选后端不可用时回退到默认后端。
+fn audio_host() -> cpal::Host {
+    let preference = AUDIO_HOST_PREFERENCE.lock().clone();
+    if let Some(name) = preference {
+        match find_audio_host(&name).map(cpal::host_from_id) {
+            Some(Ok(host)) => return host,
+            Some(Err(e)) => log::warn!("音频后端 {} 不可用，回退到默认后端: {}", name, e),
+            None => log::warn!("当前平台没有音频后端 {}，回退到默认后端", name),
+        }
+    }
+    cpal::default_host()
+}
+
+/// 当前平台默认的后端名。
+pub fn default_audio_host_name() -> String {
+    cpal::default_host().id().name().to_string()
+}
+
 pub(super) fn resolve_input_device(
     preferred_name: Option<&str>,
 ) -> Result<(cpal::Device, String), AppError> {
     use cpal::traits::{DeviceTrait, HostTrait};
-    let host = cpal::default_host();
+    let host = audio_host();
 
     if let Some(name) = preferred_name.filter(|n| !n.trim().is_empty()) {
         if let Ok(devices) = host.input_devices() {
@@ -72,50 +115,379 @@ pub(super) fn resolve_input_device(
     Ok((device, name))
 }
 
-pub(super) fn load_best_input_config(
+fn query_input_configs(
     device: &cpal::Device,
-) -> Result<cpal::SupportedStreamConfig, AppError> {
+) -> Result<Vec<cpal::SupportedStreamConfigRange>, AppError> {
     use cpal::traits::DeviceTrait;
-    use cpal::SampleFormat::{F32, I16, U16};
-
-    let configs: Vec<_> = device
+    Ok(device
         .supported_input_configs()
         .map_err(|e| AppError::Audio(format!("查询音频设备配置失败: {}", e)))?
-        .collect();
+        .collect())
+}
+
+/// `pick_input_config` 命中的规则档位，写进日志和设备能力诊断里。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputConfigTier {
+    /// 用户为该设备指定了采样率，且设备区间支持
+    DevicePreference,
+    /// 设备区间覆盖 16kHz，直接按 16kHz 采集
+    Native16k,
+    /// 没有覆盖 16kHz 的区间，取首选格式的最高采样率再重采样
+    MaxRate,
+    /// 没有 I16/F32/U16 区间，兜底第一项
+    FirstRange,
+}
+
+impl InputConfigTier {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::DevicePreference => "devicePreference",
+            Self::Native16k => "native16k",
+            Self::MaxRate => "maxRate",
+            Self::FirstRange => "firstRange",
+        }
+    }
+}
+
+fn cpal_sample_format(format: InputSampleFormat) -> cpal::SampleFormat {
+    match format {
+        InputSampleFormat::I16 => cpal::SampleFormat::I16,
+        InputSampleFormat::F32 => cpal::SampleFormat::F32,
+        InputSampleFormat::U16 => cpal::SampleFormat::U16,
+    }
+}
+
+/// 默认 I16 > F32 > U16；指定了首选格式时把它挪到最前，其余保持原顺序。
+fn format_preference(preferred_format: Option<InputSampleFormat>) -> [cpal::SampleFormat; 3] {
+    use cpal::SampleFormat::{F32, I16, U16};
+    let mut order = [I16, F32, U16];
+    if let Some(preferred) = preferred_format.map(cpal_sample_format) {
+        if let Some(index) = order.iter().position(|&format| format == preferred) {
+            order[..=index].rotate_right(1);
+        }
+    }
+    order
+}
+
+/// 选择规则：用户为该设备指定了采样率且设备支持时直接采用；否则优先能直接跑
+/// 16kHz 的配置（格式按 `format_preference` 的顺序），免去重采样；都不支持
+/// 16kHz 时按同样的格式顺序取最高采样率，最后兜底第一项。首选格式只调整
+/// 同一档位内的先后，不会为了格式放弃免重采样的配置。
+/// 返回所选配置在 `configs` 里的下标，便于诊断输出对照。
+fn pick_input_config(
+    configs: &[cpal::SupportedStreamConfigRange],
+    preferred_rate: Option<u32>,
+    preferred_format: Option<InputSampleFormat>,
+) -> Option<(usize, cpal::SupportedStreamConfig, InputConfigTier)> {
+    let format_order = format_preference(preferred_format);
+
+    let supports = |c: &cpal::SupportedStreamConfigRange, rate: u32| {
+        c.min_sample_rate().0 <= rate && c.max_sample_rate().0 >= rate
+    };
+    let position = |required_rate: Option<u32>| {
+        format_order.iter().find_map(|&format| {
+            configs.iter().position(|c| {
+                c.sample_format() == format && required_rate.is_none_or(|rate| supports(c, rate))
+            })
+        })
+    };
+
+    if let Some(rate) = preferred_rate {
+        if let Some(index) = position(Some(rate)) {
+            let config = configs[index].with_sample_rate(cpal::SampleRate(rate));
+            return Some((index, config, InputConfigTier::DevicePreference));
+        }
+    }
+    if let Some(index) = position(Some(TARGET_SAMPLE_RATE)) {
+        let config = configs[index].with_sample_rate(cpal::SampleRate(TARGET_SAMPLE_RATE));
+        return Some((index, config, InputConfigTier::Native16k));
+    }
+    let (index, tier) = match position(None) {
+        Some(index) => (index, InputConfigTier::MaxRate),
+        None if !configs.is_empty() => (0, InputConfigTier::FirstRange),
+        None => return None,
+    };
+    Some((index, configs[index].with_max_sample_rate(), tier))
+}
 
+/// 用一句话解释选中结果，日志和诊断面板共用。
+fn describe_input_config_choice(
+    config: &cpal::SupportedStreamConfig,
+    tier: InputConfigTier,
+    preferred_format: Option<InputSampleFormat>,
+) -> String {
+    let format = config.sample_format();
+    let rate = config.sample_rate().0;
+    let format_note = match preferred_format.map(cpal_sample_format) {
+        None => String::new(),
+        Some(preferred) if preferred == format => format!("（首选格式 {:?}）", preferred),
+        Some(preferred) => format!("（首选格式 {:?} 在该档位不可用，已回退）", preferred),
+    };
+    let description = match tier {
+        InputConfigTier::DevicePreference => format!(
+            "按该设备保存的采样率偏好使用 {:?}@{}Hz{}",
+            format,
+            rate,
+            if rate == TARGET_SAMPLE_RATE {
+                ""
+            } else {
+                "，录音后重采样"
+            }
+        ),
+        InputConfigTier::Native16k => format!(
+            "设备支持 {:?}@{}Hz，直接采集，无需重采样",
+            format, TARGET_SAMPLE_RATE
+        ),
+        InputConfigTier::MaxRate => format!(
+            "设备没有覆盖 {}Hz 的配置，选用 {:?} 的最高采样率 {}Hz，录音后重采样",
+            TARGET_SAMPLE_RATE, format, rate
+        ),
+        InputConfigTier::FirstRange => format!(
+            "设备没有 I16/F32/U16 配置，兜底使用第一项 {:?}@{}Hz",
+            format, rate
+        ),
+    };
+    description + &format_note
+}
+
+fn format_config_ranges(configs: &[cpal::SupportedStreamConfigRange]) -> String {
+    configs
+        .iter()
+        .enumerate()
+        .map(|(index, c)| {
+            format!(
+                "#{} {:?} {}ch {}-{}Hz",
+                index,
+                c.sample_format(),
+                c.channels(),
+                c.min_sample_rate().0,
+                c.max_sample_rate().0
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+pub(super) fn load_best_input_config(
+    device: &cpal::Device,
+    preferred_rate: Option<u32>,
+    preferred_format: Option<InputSampleFormat>,
+) -> Result<cpal::SupportedStreamConfig, AppError> {
+    let configs = query_input_configs(device)?;
     if configs.is_empty() {
         return Err(AppError::Audio("音频设备不支持任何输入配置".into()));
     }
 
-    let supports_16k = |c: &&cpal::SupportedStreamConfigRange| {
-        c.min_sample_rate().0 <= TARGET_SAMPLE_RATE && c.max_sample_rate().0 >= TARGET_SAMPLE_RATE
-    };
-    let fmt = |f| move |c: &&cpal::SupportedStreamConfigRange| c.sample_format() == f;
+    let (index, config, tier) = pick_input_config(&configs, preferred_rate, preferred_format)
+        .ok_or_else(|| AppError::Audio("无法找到合适的音频输入配置".into()))?;
+    if let Some(rate) = preferred_rate.filter(|_| tier != InputConfigTier::DevicePreference) {
+        log::warn!("设备不支持保存的采样率偏好 {}Hz，改用自动选择", rate);
+    }
+    if let Some(format) = preferred_format
+        .map(cpal_sample_format)
+        .filter(|&format| !configs.iter().any(|c| c.sample_format() == format))
+    {
+        log::warn!("设备不提供首选采样格式 {:?}，按默认顺序选择", format);
+    }
+    log::info!(
+        "音频配置选择 [{}]: 选中 #{} {:?} {}ch {}Hz，{}；设备提供: {}",
+        tier.as_str(),
+        index,
+        config.sample_format(),
+        config.channels(),
+        config.sample_rate().0,
+        describe_input_config_choice(&config, tier, preferred_format),
+        format_config_ranges(&configs)
+    );
+    Ok(config)
+}
+
+/// 最近一次协商出的输入配置。设备名、采样率偏好和首选格式都没变时直接复用，
+/// 录音开始时省掉一次设备配置查询（WASAPI 上冷查询可能要几十到上百毫秒）。
+struct NegotiatedInputConfig {
+    device_name: String,
+    preferred_rate: Option<u32>,
+    preferred_format: Option<InputSampleFormat>,
+    config: cpal::SupportedStreamConfig,
+}
+
+static NEGOTIATED_INPUT_CONFIG: parking_lot::Mutex<Option<NegotiatedInputConfig>> =
+    parking_lot::Mutex::new(None);
+
+/// 按协商出的配置调用 `open` 建流并启动。用缓存的配置失败时（设备被重新插拔、
+/// 驱动换了支持的格式等），清掉缓存、重新查询一次设备配置再试；只有成功建流的
+/// 配置才会缓存。
+pub(super) fn open_negotiated_input_stream<S>(
+    device: &cpal::Device,
+    device_name: &str,
+    preferred_rate: Option<u32>,
+    preferred_format: Option<InputSampleFormat>,
+    mut open: impl FnMut(&cpal::SupportedStreamConfig) -> Result<S, String>,
+) -> Result<(cpal::SupportedStreamConfig, S), AppError> {
+    let cached = NEGOTIATED_INPUT_CONFIG
+        .lock()
+        .as_ref()
+        .filter(|cached| {
+            cached.device_name == device_name
+                && cached.preferred_rate == preferred_rate
+                && cached.preferred_format == preferred_format
+        })
+        .map(|cached| cached.config.clone());
+    if let Some(config) = cached {
+        log::debug!("复用已协商的音频配置: {}", device_name);
+        match open(&config) {
+            Ok(stream) => return Ok((config, stream)),
+            Err(err) => {
+                log::warn!(
+                    "用已协商的音频配置打开输入流失败，重新查询设备配置: {}",
+                    err
+                );
+                clear_negotiated_input_config();
+            }
+        }
+    }
+    let config = load_best_input_config(device, preferred_rate, preferred_format)?;
+    let stream = open(&config).map_err(AppError::Audio)?;
+    *NEGOTIATED_INPUT_CONFIG.lock() = Some(NegotiatedInputConfig {
+        device_name: device_name.to_string(),
+        preferred_rate,
+        preferred_format,
+        config: config.clone(),
+    });
+    Ok((config, stream))
+}
+
+/// 设备热插拔或换了后端后调用，下次录音重新查询设备。
+pub fn clear_negotiated_input_config() {
+    NEGOTIATED_INPUT_CONFIG.lock().take();
+}
 
-    let pick = configs
+/// 导出当前（或默认）输入设备的全部输入配置，以及录音时实际会选中哪一项，
+/// 供排查 "录出来是杂音" 一类的采样率/格式问题。
+pub fn get_device_capabilities_sync(
+    selected_device_name: Option<String>,
+    device_sample_rates: DeviceSampleRates,
+    preferred_format: Option<InputSampleFormat>,
+) -> Result<DeviceCapabilitiesPayload, AppError> {
+    use cpal::traits::{DeviceTrait, HostTrait};
+    let (device, device_name) = resolve_input_device(selected_device_name.as_deref())?;
+    let default_name = audio_host()
+        .default_input_device()
+        .and_then(|d| d.name().ok());
+    let configs = query_input_configs(&device)?;
+
+    let preferred_rate = device_sample_rates.get(&device_name).copied();
+    let chosen = pick_input_config(&configs, preferred_rate, preferred_format).map(
+        |(range_index, config, tier)| ChosenInputConfigInfo {
+            range_index,
+            sample_format: format!("{:?}", config.sample_format()),
+            channels: config.channels(),
+            sample_rate: config.sample_rate().0,
+            needs_resample: config.sample_rate().0 != TARGET_SAMPLE_RATE,
+            tier: tier.as_str(),
+            reason: describe_input_config_choice(&config, tier, preferred_format),
+        },
+    );
+    let configs = configs
+        .iter()
+        .map(|c| InputConfigRangeInfo {
+            sample_format: format!("{:?}", c.sample_format()),
+            channels: c.channels(),
+            min_sample_rate: c.min_sample_rate().0,
+            max_sample_rate: c.max_sample_rate().0,
+        })
+        .collect();
+
+    Ok(DeviceCapabilitiesPayload {
+        is_default: default_name.as_deref() == Some(device_name.as_str()),
+        device_name,
+        requested_device_name: selected_device_name,
+        configs,
+        chosen,
+    })
+}
+
+/// 展示给用户的常见采样率；设备范围的上下限也会一并列出
+const COMMON_SAMPLE_RATES: [u32; 11] = [
+    8000, 11025, 16000, 22050, 32000, 44100, 48000, 88200, 96000, 176400, 192000,
+];
+
+/// 把 cpal 的配置区间折叠成去重后的采样率和声道数列表，以及是否原生支持 16kHz 单声道。
+fn summarize_input_configs(
+    configs: &[cpal::SupportedStreamConfigRange],
+) -> (Vec<u32>, Vec<u16>, bool) {
+    let mut rates: Vec<u32> = configs
         .iter()
-        .find(|c| fmt(I16)(c) && supports_16k(c))
-        .or_else(|| configs.iter().find(|c| fmt(F32)(c) && supports_16k(c)))
-        .or_else(|| configs.iter().find(|c| fmt(U16)(c) && supports_16k(c)))
-        .map(|c| c.with_sample_rate(cpal::SampleRate(TARGET_SAMPLE_RATE)))
-        .or_else(|| {
-            configs
+        .flat_map(|c| {
+            let (min, max) = (c.min_sample_rate().0, c.max_sample_rate().0);
+            COMMON_SAMPLE_RATES
                 .iter()
-                .find(|c| fmt(I16)(c))
-                .or_else(|| configs.iter().find(|c| fmt(F32)(c)))
-                .or_else(|| configs.iter().find(|c| fmt(U16)(c)))
-                .or(configs.first())
-                .map(|c| c.with_max_sample_rate())
-        });
+                .copied()
+                .filter(move |rate| (min..=max).contains(rate))
+                .chain([min, max])
+        })
+        .collect();
+    rates.sort_unstable();
+    rates.dedup();
 
-    pick.ok_or_else(|| AppError::Audio("无法找到合适的音频输入配置".into()))
+    let mut channels: Vec<u16> = configs.iter().map(|c| c.channels()).collect();
+    channels.sort_unstable();
+    channels.dedup();
+
+    let supports_16k_mono = configs.iter().any(|c| {
+        c.channels() == 1
+            && c.min_sample_rate().0 <= TARGET_SAMPLE_RATE
+            && c.max_sample_rate().0 >= TARGET_SAMPLE_RATE
+    });
+    (rates, channels, supports_16k_mono)
+}
+
+/// 面向用户的麦克风能力摘要：解释为什么会发生重采样，帮助排查音质问题。
+pub fn get_microphone_info_sync(
+    selected_device_name: Option<String>,
+    device_sample_rates: DeviceSampleRates,
+    preferred_format: Option<InputSampleFormat>,
+) -> Result<MicrophoneInfoPayload, AppError> {
+    use cpal::traits::{DeviceTrait, HostTrait};
+    let (device, device_name) = resolve_input_device(selected_device_name.as_deref())?;
+    let default_name = audio_host()
+        .default_input_device()
+        .and_then(|d| d.name().ok());
+    let configs = query_input_configs(&device)?;
+    let (sample_rates, channel_counts, supports_16k_mono) = summarize_input_configs(&configs);
+    let default_config = device
+        .default_input_config()
+        .map_err(|e| log::debug!("查询默认输入配置失败: {}", e))
+        .ok()
+        .map(|c| MicrophoneDefaultConfig {
+            sample_format: format!("{:?}", c.sample_format()),
+            channels: c.channels(),
+            sample_rate: c.sample_rate().0,
+        });
+    let capture_sample_rate = pick_input_config(
+        &configs,
+        device_sample_rates.get(&device_name).copied(),
+        preferred_format,
+    )
+    .map(|(_, c, _)| c.sample_rate().0);
+
+    Ok(MicrophoneInfoPayload {
+        is_default: default_name.as_deref() == Some(device_name.as_str()),
+        device_name,
+        sample_rates,
+        channel_counts,
+        default_config,
+        supports_16k_mono,
+        capture_sample_rate,
+        needs_resample: capture_sample_rate.is_some_and(|rate| rate != TARGET_SAMPLE_RATE),
+    })
 }
 
 pub fn list_input_devices_sync(
     selected_device_name: Option<String>,
 ) -> Result<InputDeviceListPayload, AppError> {
     use cpal::traits::{DeviceTrait, HostTrait};
-    let host = cpal::default_host();
+    let host = audio_host();
     let default_name = host.default_input_device().and_then(|d| d.name().ok());
 
     let mut devices: Vec<InputDeviceInfo> = host
@@ -136,6 +508,14 @@ pub fn list_input_devices_sync(
     })
 }
 
+/// 当前平台编译进来且可用的 cpal 后端（Windows 上一般只有 WASAPI）。
+pub fn list_audio_hosts() -> Vec<String> {
+    cpal::available_hosts()
+        .into_iter()
+        .map(|id| id.name().to_string())
+        .collect()
+}
+
 // ---------- 多声道混音到单声道 i16（带硬上限） ----------
 
 pub(crate) fn mix_to_mono_capped_i16(
@@ -279,18 +659,180 @@ pub fn spawn_waveform_emitter(
     });
 }
 
+// ---------- 滚动波形数据流（可选） ----------
+//
+// 与上面 9 根柱子的 "waveform" 不同，这里把录音按时间桶折叠成 min/max 对，
+// 以 "audio-waveform" 事件增量推给前端画滚动波形。每秒固定这么多点，
+// 与设备采样率无关，IPC 负担只有几 KB/s。
+
+const WAVEFORM_STREAM_POINTS_PER_SEC: u32 = 200;
+const WAVEFORM_STREAM_EMIT_INTERVAL_MS: u64 = 100;
+
+/// 每 `bucket` 个样本折叠成一个 [min, max]（归一化到 -1..1），不足一桶的尾部留给下一轮。
+fn waveform_min_max(samples: &[i16], bucket: usize) -> Vec<[f32; 2]> {
+    samples
+        .chunks_exact(bucket.max(1))
+        .map(|chunk| {
+            let (min, max) = chunk
+                .iter()
+                .fold((i16::MAX, i16::MIN), |(lo, hi), &s| (lo.min(s), hi.max(s)));
+            [min as f32 / 32768.0, max as f32 / 32768.0]
+        })
+        .collect()
+}
+
+pub fn spawn_waveform_stream_emitter(
+    app_handle: tauri::AppHandle,
+    session_id: u64,
+    stop_flag: Arc<AtomicBool>,
+    samples: Arc<parking_lot::Mutex<Vec<i16>>>,
+    sample_rate: u32,
+) {
+    let bucket = (sample_rate / WAVEFORM_STREAM_POINTS_PER_SEC).max(1) as usize;
+
+    tauri::async_runtime::spawn(async move {
+        let mut cursor = 0usize;
+        loop {
+            tokio::time::sleep(std::time::Duration::from_millis(
+                WAVEFORM_STREAM_EMIT_INTERVAL_MS,
+            ))
+            .await;
+            if stop_flag.load(Ordering::Relaxed) {
+                break;
+            }
+            let peaks = {
+                let guard = samples.lock();
+                // 缓冲被整体替换（例如合并录音）时从头开始
+                if cursor > guard.len() {
+                    cursor = 0;
+                }
+                let peaks = waveform_min_max(&guard[cursor..], bucket);
+                cursor += peaks.len() * bucket;
+                peaks
+            };
+            if peaks.is_empty() {
+                continue;
+            }
+            let _ = app_handle.emit(
+                "audio-waveform",
+                serde_json::json!({
+                    "sessionId": session_id,
+                    "pointsPerSec": WAVEFORM_STREAM_POINTS_PER_SEC,
+                    "peaks": peaks,
+                }),
+            );
+        }
+    });
+}
+
+// ---------- 实时音量电平 ----------
+//
+// 采集回调里按数据块算 RMS 和峰值（归一化到 0..1），只保留两次发送之间的
+// 最大值；发送任务每 50ms 取走一次，推 "audio-level" 给字幕窗口画音量条。
+
+const AUDIO_LEVEL_EMIT_INTERVAL_MS: u64 = 50;
+
+/// 采集回调与发送任务之间共享的电平。非负 f32 的位模式与数值同序，
+/// 所以直接对位模式做 `fetch_max`。
+#[derive(Debug, Default)]
+pub struct AudioLevelMeter {
+    rms: AtomicU32,
+    peak: AtomicU32,
+}
+
+impl AudioLevelMeter {
+    fn record(&self, rms: f32, peak: f32) {
+        self.rms.fetch_max(rms.to_bits(), Ordering::Relaxed);
+        self.peak.fetch_max(peak.to_bits(), Ordering::Relaxed);
+    }
+
+    /// 取走上次发送以来的最大电平并清零
+    fn take(&self) -> (f32, f32) {
+        (
+            f32::from_bits(self.rms.swap(0, Ordering::Relaxed)),
+            f32::from_bits(self.peak.swap(0, Ordering::Relaxed)),
+        )
+    }
+}
+
+fn block_level(samples: impl Iterator<Item = f32>) -> (f32, f32) {
+    let (mut sum_sq, mut peak, mut count) = (0.0f64, 0.0f32, 0usize);
+    for sample in samples {
+        let sample = sample.clamp(-1.0, 1.0);
+        sum_sq += (sample as f64) * (sample as f64);
+        peak = peak.max(sample.abs());
+        count += 1;
+    }
+    if count == 0 {
+        return (0.0, 0.0);
+    }
+    (((sum_sq / count as f64).sqrt() as f32).min(1.0), peak)
+}
+
+fn block_level_i16(data: &[i16]) -> (f32, f32) {
+    block_level(data.iter().map(|&s| s as f32 / 32768.0))
+}
+
+fn block_level_f32(data: &[f32]) -> (f32, f32) {
+    block_level(data.iter().copied().filter(|s| s.is_finite()))
+}
+
+fn block_level_u16(data: &[u16]) -> (f32, f32) {
+    block_level(data.iter().map(|&s| (s as f32 - 32768.0) / 32768.0))
+}
+
+pub fn spawn_audio_level_emitter(
+    app_handle: tauri::AppHandle,
+    session_id: u64,
+    stop_flag: Arc<AtomicBool>,
+    meter: Arc<AudioLevelMeter>,
+) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_millis(
+                AUDIO_LEVEL_EMIT_INTERVAL_MS,
+            ))
+            .await;
+            if stop_flag.load(Ordering::Relaxed) {
+                break;
+            }
+            let (rms, peak) = meter.take();
+            let _ = app_handle.emit(
+                "audio-level",
+                serde_json::json!({ "sessionId": session_id, "rms": rms, "peak": peak }),
+            );
+        }
+    });
+}
+
 // ---------- 音频捕获线程 ----------
 
+/// 录音中设备不可用（例如 USB 麦克风被拔掉）时调用一次，参数是设备名。
+pub type DeviceLostCallback = Box<dyn FnOnce(&str) + Send + 'static>;
+
+/// 采集线程的可选回调；诊断类的临时采集全部留空。
+#[derive(Default)]
+pub struct CaptureHooks {
+    pub on_device_lost: Option<DeviceLostCallback>,
+    pub level_meter: Option<Arc<AudioLevelMeter>>,
+}
+
+/// `pause_flag` 置位期间回调直接丢弃输入（不收尾），清除后继续追加到同一缓冲。
+/// 返回采集线程、实际采样率和实际使用的设备名（指定设备不可用时是回退后的默认设备）。
 pub fn spawn_audio_capture_thread(
     stop_flag: Arc<AtomicBool>,
+    pause_flag: Arc<AtomicBool>,
     samples: Arc<parking_lot::Mutex<Vec<i16>>>,
     selected_device_name: Option<String>,
-) -> Result<(std::thread::JoinHandle<()>, u32), AppError> {
+    device_sample_rates: DeviceSampleRates,
+    preferred_format: Option<InputSampleFormat>,
+    hooks: CaptureHooks,
+) -> Result<(std::thread::JoinHandle<()>, u32, String), AppError> {
     // 每个新录音会话重置警告 latch；否则进程级一次警告之后，后续会话即便
     // 再次撞上限也不会写日志，丢失诊断信息。
     RECORD_CAP_WARNED.store(false, Ordering::Relaxed);
 
-    let (rate_tx, rate_rx) = std::sync::mpsc::sync_channel::<Result<u32, String>>(1);
+    let (rate_tx, rate_rx) = std::sync::mpsc::sync_channel::<Result<(u32, String), String>>(1);
     let stop = stop_flag.clone();
 
     let handle = std::thread::Builder::new()
@@ -308,8 +850,133 @@ pub fn spawn_audio_capture_thread(
             };
             log::info!("使用音频输入设备: {}", device_name);
 
-            let config = match load_best_input_config(&device) {
-                Ok(c) => c,
+            let CaptureHooks {
+                on_device_lost,
+                level_meter,
+            } = hooks;
+            // 重试建流时回调要重新创建，设备丢失通知放进共享槽里保证只触发一次
+            let on_device_lost = Arc::new(parking_lot::Mutex::new(on_device_lost));
+            let stop_cb = stop.clone();
+            let pause_cb = pause_flag.clone();
+            let open_stream =
+                |config: &cpal::SupportedStreamConfig| -> Result<cpal::Stream, String> {
+                    let channels = config.channels() as usize;
+                    let err_cb = {
+                        let on_device_lost = on_device_lost.clone();
+                        let device_name = device_name.clone();
+                        move |e: cpal::StreamError| {
+                            log::error!("音频流错误: {}", e);
+                            if matches!(e, cpal::StreamError::DeviceNotAvailable) {
+                                // 设备重新插上后配置可能变了，下次录音重新协商
+                                clear_negotiated_input_config();
+                                if let Some(notify) = on_device_lost.lock().take() {
+                                    notify(&device_name);
+                                }
+                            }
+                        }
+                    };
+
+                    let mk_i16 = {
+                        let buf = samples.clone();
+                        let stop = stop_cb.clone();
+                        let pause = pause_cb.clone();
+                        let meter = level_meter.clone();
+                        move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                            if stop.load(Ordering::Relaxed) || pause.load(Ordering::Relaxed) {
+                                return;
+                            }
+                            if let Some(meter) = meter.as_deref() {
+                                let (rms, peak) = block_level_i16(data);
+                                meter.record(rms, peak);
+                            }
+                            let mut locked = buf.lock();
+                            if locked.len() >= MAX_RECORD_SAMPLES
+                                && !RECORD_CAP_WARNED.swap(true, Ordering::Relaxed)
+                            {
+                                log::warn!(
+                                    "录音缓冲触达硬上限 {} 个 i16 样本，后续输入将被丢弃",
+                                    MAX_RECORD_SAMPLES
+                                );
+                            }
+                            mix_to_mono_capped_i16(data, channels, &mut locked, MAX_RECORD_SAMPLES);
+                        }
+                    };
+                    let mk_f32 = {
+                        let buf = samples.clone();
+                        let stop = stop_cb.clone();
+                        let pause = pause_cb.clone();
+                        let meter = level_meter.clone();
+                        move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                            if stop.load(Ordering::Relaxed) || pause.load(Ordering::Relaxed) {
+                                return;
+                            }
+                            if let Some(meter) = meter.as_deref() {
+                                let (rms, peak) = block_level_f32(data);
+                                meter.record(rms, peak);
+                            }
+                            let mut locked = buf.lock();
+                            if locked.len() >= MAX_RECORD_SAMPLES
+                                && !RECORD_CAP_WARNED.swap(true, Ordering::Relaxed)
+                            {
+                                log::warn!(
+                                    "录音缓冲触达硬上限 {} 个 i16 样本，后续输入将被丢弃",
+                                    MAX_RECORD_SAMPLES
+                                );
+                            }
+                            mix_to_mono_capped_f32(data, channels, &mut locked, MAX_RECORD_SAMPLES);
+                        }
+                    };
+                    let mk_u16 = {
+                        let buf = samples.clone();
+                        let stop = stop_cb.clone();
+                        let pause = pause_cb.clone();
+                        let meter = level_meter.clone();
+                        move |data: &[u16], _: &cpal::InputCallbackInfo| {
+                            if stop.load(Ordering::Relaxed) || pause.load(Ordering::Relaxed) {
+                                return;
+                            }
+                            if let Some(meter) = meter.as_deref() {
+                                let (rms, peak) = block_level_u16(data);
+                                meter.record(rms, peak);
+                            }
+                            let mut locked = buf.lock();
+                            if locked.len() >= MAX_RECORD_SAMPLES
+                                && !RECORD_CAP_WARNED.swap(true, Ordering::Relaxed)
+                            {
+                                log::warn!(
+                                    "录音缓冲触达硬上限 {} 个 i16 样本，后续输入将被丢弃",
+                                    MAX_RECORD_SAMPLES
+                                );
+                            }
+                            mix_to_mono_capped_u16(data, channels, &mut locked, MAX_RECORD_SAMPLES);
+                        }
+                    };
+
+                    let stream = build_input_stream_dispatch!(
+                        device,
+                        config.clone(),
+                        config.sample_format(),
+                        err_cb,
+                        mk_i16,
+                        mk_f32,
+                        mk_u16
+                    )
+                    .map_err(|e| format!("创建音频流失败: {}", e))?;
+                    stream
+                        .play()
+                        .map_err(|e| format!("启动音频流失败: {}", e))?;
+                    Ok(stream)
+                };
+
+            let preferred_rate = device_sample_rates.get(&device_name).copied();
+            let (config, stream) = match open_negotiated_input_stream(
+                &device,
+                &device_name,
+                preferred_rate,
+                preferred_format,
+                open_stream,
+            ) {
+                Ok(opened) => opened,
                 Err(e) => {
                     let _ = rate_tx.send(Err(e.to_string()));
                     return;
@@ -317,97 +984,13 @@ pub fn spawn_audio_capture_thread(
             };
 
             let sample_rate = config.sample_rate().0;
-            let channels = config.channels() as usize;
-            let sample_format = config.sample_format();
             log::info!(
                 "音频配置: {}Hz, {}ch, {:?}",
                 sample_rate,
-                channels,
-                sample_format
+                config.channels(),
+                config.sample_format()
             );
-
-            let err_cb = |e: cpal::StreamError| log::error!("音频流错误: {}", e);
-            let stop_cb = stop.clone();
-
-            let mk_i16 = {
-                let buf = samples.clone();
-                let stop = stop_cb.clone();
-                move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                    if stop.load(Ordering::Relaxed) {
-                        return;
-                    }
-                    let mut locked = buf.lock();
-                    if locked.len() >= MAX_RECORD_SAMPLES
-                        && !RECORD_CAP_WARNED.swap(true, Ordering::Relaxed)
-                    {
-                        log::warn!(
-                            "录音缓冲触达硬上限 {} 个 i16 样本，后续输入将被丢弃",
-                            MAX_RECORD_SAMPLES
-                        );
-                    }
-                    mix_to_mono_capped_i16(data, channels, &mut locked, MAX_RECORD_SAMPLES);
-                }
-            };
-            let mk_f32 = {
-                let buf = samples.clone();
-                let stop = stop_cb.clone();
-                move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    if stop.load(Ordering::Relaxed) {
-                        return;
-                    }
-                    let mut locked = buf.lock();
-                    if locked.len() >= MAX_RECORD_SAMPLES
-                        && !RECORD_CAP_WARNED.swap(true, Ordering::Relaxed)
-                    {
-                        log::warn!(
-                            "录音缓冲触达硬上限 {} 个 i16 样本，后续输入将被丢弃",
-                            MAX_RECORD_SAMPLES
-                        );
-                    }
-                    mix_to_mono_capped_f32(data, channels, &mut locked, MAX_RECORD_SAMPLES);
-                }
-            };
-            let mk_u16 = {
-                let buf = samples.clone();
-                let stop = stop_cb.clone();
-                move |data: &[u16], _: &cpal::InputCallbackInfo| {
-                    if stop.load(Ordering::Relaxed) {
-                        return;
-                    }
-                    let mut locked = buf.lock();
-                    if locked.len() >= MAX_RECORD_SAMPLES
-                        && !RECORD_CAP_WARNED.swap(true, Ordering::Relaxed)
-                    {
-                        log::warn!(
-                            "录音缓冲触达硬上限 {} 个 i16 样本，后续输入将被丢弃",
-                            MAX_RECORD_SAMPLES
-                        );
-                    }
-                    mix_to_mono_capped_u16(data, channels, &mut locked, MAX_RECORD_SAMPLES);
-                }
-            };
-
-            let stream = match build_input_stream_dispatch!(
-                device,
-                config,
-                sample_format,
-                err_cb,
-                mk_i16,
-                mk_f32,
-                mk_u16
-            ) {
-                Ok(s) => s,
-                Err(e) => {
-                    let _ = rate_tx.send(Err(format!("创建音频流失败: {}", e)));
-                    return;
-                }
-            };
-
-            if let Err(e) = stream.play() {
-                let _ = rate_tx.send(Err(format!("启动音频流失败: {}", e)));
-                return;
-            }
-            let _ = rate_tx.send(Ok(sample_rate));
+            let _ = rate_tx.send(Ok((sample_rate, device_name)));
 
             while !stop.load(Ordering::Relaxed) {
                 std::thread::sleep(std::time::Duration::from_millis(50));
@@ -417,7 +1000,7 @@ pub fn spawn_audio_capture_thread(
         })
         .map_err(|e| AppError::Audio(format!("创建录音线程失败: {}", e)))?;
 
-    let sample_rate = match rate_rx.recv_timeout(std::time::Duration::from_secs(
+    let (sample_rate, device_name) = match rate_rx.recv_timeout(std::time::Duration::from_secs(
         AUDIO_CAPTURE_INIT_TIMEOUT_SECS,
     )) {
         Ok(r) => r.map_err(AppError::Audio)?,
@@ -435,7 +1018,156 @@ pub fn spawn_audio_capture_thread(
         Err(_) => return Err(AppError::Audio("录音线程启动后未返回结果".into())),
     };
 
-    Ok((handle, sample_rate))
+    Ok((handle, sample_rate, device_name))
+}
+
+#[cfg(test)]
+mod config_tests {
+    use super::{
+        default_audio_host_name, describe_input_config_choice, find_audio_host, format_preference,
+        pick_input_config, summarize_input_configs, InputConfigTier, TARGET_SAMPLE_RATE,
+    };
+    use crate::state::user_profile::InputSampleFormat;
+
+    fn config_range(
+        format: cpal::SampleFormat,
+        channels: u16,
+        min: u32,
+        max: u32,
+    ) -> cpal::SupportedStreamConfigRange {
+        cpal::SupportedStreamConfigRange::new(
+            channels,
+            cpal::SampleRate(min),
+            cpal::SampleRate(max),
+            cpal::SupportedBufferSize::Unknown,
+            format,
+        )
+    }
+
+    #[test]
+    fn audio_host_lookup_ignores_case_and_rejects_unknown_names() {
+        let default = default_audio_host_name();
+        assert_eq!(
+            find_audio_host(&format!(" {} ", default.to_lowercase())).map(|id| id.name()),
+            Some(default.as_str())
+        );
+        assert!(find_audio_host("NoSuchAudioHost").is_none());
+    }
+
+    #[test]
+    fn pick_input_config_prefers_16k_capable_format_order() {
+        let configs = [
+            config_range(cpal::SampleFormat::F32, 2, 8000, 48000),
+            config_range(cpal::SampleFormat::I16, 2, 44100, 48000),
+            config_range(cpal::SampleFormat::I16, 1, 8000, 96000),
+        ];
+        let (index, config, tier) = pick_input_config(&configs, None, None).expect("config");
+        assert_eq!(index, 2);
+        assert_eq!(config.sample_rate().0, TARGET_SAMPLE_RATE);
+        assert_eq!(tier, InputConfigTier::Native16k);
+    }
+
+    #[test]
+    fn pick_input_config_falls_back_to_max_rate_without_16k() {
+        let configs = [
+            config_range(cpal::SampleFormat::F32, 2, 44100, 48000),
+            config_range(cpal::SampleFormat::I16, 2, 44100, 96000),
+        ];
+        let (index, config, tier) = pick_input_config(&configs, None, None).expect("config");
+        assert_eq!(index, 1);
+        assert_eq!(config.sample_rate().0, 96000);
+        assert_eq!(tier, InputConfigTier::MaxRate);
+        assert!(describe_input_config_choice(&config, tier, None).contains("96000Hz"));
+        assert!(pick_input_config(&[], None, None).is_none());
+    }
+
+    #[test]
+    fn pick_input_config_honours_supported_device_rate_preference() {
+        let configs = [
+            config_range(cpal::SampleFormat::F32, 2, 8000, 48000),
+            config_range(cpal::SampleFormat::I16, 1, 8000, 16000),
+        ];
+        let (index, config, tier) = pick_input_config(&configs, Some(44100), None).expect("config");
+        assert_eq!(index, 0);
+        assert_eq!(config.sample_rate().0, 44100);
+        assert_eq!(tier, InputConfigTier::DevicePreference);
+
+        // 设备不支持的偏好被忽略，回到自动选择
+        let (index, _, tier) = pick_input_config(&configs, Some(96000), None).expect("config");
+        assert_eq!(index, 1);
+        assert_eq!(tier, InputConfigTier::Native16k);
+    }
+
+    #[test]
+    fn preferred_sample_format_reorders_within_the_same_tier() {
+        use cpal::SampleFormat::{F32, I16, U16};
+        assert_eq!(format_preference(None), [I16, F32, U16]);
+        assert_eq!(
+            format_preference(Some(InputSampleFormat::F32)),
+            [F32, I16, U16]
+        );
+        assert_eq!(
+            format_preference(Some(InputSampleFormat::U16)),
+            [U16, I16, F32]
+        );
+
+        let configs = [
+            config_range(I16, 1, 8000, 48000),
+            config_range(F32, 2, 8000, 48000),
+        ];
+        let (index, config, tier) =
+            pick_input_config(&configs, None, Some(InputSampleFormat::F32)).expect("config");
+        assert_eq!(index, 1);
+        assert_eq!(tier, InputConfigTier::Native16k);
+        assert!(
+            describe_input_config_choice(&config, tier, Some(InputSampleFormat::F32))
+                .contains("首选格式 F32")
+        );
+
+        // 设备不提供首选格式时按默认顺序
+        let (index, _, _) =
+            pick_input_config(&configs, None, Some(InputSampleFormat::U16)).expect("config");
+        assert_eq!(index, 0);
+
+        // 首选格式覆盖不到 16kHz 时仍优先免重采样的配置
+        let configs = [
+            config_range(F32, 2, 44100, 48000),
+            config_range(I16, 1, 8000, 16000),
+        ];
+        let (index, config, tier) =
+            pick_input_config(&configs, None, Some(InputSampleFormat::F32)).expect("config");
+        assert_eq!(index, 1);
+        assert!(
+            describe_input_config_choice(&config, tier, Some(InputSampleFormat::F32))
+                .contains("已回退")
+        );
+    }
+
+    #[test]
+    fn pick_input_config_falls_back_to_first_range_for_unknown_formats() {
+        let configs = [config_range(cpal::SampleFormat::F64, 2, 44100, 48000)];
+        let (index, config, tier) = pick_input_config(&configs, None, None).expect("config");
+        assert_eq!(index, 0);
+        assert_eq!(config.sample_rate().0, 48000);
+        assert_eq!(tier, InputConfigTier::FirstRange);
+    }
+
+    #[test]
+    fn summarize_input_configs_lists_common_rates_and_channels() {
+        let configs = [
+            config_range(cpal::SampleFormat::F32, 2, 44100, 48000),
+            config_range(cpal::SampleFormat::I16, 1, 16000, 16000),
+        ];
+        let (rates, channels, mono_16k) = summarize_input_configs(&configs);
+        assert_eq!(rates, vec![16000, 44100, 48000]);
+        assert_eq!(channels, vec![1, 2]);
+        assert!(mono_16k);
+
+        let (rates, _, mono_16k) =
+            summarize_input_configs(&[config_range(cpal::SampleFormat::F32, 2, 8000, 96000)]);
+        assert!(rates.contains(&16000) && rates.contains(&96000));
+        assert!(!mono_16k);
+    }
 }
 
 #[cfg(test)]
@@ -645,3 +1377,38 @@ mod cap_tests {
         assert_eq!(out2.len(), 30, "cap=0 must never shrink existing buffer");
     }
 }
+
+#[cfg(test)]
+mod waveform_tests {
+    use super::{
+        block_level_f32, block_level_i16, block_level_u16, waveform_min_max, AudioLevelMeter,
+    };
+
+    #[test]
+    fn waveform_min_max_folds_full_buckets_only() {
+        let samples = [0, 16384, -16384, 8192, 0, 2, 3];
+        let peaks = waveform_min_max(&samples, 3);
+        assert_eq!(peaks, vec![[-0.5, 0.5], [0.0, 0.25]]);
+        assert!(waveform_min_max(&samples[..2], 3).is_empty());
+    }
+
+    #[test]
+    fn block_levels_are_normalized_for_every_sample_format() {
+        assert_eq!(block_level_i16(&[16384, -16384]), (0.5, 0.5));
+        assert_eq!(block_level_i16(&[i16::MIN]), (1.0, 1.0));
+        assert_eq!(block_level_f32(&[0.5, -0.5]), (0.5, 0.5));
+        assert_eq!(block_level_f32(&[2.0, f32::NAN]), (1.0, 1.0));
+        assert_eq!(block_level_u16(&[32768, 32768]), (0.0, 0.0));
+        assert_eq!(block_level_u16(&[49152, 16384]), (0.5, 0.5));
+        assert_eq!(block_level_i16(&[]), (0.0, 0.0));
+    }
+
+    #[test]
+    fn level_meter_keeps_the_maximum_until_taken() {
+        let meter = AudioLevelMeter::default();
+        meter.record(0.2, 0.4);
+        meter.record(0.1, 0.9);
+        assert_eq!(meter.take(), (0.2, 0.9));
+        assert_eq!(meter.take(), (0.0, 0.0));
+    }
+}
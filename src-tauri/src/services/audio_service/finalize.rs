@@ -4,25 +4,42 @@ use std::time::{Duration, Instant};
 use serde::Serialize;
 use tauri::{Emitter, Manager};
 
+use super::loudness::normalize_loudness;
+use super::ogg_opus::encode_ogg_opus;
+use super::output_template::apply_output_template;
+use super::post_hook::{apply_post_hook, spawn_post_hook};
 use super::resample::ChunkedResampler;
+use super::text_cleanup::clean_transcript;
 use super::wav::encode_wav;
 use super::{
-    EDIT_GRAB_WAIT_MS, EMPTY_RESULT_HIDE_DELAY_MS, INTERIM_MAX_AUDIO_WINDOW_SEC,
-    MIN_AUDIO_DURATION_SEC, PASTE_DELAY_MS, RESULT_HIDE_DELAY_MS, TARGET_SAMPLE_RATE,
+    DEFAULT_PENDING_PASTE_LIMIT, EDIT_GRAB_WAIT_MS, EMPTY_RESULT_HIDE_DELAY_MS,
+    INTERIM_MAX_AUDIO_WINDOW_SEC, PASTE_DELAY_MS, PASTE_FOCUS_POLL_INTERVAL_MS,
+    PASTE_FOCUS_WAIT_TIMEOUT_MS, RESULT_HIDE_DELAY_MS, TARGET_SAMPLE_RATE,
 };
 use crate::services::{
     ai_polish_service, alibaba_asr_service, assistant_service, funasr_service, glm_asr_service,
-    history_service,
+    history_service, recording_archive_service, usage_stats_service,
+};
+use crate::state::user_profile::{
+    PostHookSettings, ResolvedAppProfile, TextVariant, TranscriptionMode, UserProfile,
 };
-use crate::state::user_profile::{ResolvedAppProfile, UserProfile};
 use crate::state::{
-    AppState, DictationOutputMode, RecordingMode, RecordingOutcomeKind, RecordingPhase,
-    RecordingSession, RecordingSnapshot, RecordingTrigger,
+    AppState, DictationOutputMode, PendingPasteReview, RecordingMode, RecordingOutcomeKind,
+    RecordingPhase, RecordingSession, RecordingSnapshot, RecordingTrigger,
 };
 use crate::utils::foreground::ForegroundApp;
 use crate::utils::{paths, AppError};
 
 const ASSISTANT_PIPELINE_TIMEOUT_SECS: u64 = 180;
+const PENDING_PASTE_RECOVERY_FILE: &str = "pending_paste_recovery.txt";
+/// 强制拆除时最多等采集线程退出这么久；卡在驱动里的线程转入后台继续 join
+const FORCE_TEARDOWN_JOIN_TIMEOUT_MS: u64 = 2000;
+/// 待确认结果的有效期。过期后直接丢弃、不再注入：离开确认时的场景太久，
+/// 光标位置已不可信；文本仍可从历史记录里找回。
+pub const PASTE_REVIEW_TIMEOUT_SECS: u64 = 120;
+/// 复用中间结果时允许的未覆盖尾部时长
+const BATCH_INTERIM_REUSE_TAIL_GAP_SEC: f64 = 0.25;
+const STREAMING_INTERIM_REUSE_TAIL_GAP_SEC: f64 = 1.0;
 
 // ---------- 最终转写 + 粘贴 ----------
 
@@ -285,7 +302,8 @@ pub async fn finalize_recording(app_handle: tauri::AppHandle, session: Recording
     {
         log::warn!("无法确认录音目标进程，已禁用本次屏幕上下文和历史保存");
     }
-    let history_settings = state.with_profile(|profile| profile.history_settings.clone());
+    let (history_settings, transcription_mode) = state
+        .with_profile(|profile| (profile.history_settings.clone(), profile.transcription_mode));
     let history_enabled = app_profile
         .history_enabled
         .unwrap_or(history_settings.enabled);
@@ -299,8 +317,13 @@ pub async fn finalize_recording(app_handle: tauri::AppHandle, session: Recording
     let duration_sec = final_count as f64 / sample_rate as f64;
     let mode = trigger.mode();
 
-    if duration_sec < MIN_AUDIO_DURATION_SEC {
-        log::info!("录音时间过短 ({:.2}s)，跳过转写", duration_sec);
+    let min_duration_sec = state.min_audio_duration_sec();
+    if duration_sec < min_duration_sec {
+        log::info!(
+            "录音时间过短 ({:.2}s < {:.2}s)，跳过转写",
+            duration_sec,
+            min_duration_sec
+        );
         emit_terminal_outcome(
             &app_handle,
             session_id,
@@ -314,8 +337,15 @@ pub async fn finalize_recording(app_handle: tauri::AppHandle, session: Recording
     }
 
     let history_audio_task = if history_enabled && history_settings.save_audio {
-        match encode_wav(&samples.lock(), sample_rate) {
-            Ok(wav) => Some(tokio::spawn(history_service::save_audio(session_id, wav))),
+        let encoded = if history_settings.recording_format == "opus" {
+            encode_ogg_opus(&samples.lock(), sample_rate).map(|audio| (audio, "ogg"))
+        } else {
+            encode_wav(&samples.lock(), sample_rate).map(|audio| (audio, "wav"))
+        };
+        match encoded {
+            Ok((audio, extension)) => Some(tokio::spawn(history_service::save_audio(
+                session_id, audio, extension,
+            ))),
             Err(error) => {
                 log::warn!("编码历史音频失败，继续仅保存文本: {error}");
                 None
@@ -325,6 +355,17 @@ pub async fn finalize_recording(app_handle: tauri::AppHandle, session: Recording
         None
     };
 
+    // 应用规则关闭了历史（包括无法确认前台进程）时同样不存档录音
+    let archive_wav = if state.recording.save_recordings.load(Ordering::Relaxed)
+        && app_profile.history_enabled != Some(false)
+    {
+        encode_wav(&samples.lock(), sample_rate)
+            .map_err(|error| log::warn!("编码录音存档失败: {error}"))
+            .ok()
+    } else {
+        None
+    };
+
     let history_workflow = if mode == RecordingMode::Assistant {
         "assistant"
     } else if edit_context.is_some() {
@@ -355,11 +396,13 @@ pub async fn finalize_recording(app_handle: tauri::AppHandle, session: Recording
     //   2. 尾部间隙 <= 250ms。以前是 "覆盖率 >=90%"，在短录音 / 快语速下可能把
     //      250ms~500ms 的尾部音节丢掉。250ms 绝对阈值比百分比更保守，在长录音上
     //      也不会放宽门槛；最差只会丢掉一次 interim 间隔内的静音/换气。
+    //      流式模式用户主动选择了低延迟，放宽到 1s。
     //   3. interim 确实返回了非空文本。
     let max_interim_window_samples = (sample_rate as f64 * INTERIM_MAX_AUDIO_WINDOW_SEC) as usize;
-    let tail_gap_threshold_samples = (sample_rate as f64 * 0.25) as usize;
+    let tail_gap_threshold_samples =
+        interim_reuse_tail_gap_samples(transcription_mode, sample_rate);
     let asr_start = Instant::now();
-    let (asr_text, detected_lang): (Result<String, String>, Option<String>) = match cached {
+    let (asr_text, detected_lang, asr_timed) = match cached {
         Some(ref c)
             if final_count > 0
                 && final_count <= max_interim_window_samples
@@ -371,18 +414,32 @@ pub async fn finalize_recording(app_handle: tauri::AppHandle, session: Recording
                 "复用 interim 缓存 (尾部间隙 {:.0}ms)",
                 (final_count - c.sample_count) as f64 * 1000.0 / sample_rate as f64
             );
-            (Ok(c.text.clone()), c.language.clone())
+            (Ok(c.text.clone()), c.language.clone(), false)
         }
         _ => match do_final_asr(&app_handle, state.inner(), &samples, sample_rate).await {
-            Ok(r) => (Ok(r.text), r.language),
-            Err(e) => (Err(e), None),
+            Ok(r) => (Ok(r.text), r.language, true),
+            Err(e) => (Err(e), None, true),
         },
     };
 
     let asr_elapsed_ms = elapsed_ms(asr_start);
+    // 复用 interim 缓存时没有测到识别耗时，只计次数、时长和字数，不参与 RTF
+    let usage_asr_secs = asr_timed.then_some(asr_elapsed_ms as f64 / 1000.0);
+    let record_usage = |produced: &str| {
+        usage_stats_service::record_transcription(
+            &history_engine,
+            duration_sec,
+            usage_asr_secs,
+            produced.trim().chars().count() as u64,
+        );
+    };
     let text = match asr_text {
-        Ok(t) => t.trim().to_string(),
+        Ok(t) => state.with_profile(|profile| clean_transcript(&t, profile)),
         Err(e) => {
+            // 识别失败的录音最值得事后复核，文本文件里记下错误原因
+            if let Some(wav) = archive_wav {
+                recording_archive_service::spawn_save(session_id, wav, format!("识别失败: {e}"));
+            }
             let history = build_history_context(resolve_history_audio(history_audio_task).await);
             history
                 .persist(
@@ -419,6 +476,9 @@ pub async fn finalize_recording(app_handle: tauri::AppHandle, session: Recording
     let lang_ref = detected_lang.as_deref();
 
     if text.is_empty() {
+        if let Some(wav) = archive_wav {
+            recording_archive_service::spawn_save(session_id, wav, String::new());
+        }
         let history = build_history_context(resolve_history_audio(history_audio_task).await);
         history
             .persist(
@@ -451,6 +511,10 @@ pub async fn finalize_recording(app_handle: tauri::AppHandle, session: Recording
         return;
     }
 
+    if let Some(wav) = archive_wav {
+        recording_archive_service::spawn_save(session_id, wav, text.clone());
+    }
+
     let history = build_history_context(resolve_history_audio(history_audio_task).await);
 
     if mode == RecordingMode::Dictation && edit_context.is_some() {
@@ -473,6 +537,7 @@ pub async fn finalize_recording(app_handle: tauri::AppHandle, session: Recording
         {
             Ok(outcome) => {
                 let result = outcome.text;
+                record_usage(&result);
                 let timing = TranscriptionTiming {
                     asr_ms: Some(asr_elapsed_ms),
                     polish_ms: Some(elapsed_ms(edit_started)),
@@ -508,7 +573,6 @@ pub async fn finalize_recording(app_handle: tauri::AppHandle, session: Recording
                 if !result.is_empty() {
                     let app = app_handle.clone();
                     tokio::spawn(async move {
-                        tokio::time::sleep(std::time::Duration::from_millis(PASTE_DELAY_MS)).await;
                         do_paste(&app, &result).await;
                     });
                 } else {
@@ -615,6 +679,7 @@ pub async fn finalize_recording(app_handle: tauri::AppHandle, session: Recording
         match assistant_result {
             Ok(outcome) => {
                 let result = outcome.text;
+                record_usage(&result);
                 history
                     .persist(
                         &app_handle,
@@ -699,7 +764,24 @@ pub async fn finalize_recording(app_handle: tauri::AppHandle, session: Recording
             .ai_polish_enabled
             .unwrap_or_else(|| state.profile.ai_polish_enabled.load(Ordering::Acquire));
         let raw_preview_stage = dictation_raw_preview_stage(trigger, ai_polish_enabled);
-        let raw_paste_replacement = if should_raw_first_paste(trigger, ai_polish_enabled, true) {
+        let (output_template, post_hook, subtitle_variant, paste_variant) =
+            state.with_profile(|profile| {
+                (
+                    profile.output_template.clone(),
+                    profile.post_hook.clone(),
+                    profile.subtitle_text_variant,
+                    profile.paste_text_variant,
+                )
+            });
+        let review_before_paste = state.with_profile(|profile| profile.review_before_paste);
+        // 自定义输出模板或会替换输出的钩子时不走 raw-first：先粘贴的原文无法再整体替换。
+        // 粘贴原文版本或需要先确认时也不需要：润色结果本来就不会替换上去
+        let raw_paste_replacement = if output_template.is_none()
+            && paste_variant == TextVariant::Formatted
+            && !review_before_paste
+            && !post_hook.as_ref().is_some_and(|hook| hook.replace_output)
+            && should_raw_first_paste(trigger, ai_polish_enabled, true)
+        {
             crate::commands::clipboard::capture_raw_paste_replacement_target(&original)
         } else {
             None
@@ -718,6 +800,20 @@ pub async fn finalize_recording(app_handle: tauri::AppHandle, session: Recording
         } else {
             false
         };
+        // 只粘贴原文时不必等润色：先把原文送出去，润色结果只进历史和字幕
+        let raw_pasted_before_polish =
+            paste_variant == TextVariant::Raw && ai_polish_enabled && !original.is_empty();
+        if raw_pasted_before_polish {
+            spawn_dictation_paste(
+                &app_handle,
+                session_id,
+                output_template.as_deref(),
+                post_hook.clone(),
+                review_before_paste,
+                &original,
+                duration_sec,
+            );
+        }
         let raw_first_preview_status = raw_first_preview_status_for_paste(raw_was_pasted);
         if let Some(stage) = raw_preview_stage {
             let timing = TranscriptionTiming {
@@ -794,6 +890,7 @@ pub async fn finalize_recording(app_handle: tauri::AppHandle, session: Recording
                 (original.clone(), None, None, None)
             }
         };
+        record_usage(&text);
         let polished = text != original;
         let result_stage = dictation_final_result_stage(raw_preview_stage, polished);
         let mut should_paste_final = false;
@@ -854,28 +951,39 @@ pub async fn finalize_recording(app_handle: tauri::AppHandle, session: Recording
                 None,
             )
             .await;
+        let subtitle_text = text_for_variant(subtitle_variant, &text, &original);
         emit_done_with_stage(
             &app_handle,
             session_id,
             subtitle_show_gen,
             mode,
-            &text,
+            subtitle_text,
             &original,
             duration_sec,
-            polished,
+            subtitle_text != original,
             lang_ref,
             edit_grab_status,
             result_stage,
             Some(timing),
         );
 
-        if !text.is_empty() {
+        let paste_text = text_for_variant(paste_variant, &text, &original);
+        if raw_pasted_before_polish {
+            // 原文在润色前已经粘贴
+        } else if !paste_text.is_empty() {
             if should_paste_final {
-                let app = app_handle.clone();
-                tokio::spawn(async move {
-                    tokio::time::sleep(std::time::Duration::from_millis(PASTE_DELAY_MS)).await;
-                    do_paste(&app, &text).await;
-                });
+                spawn_dictation_paste(
+                    &app_handle,
+                    session_id,
+                    output_template.as_deref(),
+                    post_hook,
+                    review_before_paste,
+                    paste_text,
+                    duration_sec,
+                );
+            } else if let Some(hook) = post_hook {
+                // raw-first 已经粘贴完成，钩子只在后台拿到最终文本
+                spawn_post_hook(hook, text);
             }
         } else {
             flush_pending_paste(&app_handle);
@@ -883,6 +991,28 @@ pub async fn finalize_recording(app_handle: tauri::AppHandle, session: Recording
     }
 }
 
+/// 套用输出模板后在后台跑钩子，再粘贴或交给用户确认。
+fn spawn_dictation_paste(
+    app_handle: &tauri::AppHandle,
+    session_id: u64,
+    output_template: Option<&str>,
+    post_hook: Option<PostHookSettings>,
+    review_before_paste: bool,
+    text: &str,
+    duration_sec: f64,
+) {
+    let app = app_handle.clone();
+    let pasted = apply_output_template(output_template, text, duration_sec);
+    tokio::spawn(async move {
+        let pasted = apply_post_hook(post_hook, pasted).await;
+        if review_before_paste {
+            request_paste_review(&app, session_id, pasted);
+        } else {
+            do_paste(&app, &pasted).await;
+        }
+    });
+}
+
 pub async fn discard_recording(session: RecordingSession) {
     // 中止本会话持有的 grab handle（spawn_blocking 不可抢占，但 abort 会让
     // JoinHandle 提前 detach，结果被丢弃，不会影响后续会话）。
@@ -907,65 +1037,181 @@ pub async fn discard_recording(session: RecordingSession) {
     log::info!("已丢弃录音会话 (session {})", session.session_id);
 }
 
-async fn do_final_asr(
-    app_handle: &tauri::AppHandle,
-    state: &AppState,
-    samples: &parking_lot::Mutex<Vec<i16>>,
-    sample_rate: u32,
-) -> Result<funasr_service::TranscriptionResult, String> {
-    let data = std::mem::take(&mut *samples.lock());
-    let (asr_audio, asr_sample_rate) = match ChunkedResampler::new(sample_rate) {
-        Ok(mut resampler) => {
-            let mut output = Vec::with_capacity(
-                ((data.len() as f64 * TARGET_SAMPLE_RATE as f64 / sample_rate as f64).ceil()
-                    as usize)
-                    + 8,
+/// 强制拆除一个录音会话：不转写、不粘贴，也不等待卡住的 interim 任务。
+///
+/// interim 任务只有在拿不到进程锁时才可能正处于 IPC 中途；此时中止它会让
+/// stdin/stdout 协议错位，所以只在能拿到 `funasr_process` 锁时中止（拿着锁，
+/// 任务最多停在等锁处），否则让它自行在 stop_flag 下退出。返回是否中止了 interim。
+pub async fn force_teardown_recording(state: &AppState, session: RecordingSession) -> bool {
+    session.stop_flag.store(true, Ordering::Release);
+    session.stop_notify.notify_waiters();
+    if let Some(grab) = session.edit_grab {
+        grab.abort();
+    }
+    if let Some(h) = session.audio_thread {
+        let join = tokio::task::spawn_blocking(move || {
+            let _ = h.join();
+        });
+        if tokio::time::timeout(Duration::from_millis(FORCE_TEARDOWN_JOIN_TIMEOUT_MS), join)
+            .await
+            .is_err()
+        {
+            log::warn!(
+                "录音线程 {}ms 内未退出，转入后台等待，继续拆除 (session {})",
+                FORCE_TEARDOWN_JOIN_TIMEOUT_MS,
+                session.session_id
             );
-            match resampler
-                .process_chunk(&data, &mut output)
-                .and_then(|_| resampler.finish(&mut output))
-            {
-                Ok(()) => {
-                    if sample_rate == TARGET_SAMPLE_RATE {
-                        (std::borrow::Cow::Borrowed(data.as_slice()), sample_rate)
-                    } else {
-                        (std::borrow::Cow::Owned(output), TARGET_SAMPLE_RATE)
-                    }
-                }
-                Err(err) => {
-                    log::warn!(
-                        "最终音频重采样失败，保留原始采样率 {}Hz: {}",
-                        sample_rate,
-                        err
-                    );
-                    (std::borrow::Cow::Borrowed(data.as_slice()), sample_rate)
+        }
+    }
+    let mut interim_aborted = false;
+    if let Some(t) = session.interim_task {
+        if !t.is_finished() {
+            match state.engine.funasr_process.try_lock() {
+                Ok(_ipc_idle) => {
+                    t.abort();
+                    interim_aborted = true;
                 }
+                Err(_) => log::warn!(
+                    "中间转写可能正处于引擎通信中，不强制中止，等待其自行退出 (session {})",
+                    session.session_id
+                ),
             }
         }
+    }
+    session.samples.lock().clear();
+    *session.interim_cache.lock() = None;
+    log::info!("已强制拆除录音会话 (session {})", session.session_id);
+    interim_aborted
+}
+
+/// 送入识别前的音频处理：各处理阶段（目前是响度归一化）在原始采集采样率上
+/// 进行，保留高采样率下的细节，最后一步才降到 16kHz 单声道交给模型。
+/// 重采样失败时保留原始采样率，由引擎自行处理。
+fn prepare_asr_audio(
+    mut data: Vec<i16>,
+    sample_rate: u32,
+    loudness_target: Option<f64>,
+) -> (Vec<i16>, u32) {
+    if let Some(target_lufs) = loudness_target {
+        if let Some(gain_db) = normalize_loudness(&mut data, sample_rate, target_lufs) {
+            log::info!(
+                "响度归一化: 目标 {:.1} LUFS，增益 {:+.1}dB（{}Hz）",
+                target_lufs,
+                gain_db,
+                sample_rate
+            );
+        }
+    }
+    downsample_for_model(data, sample_rate)
+}
+
+/// 开头补 `pad_ms` 毫秒静音：部分模型在语音紧贴第 0 个采样时会吞掉第一个音节。
+fn pad_leading_silence(data: Vec<i16>, sample_rate: u32, pad_ms: u32) -> Vec<i16> {
+    let pad_samples = (sample_rate as u64 * pad_ms as u64 / 1000) as usize;
+    if pad_samples == 0 || data.is_empty() {
+        return data;
+    }
+    let mut padded = vec![0i16; pad_samples];
+    padded.extend_from_slice(&data);
+    padded
+}
+
+fn downsample_for_model(data: Vec<i16>, sample_rate: u32) -> (Vec<i16>, u32) {
+    if sample_rate == TARGET_SAMPLE_RATE {
+        return (data, sample_rate);
+    }
+    let resampled = ChunkedResampler::new(sample_rate).and_then(|mut resampler| {
+        let mut output = Vec::with_capacity(
+            ((data.len() as f64 * TARGET_SAMPLE_RATE as f64 / sample_rate as f64).ceil() as usize)
+                + 8,
+        );
+        resampler
+            .process_chunk(&data, &mut output)
+            .and_then(|_| resampler.finish(&mut output))
+            .map(|_| output)
+    });
+    match resampled {
+        Ok(output) => (output, TARGET_SAMPLE_RATE),
         Err(err) => {
             log::warn!(
                 "最终音频重采样失败，保留原始采样率 {}Hz: {}",
                 sample_rate,
                 err
             );
-            (std::borrow::Cow::Borrowed(data.as_slice()), sample_rate)
+            (data, sample_rate)
         }
+    }
+}
+
+/// 按配置挑听写结果的版本：`formatted` 是润色后的文本，`raw` 是识别原文。
+fn text_for_variant<'a>(variant: TextVariant, formatted: &'a str, raw: &'a str) -> &'a str {
+    match variant {
+        TextVariant::Formatted => formatted,
+        TextVariant::Raw => raw,
+    }
+}
+
+fn interim_reuse_tail_gap_samples(mode: TranscriptionMode, sample_rate: u32) -> usize {
+    let gap_sec = match mode {
+        TranscriptionMode::Batch => BATCH_INTERIM_REUSE_TAIL_GAP_SEC,
+        TranscriptionMode::Streaming => STREAMING_INTERIM_REUSE_TAIL_GAP_SEC,
     };
+    (sample_rate as f64 * gap_sec) as usize
+}
+
+/// 按引擎转写一段完整的音频文件内容（最终识别、历史重识别、剪贴板文件共用）。
+/// 在线引擎只收 WAV；本地引擎按 `extension` 交给 Python 端解码。
+pub async fn transcribe_audio_file(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+    engine: &str,
+    audio: Vec<u8>,
+    extension: &str,
+) -> Result<funasr_service::TranscriptionResult, AppError> {
+    match engine {
+        "alibaba-asr" => alibaba_asr_service::transcribe(state, audio).await,
+        "glm-asr" => glm_asr_service::transcribe(state, audio).await,
+        _ => funasr_service::transcribe_audio_file_bytes(state, audio, extension, app_handle).await,
+    }
+}
+
+async fn do_final_asr(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+    samples: &parking_lot::Mutex<Vec<i16>>,
+    sample_rate: u32,
+) -> Result<funasr_service::TranscriptionResult, String> {
+    let data = std::mem::take(&mut *samples.lock());
+    let (loudness_target, leading_silence_ms) = state.with_profile(|profile| {
+        (
+            profile
+                .loudness_settings
+                .enabled
+                .then_some(profile.loudness_settings.target_lufs),
+            profile.leading_silence_ms.unwrap_or(0),
+        )
+    });
+    let (asr_audio, asr_sample_rate) = prepare_asr_audio(data, sample_rate, loudness_target);
+    // RTF 按实际录音时长算，补的静音不算在内
+    let audio_secs = asr_audio.len() as f64 / asr_sample_rate.max(1) as f64;
+    let asr_audio = pad_leading_silence(asr_audio, asr_sample_rate, leading_silence_ms);
 
     let engine = paths::read_engine_config();
+    let asr_started = Instant::now();
     let result = if paths::is_online_engine(&engine) {
         let wav =
             encode_wav(&asr_audio, asr_sample_rate).map_err(|e| format!("WAV 编码失败: {}", e))?;
-        match engine.as_str() {
-            "alibaba-asr" => alibaba_asr_service::transcribe(state, wav).await,
-            _ => glm_asr_service::transcribe(state, wav).await,
-        }
+        transcribe_audio_file(app_handle, state, &engine, wav, "wav").await
     } else {
         funasr_service::transcribe_pcm16(state, &asr_audio, asr_sample_rate, app_handle).await
     };
 
     match result {
-        Ok(r) if r.success => Ok(r),
+        Ok(r) if r.success => {
+            let asr_secs = asr_started.elapsed().as_secs_f64();
+            state.engine.record_transcription_rtf(asr_secs, audio_secs);
+            Ok(r)
+        }
         Ok(r) => Err(r.error.unwrap_or_else(|| "语音识别失败".into())),
         Err(e) => Err(format!("语音识别失败: {}", e)),
     }
@@ -1114,6 +1360,9 @@ fn emit_transcription_result(
         payload["timing"] = serde_json::json!(timing);
     }
     let _ = app.emit("transcription-result", payload);
+    if !text.is_empty() {
+        crate::commands::window::emit_subtitle_text(app, sid, text, false);
+    }
 }
 
 fn recording_outcome_payload(
@@ -1206,11 +1455,65 @@ fn emit_recording_state_snapshot(
         payload["error"] = serde_json::json!(err);
     }
     let _ = app.emit("recording-state", payload);
+    crate::commands::window::reassert_subtitle_topmost(app);
 }
 
 // ---------- 粘贴逻辑 ----------
 
-fn flush_pending_paste(app: &tauri::AppHandle) {
+fn is_cjk_char(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30ff}'   // 平假名 / 片假名
+        | '\u{3400}'..='\u{4dbf}' // CJK 扩展 A
+        | '\u{4e00}'..='\u{9fff}' // CJK 统一表意文字
+        | '\u{ac00}'..='\u{d7af}' // 韩文音节
+        | '\u{f900}'..='\u{faff}' // CJK 兼容表意文字
+    )
+}
+
+/// 文字字符里 CJK 占多数就视为 CJK 文本；标点、数字、空白不参与统计。
+fn is_predominantly_cjk(text: &str) -> bool {
+    let (cjk, total) = text
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .fold((0usize, 0usize), |(cjk, total), c| {
+            (cjk + is_cjk_char(c) as usize, total + 1)
+        });
+    total > 0 && cjk * 2 > total
+}
+
+/// 轮询前台窗口，直到它不再是本程序的窗口再注入，取代盲等固定延迟。
+/// 无法查询焦点的平台退回 `PASTE_DELAY_MS`；超时只记日志，仍然继续粘贴。
+async fn wait_for_paste_target() {
+    let started = Instant::now();
+    loop {
+        match crate::utils::foreground::is_foreground_paste_target_ready() {
+            None => {
+                tokio::time::sleep(Duration::from_millis(PASTE_DELAY_MS)).await;
+                return;
+            }
+            Some(true) => {
+                let waited_ms = elapsed_ms(started);
+                if waited_ms > PASTE_FOCUS_POLL_INTERVAL_MS {
+                    log::debug!("等待目标窗口获得焦点 {}ms 后粘贴", waited_ms);
+                }
+                return;
+            }
+            Some(false) => {
+                if elapsed_ms(started) >= PASTE_FOCUS_WAIT_TIMEOUT_MS {
+                    log::warn!(
+                        "等待目标窗口获得焦点超时（{}ms），前台仍是本程序窗口，继续粘贴",
+                        PASTE_FOCUS_WAIT_TIMEOUT_MS
+                    );
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(PASTE_FOCUS_POLL_INTERVAL_MS)).await;
+            }
+        }
+    }
+}
+
+/// 把待粘贴队列里的文本一次性粘贴出去（录音中会重新排队，等录音结束）。
+pub fn flush_pending_paste(app: &tauri::AppHandle) {
     let texts: Vec<String> = app
         .state::<AppState>()
         .recording
@@ -1224,11 +1527,138 @@ fn flush_pending_paste(app: &tauri::AppHandle) {
     let combined: String = texts.into_iter().collect();
     let app = app.clone();
     tokio::spawn(async move {
-        tokio::time::sleep(std::time::Duration::from_millis(PASTE_DELAY_MS)).await;
         do_paste(&app, &combined).await;
     });
 }
 
+fn pending_paste_recovery_path() -> std::path::PathBuf {
+    paths::get_data_dir().join(PENDING_PASTE_RECOVERY_FILE)
+}
+
+/// 把文本追加进恢复文件。上次的恢复内容还没被领取时不覆盖，避免二次丢失。
+fn append_recovery_text(path: &std::path::Path, text: &str) -> std::io::Result<()> {
+    let mut content = std::fs::read_to_string(path).unwrap_or_default();
+    content.push_str(text);
+    std::fs::write(path, content)
+}
+
+fn take_recovery_text(path: &std::path::Path) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    if let Err(err) = std::fs::remove_file(path) {
+        log::warn!("删除待粘贴恢复文件失败: {}", err);
+    }
+    Some(content).filter(|text| !text.is_empty())
+}
+
+/// 退出前调用：待粘贴队列里的文本不再尝试模拟按键（托盘菜单刚关闭，前台窗口
+/// 不确定），而是写进剪贴板并落盘到恢复文件，下次启动时放回待粘贴队列。
+/// 合并窗口里还没收尾的上一段来不及再识别，有中间结果时一并保存。
+/// 返回保存的字符数，队列为空时为 0。
+pub fn preserve_pending_paste_on_exit(app: &tauri::AppHandle) -> usize {
+    let state = app.state::<AppState>();
+    let mut combined: String = state.recording.pending_paste.lock().drain(..).collect();
+    if let Some(candidate) = state.recording.merge_candidate.lock().take() {
+        let interim = candidate
+            .interim_cache
+            .lock()
+            .as_ref()
+            .map(|cache| cache.text.trim().to_string())
+            .unwrap_or_default();
+        if interim.is_empty() {
+            log::warn!(
+                "退出时合并窗口内的录音尚无识别结果，无法保留 (session {})",
+                candidate.session_id
+            );
+        } else {
+            combined.push_str(&interim);
+        }
+    }
+    if combined.is_empty() {
+        return 0;
+    }
+    if let Err(err) = crate::commands::clipboard::write_text_to_clipboard(app, &combined) {
+        log::warn!("退出时写入待粘贴文本到剪贴板失败: {}", err);
+    }
+    match append_recovery_text(&pending_paste_recovery_path(), &combined) {
+        Ok(()) => log::info!(
+            "退出时保留了 {} 个字符的待粘贴文本（已复制到剪贴板并写入恢复文件）",
+            combined.len()
+        ),
+        Err(err) => log::warn!("写入待粘贴恢复文件失败: {}", err),
+    }
+    combined.len()
+}
+
+/// 入队后按上限裁掉最早的段，返回被裁掉的段。
+fn push_capped(queue: &mut Vec<String>, text: String, limit: usize) -> Vec<String> {
+    queue.push(text);
+    let overflow = queue.len().saturating_sub(limit.max(1));
+    queue.drain(..overflow).collect()
+}
+
+/// 录音进行中时把文本排进待粘贴队列。队列超过上限时最早的段不再等待粘贴，
+/// 而是转存到恢复文件（与退出时同一个，下次启动时放回队列），并发
+/// `pending-paste-overflow` 提醒前端。
+fn push_pending_paste(app: &tauri::AppHandle, text: &str) {
+    let state = app.state::<AppState>();
+    let limit = state
+        .with_profile(|profile| profile.pending_paste_limit)
+        .map_or(DEFAULT_PENDING_PASTE_LIMIT, |limit| limit as usize);
+    let dropped = push_capped(
+        &mut state.recording.pending_paste.lock(),
+        text.to_string(),
+        limit,
+    );
+    log::info!("录音进行中，文本已加入待粘贴队列（{} 个字符）", text.len());
+    if dropped.is_empty() {
+        return;
+    }
+
+    let dropped_text = dropped.concat();
+    log::warn!(
+        "待粘贴队列超过上限 {} 段，最早的 {} 段（{} 个字符）转存到恢复文件",
+        limit,
+        dropped.len(),
+        dropped_text.len()
+    );
+    if let Err(err) = append_recovery_text(&pending_paste_recovery_path(), &dropped_text) {
+        log::warn!("写入待粘贴恢复文件失败: {}", err);
+    }
+    let _ = app.emit(
+        "pending-paste-overflow",
+        serde_json::json!({
+            "limit": limit,
+            "droppedEntries": dropped.len(),
+            "droppedChars": dropped_text.chars().count(),
+        }),
+    );
+}
+
+/// 把恢复文件里的文本放到待粘贴队列最前面，返回恢复的字符数。
+fn restore_recovery_into(queue: &mut Vec<String>, path: &std::path::Path) -> usize {
+    let Some(text) = take_recovery_text(path) else {
+        return 0;
+    };
+    let chars = text.chars().count();
+    queue.insert(0, text);
+    chars
+}
+
+/// 启动时调用：上次退出（或队列溢出）时保存的文本放回待粘贴队列，
+/// 随下一次听写结果一起粘贴。恢复文件读取后即删除。
+pub fn restore_pending_paste_recovery(app: &tauri::AppHandle) {
+    let restored = restore_recovery_into(
+        &mut app.state::<AppState>().recording.pending_paste.lock(),
+        &pending_paste_recovery_path(),
+    );
+    if restored > 0 {
+        log::info!(
+            "已恢复上次未粘贴的 {} 个字符，将随下一次听写结果一起粘贴",
+            restored
+        );
+    }
+}
+
 async fn do_paste(app: &tauri::AppHandle, text: &str) {
     if let Err(e) = do_paste_result(app, text).await {
         log::error!("自动粘贴失败: {}", e);
@@ -1238,8 +1668,7 @@ async fn do_paste(app: &tauri::AppHandle, text: &str) {
 async fn do_paste_result(app: &tauri::AppHandle, text: &str) -> Result<(), AppError> {
     let state = app.state::<AppState>();
     if state.recording.recording.lock().is_some() {
-        state.recording.pending_paste.lock().push(text.to_string());
-        log::info!("录音进行中，文本已加入待粘贴队列（{} 个字符）", text.len());
+        push_pending_paste(app, text);
         return Ok(());
     }
 
@@ -1249,10 +1678,174 @@ async fn do_paste_result(app: &tauri::AppHandle, text: &str) -> Result<(), AppEr
     }
     full.push_str(text);
 
-    let method = state.ui.input_method.lock().clone();
-    crate::commands::clipboard::paste_text_impl(app, &full, &method)
-        .await
-        .map(|_| ())
+    wait_for_paste_target().await;
+    let target = crate::utils::foreground::capture_foreground_window();
+
+    let method = state
+        .take_next_input_method(std::time::Instant::now())
+        .unwrap_or_else(|| state.input_method_for_language(is_predominantly_cjk(&full)));
+    let fallback = state
+        .with_profile(|profile| profile.input_method_fallback.clone())
+        .unwrap_or_else(crate::commands::clipboard::default_input_method_fallback);
+    let chain = crate::commands::clipboard::input_method_chain(&method, &fallback);
+    crate::commands::clipboard::paste_text_with_fallback(app, &full, &chain).await?;
+    *state.recording.last_pasted_text.lock() = Some(full);
+    if target.is_some() {
+        *state.recording.last_paste_target.lock() = target;
+    }
+    Ok(())
+}
+
+/// 从本程序窗口（主窗口按钮等）发起注入时，先把焦点还给记下的目标窗口，
+/// 否则文本会落进本程序自己。前台已是别的应用时不动它。
+fn refocus_paste_target(target: Option<crate::utils::foreground::WindowHandle>) {
+    let Some(target) = target else {
+        return;
+    };
+    if crate::utils::foreground::is_foreground_paste_target_ready() != Some(false) {
+        return;
+    }
+    if !crate::utils::foreground::restore_foreground_window(target) {
+        log::warn!("无法切回粘贴目标窗口，文本将注入当前前台窗口");
+    }
+}
+
+/// 重新注入最近一次粘贴的文本，走和自动粘贴相同的路径（录音中同样进入待粘贴队列）。
+/// 从主窗口发起时先切回上次粘贴的窗口。没有可重贴的文本时返回 false。
+pub async fn repaste_last(app: &tauri::AppHandle) -> Result<bool, AppError> {
+    let state = app.state::<AppState>();
+    let last = state.recording.last_pasted_text.lock().clone();
+    let Some(text) = last else {
+        return Ok(false);
+    };
+    refocus_paste_target(*state.recording.last_paste_target.lock());
+    do_paste_result(app, &text).await?;
+    Ok(true)
+}
+
+/// 重新注入一段指定文本（例如历史记录），与自动粘贴同一路径：录音中先排入待粘贴队列。
+/// 与 repaste_last 一样，从主窗口发起时先切回上次粘贴的窗口。
+pub async fn repaste_text(app: &tauri::AppHandle, text: &str) -> Result<(), AppError> {
+    refocus_paste_target(*app.state::<AppState>().recording.last_paste_target.lock());
+    do_paste_result(app, text).await
+}
+
+// ---------- 粘贴前确认 ----------
+
+fn paste_review_payload(
+    status: &str,
+    review: &PendingPasteReview,
+    error: Option<&str>,
+) -> serde_json::Value {
+    let mut payload = serde_json::json!({
+        "status": status,
+        "id": review.id,
+    });
+    if status == "pending" {
+        payload["text"] = serde_json::json!(review.text);
+        payload["expiresInMs"] = serde_json::json!(PASTE_REVIEW_TIMEOUT_SECS * 1000);
+    }
+    if let Some(error) = error {
+        payload["error"] = serde_json::json!(error);
+    }
+    payload
+}
+
+fn emit_paste_review(app: &tauri::AppHandle, status: &str, review: &PendingPasteReview) {
+    let _ = app.emit("paste-review", paste_review_payload(status, review, None));
+}
+
+/// 只有 id 对得上（或不指定 id）才取走，避免确认按钮作用到被替换掉的旧结果上。
+fn take_review_if_matches(
+    slot: &mut Option<PendingPasteReview>,
+    id: Option<u64>,
+) -> Option<PendingPasteReview> {
+    match (slot.as_ref(), id) {
+        (Some(review), Some(id)) if review.id != id => None,
+        _ => slot.take(),
+    }
+}
+
+/// 把结果挂起等用户确认。已有未处理的结果时直接作废（状态 "replaced"），
+/// 同一时间只等一条；超过 `PASTE_REVIEW_TIMEOUT_SECS` 未处理则作废（"expired"）。
+fn request_paste_review(app: &tauri::AppHandle, session_id: u64, text: String) {
+    let review = PendingPasteReview {
+        id: session_id,
+        text,
+        target_window: crate::utils::foreground::capture_foreground_window(),
+    };
+    let replaced = app
+        .state::<AppState>()
+        .recording
+        .pending_review
+        .lock()
+        .replace(review.clone());
+    if let Some(previous) = replaced {
+        log::info!("待确认结果 {} 被新结果替换，已丢弃", previous.id);
+        emit_paste_review(app, "replaced", &previous);
+    }
+    log::info!(
+        "听写结果等待确认后粘贴 (session {}，{} 个字符)",
+        session_id,
+        review.text.chars().count()
+    );
+    emit_paste_review(app, "pending", &review);
+
+    let app = app.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(PASTE_REVIEW_TIMEOUT_SECS)).await;
+        let expired = take_review_if_matches(
+            &mut app.state::<AppState>().recording.pending_review.lock(),
+            Some(session_id),
+        );
+        if let Some(expired) = expired {
+            log::info!("待确认结果 {} 超时未处理，已丢弃", expired.id);
+            emit_paste_review(&app, "expired", &expired);
+        }
+    });
+}
+
+/// 确认并注入待确认的结果；`id` 为 None 时处理当前那一条。没有匹配的结果时返回 false。
+/// 先切回挂起时的目标窗口再粘贴；粘贴失败时结果放回去（状态 "failed"），可以重试。
+pub async fn confirm_paste_review(
+    app: &tauri::AppHandle,
+    id: Option<u64>,
+) -> Result<bool, AppError> {
+    let state = app.state::<AppState>();
+    let review = take_review_if_matches(&mut state.recording.pending_review.lock(), id);
+    let Some(review) = review else {
+        return Ok(false);
+    };
+    refocus_paste_target(review.target_window);
+    if let Err(err) = do_paste_result(app, &review.text).await {
+        let message = err.to_string();
+        log::warn!(
+            "待确认结果 {} 粘贴失败，保留等待重试: {}",
+            review.id,
+            message
+        );
+        let payload = paste_review_payload("failed", &review, Some(&message));
+        // 期间来了新结果就不再放回，旧结果已被取代
+        state.recording.pending_review.lock().get_or_insert(review);
+        let _ = app.emit("paste-review", payload);
+        return Err(err);
+    }
+    emit_paste_review(app, "confirmed", &review);
+    Ok(true)
+}
+
+/// 丢弃待确认的结果，不注入。没有匹配的结果时返回 false。
+pub fn discard_paste_review(app: &tauri::AppHandle, id: Option<u64>) -> bool {
+    let review = take_review_if_matches(
+        &mut app.state::<AppState>().recording.pending_review.lock(),
+        id,
+    );
+    let Some(review) = review else {
+        return false;
+    };
+    log::info!("用户丢弃了待确认结果 {}", review.id);
+    emit_paste_review(app, "discarded", &review);
+    true
 }
 
 #[cfg(test)]
@@ -1260,6 +1853,127 @@ mod tests {
     use super::*;
     use crate::state::RecordingTrigger;
 
+    fn tone(amplitude: f64, sample_rate: u32, secs: f64) -> Vec<i16> {
+        let len = (sample_rate as f64 * secs) as usize;
+        (0..len)
+            .map(|i| {
+                let t = i as f64 / sample_rate as f64;
+                ((t * 440.0 * std::f64::consts::TAU).sin() * amplitude) as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn native_rate_audio_is_processed_then_downsampled_last() {
+        use super::super::loudness::{measure_loudness, DEFAULT_LOUDNESS_TARGET_LUFS};
+
+        let data = tone(300.0, 48_000, 1.0);
+        let (audio, rate) = prepare_asr_audio(data, 48_000, Some(DEFAULT_LOUDNESS_TARGET_LUFS));
+        assert_eq!(rate, TARGET_SAMPLE_RATE);
+        assert!((audio.len() as i64 - TARGET_SAMPLE_RATE as i64).abs() < 64);
+        let loudness = measure_loudness(&audio, rate).unwrap();
+        assert!((loudness - DEFAULT_LOUDNESS_TARGET_LUFS).abs() < 1.0);
+    }
+
+    #[test]
+    fn target_rate_audio_passes_through_unchanged_without_processing() {
+        let data = tone(3000.0, TARGET_SAMPLE_RATE, 0.5);
+        let (audio, rate) = prepare_asr_audio(data.clone(), TARGET_SAMPLE_RATE, None);
+        assert_eq!(rate, TARGET_SAMPLE_RATE);
+        assert_eq!(audio, data);
+
+        let (audio, rate) = prepare_asr_audio(Vec::new(), 44_100, None);
+        assert_eq!(rate, TARGET_SAMPLE_RATE);
+        assert!(audio.is_empty());
+    }
+
+    #[test]
+    fn leading_silence_pad_adds_zero_samples_before_encoding() {
+        let data = tone(3000.0, TARGET_SAMPLE_RATE, 0.5);
+        let padded = pad_leading_silence(data.clone(), TARGET_SAMPLE_RATE, 100);
+        let pad_samples = TARGET_SAMPLE_RATE as usize / 10;
+        assert_eq!(padded.len(), data.len() + pad_samples);
+
+        let plain_wav = encode_wav(&data, TARGET_SAMPLE_RATE).unwrap();
+        let padded_wav = encode_wav(&padded, TARGET_SAMPLE_RATE).unwrap();
+        assert_eq!(padded_wav.len(), plain_wav.len() + pad_samples * 2);
+
+        let reader = hound::WavReader::new(std::io::Cursor::new(padded_wav)).unwrap();
+        assert_eq!(reader.spec().sample_rate, TARGET_SAMPLE_RATE);
+        let decoded: Vec<i16> = reader.into_samples::<i16>().map(|s| s.unwrap()).collect();
+        assert!(decoded[..pad_samples].iter().all(|&s| s == 0));
+        assert_eq!(&decoded[pad_samples..], data.as_slice());
+
+        assert_eq!(
+            pad_leading_silence(data.clone(), TARGET_SAMPLE_RATE, 0),
+            data
+        );
+        assert!(pad_leading_silence(Vec::new(), TARGET_SAMPLE_RATE, 100).is_empty());
+    }
+
+    #[test]
+    fn recovery_text_appends_and_is_taken_once() {
+        let path = std::env::temp_dir().join(format!(
+            "light_whisper_pending_paste_test_{}.txt",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(take_recovery_text(&path), None);
+
+        append_recovery_text(&path, "第一段").unwrap();
+        append_recovery_text(&path, "第二段").unwrap();
+        assert_eq!(take_recovery_text(&path).as_deref(), Some("第一段第二段"));
+        assert!(!path.exists());
+        assert_eq!(take_recovery_text(&path), None);
+    }
+
+    #[test]
+    fn pending_paste_queue_drops_oldest_entries_over_limit() {
+        let mut queue = Vec::new();
+        for i in 0..3 {
+            assert!(push_capped(&mut queue, format!("段{}", i), 3).is_empty());
+        }
+        assert_eq!(push_capped(&mut queue, "段3".into(), 3), vec!["段0"]);
+        assert_eq!(queue, vec!["段1", "段2", "段3"]);
+
+        // 上限调低后一次裁掉多段；上限 0 按 1 处理，最新一段总会保留
+        assert_eq!(
+            push_capped(&mut queue, "段4".into(), 0),
+            vec!["段1", "段2", "段3"]
+        );
+        assert_eq!(queue, vec!["段4"]);
+    }
+
+    #[test]
+    fn overflowed_pending_paste_is_restored_after_restart() {
+        let path = std::env::temp_dir().join(format!(
+            "light_whisper_pending_overflow_test_{}.txt",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let mut queue = vec!["段0".to_string()];
+        let dropped = push_capped(&mut queue, "段1".into(), 1);
+        append_recovery_text(&path, &dropped.concat()).unwrap();
+
+        // 重启后内存队列为空，溢出的段从恢复文件回到队列最前面
+        let mut restarted = vec!["新段".to_string()];
+        assert_eq!(restore_recovery_into(&mut restarted, &path), 2);
+        assert_eq!(restarted, vec!["段0", "新段"]);
+        assert!(!path.exists());
+        assert_eq!(restore_recovery_into(&mut restarted, &path), 0);
+    }
+
+    #[test]
+    fn cjk_detection_uses_majority_of_letters() {
+        assert!(is_predominantly_cjk("今天天气不错，OK"));
+        assert!(is_predominantly_cjk("こんにちは"));
+        assert!(is_predominantly_cjk("안녕하세요"));
+        assert!(!is_predominantly_cjk("Hello world, 你好"));
+        assert!(!is_predominantly_cjk("123，。！"));
+        assert!(!is_predominantly_cjk(""));
+    }
+
     fn foreground(process_name: &str, window_title: &str) -> ForegroundApp {
         ForegroundApp {
             process_name: process_name.into(),
@@ -1446,4 +2160,52 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn streaming_mode_tolerates_longer_uncovered_tail() {
+        let batch = interim_reuse_tail_gap_samples(TranscriptionMode::Batch, 16_000);
+        let streaming = interim_reuse_tail_gap_samples(TranscriptionMode::Streaming, 16_000);
+        assert_eq!(batch, 4_000);
+        assert_eq!(streaming, 16_000);
+        assert_eq!(
+            interim_reuse_tail_gap_samples(TranscriptionMode::Batch, 48_000),
+            12_000
+        );
+    }
+
+    #[test]
+    fn text_variant_selects_polished_or_raw_text() {
+        assert_eq!(
+            text_for_variant(TextVariant::Formatted, "你好，世界。", "你好世界"),
+            "你好，世界。"
+        );
+        assert_eq!(
+            text_for_variant(TextVariant::Raw, "你好，世界。", "你好世界"),
+            "你好世界"
+        );
+    }
+
+    #[test]
+    fn paste_review_is_taken_only_for_matching_id() {
+        let review = |id| PendingPasteReview {
+            id,
+            text: "你好".to_string(),
+            target_window: None,
+        };
+        let mut slot = Some(review(3));
+        assert!(take_review_if_matches(&mut slot, Some(2)).is_none());
+        assert!(slot.is_some());
+        assert_eq!(
+            take_review_if_matches(&mut slot, Some(3)).map(|r| r.id),
+            Some(3)
+        );
+        assert!(slot.is_none());
+
+        let mut slot = Some(review(4));
+        assert_eq!(
+            take_review_if_matches(&mut slot, None).map(|r| r.id),
+            Some(4)
+        );
+        assert!(take_review_if_matches(&mut slot, None).is_none());
+    }
 }
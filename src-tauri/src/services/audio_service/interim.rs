@@ -6,16 +6,85 @@ use std::sync::{
 use tauri::{Emitter, Manager};
 
 use super::resample::ResamplerState;
+use super::text_cleanup::clean_transcript;
 use super::{
-    INTERIM_HEAVY_COST_MS, INTERIM_INTERVAL_BASE_MS, INTERIM_INTERVAL_DOWN_STEP_MS,
-    INTERIM_INTERVAL_MAX_MS, INTERIM_INTERVAL_MIN_MS, INTERIM_INTERVAL_UP_STEP_MS,
-    INTERIM_LIGHT_COST_MS, INTERIM_MAX_AUDIO_WINDOW_SEC, MIN_INTERIM_DURATION_SEC,
-    MIN_SAMPLES_GROWTH, TARGET_SAMPLE_RATE,
+    INTERIM_CALIBRATED_INTERVAL_CEIL_MS, INTERIM_CALIBRATED_INTERVAL_FLOOR_MS,
+    INTERIM_CALIBRATION_SAMPLES, INTERIM_HEAVY_COST_MS, INTERIM_INTERVAL_BASE_MS,
+    INTERIM_INTERVAL_DOWN_STEP_MS, INTERIM_INTERVAL_MAX_MS, INTERIM_INTERVAL_MIN_MS,
+    INTERIM_INTERVAL_UP_STEP_MS, INTERIM_LIGHT_COST_MS, INTERIM_MAX_AUDIO_WINDOW_SEC,
+    MIN_INTERIM_DURATION_SEC, MIN_SAMPLES_GROWTH, TARGET_SAMPLE_RATE,
 };
 use crate::services::funasr_service;
+use crate::state::user_profile::{InterimCostSettings, TranscriptionMode};
 use crate::state::AppState;
 use crate::utils::paths;
 
+// ---------- 自适应间隔参数 ----------
+
+/// `adjust_interval` 使用的阈值与间隔边界。默认值即原先的常量；
+/// 设置里可改两个耗时阈值，自校准则按实测耗时整体推算。
+/// 约定 min ≤ base ≤ max、light < heavy。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct InterimTuning {
+    heavy_cost_ms: u64,
+    light_cost_ms: u64,
+    min_interval_ms: u64,
+    base_interval_ms: u64,
+    max_interval_ms: u64,
+}
+
+impl Default for InterimTuning {
+    fn default() -> Self {
+        Self {
+            heavy_cost_ms: INTERIM_HEAVY_COST_MS,
+            light_cost_ms: INTERIM_LIGHT_COST_MS,
+            min_interval_ms: INTERIM_INTERVAL_MIN_MS,
+            base_interval_ms: INTERIM_INTERVAL_BASE_MS,
+            max_interval_ms: INTERIM_INTERVAL_MAX_MS,
+        }
+    }
+}
+
+impl InterimTuning {
+    fn from_settings(settings: &InterimCostSettings) -> Self {
+        Self {
+            heavy_cost_ms: settings.heavy_cost_ms,
+            light_cost_ms: settings.light_cost_ms,
+            ..Self::default()
+        }
+    }
+
+    /// 以实测耗时中位数 c 为尺度：间隔下限 1.25c（给最终识别留出空闲），
+    /// 基准 2c，上限 4c；单次耗时超过 1.5c 视为偏重，低于 0.75c 视为偏轻。
+    fn calibrated(costs: &[u64]) -> Option<Self> {
+        if costs.is_empty() {
+            return None;
+        }
+        let mut sorted = costs.to_vec();
+        sorted.sort_unstable();
+        let median = sorted[sorted.len() / 2].max(1);
+
+        let bound = |value: u64| {
+            value.clamp(
+                INTERIM_CALIBRATED_INTERVAL_FLOOR_MS,
+                INTERIM_CALIBRATED_INTERVAL_CEIL_MS,
+            )
+        };
+        let min_interval_ms = bound(median + median / 4);
+        let base_interval_ms = bound(median * 2).max(min_interval_ms);
+        let max_interval_ms = bound(median * 4).max(base_interval_ms);
+        let light_cost_ms = median * 3 / 4;
+        let heavy_cost_ms = (median * 3 / 2).max(light_cost_ms + 1);
+        Some(Self {
+            heavy_cost_ms,
+            light_cost_ms,
+            min_interval_ms,
+            base_interval_ms,
+            max_interval_ms,
+        })
+    }
+}
+
 // ---------- 中间转写循环 ----------
 
 pub fn spawn_interim_loop(
@@ -29,7 +98,21 @@ pub fn spawn_interim_loop(
 ) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
         let state = app_handle.state::<AppState>();
-        let mut interval_ms = INTERIM_INTERVAL_BASE_MS;
+        let (mut tuning, auto_calibrate, transcription_mode) = state.with_profile(|profile| {
+            (
+                InterimTuning::from_settings(&profile.interim_cost_settings),
+                profile.interim_cost_settings.auto_calibrate,
+                profile.transcription_mode,
+            )
+        });
+        // 最短录音时长调得比中间结果下限还短时，中间结果也跟着提前开始
+        let min_interim_sec = MIN_INTERIM_DURATION_SEC.min(state.min_audio_duration_sec());
+        let mut calibration_costs: Vec<u64> = Vec::new();
+        // 流式模式从最快节奏起步，之后仍按实际耗时自适应
+        let mut interval_ms = match transcription_mode {
+            TranscriptionMode::Batch => tuning.base_interval_ms,
+            TranscriptionMode::Streaming => tuning.min_interval_ms,
+        };
         let mut last_sample_count: usize = 0;
         // 会话级重采样缓存：只对新增的原始增量执行一次重采样，结果追加到这里
         // 原生 16k 设备时与原始数据相同（resample_to_16k 走零拷贝路径）
@@ -82,10 +165,10 @@ pub fn spawn_interim_loop(
                 let guard = samples.lock();
                 let count = guard.len();
                 if count.saturating_sub(last_sample_count) < MIN_SAMPLES_GROWTH {
-                    interval_ms = adjust_interval(interval_ms, false, 0);
+                    interval_ms = adjust_interval(interval_ms, false, 0, &tuning);
                     continue;
                 }
-                if (count as f64 / sample_rate as f64) < MIN_INTERIM_DURATION_SEC {
+                if (count as f64 / sample_rate as f64) < min_interim_sec {
                     continue;
                 }
                 let delta: Vec<i16> = guard[raw_processed..count].to_vec();
@@ -145,30 +228,45 @@ pub fn spawn_interim_loop(
             let covered_sample_count =
                 current_count.min((sample_rate as f64 * INTERIM_MAX_AUDIO_WINDOW_SEC) as usize);
 
-            match funasr_service::transcribe_pcm16(
+            // 预处理期间可能已经松键：不再发起新一轮，最终识别不用排在它后面
+            if stop_flag.load(Ordering::Acquire) {
+                break;
+            }
+            match funasr_service::transcribe_interim_pcm16(
                 state.inner(),
                 interim_samples,
                 interim_sample_rate,
                 &app_handle,
+                &stop_flag,
             )
             .await
             {
-                Ok(result) if result.success && !result.text.is_empty() => {
-                    let _ = app_handle.emit(
-                        "transcription-result",
-                        serde_json::json!({
-                            "sessionId": session_id,
-                            "text": &result.text,
-                            "interim": true,
-                            "language": &result.language,
-                        }),
-                    );
-                    *interim_cache.lock() = Some(crate::state::InterimCache {
-                        text: result.text,
-                        language: result.language,
-                        sample_count: covered_sample_count,
-                    });
-                    last_sample_count = current_count;
+                Ok(result) if result.success => {
+                    let text =
+                        state.with_profile(|profile| clean_transcript(&result.text, profile));
+                    if !text.is_empty() {
+                        let _ = app_handle.emit(
+                            "transcription-result",
+                            serde_json::json!({
+                                "sessionId": session_id,
+                                "text": &text,
+                                "interim": true,
+                                "language": &result.language,
+                            }),
+                        );
+                        crate::commands::window::emit_subtitle_text(
+                            &app_handle,
+                            session_id,
+                            &text,
+                            true,
+                        );
+                        *interim_cache.lock() = Some(crate::state::InterimCache {
+                            text,
+                            language: result.language,
+                            sample_count: covered_sample_count,
+                        });
+                        last_sample_count = current_count;
+                    }
                 }
                 _ => {}
             }
@@ -176,29 +274,108 @@ pub fn spawn_interim_loop(
             if stop_flag.load(Ordering::Relaxed) {
                 break;
             }
-            interval_ms = adjust_interval(interval_ms, true, start.elapsed().as_millis() as u64);
+            let elapsed_ms = start.elapsed().as_millis() as u64;
+            if auto_calibrate && calibration_costs.len() < INTERIM_CALIBRATION_SAMPLES {
+                calibration_costs.push(elapsed_ms);
+                if calibration_costs.len() == INTERIM_CALIBRATION_SAMPLES {
+                    if let Some(calibrated) = InterimTuning::calibrated(&calibration_costs) {
+                        log::info!(
+                            "中间转写自校准完成 (session {}): 耗时 {:?}ms → 间隔 {}-{}-{}ms，阈值 {}/{}ms",
+                            session_id,
+                            calibration_costs,
+                            calibrated.min_interval_ms,
+                            calibrated.base_interval_ms,
+                            calibrated.max_interval_ms,
+                            calibrated.light_cost_ms,
+                            calibrated.heavy_cost_ms
+                        );
+                        tuning = calibrated;
+                        interval_ms =
+                            interval_ms.clamp(tuning.min_interval_ms, tuning.max_interval_ms);
+                    }
+                }
+            }
+            interval_ms = adjust_interval(interval_ms, true, elapsed_ms, &tuning);
         }
         log::info!("中间转写循环结束 (session {})", session_id);
     })
 }
 
-fn adjust_interval(current: u64, executed: bool, elapsed_ms: u64) -> u64 {
+fn adjust_interval(current: u64, executed: bool, elapsed_ms: u64, tuning: &InterimTuning) -> u64 {
     if !executed {
         return current
             .saturating_sub(8)
-            .clamp(INTERIM_INTERVAL_MIN_MS, INTERIM_INTERVAL_BASE_MS);
+            .clamp(tuning.min_interval_ms, tuning.base_interval_ms);
     }
-    if elapsed_ms >= INTERIM_HEAVY_COST_MS {
-        (current + INTERIM_INTERVAL_UP_STEP_MS).min(INTERIM_INTERVAL_MAX_MS)
-    } else if elapsed_ms <= INTERIM_LIGHT_COST_MS {
+    if elapsed_ms >= tuning.heavy_cost_ms {
+        (current + INTERIM_INTERVAL_UP_STEP_MS).min(tuning.max_interval_ms)
+    } else if elapsed_ms <= tuning.light_cost_ms {
         current
             .saturating_sub(INTERIM_INTERVAL_DOWN_STEP_MS)
-            .max(INTERIM_INTERVAL_MIN_MS)
+            .max(tuning.min_interval_ms)
     } else {
-        match current.cmp(&INTERIM_INTERVAL_BASE_MS) {
-            std::cmp::Ordering::Greater => current.saturating_sub(8).max(INTERIM_INTERVAL_BASE_MS),
-            std::cmp::Ordering::Less => (current + 4).min(INTERIM_INTERVAL_BASE_MS),
+        match current.cmp(&tuning.base_interval_ms) {
+            std::cmp::Ordering::Greater => current.saturating_sub(8).max(tuning.base_interval_ms),
+            std::cmp::Ordering::Less => (current + 4).min(tuning.base_interval_ms),
             std::cmp::Ordering::Equal => current,
         }
     }
 }
+
+// ---------- 单元测试 ----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_tuning_keeps_original_thresholds() {
+        let tuning = InterimTuning::default();
+        assert_eq!(adjust_interval(220, true, 500, &tuning), 262);
+        assert_eq!(adjust_interval(460, true, 500, &tuning), 460);
+        assert_eq!(adjust_interval(220, true, 100, &tuning), 196);
+        assert_eq!(adjust_interval(150, true, 100, &tuning), 140);
+        assert_eq!(adjust_interval(300, true, 300, &tuning), 292);
+        assert_eq!(adjust_interval(200, false, 0, &tuning), 192);
+    }
+
+    #[test]
+    fn calibration_scales_bounds_from_median_cost() {
+        // 快 GPU：中位数 40ms
+        let fast = InterimTuning::calibrated(&[300, 40, 36]).expect("calibrated");
+        assert_eq!(fast.min_interval_ms, 60);
+        assert_eq!(fast.base_interval_ms, 80);
+        assert_eq!(fast.max_interval_ms, 160);
+        assert_eq!((fast.light_cost_ms, fast.heavy_cost_ms), (30, 60));
+        assert_eq!(adjust_interval(80, true, 20, &fast), 60);
+        assert_eq!(adjust_interval(150, true, 90, &fast), 160);
+
+        // 慢 CPU：中位数 800ms，上限封顶
+        let slow = InterimTuning::calibrated(&[700, 900, 800]).expect("calibrated");
+        assert_eq!(slow.min_interval_ms, 1000);
+        assert_eq!(slow.base_interval_ms, 1600);
+        assert_eq!(slow.max_interval_ms, 2000);
+        assert_eq!(adjust_interval(1990, true, 1500, &slow), 2000);
+        assert_eq!(adjust_interval(1000, true, 500, &slow), 1000);
+    }
+
+    #[test]
+    fn calibration_keeps_invariants_for_tiny_costs() {
+        let tuning = InterimTuning::calibrated(&[0, 0, 0]).expect("calibrated");
+        assert!(tuning.min_interval_ms <= tuning.base_interval_ms);
+        assert!(tuning.base_interval_ms <= tuning.max_interval_ms);
+        assert!(tuning.light_cost_ms < tuning.heavy_cost_ms);
+        assert!(InterimTuning::calibrated(&[]).is_none());
+    }
+
+    #[test]
+    fn configured_thresholds_replace_defaults() {
+        let tuning = InterimTuning::from_settings(&InterimCostSettings {
+            heavy_cost_ms: 200,
+            light_cost_ms: 50,
+            auto_calibrate: false,
+        });
+        assert_eq!(adjust_interval(220, true, 250, &tuning), 262);
+        assert_eq!(adjust_interval(220, true, 100, &tuning), 220);
+    }
+}
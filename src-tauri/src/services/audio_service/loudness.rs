@@ -0,0 +1,121 @@
+// ---------- 响度归一化（送入识别前） ----------
+//
+// 近似 LUFS：按 400ms 块计算 RMS 响度（dBFS），丢弃低于绝对门限的静音块后
+// 取能量平均，未做 K 加权；对语音识别来说这个近似足够。增益同时受峰值约束，
+// 归一化后不会削波。
+
+/// 默认目标响度，语音识别模型在这个附近表现稳定
+pub const DEFAULT_LOUDNESS_TARGET_LUFS: f64 = -20.0;
+pub const MIN_LOUDNESS_TARGET_LUFS: f64 = -40.0;
+pub const MAX_LOUDNESS_TARGET_LUFS: f64 = -6.0;
+/// 单次归一化最多放大/衰减的幅度，避免把底噪放大成主体
+const MAX_NORMALIZE_GAIN_DB: f64 = 24.0;
+/// 峰值上限约 -0.3dBFS
+const PEAK_CEILING: f64 = 0.966 * i16::MAX as f64;
+/// 静音门限：低于此响度的块不参与平均
+const LOUDNESS_ABSOLUTE_GATE_DB: f64 = -70.0;
+const LOUDNESS_BLOCK_SEC: f64 = 0.4;
+/// 增益小于这个值时视为已达标，不改写样本
+const NORMALIZE_MIN_ADJUST_DB: f64 = 0.5;
+
+fn block_loudness_db(block: &[i16]) -> f64 {
+    let mean_square = block
+        .iter()
+        .map(|&s| {
+            let v = s as f64 / i16::MAX as f64;
+            v * v
+        })
+        .sum::<f64>()
+        / block.len().max(1) as f64;
+    10.0 * mean_square.max(1e-12).log10()
+}
+
+/// 门限后的积分响度（dBFS）；整段都是静音时返回 None。
+pub fn measure_loudness(samples: &[i16], sample_rate: u32) -> Option<f64> {
+    if samples.is_empty() || sample_rate == 0 {
+        return None;
+    }
+    let block_len = ((sample_rate as f64 * LOUDNESS_BLOCK_SEC) as usize).max(1);
+    let gated: Vec<f64> = samples
+        .chunks(block_len)
+        .map(block_loudness_db)
+        .filter(|&db| db > LOUDNESS_ABSOLUTE_GATE_DB)
+        .collect();
+    if gated.is_empty() {
+        return None;
+    }
+    let mean_power = gated.iter().map(|db| 10f64.powf(db / 10.0)).sum::<f64>() / gated.len() as f64;
+    Some(10.0 * mean_power.log10())
+}
+
+/// 把响度调到 `target_lufs`，返回实际施加的增益（dB）。静音或已达标时返回 None 且不改写样本。
+pub fn normalize_loudness(samples: &mut [i16], sample_rate: u32, target_lufs: f64) -> Option<f64> {
+    let loudness = measure_loudness(samples, sample_rate)?;
+    let peak = samples
+        .iter()
+        .map(|&s| (s as i32).unsigned_abs())
+        .max()
+        .unwrap_or(0)
+        .max(1) as f64;
+    let peak_limit_db = 20.0 * (PEAK_CEILING / peak).log10();
+    let gain_db = (target_lufs - loudness)
+        .clamp(-MAX_NORMALIZE_GAIN_DB, MAX_NORMALIZE_GAIN_DB)
+        .min(peak_limit_db);
+    if gain_db.abs() < NORMALIZE_MIN_ADJUST_DB {
+        return None;
+    }
+    let gain = 10f64.powf(gain_db / 20.0);
+    for sample in samples.iter_mut() {
+        *sample = (*sample as f64 * gain)
+            .round()
+            .clamp(i16::MIN as f64, i16::MAX as f64) as i16;
+    }
+    Some(gain_db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{measure_loudness, normalize_loudness, DEFAULT_LOUDNESS_TARGET_LUFS};
+
+    fn tone(amplitude: f64, len: usize) -> Vec<i16> {
+        (0..len)
+            .map(|i| ((i as f64 * 0.07).sin() * amplitude) as i16)
+            .collect()
+    }
+
+    #[test]
+    fn quiet_signal_is_boosted_toward_target() {
+        let mut samples = tone(300.0, 16000);
+        let before = measure_loudness(&samples, 16000).unwrap();
+        let gain = normalize_loudness(&mut samples, 16000, DEFAULT_LOUDNESS_TARGET_LUFS).unwrap();
+        let after = measure_loudness(&samples, 16000).unwrap();
+        assert!(gain > 0.0);
+        assert!(after > before);
+        assert!((after - DEFAULT_LOUDNESS_TARGET_LUFS).abs() < 1.0);
+    }
+
+    #[test]
+    fn loud_signal_is_attenuated_without_clipping() {
+        let mut samples = tone(32000.0, 16000);
+        let gain = normalize_loudness(&mut samples, 16000, DEFAULT_LOUDNESS_TARGET_LUFS).unwrap();
+        assert!(gain < 0.0);
+        let peak = samples.iter().map(|s| s.unsigned_abs()).max().unwrap();
+        assert!(peak < i16::MAX as u16);
+        let after = measure_loudness(&samples, 16000).unwrap();
+        assert!((after - DEFAULT_LOUDNESS_TARGET_LUFS).abs() < 1.0);
+    }
+
+    #[test]
+    fn boost_is_limited_by_peak_and_silence_is_untouched() {
+        // 单个尖峰 + 低电平：放大会被峰值上限截住，余量不足时不改写样本
+        let mut samples = tone(200.0, 16000);
+        samples[100] = 30000;
+        let original = samples.clone();
+        assert!(normalize_loudness(&mut samples, 16000, -6.0).is_none());
+        assert_eq!(samples, original);
+
+        let mut silence = vec![0i16; 16000];
+        assert!(normalize_loudness(&mut silence, 16000, DEFAULT_LOUDNESS_TARGET_LUFS).is_none());
+        assert!(silence.iter().all(|&s| s == 0));
+    }
+}
@@ -3,8 +3,13 @@ use serde::Serialize;
 // ---------- 常量 ----------
 
 pub(crate) const TARGET_SAMPLE_RATE: u32 = 16000;
-/// finalize_recording 的下限：低于这个时长整段录音直接跳过（视为误按）
+/// finalize_recording 的默认下限：低于这个时长整段录音直接跳过（视为误按）。
+/// 运行时可通过 set_min_audio_duration 调整并保存到画像，见 `RecordingState::min_audio_duration_ms`
 pub(crate) const MIN_AUDIO_DURATION_SEC: f64 = 0.5;
+pub(crate) const MIN_AUDIO_DURATION_LOWER_SEC: f64 = 0.1;
+pub(crate) const MIN_AUDIO_DURATION_UPPER_SEC: f64 = 5.0;
+/// 最终识别前开头补静音的上限
+pub(crate) const MAX_LEADING_SILENCE_MS: u32 = 1000;
 /// interim 的下限：首个 tick 积到这个时长就开始送 Python 推理，不再等到 0.5s。
 /// 短于 0.5s 的部分会在 funasr_service::transcribe_pcm16 里尾部补零对齐 Python VAD。
 pub(crate) const MIN_INTERIM_DURATION_SEC: f64 = 0.2;
@@ -18,6 +23,10 @@ pub(crate) const INTERIM_INTERVAL_UP_STEP_MS: u64 = 42;
 pub(crate) const INTERIM_HEAVY_COST_MS: u64 = 420;
 pub(crate) const INTERIM_LIGHT_COST_MS: u64 = 180;
 pub(crate) const INTERIM_MAX_AUDIO_WINDOW_SEC: f64 = 12.0;
+/// 自校准：取每次录音前几次中间转写的实测耗时，用中位数推算阈值与间隔上下限
+pub(crate) const INTERIM_CALIBRATION_SAMPLES: usize = 3;
+pub(crate) const INTERIM_CALIBRATED_INTERVAL_FLOOR_MS: u64 = 60;
+pub(crate) const INTERIM_CALIBRATED_INTERVAL_CEIL_MS: u64 = 2000;
 
 pub(crate) const RESULT_HIDE_DELAY_MS: u64 = 2500;
 pub(crate) const EMPTY_RESULT_HIDE_DELAY_MS: u64 = 360;
@@ -41,11 +50,21 @@ pub(crate) const EMPTY_RESULT_HIDE_DELAY_MS: u64 = 360;
 /// 如果用户反馈 "结果出现和粘贴同时发生感觉太突然"，可以往回调到 120-150ms。
 /// 如果以后真的出现 "粘到字幕窗口而不是目标 app" 或按键顺序错乱，说明焦点理论被
 /// 翻案了，需要调回 200+ ms 并重新审查 show_subtitle_window 里的窗口操作序列。
+///
+/// 现在 `do_paste` 优先轮询前台窗口（见 `PASTE_FOCUS_WAIT_TIMEOUT_MS`），这个固定
+/// 延迟只在无法查询焦点的平台上作为回退。
 pub(crate) const PASTE_DELAY_MS: u64 = 60;
+/// 粘贴前等待前台窗口离开本程序的最长时间；超时后仍然照常粘贴。
+/// 隐藏主窗口后、或窗口管理器较慢时，焦点可能要几百毫秒才回到目标 app。
+pub(crate) const PASTE_FOCUS_WAIT_TIMEOUT_MS: u64 = 800;
+pub(crate) const PASTE_FOCUS_POLL_INTERVAL_MS: u64 = 15;
 pub(crate) const AUDIO_CAPTURE_INIT_TIMEOUT_SECS: u64 = 8;
 pub(crate) const MICROPHONE_LEVEL_EMIT_INTERVAL_MS: u64 = 70;
 /// finalize 阶段等待并行抓取选中文本的最大时长。超时就按普通听写处理。
 pub(crate) const EDIT_GRAB_WAIT_MS: u64 = 650;
+/// 录音期间排队等待粘贴的段数上限；超出时最早的段转存到恢复文件
+pub(crate) const DEFAULT_PENDING_PASTE_LIMIT: usize = 50;
+pub(crate) const MAX_PENDING_PASTE_LIMIT: u32 = 1000;
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -61,6 +80,99 @@ pub struct InputDeviceListPayload {
     pub selected_device_name: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioSubsystemResetPayload {
+    /// 被强制拆除的录音会话
+    pub stopped_session_id: Option<u64>,
+    pub monitor_stopped: bool,
+    /// 重置前缓存的设备选择；设备仍在时前端可以据此重新选回
+    pub cleared_device_name: Option<String>,
+    /// 当前平台可用的 cpal 音频后端
+    pub hosts: Vec<String>,
+    pub devices: Vec<InputDeviceInfo>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputConfigRangeInfo {
+    pub sample_format: String,
+    pub channels: u16,
+    pub min_sample_rate: u32,
+    pub max_sample_rate: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChosenInputConfigInfo {
+    /// 对应 `DeviceCapabilitiesPayload::configs` 的下标
+    pub range_index: usize,
+    pub sample_format: String,
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub needs_resample: bool,
+    /// 命中的选择规则：native16k / maxRate / firstRange
+    pub tier: &'static str,
+    /// 人类可读的选择理由，与录音时的日志一致
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceCapabilitiesPayload {
+    pub device_name: String,
+    pub is_default: bool,
+    /// 用户选择的设备名；不可用时 `device_name` 会是回退后的默认设备
+    pub requested_device_name: Option<String>,
+    pub configs: Vec<InputConfigRangeInfo>,
+    pub chosen: Option<ChosenInputConfigInfo>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MicrophoneDefaultConfig {
+    pub sample_format: String,
+    pub channels: u16,
+    pub sample_rate: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MicrophoneInfoPayload {
+    pub device_name: String,
+    pub is_default: bool,
+    /// 设备支持范围内的常见采样率（升序）
+    pub sample_rates: Vec<u32>,
+    pub channel_counts: Vec<u16>,
+    pub default_config: Option<MicrophoneDefaultConfig>,
+    /// 能否直接以 16kHz 单声道采集；否则录音时需要混音或重采样
+    pub supports_16k_mono: bool,
+    /// 录音实际会使用的采样率
+    pub capture_sample_rate: Option<u32>,
+    pub needs_resample: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioHostListPayload {
+    /// 当前平台可用的 cpal 后端
+    pub hosts: Vec<String>,
+    pub default_host: String,
+    /// 用户选择的后端；None 使用默认后端
+    pub selected_host: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioPrewarmReport {
+    pub device_name: String,
+    pub sample_format: String,
+    pub channels: u16,
+    pub sample_rate: u32,
+    /// 枚举设备、协商配置到建流完成的总耗时
+    pub elapsed_ms: u64,
+}
+
 // ---------- 统一的多格式音频流构建宏 ----------
 
 /// 为三种采样格式（I16/F32/U16）构建 cpal 输入流，消除重复代码。
@@ -86,11 +198,17 @@ macro_rules! build_input_stream_dispatch {
 
 // ---------- 子模块 ----------
 
+mod benchmark;
 mod capture;
 mod finalize;
 mod interim;
+mod loudness;
 mod monitor;
+mod ogg_opus;
+mod output_template;
+mod post_hook;
 mod resample;
+mod text_cleanup;
 mod wav;
 
 // ---------- 外部 API 再导出 ----------
@@ -98,10 +216,30 @@ mod wav;
 // 保持外部引用点零改动：
 // `use crate::services::audio_service::X` 在拆分前后语义相同。
 
-pub use capture::{list_input_devices_sync, spawn_audio_capture_thread, spawn_waveform_emitter};
-pub use finalize::{discard_recording, finalize_recording};
+pub use benchmark::{benchmark_audio_pipeline_sync, synthetic_sample_wav, AudioPipelineBenchmark};
+pub use capture::{
+    clear_negotiated_input_config, default_audio_host_name, find_audio_host,
+    get_device_capabilities_sync, get_microphone_info_sync, list_audio_hosts,
+    list_input_devices_sync, set_audio_host_preference, spawn_audio_capture_thread,
+    spawn_audio_level_emitter, spawn_waveform_emitter, spawn_waveform_stream_emitter,
+    AudioLevelMeter, CaptureHooks, DeviceLostCallback, DeviceSampleRates,
+};
+pub use finalize::{
+    confirm_paste_review, discard_paste_review, discard_recording, finalize_recording,
+    flush_pending_paste, force_teardown_recording, preserve_pending_paste_on_exit, repaste_last,
+    repaste_text, restore_pending_paste_recovery, transcribe_audio_file,
+};
 pub use interim::spawn_interim_loop;
+pub use loudness::{
+    DEFAULT_LOUDNESS_TARGET_LUFS, MAX_LOUDNESS_TARGET_LUFS, MIN_LOUDNESS_TARGET_LUFS,
+};
 pub use monitor::{
-    start_microphone_level_monitor, stop_microphone_level_monitor, test_microphone_sync,
+    measure_noise_floor_sync, prewarm_audio_sync, start_microphone_level_monitor,
+    stop_microphone_level_monitor, test_microphone_sync, NoiseFloorReport,
 };
+pub use ogg_opus::{decode_ogg_opus, encode_ogg_opus, is_ogg_stream};
+pub use output_template::{validate_output_template, DEFAULT_OUTPUT_TEMPLATE};
+pub use post_hook::{validate_post_hook, DEFAULT_POST_HOOK_TIMEOUT_MS};
+pub use resample::resample_to_16k;
+pub use text_cleanup::{normalize_strip_prefixes, MAX_STRIP_PREFIXES, MAX_STRIP_PREFIX_CHARS};
 pub use wav::encode_wav;
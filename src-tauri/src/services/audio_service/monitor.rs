@@ -5,9 +5,14 @@ use std::sync::{
 
 use tauri::Emitter;
 
-use super::capture::{load_best_input_config, resolve_input_device};
+use super::capture::{
+    load_best_input_config, open_negotiated_input_stream, resolve_input_device,
+    spawn_audio_capture_thread, CaptureHooks, DeviceSampleRates,
+};
 use super::resample::{f32_to_i16, u16_to_i16};
+use super::AudioPrewarmReport;
 use super::MICROPHONE_LEVEL_EMIT_INTERVAL_MS;
+use crate::state::user_profile::InputSampleFormat;
 use crate::state::{AppState, MicrophoneLevelMonitor};
 use crate::utils::AppError;
 
@@ -84,7 +89,13 @@ pub fn start_microphone_level_monitor(
 
     let (device, device_name) =
         resolve_input_device(state.selected_input_device_name().as_deref())?;
-    let config = load_best_input_config(&device)?;
+    let (preferred_rate, preferred_format) = state.with_profile(|p| {
+        (
+            p.device_sample_rates.get(&device_name).copied(),
+            p.preferred_sample_format,
+        )
+    });
+    let config = load_best_input_config(&device, preferred_rate, preferred_format)?;
     let fmt = config.sample_format();
     let ch = config.channels() as usize;
 
@@ -176,11 +187,19 @@ pub fn start_microphone_level_monitor(
     Ok(device_name)
 }
 
-pub fn test_microphone_sync(selected_device_name: Option<String>) -> Result<String, AppError> {
+pub fn test_microphone_sync(
+    selected_device_name: Option<String>,
+    device_sample_rates: DeviceSampleRates,
+    preferred_format: Option<InputSampleFormat>,
+) -> Result<String, AppError> {
     use cpal::traits::StreamTrait;
 
     let (device, device_name) = resolve_input_device(selected_device_name.as_deref())?;
-    let config = load_best_input_config(&device)?;
+    let config = load_best_input_config(
+        &device,
+        device_sample_rates.get(&device_name).copied(),
+        preferred_format,
+    )?;
     let received = Arc::new(AtomicBool::new(false));
     let fmt = config.sample_format();
 
@@ -218,3 +237,211 @@ pub fn test_microphone_sync(selected_device_name: Option<String>) -> Result<Stri
         Ok(format!("麦克风已连接但未检测到音频数据 ({})", device_name))
     }
 }
+
+/// 预热录音链路：枚举设备、协商配置并建一条输入流后立即关掉，让系统音频
+/// 服务和驱动提前完成初始化，协商结果留给第一次录音复用。会短暂打开麦克风。
+pub fn prewarm_audio_sync(
+    selected_device_name: Option<String>,
+    device_sample_rates: DeviceSampleRates,
+    preferred_format: Option<InputSampleFormat>,
+) -> Result<AudioPrewarmReport, AppError> {
+    use cpal::traits::StreamTrait;
+
+    let started = std::time::Instant::now();
+    let (device, device_name) = resolve_input_device(selected_device_name.as_deref())?;
+    let (config, stream) = open_negotiated_input_stream(
+        &device,
+        &device_name,
+        device_sample_rates.get(&device_name).copied(),
+        preferred_format,
+        |config| {
+            let err_cb = |e: cpal::StreamError| log::warn!("音频预热流错误: {}", e);
+            let stream = build_input_stream_dispatch!(
+                device,
+                config.clone(),
+                config.sample_format(),
+                err_cb,
+                |_: &[i16], _: &cpal::InputCallbackInfo| {},
+                |_: &[f32], _: &cpal::InputCallbackInfo| {},
+                |_: &[u16], _: &cpal::InputCallbackInfo| {}
+            )
+            .map_err(|e| format!("创建音频流失败: {}", e))?;
+            stream
+                .play()
+                .map_err(|e| format!("启动音频流失败: {}", e))?;
+            Ok(stream)
+        },
+    )?;
+    drop(stream);
+    let sample_rate = config.sample_rate().0;
+    let channels = config.channels();
+    let fmt = config.sample_format();
+
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    log::info!(
+        "录音链路预热完成 ({}，{:?} {}ch {}Hz)，耗时 {}ms",
+        device_name,
+        fmt,
+        channels,
+        sample_rate,
+        elapsed_ms
+    );
+    Ok(AudioPrewarmReport {
+        device_name,
+        sample_format: format!("{:?}", fmt),
+        channels,
+        sample_rate,
+        elapsed_ms,
+    })
+}
+
+// ---------- 环境底噪测量 ----------
+//
+// 用录音同一条采集线程录几秒环境声（期间不说话），按 50ms 块统计 RMS，
+// 取 95 分位作为底噪，再留出余量给出静音/VAD 阈值建议。
+
+pub const MIN_NOISE_FLOOR_SECS: f64 = 1.0;
+pub const MAX_NOISE_FLOOR_SECS: f64 = 10.0;
+const NOISE_BLOCK_SEC: f64 = 0.05;
+/// 建议阈值高出底噪的余量
+const NOISE_THRESHOLD_MARGIN_DB: f64 = 6.0;
+const NOISE_THRESHOLD_MIN_DBFS: f64 = -70.0;
+const NOISE_THRESHOLD_MAX_DBFS: f64 = -20.0;
+/// 丢掉开头这段，避开设备刚打开时的爆音/AGC 收敛
+const NOISE_WARMUP_SEC: f64 = 0.2;
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoiseFloorReport {
+    pub device_name: String,
+    pub duration_secs: f64,
+    pub sample_rate: u32,
+    pub rms_dbfs: f64,
+    pub peak_dbfs: f64,
+    /// 50ms 块 RMS 的 95 分位
+    pub noise_floor_dbfs: f64,
+    pub recommended_threshold_dbfs: f64,
+    /// 建议阈值换算成 16-bit 振幅
+    pub recommended_threshold_amplitude: u16,
+}
+
+fn amplitude_to_dbfs(amplitude: f64) -> f64 {
+    20.0 * (amplitude / i16::MAX as f64).max(1e-6).log10()
+}
+
+fn block_rms(block: &[i16]) -> f64 {
+    let sum: f64 = block.iter().map(|&s| (s as f64) * (s as f64)).sum();
+    (sum / block.len().max(1) as f64).sqrt()
+}
+
+/// 由录到的环境声计算 (rms, peak, 95 分位块 rms, 建议阈值)，单位 dBFS。
+fn noise_floor_stats(samples: &[i16], sample_rate: u32) -> Option<(f64, f64, f64, f64)> {
+    let skip = ((sample_rate as f64 * NOISE_WARMUP_SEC) as usize).min(samples.len());
+    let samples = &samples[skip..];
+    if samples.is_empty() || sample_rate == 0 {
+        return None;
+    }
+    let block_len = ((sample_rate as f64 * NOISE_BLOCK_SEC) as usize).max(1);
+    let mut blocks: Vec<f64> = samples.chunks(block_len).map(block_rms).collect();
+    blocks.sort_by(|a, b| a.total_cmp(b));
+    let p95 = blocks[((blocks.len() - 1) as f64 * 0.95).round() as usize];
+    let peak = samples
+        .iter()
+        .map(|&s| (s as i32).unsigned_abs())
+        .max()
+        .unwrap_or(0) as f64;
+    let noise_floor = amplitude_to_dbfs(p95);
+    let recommended = (noise_floor + NOISE_THRESHOLD_MARGIN_DB)
+        .clamp(NOISE_THRESHOLD_MIN_DBFS, NOISE_THRESHOLD_MAX_DBFS);
+    Some((
+        amplitude_to_dbfs(block_rms(samples)),
+        amplitude_to_dbfs(peak),
+        noise_floor,
+        recommended,
+    ))
+}
+
+pub fn measure_noise_floor_sync(
+    selected_device_name: Option<String>,
+    device_sample_rates: DeviceSampleRates,
+    preferred_format: Option<InputSampleFormat>,
+    secs: f64,
+) -> Result<NoiseFloorReport, AppError> {
+    if !secs.is_finite() || !(MIN_NOISE_FLOOR_SECS..=MAX_NOISE_FLOOR_SECS).contains(&secs) {
+        return Err(AppError::Audio(format!(
+            "底噪测量时长需在 {}-{} 秒之间",
+            MIN_NOISE_FLOOR_SECS, MAX_NOISE_FLOOR_SECS
+        )));
+    }
+    let (_, device_name) = resolve_input_device(selected_device_name.as_deref())?;
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let samples = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let (handle, sample_rate, _) = spawn_audio_capture_thread(
+        stop_flag.clone(),
+        Arc::new(AtomicBool::new(false)),
+        samples.clone(),
+        selected_device_name,
+        device_sample_rates,
+        preferred_format,
+        CaptureHooks::default(),
+    )?;
+    std::thread::sleep(std::time::Duration::from_secs_f64(secs));
+    stop_flag.store(true, Ordering::Release);
+    let _ = handle.join();
+
+    let samples = std::mem::take(&mut *samples.lock());
+    let (rms_dbfs, peak_dbfs, noise_floor_dbfs, recommended_threshold_dbfs) =
+        noise_floor_stats(&samples, sample_rate)
+            .ok_or_else(|| AppError::Audio("未录到任何音频，无法测量底噪".into()))?;
+    let report = NoiseFloorReport {
+        device_name,
+        duration_secs: samples.len() as f64 / sample_rate.max(1) as f64,
+        sample_rate,
+        rms_dbfs,
+        peak_dbfs,
+        noise_floor_dbfs,
+        recommended_threshold_dbfs,
+        recommended_threshold_amplitude: (10f64.powf(recommended_threshold_dbfs / 20.0)
+            * i16::MAX as f64)
+            .round() as u16,
+    };
+    log::info!(
+        "底噪测量 ({}): RMS {:.1}dBFS，峰值 {:.1}dBFS，底噪 {:.1}dBFS，建议阈值 {:.1}dBFS",
+        report.device_name,
+        report.rms_dbfs,
+        report.peak_dbfs,
+        report.noise_floor_dbfs,
+        report.recommended_threshold_dbfs
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod noise_floor_tests {
+    use super::noise_floor_stats;
+
+    #[test]
+    fn quiet_room_gets_threshold_above_its_floor() {
+        let samples: Vec<i16> = (0..32000)
+            .map(|i| if i % 2 == 0 { 30 } else { -30 })
+            .collect();
+        let (rms, peak, floor, recommended) = noise_floor_stats(&samples, 16000).unwrap();
+        assert!((rms - floor).abs() < 0.5);
+        assert!((peak - floor).abs() < 0.5);
+        assert!((recommended - (floor + 6.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn threshold_is_clamped_and_silence_is_handled() {
+        let digital_silence = vec![0i16; 16000];
+        let (_, _, _, recommended) = noise_floor_stats(&digital_silence, 16000).unwrap();
+        assert_eq!(recommended, -70.0);
+
+        let loud = vec![20000i16; 16000];
+        let (_, _, _, recommended) = noise_floor_stats(&loud, 16000).unwrap();
+        assert_eq!(recommended, -20.0);
+
+        // 只有预热段的数据不足以测量
+        assert!(noise_floor_stats(&[0i16; 100], 16000).is_none());
+    }
+}
@@ -0,0 +1,230 @@
+use std::borrow::Cow;
+
+use super::resample::ChunkedResampler;
+use super::TARGET_SAMPLE_RATE;
+use crate::utils::AppError;
+
+// ---------- Ogg/Opus 编解码（历史音频压缩存储） ----------
+//
+// 转写链路始终使用内存里的 16-bit PCM；这里只负责把历史录音压成 Ogg/Opus
+// 落盘，以及在 "重新识别" 时解回 PCM。统一按 16kHz 单声道编码，
+// 语音场景下 24kbps 已足够清晰，体积约为同时长 WAV 的 1/10。
+
+/// 20ms 帧，16kHz 下为 320 个采样点
+const OPUS_FRAME_SAMPLES: usize = (TARGET_SAMPLE_RATE as usize) / 50;
+const OPUS_BITRATE_BPS: i32 = 24_000;
+/// libopus 推荐的单包上限
+const OPUS_MAX_PACKET_BYTES: usize = 4000;
+/// 单帧最长 120ms，按 16kHz 输出
+const OPUS_MAX_FRAME_SAMPLES: usize = (TARGET_SAMPLE_RATE as usize) * 120 / 1000;
+/// Ogg/Opus 的 granule position 永远按 48kHz 计数
+const OPUS_GRANULE_RATE: u64 = 48_000;
+const OPUS_GRANULE_PER_SAMPLE: u64 = OPUS_GRANULE_RATE / TARGET_SAMPLE_RATE as u64;
+const OGG_STREAM_SERIAL: u32 = 0x4C57_4F50; // "LWOP"
+
+pub fn is_ogg_stream(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"OggS")
+}
+
+fn to_16k(samples: &[i16], sample_rate: u32) -> Result<Cow<'_, [i16]>, AppError> {
+    if sample_rate == TARGET_SAMPLE_RATE {
+        return Ok(Cow::Borrowed(samples));
+    }
+    let mut resampler = ChunkedResampler::new(sample_rate).map_err(AppError::Audio)?;
+    let mut output = Vec::with_capacity(
+        ((samples.len() as f64 * TARGET_SAMPLE_RATE as f64 / sample_rate as f64).ceil() as usize)
+            + 8,
+    );
+    resampler
+        .process_chunk(samples, &mut output)
+        .and_then(|_| resampler.finish(&mut output))
+        .map_err(AppError::Audio)?;
+    Ok(Cow::Owned(output))
+}
+
+fn opus_head(pre_skip: u16) -> Vec<u8> {
+    let mut head = Vec::with_capacity(19);
+    head.extend_from_slice(b"OpusHead");
+    head.push(1); // version
+    head.push(1); // channel count
+    head.extend_from_slice(&pre_skip.to_le_bytes());
+    head.extend_from_slice(&TARGET_SAMPLE_RATE.to_le_bytes());
+    head.extend_from_slice(&0i16.to_le_bytes()); // output gain
+    head.push(0); // channel mapping family
+    head
+}
+
+fn opus_tags() -> Vec<u8> {
+    let vendor = b"light-whisper";
+    let mut tags = Vec::with_capacity(16 + vendor.len());
+    tags.extend_from_slice(b"OpusTags");
+    tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+    tags.extend_from_slice(vendor);
+    tags.extend_from_slice(&0u32.to_le_bytes()); // user comment count
+    tags
+}
+
+pub fn encode_ogg_opus(samples: &[i16], sample_rate: u32) -> Result<Vec<u8>, AppError> {
+    let pcm = to_16k(samples, sample_rate)?;
+
+    let mut encoder = opus::Encoder::new(
+        TARGET_SAMPLE_RATE,
+        opus::Channels::Mono,
+        opus::Application::Voip,
+    )
+    .map_err(|e| AppError::Audio(format!("Opus 编码器初始化失败: {}", e)))?;
+    encoder
+        .set_bitrate(opus::Bitrate::Bits(OPUS_BITRATE_BPS))
+        .map_err(|e| AppError::Audio(format!("Opus 码率设置失败: {}", e)))?;
+    // lookahead 以编码采样率计，pre-skip 以 48kHz 计
+    let lookahead = encoder
+        .get_lookahead()
+        .map_err(|e| AppError::Audio(format!("Opus lookahead 查询失败: {}", e)))?
+        .max(0) as u64;
+    let pre_skip = (lookahead * OPUS_GRANULE_PER_SAMPLE).min(u16::MAX as u64) as u16;
+
+    let mut cursor = std::io::Cursor::new(Vec::with_capacity(pcm.len() / 8 + 256));
+    {
+        let mut writer = ogg::writing::PacketWriter::new(&mut cursor);
+        let io_err = |e: std::io::Error| AppError::Audio(format!("Ogg 写入失败: {}", e));
+        writer
+            .write_packet(
+                opus_head(pre_skip),
+                OGG_STREAM_SERIAL,
+                ogg::writing::PacketWriteEndInfo::EndPage,
+                0,
+            )
+            .map_err(io_err)?;
+        writer
+            .write_packet(
+                opus_tags(),
+                OGG_STREAM_SERIAL,
+                ogg::writing::PacketWriteEndInfo::EndPage,
+                0,
+            )
+            .map_err(io_err)?;
+
+        let total_granule = pre_skip as u64 + pcm.len() as u64 * OPUS_GRANULE_PER_SAMPLE;
+        // 多编一点覆盖编码器 lookahead，解码端跳过 pre-skip 后才不会短一截
+        let frame_count = (pcm.len() + lookahead as usize)
+            .div_ceil(OPUS_FRAME_SAMPLES)
+            .max(1);
+        let mut frame = [0i16; OPUS_FRAME_SAMPLES];
+        let mut packet = vec![0u8; OPUS_MAX_PACKET_BYTES];
+        for index in 0..frame_count {
+            let start = index * OPUS_FRAME_SAMPLES;
+            let end = (start + OPUS_FRAME_SAMPLES).min(pcm.len());
+            frame.fill(0);
+            if start < end {
+                frame[..end - start].copy_from_slice(&pcm[start..end]);
+            }
+            let len = encoder
+                .encode(&frame, &mut packet)
+                .map_err(|e| AppError::Audio(format!("Opus 编码失败: {}", e)))?;
+
+            let is_last = index + 1 == frame_count;
+            let granule = if is_last {
+                // 末帧补零部分靠 granule position 截掉
+                total_granule
+            } else {
+                (pre_skip as u64
+                    + ((index + 1) * OPUS_FRAME_SAMPLES) as u64 * OPUS_GRANULE_PER_SAMPLE)
+                    .min(total_granule)
+            };
+            let end_info = if is_last {
+                ogg::writing::PacketWriteEndInfo::EndStream
+            } else {
+                ogg::writing::PacketWriteEndInfo::NormalPacket
+            };
+            writer
+                .write_packet(packet[..len].to_vec(), OGG_STREAM_SERIAL, end_info, granule)
+                .map_err(io_err)?;
+        }
+    }
+    Ok(cursor.into_inner())
+}
+
+/// 解码 `encode_ogg_opus` 写出的单声道 Ogg/Opus，返回 16kHz PCM。
+pub fn decode_ogg_opus(bytes: &[u8]) -> Result<Vec<i16>, AppError> {
+    if !is_ogg_stream(bytes) {
+        return Err(AppError::Audio("不是有效的 Ogg 音频".to_string()));
+    }
+    let mut reader = ogg::reading::PacketReader::new(std::io::Cursor::new(bytes));
+    let read_err = |e: ogg::OggReadError| AppError::Audio(format!("Ogg 读取失败: {}", e));
+
+    let head = reader
+        .read_packet()
+        .map_err(read_err)?
+        .ok_or_else(|| AppError::Audio("Ogg 音频缺少 OpusHead".to_string()))?;
+    if head.data.len() < 19 || !head.data.starts_with(b"OpusHead") {
+        return Err(AppError::Audio("Ogg 音频不是 Opus 编码".to_string()));
+    }
+    if head.data[9] != 1 {
+        return Err(AppError::Audio("仅支持单声道 Opus 音频".to_string()));
+    }
+    let pre_skip = u16::from_le_bytes([head.data[10], head.data[11]]) as u64;
+
+    let mut decoder = opus::Decoder::new(TARGET_SAMPLE_RATE, opus::Channels::Mono)
+        .map_err(|e| AppError::Audio(format!("Opus 解码器初始化失败: {}", e)))?;
+    let mut pcm = Vec::new();
+    let mut frame = vec![0i16; OPUS_MAX_FRAME_SAMPLES];
+    let mut last_granule = None;
+    let mut header_packets = 1;
+    while let Some(packet) = reader.read_packet().map_err(read_err)? {
+        if header_packets < 2 {
+            // 第二个包是 OpusTags
+            header_packets += 1;
+            continue;
+        }
+        let decoded = decoder
+            .decode(&packet.data, &mut frame, false)
+            .map_err(|e| AppError::Audio(format!("Opus 解码失败: {}", e)))?;
+        pcm.extend_from_slice(&frame[..decoded]);
+        last_granule = Some(packet.absgp_page());
+    }
+
+    let skip = (pre_skip / OPUS_GRANULE_PER_SAMPLE) as usize;
+    let mut pcm = pcm.split_off(skip.min(pcm.len()));
+    if let Some(granule) = last_granule {
+        let expected = (granule.saturating_sub(pre_skip) / OPUS_GRANULE_PER_SAMPLE) as usize;
+        pcm.truncate(expected);
+    }
+    Ok(pcm)
+}
+
+// ---------- 单元测试 ----------
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_ogg_opus, encode_ogg_opus, is_ogg_stream};
+
+    fn tone(len: usize) -> Vec<i16> {
+        (0..len)
+            .map(|i| ((i as f32 * 0.05).sin() * 8000.0) as i16)
+            .collect()
+    }
+
+    #[test]
+    fn round_trip_preserves_length_at_16k() {
+        let samples = tone(16000 + 123);
+        let encoded = encode_ogg_opus(&samples, 16000).expect("encode");
+        assert!(is_ogg_stream(&encoded));
+        assert!(encoded.len() < samples.len() * 2);
+        let decoded = decode_ogg_opus(&encoded).expect("decode");
+        assert_eq!(decoded.len(), samples.len());
+    }
+
+    #[test]
+    fn non_16k_input_is_resampled_before_encoding() {
+        let samples = tone(48000);
+        let encoded = encode_ogg_opus(&samples, 48000).expect("encode");
+        let decoded = decode_ogg_opus(&encoded).expect("decode");
+        assert!((decoded.len() as i64 - 16000).abs() < 64);
+    }
+
+    #[test]
+    fn wav_bytes_are_not_mistaken_for_ogg() {
+        assert!(!is_ogg_stream(b"RIFF\0\0\0\0WAVE"));
+        assert!(decode_ogg_opus(b"RIFF\0\0\0\0WAVE").is_err());
+    }
+}
@@ -0,0 +1,175 @@
+use chrono::{DateTime, Local};
+
+// ---------- 输出文本模板（粘贴前套用） ----------
+//
+// 支持的占位符：{text} 转写结果、{time} 本地时间 HH:MM:SS、{date} 本地日期
+// YYYY-MM-DD、{duration} 录音时长（秒，保留一位小数）。`{{` / `}}` 输出字面
+// 花括号；未知占位符和不成对的花括号原样保留。
+
+pub const DEFAULT_OUTPUT_TEMPLATE: &str = "{text}";
+pub const MAX_OUTPUT_TEMPLATE_CHARS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Placeholder {
+    Text,
+    Time,
+    Date,
+    Duration,
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment<'a> {
+    Literal(&'a str),
+    Placeholder(Placeholder),
+}
+
+fn placeholder_by_name(name: &str) -> Option<Placeholder> {
+    match name {
+        "text" => Some(Placeholder::Text),
+        "time" => Some(Placeholder::Time),
+        "date" => Some(Placeholder::Date),
+        "duration" => Some(Placeholder::Duration),
+        _ => None,
+    }
+}
+
+fn parse_template(template: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+    while let Some(pos) = rest.find(['{', '}']) {
+        if pos > 0 {
+            segments.push(Segment::Literal(&rest[..pos]));
+        }
+        let tail = &rest[pos..];
+        if tail.starts_with("{{") {
+            segments.push(Segment::Literal("{"));
+            rest = &tail[2..];
+        } else if tail.starts_with("}}") {
+            segments.push(Segment::Literal("}"));
+            rest = &tail[2..];
+        } else if tail.starts_with('}') {
+            segments.push(Segment::Literal("}"));
+            rest = &tail[1..];
+        } else {
+            // 以 '{' 开头：只有闭合且名字已知时才算占位符
+            let placeholder = tail[1..]
+                .find('}')
+                .and_then(|end| placeholder_by_name(&tail[1..1 + end]).map(|p| (p, end + 2)));
+            match placeholder {
+                Some((placeholder, consumed)) => {
+                    segments.push(Segment::Placeholder(placeholder));
+                    rest = &tail[consumed..];
+                }
+                None => {
+                    segments.push(Segment::Literal("{"));
+                    rest = &tail[1..];
+                }
+            }
+        }
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Literal(rest));
+    }
+    segments
+}
+
+/// 校验模板：必须包含 `{text}`，否则转写结果会被整个吞掉。
+pub fn validate_output_template(template: &str) -> Result<(), String> {
+    if template.chars().count() > MAX_OUTPUT_TEMPLATE_CHARS {
+        return Err(format!(
+            "输出模板不能超过 {} 个字符",
+            MAX_OUTPUT_TEMPLATE_CHARS
+        ));
+    }
+    let has_text = parse_template(template)
+        .iter()
+        .any(|segment| *segment == Segment::Placeholder(Placeholder::Text));
+    if !has_text {
+        return Err("输出模板必须包含 {text} 占位符".to_string());
+    }
+    Ok(())
+}
+
+/// 按模板渲染最终要粘贴的文本。模板缺少 `{text}`（例如手改配置文件）时
+/// 直接返回原文，宁可不套模板也不丢内容。
+pub fn render_output_template(
+    template: &str,
+    text: &str,
+    duration_sec: f64,
+    now: DateTime<Local>,
+) -> String {
+    let segments = parse_template(template);
+    if !segments.contains(&Segment::Placeholder(Placeholder::Text)) {
+        log::warn!("输出模板缺少 {{text}} 占位符，按原文粘贴");
+        return text.to_string();
+    }
+    let mut output = String::with_capacity(template.len() + text.len());
+    for segment in segments {
+        match segment {
+            Segment::Literal(literal) => output.push_str(literal),
+            Segment::Placeholder(Placeholder::Text) => output.push_str(text),
+            Segment::Placeholder(Placeholder::Time) => {
+                output.push_str(&now.format("%H:%M:%S").to_string())
+            }
+            Segment::Placeholder(Placeholder::Date) => {
+                output.push_str(&now.format("%Y-%m-%d").to_string())
+            }
+            Segment::Placeholder(Placeholder::Duration) => {
+                output.push_str(&format!("{:.1}", duration_sec.max(0.0)))
+            }
+        }
+    }
+    output
+}
+
+/// `None` 或默认模板时直接返回原文。
+pub fn apply_output_template(template: Option<&str>, text: &str, duration_sec: f64) -> String {
+    match template {
+        Some(template) if template != DEFAULT_OUTPUT_TEMPLATE => {
+            render_output_template(template, text, duration_sec, Local::now())
+        }
+        _ => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_output_template, render_output_template, validate_output_template};
+    use chrono::{Local, TimeZone};
+
+    fn fixed_now() -> chrono::DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 3, 7, 9, 5, 2).unwrap()
+    }
+
+    #[test]
+    fn substitutes_all_placeholders() {
+        let rendered = render_output_template(
+            "[{date} {time}] {text} ({duration}s)",
+            "你好",
+            3.24,
+            fixed_now(),
+        );
+        assert_eq!(rendered, "[2026-03-07 09:05:02] 你好 (3.2s)");
+        assert_eq!(apply_output_template(None, "你好", 1.0), "你好");
+        assert_eq!(apply_output_template(Some("{text}"), "你好", 1.0), "你好");
+    }
+
+    #[test]
+    fn doubled_braces_are_literal_and_unknown_placeholders_are_kept() {
+        let rendered = render_output_template("{{text}} {text} {foo} {", "hi", 0.0, fixed_now());
+        assert_eq!(rendered, "{text} hi {foo} {");
+        let rendered = render_output_template("}}{text}}", "hi", 0.0, fixed_now());
+        assert_eq!(rendered, "}hi}");
+    }
+
+    #[test]
+    fn template_without_text_placeholder_falls_back_to_plain_text() {
+        assert!(validate_output_template("{date} {time}").is_err());
+        assert!(validate_output_template("{{text}}").is_err());
+        assert!(validate_output_template("> {text}\n").is_ok());
+        assert_eq!(
+            render_output_template("{date} {time}", "内容", 2.0, fixed_now()),
+            "内容"
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::io::AsyncWriteExt;
+
+use crate::state::user_profile::{PostHookInput, PostHookSettings};
+
+// ---------- 听写后置钩子 ----------
+//
+// 听写成功后把文本交给用户配置的外部程序（翻译、TTS、日志等）。程序直接
+// 启动、不经过 shell；超时或失败只记日志，粘贴照常进行。`replace_output`
+// 打开时等待程序退出并用 stdout 替换粘贴内容，否则在后台运行、不等待。
+
+pub const DEFAULT_POST_HOOK_TIMEOUT_MS: u64 = 5_000;
+pub const MAX_POST_HOOK_TIMEOUT_MS: u64 = 60_000;
+/// stderr 只截取这么多字符写日志
+const POST_HOOK_STDERR_LOG_CHARS: usize = 500;
+
+pub fn validate_post_hook(settings: &PostHookSettings) -> Result<(), String> {
+    if settings.program.trim().is_empty() {
+        return Err("后置钩子程序路径不能为空".to_string());
+    }
+    if !(1..=MAX_POST_HOOK_TIMEOUT_MS).contains(&settings.timeout_ms) {
+        return Err(format!(
+            "后置钩子超时必须在 1-{} 毫秒之间",
+            MAX_POST_HOOK_TIMEOUT_MS
+        ));
+    }
+    Ok(())
+}
+
+/// 钩子 stdout 转成替换文本：去掉末尾换行，空输出视为不替换。
+fn hook_output_text(stdout: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(stdout);
+    let text = text.trim_end_matches(['\r', '\n']);
+    (!text.trim().is_empty()).then(|| text.to_string())
+}
+
+async fn run_post_hook(settings: &PostHookSettings, text: &str) -> Result<Option<String>, String> {
+    let mut cmd = tokio::process::Command::new(settings.program.trim());
+    cmd.args(&settings.args);
+    if settings.input == PostHookInput::Argument {
+        cmd.arg(text);
+    }
+    cmd.stdin(if settings.input == PostHookInput::Stdin {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    })
+    .stdout(if settings.replace_output {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    })
+    .stderr(Stdio::piped())
+    .kill_on_drop(true);
+
+    #[cfg(target_os = "windows")]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("启动后置钩子失败: {}", e))?;
+    // 写 stdin 也算在超时里：程序不读 stdin 时管道写满会一直阻塞。
+    // 超时后 future 被丢弃，kill_on_drop 负责结束子进程。
+    let run = async move {
+        if let Some(mut stdin) = child.stdin.take() {
+            // 程序不读 stdin 就退出时写入会 broken pipe，不算失败
+            if let Err(err) = stdin.write_all(text.as_bytes()).await {
+                log::debug!("写入后置钩子 stdin 失败: {}", err);
+            }
+        }
+        child.wait_with_output().await
+    };
+
+    let output = tokio::time::timeout(Duration::from_millis(settings.timeout_ms), run)
+        .await
+        .map_err(|_| format!("后置钩子超过 {}ms 未退出，已终止", settings.timeout_ms))?
+        .map_err(|e| format!("等待后置钩子失败: {}", e))?;
+
+    if !output.status.success() {
+        let stderr: String = String::from_utf8_lossy(&output.stderr)
+            .chars()
+            .take(POST_HOOK_STDERR_LOG_CHARS)
+            .collect();
+        return Err(format!(
+            "后置钩子退出码异常 ({}): {}",
+            output.status,
+            stderr.trim()
+        ));
+    }
+    Ok(settings
+        .replace_output
+        .then(|| hook_output_text(&output.stdout))
+        .flatten())
+}
+
+/// 后台执行钩子，不等待结果（raw-first 已粘贴或不替换输出时）。
+pub(super) fn spawn_post_hook(settings: PostHookSettings, text: String) {
+    tokio::spawn(async move {
+        if let Err(err) = run_post_hook(&settings, &text).await {
+            log::warn!("{}", err);
+        }
+    });
+}
+
+/// 返回最终要粘贴的文本：未配置钩子或钩子失败时原样返回。
+pub(super) async fn apply_post_hook(settings: Option<PostHookSettings>, text: String) -> String {
+    let Some(settings) = settings else {
+        return text;
+    };
+    if !settings.replace_output {
+        spawn_post_hook(settings, text.clone());
+        return text;
+    }
+    match run_post_hook(&settings, &text).await {
+        Ok(Some(replaced)) => replaced,
+        Ok(None) => {
+            log::warn!("后置钩子没有输出，按原文粘贴");
+            text
+        }
+        Err(err) => {
+            log::warn!("{}，按原文粘贴", err);
+            text
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hook_output_text, validate_post_hook, DEFAULT_POST_HOOK_TIMEOUT_MS};
+    use crate::state::user_profile::{PostHookInput, PostHookSettings};
+
+    fn settings(program: &str, timeout_ms: u64) -> PostHookSettings {
+        PostHookSettings {
+            program: program.to_string(),
+            args: Vec::new(),
+            input: PostHookInput::Stdin,
+            replace_output: true,
+            timeout_ms,
+        }
+    }
+
+    #[test]
+    fn output_trims_trailing_newlines_and_ignores_blank() {
+        assert_eq!(hook_output_text(b"hello\r\n").as_deref(), Some("hello"));
+        assert_eq!(hook_output_text(b"  a b\n\n").as_deref(), Some("  a b"));
+        assert_eq!(hook_output_text(b" \n"), None);
+    }
+
+    #[test]
+    fn validation_rejects_empty_program_and_bad_timeout() {
+        assert!(validate_post_hook(&settings("translate", DEFAULT_POST_HOOK_TIMEOUT_MS)).is_ok());
+        assert!(validate_post_hook(&settings("  ", DEFAULT_POST_HOOK_TIMEOUT_MS)).is_err());
+        assert!(validate_post_hook(&settings("translate", 0)).is_err());
+        assert!(validate_post_hook(&settings("translate", 600_000)).is_err());
+    }
+}
@@ -158,8 +158,7 @@ impl ResamplerState {
     }
 }
 
-#[allow(dead_code)]
-pub(super) fn resample_to_16k(input: &[i16], input_rate: u32) -> Result<Cow<'_, [i16]>, String> {
+pub fn resample_to_16k(input: &[i16], input_rate: u32) -> Result<Cow<'_, [i16]>, String> {
     if input.is_empty() || input_rate == TARGET_SAMPLE_RATE {
         return Ok(Cow::Borrowed(input));
     }
@@ -0,0 +1,267 @@
+use crate::state::user_profile::{OutputCase, OutputCleanupSettings, UserProfile};
+
+// ---------- 识别结果文本清理（中间与最终结果共用） ----------
+//
+// 不同引擎返回的首尾空白、连续空格和大小写各不相同。中间结果和最终结果都
+// 走这里，避免字幕上的中间文本和最终粘贴的文本只差几个空格。首尾空白总是
+// 去掉；换行原样保留。
+
+/// 把行内连续的空格/制表符合并成一个空格，换行不动。
+fn collapse_inline_spaces(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut previous_space = false;
+    for ch in text.chars() {
+        if ch == ' ' || ch == '\t' {
+            if !previous_space {
+                output.push(' ');
+            }
+            previous_space = true;
+        } else {
+            output.push(ch);
+            previous_space = false;
+        }
+    }
+    output
+}
+
+pub fn clean_output_text(text: &str, settings: &OutputCleanupSettings) -> String {
+    let trimmed = text.trim();
+    let collapsed = if settings.collapse_spaces {
+        collapse_inline_spaces(trimmed)
+    } else {
+        trimmed.to_string()
+    };
+    match settings.case {
+        OutputCase::Preserve => collapsed,
+        OutputCase::Lowercase => collapsed.to_lowercase(),
+        OutputCase::Uppercase => collapsed.to_uppercase(),
+    }
+}
+
+// ---------- 开头唤醒词/引导语去除 ----------
+//
+// 只看转写结果的开头：用户习惯先说的 "computer，" 一类引导语按列表去掉，
+// 忽略大小写；模糊匹配允许少量识别错字（按字符计的编辑距离）。
+
+pub const MAX_STRIP_PREFIXES: usize = 20;
+pub const MAX_STRIP_PREFIX_CHARS: usize = 40;
+/// 短于这个长度的短语只做精确匹配，否则模糊匹配太容易误删正文
+const FUZZY_MIN_PHRASE_CHARS: usize = 4;
+/// 每这么多个字符允许一个编辑
+const FUZZY_CHARS_PER_EDIT: usize = 4;
+
+/// 去掉首尾空白、空项和（忽略大小写的）重复项，超长或超出数量的丢弃。
+pub fn normalize_strip_prefixes(phrases: Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    phrases
+        .into_iter()
+        .map(|phrase| phrase.trim().to_string())
+        .filter(|phrase| {
+            !phrase.is_empty()
+                && phrase.chars().count() <= MAX_STRIP_PREFIX_CHARS
+                && seen.insert(phrase.to_lowercase())
+        })
+        .take(MAX_STRIP_PREFIXES)
+        .collect()
+}
+
+fn is_prefix_separator(ch: char) -> bool {
+    ch.is_whitespace() || ",，.。:：;；!！?？、-—~～".contains(ch)
+}
+
+fn char_edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, &ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// 引导语后面紧跟字母数字时不算命中，避免 "computer" 吃掉 "computers"。
+/// 中文没有词边界，只要前后不是连续的 ASCII 字母数字就放行。
+fn ends_at_word_boundary(chars: &[char], matched_len: usize) -> bool {
+    match (
+        matched_len.checked_sub(1).map(|i| chars[i]),
+        chars.get(matched_len),
+    ) {
+        (_, None) => true,
+        (Some(last), Some(next)) => !(last.is_ascii_alphanumeric() && next.is_ascii_alphanumeric()),
+        (None, Some(_)) => false,
+    }
+}
+
+/// 开头命中的字符数（按 `chars` 计），没有命中时返回 None。
+fn match_leading_phrase(
+    chars: &[char],
+    lowered: &[char],
+    phrase: &str,
+    fuzzy: bool,
+) -> Option<usize> {
+    let phrase: Vec<char> = phrase.to_lowercase().chars().collect();
+    if phrase.is_empty() || phrase.len() > lowered.len() + 1 {
+        return None;
+    }
+    if lowered.starts_with(&phrase) && ends_at_word_boundary(chars, phrase.len()) {
+        return Some(phrase.len());
+    }
+    if !fuzzy || phrase.len() < FUZZY_MIN_PHRASE_CHARS {
+        return None;
+    }
+    // 候选长度不超过短语本身：允许漏字和错字，不允许多字，避免吃掉 "computers" 的复数尾巴
+    let max_edits = phrase.len() / FUZZY_CHARS_PER_EDIT;
+    (phrase.len().saturating_sub(max_edits)..=phrase.len())
+        .filter(|&len| len > 0 && len <= lowered.len())
+        .filter(|&len| ends_at_word_boundary(chars, len))
+        .map(|len| (char_edit_distance(&lowered[..len], &phrase), len))
+        .filter(|&(distance, _)| distance <= max_edits)
+        .min()
+        .map(|(_, len)| len)
+}
+
+/// 识别结果的完整清理：先统一空白和大小写，再去掉开头的引导语。
+pub fn clean_transcript(text: &str, profile: &UserProfile) -> String {
+    let cleaned = clean_output_text(text, &profile.output_cleanup);
+    strip_leading_phrases(&cleaned, &profile.strip_prefix, profile.strip_prefix_fuzzy)
+}
+
+/// 去掉开头命中的第一条引导语及其后的标点空白；多条都能命中时取最长的。
+pub fn strip_leading_phrases(text: &str, phrases: &[String], fuzzy: bool) -> String {
+    if phrases.is_empty() {
+        return text.to_string();
+    }
+    let chars: Vec<char> = text.trim_start().chars().collect();
+    // 逐字符转小写，保证下标与 `chars` 对齐
+    let lowered: Vec<char> = chars
+        .iter()
+        .map(|ch| ch.to_lowercase().next().unwrap_or(*ch))
+        .collect();
+    let Some(matched_len) = phrases
+        .iter()
+        .filter_map(|phrase| match_leading_phrase(&chars, &lowered, phrase, fuzzy))
+        .max()
+    else {
+        return text.to_string();
+    };
+    let rest: String = chars[matched_len..]
+        .iter()
+        .skip_while(|ch| is_prefix_separator(**ch))
+        .collect();
+    log::debug!("已去除开头引导语（{} 个字符）", matched_len);
+    rest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        clean_output_text, normalize_strip_prefixes, strip_leading_phrases, MAX_STRIP_PREFIX_CHARS,
+    };
+    use crate::state::user_profile::{OutputCase, OutputCleanupSettings};
+
+    #[test]
+    fn trims_and_collapses_inline_spaces_but_keeps_newlines() {
+        let settings = OutputCleanupSettings::default();
+        assert_eq!(
+            clean_output_text("  Hello   world \t again \n", &settings),
+            "Hello world again"
+        );
+        assert_eq!(
+            clean_output_text("第一行  \n  第二行", &settings),
+            "第一行 \n 第二行"
+        );
+        assert_eq!(clean_output_text(" \n\t ", &settings), "");
+    }
+
+    fn phrases(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn exact_prefix_is_removed_case_insensitively() {
+        let list = phrases(&["computer", "小助手"]);
+        assert_eq!(
+            strip_leading_phrases("Computer, open the door", &list, false),
+            "open the door"
+        );
+        assert_eq!(
+            strip_leading_phrases("  小助手，帮我记一下", &list, false),
+            "帮我记一下"
+        );
+        // 只处理开头，且不能截断单词
+        assert_eq!(
+            strip_leading_phrases("computers are fast", &list, false),
+            "computers are fast"
+        );
+        assert_eq!(
+            strip_leading_phrases("my computer, hi", &list, false),
+            "my computer, hi"
+        );
+        assert_eq!(strip_leading_phrases("hello", &[], false), "hello");
+    }
+
+    #[test]
+    fn near_match_prefix_is_removed_only_when_fuzzy() {
+        let list = phrases(&["computer", "ok"]);
+        assert_eq!(
+            strip_leading_phrases("Compuder, open the door", &list, true),
+            "open the door"
+        );
+        assert_eq!(
+            strip_leading_phrases("Compuder, open the door", &list, false),
+            "Compuder, open the door"
+        );
+        assert_eq!(strip_leading_phrases("Compter: hi", &list, true), "hi");
+        assert_eq!(
+            strip_leading_phrases("computers are fast", &list, true),
+            "computers are fast"
+        );
+        assert_eq!(
+            strip_leading_phrases("Complete the form", &list, true),
+            "Complete the form"
+        );
+        // 过短的短语不做模糊匹配
+        assert_eq!(strip_leading_phrases("oh, wait", &list, true), "oh, wait");
+        assert_eq!(
+            strip_leading_phrases("banana split", &list, true),
+            "banana split"
+        );
+    }
+
+    #[test]
+    fn strip_prefix_list_is_normalized() {
+        let normalized = normalize_strip_prefixes(phrases(&[
+            " Computer ",
+            "computer",
+            "",
+            &"x".repeat(MAX_STRIP_PREFIX_CHARS + 1),
+            "嘿",
+        ]));
+        assert_eq!(normalized, phrases(&["Computer", "嘿"]));
+    }
+
+    #[test]
+    fn collapse_and_case_transforms_are_optional() {
+        let keep_spaces = OutputCleanupSettings {
+            collapse_spaces: false,
+            ..Default::default()
+        };
+        assert_eq!(clean_output_text(" a  b ", &keep_spaces), "a  b");
+
+        let lower = OutputCleanupSettings {
+            case: OutputCase::Lowercase,
+            ..Default::default()
+        };
+        assert_eq!(clean_output_text(" Hello  World ", &lower), "hello world");
+
+        let upper = OutputCleanupSettings {
+            case: OutputCase::Uppercase,
+            ..Default::default()
+        };
+        assert_eq!(clean_output_text("ok 你好", &upper), "OK 你好");
+    }
+}
@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+
+use crate::services::profile_service;
+use crate::state::user_profile::UserProfile;
+use crate::utils::paths;
+
+// ---------- 整机配置导入导出 ----------
+//
+// 把用户画像和引擎选择打成一个可移植的 JSON，用于在多台机器之间同步设置。
+// 与只导出画像的 export_user_profile 不同，这里带 schema 版本，导入前先整体
+// 校验再一次性替换，并剔除只在本机有意义的字段（外部程序路径、屏幕坐标、
+// 显存预算、开机自启动、音频后端）。
+// API Key 存在系统密钥环里，不会进入导出文件。输入设备和默认注入方式不在画像
+// 里（前端存在 localStorage，启动时下发），单独放进 `input`。
+
+pub const CONFIG_BUNDLE_KIND: &str = "light-whisper-config";
+pub const CONFIG_BUNDLE_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    pub kind: String,
+    pub schema_version: u32,
+    /// 导出时的应用版本，仅供排查
+    #[serde(default)]
+    pub app_version: String,
+    #[serde(default)]
+    pub exported_at: u64,
+    /// 识别引擎；环境变量固定了引擎或值不受支持时导入会忽略
+    #[serde(default)]
+    pub engine: Option<String>,
+    /// 旧版本导出的文件没有这一项，导入时保持本机当前值
+    #[serde(default)]
+    pub input: ConfigInputSettings,
+    pub profile: UserProfile,
+}
+
+/// 运行时的输入设置：麦克风和默认注入方式。None 表示系统默认 / 不改动。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigInputSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_method: Option<String>,
+}
+
+/// 清掉换一台机器就失效的字段。
+fn strip_machine_specific(profile: &mut UserProfile) {
+    profile.post_hook = None;
+    profile.subtitle_position = None;
+    profile.max_vram_gb = None;
+    profile.autostart_enabled = None;
+    profile.audio_host = None;
+}
+
+pub fn build_config_bundle(
+    mut profile: UserProfile,
+    engine: String,
+    input: ConfigInputSettings,
+) -> ConfigBundle {
+    strip_machine_specific(&mut profile);
+    ConfigBundle {
+        kind: CONFIG_BUNDLE_KIND.to_string(),
+        schema_version: CONFIG_BUNDLE_SCHEMA_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        engine: Some(engine),
+        input,
+        profile,
+    }
+}
+
+/// 解析并校验导出文件。旧版本 schema 在这里升级到当前版本，画像本身的
+/// 字段迁移交给 `normalize_profile`。
+pub fn parse_config_bundle(json: &str) -> Result<ConfigBundle, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| format!("配置文件不是有效的 JSON: {}", e))?;
+    if value.get("kind").and_then(|kind| kind.as_str()) != Some(CONFIG_BUNDLE_KIND) {
+        return Err("不是 Light Whisper 导出的配置文件".to_string());
+    }
+    let version = value
+        .get("schema_version")
+        .and_then(|version| version.as_u64())
+        .ok_or_else(|| "配置文件缺少 schema_version".to_string())?;
+    if version == 0 || version > CONFIG_BUNDLE_SCHEMA_VERSION as u64 {
+        return Err(format!(
+            "配置文件版本 {} 不受支持（当前支持 1-{}），请升级应用后再导入",
+            version, CONFIG_BUNDLE_SCHEMA_VERSION
+        ));
+    }
+
+    let mut bundle: ConfigBundle =
+        serde_json::from_value(value).map_err(|e| format!("解析配置文件失败: {}", e))?;
+    bundle.schema_version = CONFIG_BUNDLE_SCHEMA_VERSION;
+    bundle.engine = bundle
+        .engine
+        .filter(|engine| paths::SUPPORTED_ENGINES.contains(&engine.as_str()));
+    profile_service::normalize_profile(&mut bundle.profile);
+    Ok(bundle)
+}
+
+/// 导入时保留本机的机器相关字段，其余全部以导入文件为准。
+pub fn merge_imported_profile(current: &UserProfile, mut imported: UserProfile) -> UserProfile {
+    imported.post_hook = current.post_hook.clone();
+    imported.subtitle_position = current.subtitle_position;
+    imported.max_vram_gb = current.max_vram_gb;
+    imported.autostart_enabled = current.autostart_enabled;
+    imported.audio_host = current.audio_host.clone();
+    imported
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_config_bundle, merge_imported_profile, parse_config_bundle, ConfigInputSettings,
+        CONFIG_BUNDLE_SCHEMA_VERSION,
+    };
+    use crate::state::user_profile::{
+        PostHookInput, PostHookSettings, SubtitlePosition, UserProfile,
+    };
+
+    fn local_profile() -> UserProfile {
+        UserProfile {
+            post_hook: Some(PostHookSettings {
+                program: "/usr/local/bin/translate".to_string(),
+                args: Vec::new(),
+                input: PostHookInput::Stdin,
+                replace_output: false,
+                timeout_ms: 5000,
+            }),
+            subtitle_position: Some(SubtitlePosition { x: 10, y: 20 }),
+            cpu_threads: Some(4),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn export_strips_machine_specific_fields_and_round_trips() {
+        let input = ConfigInputSettings {
+            input_device: Some("USB Mic".to_string()),
+            input_method: Some("clipboard".to_string()),
+        };
+        let bundle = build_config_bundle(local_profile(), "whisper".to_string(), input.clone());
+        assert!(bundle.profile.post_hook.is_none());
+        assert!(bundle.profile.subtitle_position.is_none());
+
+        let json = serde_json::to_string(&bundle).unwrap();
+        let parsed = parse_config_bundle(&json).expect("parse");
+        assert_eq!(parsed.schema_version, CONFIG_BUNDLE_SCHEMA_VERSION);
+        assert_eq!(parsed.engine.as_deref(), Some("whisper"));
+        assert_eq!(parsed.profile.cpu_threads, Some(4));
+        assert_eq!(parsed.input, input);
+    }
+
+    #[test]
+    fn rejects_foreign_or_newer_files_and_drops_unknown_engine() {
+        assert!(parse_config_bundle("{}").is_err());
+        assert!(parse_config_bundle("not json").is_err());
+        let newer = serde_json::json!({
+            "kind": "light-whisper-config",
+            "schema_version": CONFIG_BUNDLE_SCHEMA_VERSION + 1,
+            "profile": {},
+        });
+        assert!(parse_config_bundle(&newer.to_string()).is_err());
+
+        let mut bundle = build_config_bundle(
+            UserProfile::default(),
+            "nope".to_string(),
+            ConfigInputSettings::default(),
+        );
+        bundle.engine = Some("nope".to_string());
+        let parsed = parse_config_bundle(&serde_json::to_string(&bundle).unwrap()).unwrap();
+        assert_eq!(parsed.engine, None);
+    }
+
+    #[test]
+    fn import_keeps_local_machine_fields() {
+        let imported = UserProfile {
+            cpu_threads: Some(8),
+            ..Default::default()
+        };
+        let merged = merge_imported_profile(&local_profile(), imported);
+        assert_eq!(merged.cpu_threads, Some(8));
+        assert!(merged.post_hook.is_some());
+        assert_eq!(
+            merged.subtitle_position,
+            Some(SubtitlePosition { x: 10, y: 20 })
+        );
+    }
+}
@@ -8,7 +8,7 @@ use crate::utils::{paths, AppError};
 use serde::de::DeserializeOwned;
 use std::process::Stdio;
 use std::sync::atomic::{AtomicU64, Ordering};
-use tauri::Emitter;
+use tauri::{Emitter, Manager};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 use tokio::sync::oneshot;
@@ -84,69 +84,179 @@ fn emit_download_status(app_handle: &tauri::AppHandle, payload: serde_json::Valu
     let _ = app_handle.emit("model-download-status", payload);
 }
 
-/// 执行模型下载
-///
-/// 启动 Python 下载脚本，逐行读取进度并通过 Tauri 事件转发给前端。
-/// 支持通过 cancel channel 取消下载。
-pub async fn run_download(
-    app_handle: &tauri::AppHandle,
-    state: &AppState,
-) -> Result<String, AppError> {
-    // 获取下载脚本路径，清理 Windows \\?\ 前缀
-    let download_script = paths::get_download_script_path(app_handle);
-    let download_script_str = paths::strip_win_prefix(&download_script);
+/// 下载完成后发出 `models-ready`，并在引擎既未就绪也不在启动中时自动启动。
+/// 启动期间若模型还不存在，后台自启会失败且不会重试，这里补上这一步。
+/// `start_server` 自身也会跳过已在运行/启动中的情况，不会重复拉起进程。
+fn start_engine_after_download(app_handle: &tauri::AppHandle) {
+    let engine = paths::read_engine_config();
+    let _ = app_handle.emit("models-ready", serde_json::json!({ "engine": &engine }));
+    log::info!("模型下载完成，自动启动语音识别引擎 (engine={})", engine);
+    spawn_engine_start(app_handle);
+}
 
-    let data_dir = paths::strip_win_prefix(paths::get_data_dir());
+/// 下载前为释放模型文件而停掉了引擎，但下载没有完成时，把引擎拉回来。
+fn restore_stopped_engine(app_handle: &tauri::AppHandle, stopped_engine: bool) {
+    if stopped_engine {
+        log::info!("下载未完成，恢复下载前停止的引擎");
+        spawn_engine_start(app_handle);
+    }
+}
 
-    let (cancel_tx, mut cancel_rx) = oneshot::channel();
-    let task_id = NEXT_DOWNLOAD_TASK_ID.fetch_add(1, Ordering::Relaxed);
+fn spawn_engine_start(app_handle: &tauri::AppHandle) {
+    let state = app_handle.state::<AppState>();
+    if paths::is_online_engine(&paths::read_engine_config())
+        || state.is_funasr_ready()
+        || state.engine.is_funasr_starting()
     {
-        // 与模型目录切换串行登记。登记完成后 set_models_dir 会看到 active slot
-        // 并拒绝迁移，直到下载子进程真正退出并清理自己的 task ID。
-        let _lifecycle_guard = state.engine.funasr_lifecycle_op.lock().await;
-        let mut guard = state.engine.download_task.lock().await;
-        if guard.is_some() {
-            return Err(AppError::Download(
-                "已有下载任务正在进行，请先取消或等待完成".to_string(),
-            ));
-        }
-        *guard = Some(crate::state::DownloadTask {
-            id: task_id,
-            cancel: Some(cancel_tx),
-        });
+        return;
     }
-
-    // 查找引擎运行时；失败时也必须释放刚登记的下载槽。
-    let generation = state.engine.funasr_generation.load(Ordering::SeqCst);
-    let runtime = match funasr_service::find_engine(app_handle, state, generation).await {
-        Ok(runtime) => runtime,
-        Err(err) => {
-            clear_download_task(state, task_id).await;
-            return Err(err);
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        if let Err(err) = funasr_service::start_server(&app_handle, state.inner()).await {
+            log::warn!("下载结束后自动启动引擎失败: {}", err);
         }
+    });
+}
+
+// 模型仓库体积的保守估计（略大于当前快照），只计尚未下载的仓库
+const SENSEVOICE_ASR_MODEL_BYTES: u64 = 1_000 * 1024 * 1024;
+const SENSEVOICE_VAD_MODEL_BYTES: u64 = 10 * 1024 * 1024;
+const WHISPER_MODEL_BYTES: u64 = 1_700 * 1024 * 1024;
+/// 下载过程中的临时文件和锁文件另外预留的空间
+const DOWNLOAD_SPACE_HEADROOM_BYTES: u64 = 512 * 1024 * 1024;
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadSpaceReport {
+    pub engine: String,
+    /// 模型缓存目录，剩余空间按它所在的磁盘计算
+    pub cache_path: String,
+    /// 缺失模型的估计体积加预留空间；模型齐全时为 0
+    pub required_bytes: u64,
+    /// None 表示当前系统查询不到剩余空间
+    pub available_bytes: Option<u64>,
+    /// 查询不到剩余空间时按足够处理，不阻止下载
+    pub sufficient: bool,
+    pub missing_models: Vec<String>,
+}
+
+fn estimate_download_bytes(check: &funasr_service::ModelCheckResult) -> u64 {
+    let models = if check.engine == "whisper" {
+        u64::from(!check.asr_model) * WHISPER_MODEL_BYTES
+    } else {
+        u64::from(!check.asr_model) * SENSEVOICE_ASR_MODEL_BYTES
+            + u64::from(!check.vad_model) * SENSEVOICE_VAD_MODEL_BYTES
     };
+    if models == 0 {
+        0
+    } else {
+        models + DOWNLOAD_SPACE_HEADROOM_BYTES
+    }
+}
 
-    // 仅开发模式需要检查脚本是否存在；此时 task 已登记，错误路径必须按 ID 清理。
-    if matches!(runtime, funasr_service::EngineRuntime::Development { .. })
-        && !download_script.exists()
-    {
-        clear_download_task(state, task_id).await;
-        return Err(AppError::Download(format!(
-            "模型下载脚本不存在: {}",
-            download_script_str
-        )));
+fn format_gb(bytes: u64) -> String {
+    format!("{:.1} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+}
+
+/// 估算当前引擎还要下载多少、模型目录所在磁盘还剩多少。
+pub fn check_download_space() -> DownloadSpaceReport {
+    let check = funasr_service::inspect_model_files_for_engine(&paths::read_engine_config());
+    let models_dir = paths::get_effective_models_dir();
+    let required_bytes = if paths::is_online_engine(&check.engine) {
+        0
+    } else {
+        estimate_download_bytes(&check)
+    };
+    let available_bytes = paths::available_disk_space(&models_dir);
+    DownloadSpaceReport {
+        engine: check.engine,
+        cache_path: paths::strip_win_prefix(&models_dir),
+        required_bytes,
+        available_bytes,
+        sufficient: available_bytes.is_none_or(|available| available >= required_bytes),
+        missing_models: check.missing_models,
     }
+}
 
-    // 通知前端开始下载
-    emit_download_status(
-        app_handle,
-        serde_json::json!({
-            "status": "downloading",
-            "message": "开始下载模型文件..."
-        }),
-    );
+/// 默认最多尝试 3 次（含首次）
+pub const DEFAULT_DOWNLOAD_MAX_ATTEMPTS: u32 = 3;
+pub const MAX_DOWNLOAD_ATTEMPTS: u32 = 10;
+/// 下载脚本连续这么久没有任何输出就视为网络卡死，杀掉后按网络失败重试
+pub const DEFAULT_DOWNLOAD_STALL_TIMEOUT_SECS: u64 = 300;
+pub const MAX_DOWNLOAD_STALL_TIMEOUT_SECS: u64 = 3600;
+const DOWNLOAD_RETRY_BASE_DELAY_MS: u64 = 2000;
+const DOWNLOAD_RETRY_MAX_DELAY_MS: u64 = 30_000;
+
+/// 这些关键字作为完整的词出现在错误信息里时按网络类故障处理；其余失败（磁盘满、
+/// 模型不存在、脚本异常等）重试也无济于事，直接报错。前后必须是非字母数字，
+/// 避免 `15023 bytes`、`connections.json` 之类的误判；Python 的异常类名单独列出。
+const NETWORK_ERROR_MARKERS: [&str; 24] = [
+    "timeout",
+    "timed out",
+    "connection",
+    "network",
+    "ssl",
+    "proxy",
+    "temporarily unavailable",
+    "unreachable",
+    "name resolution",
+    "connectionerror",
+    "connecttimeout",
+    "readtimeout",
+    "timeouterror",
+    "connectionreseterror",
+    "remotedisconnected",
+    "proxyerror",
+    "sslerror",
+    "incompleteread",
+    "502",
+    "503",
+    "504",
+    "429",
+    "网络",
+    "超时",
+];
+
+enum DownloadAttemptOutcome {
+    Completed,
+    Cancelled,
+    Failed { error: String, retryable: bool },
+}
 
-    // 启动下载脚本（逐行读取 stdout 以转发进度）
+/// `needle` 在 `haystack` 中作为完整的词出现：前后相邻的字符不是 ASCII 字母、数字或下划线。
+fn contains_word(haystack: &str, needle: &str) -> bool {
+    let is_word_byte = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    haystack.match_indices(needle).any(|(start, matched)| {
+        let bytes = haystack.as_bytes();
+        let end = start + matched.len();
+        (start == 0 || !is_word_byte(bytes[start - 1]))
+            && (end == bytes.len() || !is_word_byte(bytes[end]))
+    })
+}
+
+fn is_network_failure(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    NETWORK_ERROR_MARKERS
+        .iter()
+        .any(|marker| contains_word(&lower, marker))
+}
+
+/// 指数退避：2s、4s、8s…，上限 30s。`attempt` 为刚失败的那次（从 1 开始）。
+fn download_retry_delay(attempt: u32) -> std::time::Duration {
+    let factor = 1u64 << attempt.saturating_sub(1).min(16);
+    std::time::Duration::from_millis(
+        DOWNLOAD_RETRY_BASE_DELAY_MS
+            .saturating_mul(factor)
+            .min(DOWNLOAD_RETRY_MAX_DELAY_MS),
+    )
+}
+
+fn build_download_command(
+    runtime: &funasr_service::EngineRuntime,
+    download_script_str: &str,
+    data_dir: &str,
+) -> Command {
     // 模型从 HuggingFace 下载，使用 HF 默认缓存目录
     let engine = paths::read_engine_config();
     let engine_arg = if engine == "whisper" {
@@ -155,7 +265,7 @@ pub async fn run_download(
         "sensevoice"
     };
 
-    let mut cmd = match &runtime {
+    let mut cmd = match runtime {
         funasr_service::EngineRuntime::Bundled { exe_path } => {
             let mut c = Command::new(exe_path);
             c.arg("download").arg("--engine").arg(engine_arg);
@@ -166,7 +276,7 @@ pub async fn run_download(
             c.arg("-X")
                 .arg("utf8")
                 .arg("-u")
-                .arg(&download_script_str)
+                .arg(download_script_str)
                 .arg("--engine")
                 .arg(engine_arg);
             c
@@ -176,7 +286,7 @@ pub async fn run_download(
     let models_dir = paths::strip_win_prefix(&paths::get_effective_models_dir());
     cmd.env("PYTHONIOENCODING", "utf-8")
         .env("PYTHONUTF8", "1")
-        .env("LIGHT_WHISPER_DATA_DIR", &data_dir)
+        .env("LIGHT_WHISPER_DATA_DIR", data_dir)
         .env("HF_HUB_CACHE", &models_dir)
         .stdout(Stdio::piped())
         .stderr({
@@ -186,6 +296,10 @@ pub async fn run_download(
                 Err(_) => Stdio::null(),
             }
         });
+    // 与引擎进程保持同一个 HF_HOME
+    if let Some(hf_home) = paths::get_hf_home_override() {
+        cmd.env("HF_HOME", paths::strip_win_prefix(&hf_home));
+    }
 
     // Windows 上隐藏控制台窗口
     #[cfg(target_os = "windows")]
@@ -193,40 +307,58 @@ pub async fn run_download(
         const CREATE_NO_WINDOW: u32 = 0x08000000;
         cmd.creation_flags(CREATE_NO_WINDOW);
     }
+    cmd
+}
 
-    let mut child = match cmd.spawn() {
-        Ok(child) => child,
-        Err(e) => {
-            clear_download_task(state, task_id).await;
-            return Err(AppError::Download(format!("启动模型下载脚本失败: {}", e)));
-        }
-    };
-
-    let stdout = match child.stdout.take() {
-        Some(stdout) => stdout,
-        None => {
-            clear_download_task(state, task_id).await;
-            return Err(AppError::Download("无法读取模型下载脚本输出".to_string()));
-        }
-    };
+/// 跑一次下载脚本，逐行转发进度，直到脚本退出、被取消或长时间无输出。
+async fn run_download_attempt(
+    app_handle: &tauri::AppHandle,
+    mut cmd: Command,
+    cancel_rx: &mut oneshot::Receiver<()>,
+    stall_timeout: Option<std::time::Duration>,
+) -> Result<DownloadAttemptOutcome, AppError> {
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| AppError::Download(format!("启动模型下载脚本失败: {}", e)))?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| AppError::Download("无法读取模型下载脚本输出".to_string()))?;
 
     let mut reader = BufReader::new(stdout);
     let mut final_result: Option<DownloadLine> = None;
-    let mut cancelled = false;
+    // 进度行里的 error 也要留着：脚本崩溃时可能来不及输出最终结果行
+    let mut last_error: Option<String> = None;
+    let mut stalled = false;
     let mut line_bytes = Vec::new();
     loop {
         line_bytes.clear();
+        let read_line = async {
+            match stall_timeout {
+                Some(timeout) => {
+                    tokio::time::timeout(timeout, reader.read_until(b'\n', &mut line_bytes))
+                        .await
+                        .ok()
+                }
+                None => Some(reader.read_until(b'\n', &mut line_bytes).await),
+            }
+        };
         tokio::select! {
-            _ = &mut cancel_rx => {
-                cancelled = true;
+            _ = &mut *cancel_rx => {
                 let _ = child.kill().await;
                 emit_download_status(app_handle, serde_json::json!({
                     "status": "cancelled",
                     "message": "下载已取消"
                 }));
-                break;
+                return Ok(DownloadAttemptOutcome::Cancelled);
             }
-            bytes = reader.read_until(b'\n', &mut line_bytes) => {
+            bytes = read_line => {
+                let Some(bytes) = bytes else {
+                    log::warn!("模型下载长时间无输出，终止本次下载");
+                    stalled = true;
+                    let _ = child.kill().await;
+                    break;
+                };
                 let bytes = match bytes {
                     Ok(bytes) => bytes,
                     Err(e) => {
@@ -260,6 +392,9 @@ pub async fn run_download(
                     Some("error") => "error",
                     _ => "progress",
                 };
+                if payload.error.is_some() {
+                    last_error = payload.error.clone();
+                }
 
                 emit_download_status(app_handle, serde_json::json!({
                     "status": status,
@@ -271,59 +406,251 @@ pub async fn run_download(
         }
     }
 
-    let status = match child.wait().await {
-        Ok(status) => status,
-        Err(e) => {
-            clear_download_task(state, task_id).await;
-            return Err(AppError::Download(format!("模型下载进程异常退出: {}", e)));
-        }
-    };
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| AppError::Download(format!("模型下载进程异常退出: {}", e)))?;
+    if stalled {
+        return Ok(DownloadAttemptOutcome::Failed {
+            error: "模型下载长时间无响应".to_string(),
+            retryable: true,
+        });
+    }
 
-    let final_success = download_completed_successfully(
+    if download_completed_successfully(
         final_result.as_ref().and_then(|r| r.success),
         status.success(),
-    );
+    ) {
+        return Ok(DownloadAttemptOutcome::Completed);
+    }
+    let error = final_result
+        .and_then(|r| r.error.or(r.message))
+        .or(last_error)
+        .unwrap_or_else(|| "模型下载失败".to_string());
+    Ok(DownloadAttemptOutcome::Failed {
+        retryable: is_network_failure(&error),
+        error,
+    })
+}
 
-    // 清理下载任务
-    clear_download_task(state, task_id).await;
+/// 执行模型下载
+///
+/// 启动 Python 下载脚本，逐行读取进度并通过 Tauri 事件转发给前端。
+/// 支持通过 cancel channel 取消下载。网络类失败会按退避自动重试，
+/// HF 缓存保留已完成的文件，重试时从断点继续。
+pub async fn run_download(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+) -> Result<String, AppError> {
+    // 获取下载脚本路径，清理 Windows \\?\ 前缀
+    let download_script = paths::get_download_script_path(app_handle);
+    let download_script_str = paths::strip_win_prefix(&download_script);
 
-    if cancelled {
-        return Ok("模型下载已取消".to_string());
+    let data_dir = paths::strip_win_prefix(paths::get_data_dir());
+
+    // 空间明显不够时在开始前拒绝，免得下到一半才报磁盘已满
+    let space = check_download_space();
+    match space.available_bytes {
+        Some(available) if !space.sufficient => {
+            let error = format!(
+                "模型目录所在磁盘剩余 {}，下载预计需要 {}，请清理磁盘或更换模型目录: {}",
+                format_gb(available),
+                format_gb(space.required_bytes),
+                space.cache_path
+            );
+            log::warn!("{}", error);
+            emit_download_status(
+                app_handle,
+                serde_json::json!({
+                    "status": "error",
+                    "message": &error
+                }),
+            );
+            return Err(AppError::Download(error));
+        }
+        Some(available) => log::info!(
+            "模型下载空间检查通过：剩余 {}，预计需要 {}",
+            format_gb(available),
+            format_gb(space.required_bytes)
+        ),
+        None => log::warn!("无法查询模型目录所在磁盘的剩余空间，跳过下载前的空间检查"),
     }
 
-    if final_success {
-        emit_download_status(
-            app_handle,
-            serde_json::json!({
-                "status": "completed",
-                "progress": 100,
-                "message": "模型下载完成"
-            }),
-        );
-        Ok("模型下载完成".to_string())
-    } else {
-        let error_msg = final_result
-            .and_then(|r| r.error.or(r.message))
-            .unwrap_or_else(|| "模型下载失败".to_string());
+    let (cancel_tx, mut cancel_rx) = oneshot::channel();
+    let task_id = NEXT_DOWNLOAD_TASK_ID.fetch_add(1, Ordering::Relaxed);
+    let stopped_engine = {
+        // 与模型目录切换串行登记。登记完成后 set_models_dir 会看到 active slot
+        // 并拒绝迁移，直到下载子进程真正退出并清理自己的 task ID。
+        let _lifecycle_guard = state.engine.funasr_lifecycle_op.lock().await;
+        let mut guard = state.engine.download_task.lock().await;
+        if guard.is_some() {
+            return Err(AppError::Download(
+                "已有下载任务正在进行，请先取消或等待完成".to_string(),
+            ));
+        }
+        *guard = Some(crate::state::DownloadTask {
+            id: task_id,
+            cancel: Some(cancel_tx),
+        });
+        drop(guard);
+
+        // 运行中/启动中的本地引擎可能正打开着要写入的模型文件（Windows 上还会锁文件），
+        // 先停掉；仍在 lifecycle 临界区内，start_server 会看到下载槽而拒绝重新拉起。
+        // 下载完成后 start_engine_after_download 负责重新启动。
+        let engine_active =
+            state.engine.funasr_process.lock().await.is_some() || state.engine.is_funasr_starting();
+        if engine_active && !paths::is_online_engine(&paths::read_engine_config()) {
+            log::info!("下载模型前先停止正在运行的引擎");
+            if let Err(err) = funasr_service::stop_server(state).await {
+                log::warn!("下载前停止引擎失败: {}", err);
+            }
+            let _ = app_handle.emit(
+                "funasr-status",
+                serde_json::json!({
+                    "status": "waiting_download",
+                    "message": funasr_service::WAITING_FOR_DOWNLOAD_MESSAGE
+                }),
+            );
+            true
+        } else {
+            false
+        }
+    };
+
+    // 查找引擎运行时；失败时也必须释放刚登记的下载槽。
+    let generation = state.engine.funasr_generation.load(Ordering::SeqCst);
+    let runtime = match funasr_service::find_engine(app_handle, state, generation).await {
+        Ok(runtime) => runtime,
+        Err(err) => {
+            clear_download_task(state, task_id).await;
+            restore_stopped_engine(app_handle, stopped_engine);
+            return Err(err);
+        }
+    };
 
+    // 仅开发模式需要检查脚本是否存在；此时 task 已登记，错误路径必须按 ID 清理。
+    if matches!(runtime, funasr_service::EngineRuntime::Development { .. })
+        && !download_script.exists()
+    {
+        clear_download_task(state, task_id).await;
+        restore_stopped_engine(app_handle, stopped_engine);
+        return Err(AppError::Download(format!(
+            "模型下载脚本不存在: {}",
+            download_script_str
+        )));
+    }
+
+    // 通知前端开始下载
+    emit_download_status(
+        app_handle,
+        serde_json::json!({
+            "status": "downloading",
+            "message": "开始下载模型文件..."
+        }),
+    );
+
+    let retry = state.with_profile(|profile| profile.download_retry_settings.clone());
+    let max_attempts = retry.max_attempts.clamp(1, MAX_DOWNLOAD_ATTEMPTS);
+    let stall_timeout = (retry.stall_timeout_secs > 0)
+        .then(|| std::time::Duration::from_secs(retry.stall_timeout_secs));
+
+    let mut attempt = 1;
+    let outcome = loop {
+        let cmd = build_download_command(&runtime, &download_script_str, &data_dir);
+        let outcome =
+            match run_download_attempt(app_handle, cmd, &mut cancel_rx, stall_timeout).await {
+                Ok(outcome) => outcome,
+                Err(err) => {
+                    clear_download_task(state, task_id).await;
+                    restore_stopped_engine(app_handle, stopped_engine);
+                    return Err(err);
+                }
+            };
+        let DownloadAttemptOutcome::Failed {
+            error,
+            retryable: true,
+        } = &outcome
+        else {
+            break outcome;
+        };
+        if attempt >= max_attempts {
+            break outcome;
+        }
+
+        let delay = download_retry_delay(attempt);
+        log::warn!(
+            "模型下载失败（第 {}/{} 次），{}ms 后重试: {}",
+            attempt,
+            max_attempts,
+            delay.as_millis(),
+            error
+        );
         emit_download_status(
             app_handle,
             serde_json::json!({
-                "status": "error",
-                "message": &error_msg
+                "status": "retrying",
+                "attempt": attempt + 1,
+                "maxAttempts": max_attempts,
+                "delayMs": delay.as_millis() as u64,
+                "message": format!("网络异常，{} 秒后重试（{}/{}）", delay.as_secs(), attempt + 1, max_attempts),
+                "error": error
             }),
         );
+        tokio::select! {
+            _ = &mut cancel_rx => {
+                emit_download_status(app_handle, serde_json::json!({
+                    "status": "cancelled",
+                    "message": "下载已取消"
+                }));
+                break DownloadAttemptOutcome::Cancelled;
+            }
+            _ = tokio::time::sleep(delay) => {}
+        }
+        attempt += 1;
+    };
 
-        Err(AppError::Download(error_msg))
+    // 清理下载任务
+    clear_download_task(state, task_id).await;
+
+    match outcome {
+        DownloadAttemptOutcome::Cancelled => {
+            restore_stopped_engine(app_handle, stopped_engine);
+            Ok("模型下载已取消".to_string())
+        }
+        DownloadAttemptOutcome::Completed => {
+            emit_download_status(
+                app_handle,
+                serde_json::json!({
+                    "status": "completed",
+                    "progress": 100,
+                    "message": "模型下载完成"
+                }),
+            );
+            start_engine_after_download(app_handle);
+            Ok("模型下载完成".to_string())
+        }
+        DownloadAttemptOutcome::Failed { error, .. } => {
+            emit_download_status(
+                app_handle,
+                serde_json::json!({
+                    "status": "error",
+                    "message": &error
+                }),
+            );
+            restore_stopped_engine(app_handle, stopped_engine);
+            Err(AppError::Download(error))
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        clear_download_task, download_completed_successfully, parse_json_line_with_recovery,
-        DownloadLine,
+        clear_download_task, download_completed_successfully, download_retry_delay,
+        estimate_download_bytes, is_network_failure, parse_json_line_with_recovery, DownloadLine,
+        DOWNLOAD_SPACE_HEADROOM_BYTES, SENSEVOICE_VAD_MODEL_BYTES,
     };
+    use crate::services::funasr_service::ModelCheckResult;
     use crate::state::{AppState, DownloadTask};
 
     #[tokio::test]
@@ -418,4 +745,59 @@ mod tests {
 
         assert!(payload.is_none());
     }
+
+    #[test]
+    fn network_failures_are_retryable_but_other_errors_are_not() {
+        assert!(is_network_failure(
+            "HTTPSConnectionPool: Read timed out. (read timeout=10)"
+        ));
+        assert!(is_network_failure("503 Server Error: Service Unavailable"));
+        assert!(is_network_failure("网络连接失败"));
+        assert!(!is_network_failure("No space left on device"));
+        assert!(!is_network_failure("模型下载失败"));
+        assert!(is_network_failure(
+            "requests.exceptions.ConnectionError: Max retries exceeded"
+        ));
+        assert!(is_network_failure("HTTP Error 429: Too Many Requests"));
+    }
+
+    #[test]
+    fn network_markers_must_match_whole_words() {
+        assert!(!is_network_failure("写入 15023 字节后磁盘已满"));
+        assert!(!is_network_failure("OSError: connections.json not found"));
+        assert!(!is_network_failure("KeyError: 'sslib_version'"));
+        assert!(!is_network_failure("checksum mismatch for file_5040.bin"));
+    }
+
+    #[test]
+    fn download_estimate_counts_only_missing_repos() {
+        let check = |engine: &str, asr_model: bool, vad_model: bool| ModelCheckResult {
+            all_present: asr_model && vad_model,
+            asr_model,
+            vad_model,
+            punc_model: true,
+            engine: engine.to_string(),
+            cache_path: String::new(),
+            missing_models: Vec::new(),
+        };
+        assert_eq!(estimate_download_bytes(&check("sensevoice", true, true)), 0);
+        assert_eq!(estimate_download_bytes(&check("whisper", true, true)), 0);
+        assert_eq!(
+            estimate_download_bytes(&check("sensevoice", true, false)),
+            SENSEVOICE_VAD_MODEL_BYTES + DOWNLOAD_SPACE_HEADROOM_BYTES
+        );
+        assert!(
+            estimate_download_bytes(&check("whisper", false, true))
+                > estimate_download_bytes(&check("sensevoice", false, false))
+        );
+    }
+
+    #[test]
+    fn retry_delay_backs_off_exponentially_with_cap() {
+        assert_eq!(download_retry_delay(1).as_millis(), 2000);
+        assert_eq!(download_retry_delay(2).as_millis(), 4000);
+        assert_eq!(download_retry_delay(3).as_millis(), 8000);
+        assert_eq!(download_retry_delay(10).as_millis(), 30_000);
+        assert_eq!(download_retry_delay(u32::MAX).as_millis(), 30_000);
+    }
 }
@@ -23,12 +23,13 @@
 
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::Command;
 
+use crate::state::user_profile::AsrFormattingSettings;
 use crate::state::{AppState, EngineState, FunasrProcess, StartingFunasrProcess};
 use crate::utils::paths;
 use crate::utils::AppError;
@@ -77,10 +78,46 @@ pub enum ServerCommand {
     },
     /// 查询服务器状态
     Status,
+    /// 运行时调整服务器配置，不需要重启；未设置的字段保持服务器当前值
+    Configure {
+        /// Python logging 级别：debug/info/warning/error
+        #[serde(skip_serializing_if = "Option::is_none")]
+        log_level: Option<String>,
+        /// 逆文本正则化（"一二三" → "123"），目前只有 SenseVoice 支持关闭
+        #[serde(skip_serializing_if = "Option::is_none")]
+        itn: Option<bool>,
+        /// 是否保留标点
+        #[serde(skip_serializing_if = "Option::is_none")]
+        punctuation: Option<bool>,
+        /// 说话人分离；引擎不支持时服务端忽略并回报 `diarization: null`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        diarization: Option<bool>,
+        /// 识别语言提示；`Some(None)` 下发 null，恢复自动检测
+        #[serde(skip_serializing_if = "Option::is_none")]
+        language: Option<Option<String>>,
+    },
+    /// 在当前进程里释放并重新加载模型，保留已预热的进程；
+    /// 服务端在初始化握手的 `capabilities` 里声明 `reload_model` 才可用
+    ReloadModel,
     /// 退出服务器
     Exit,
 }
 
+impl ServerCommand {
+    /// 重新加载要走一遍完整的模型初始化，按初始化超时等待
+    fn response_timeout(&self) -> Duration {
+        match self {
+            Self::ReloadModel => Duration::from_secs(SERVER_INIT_TIMEOUT_SECS),
+            _ => Duration::from_secs(SERVER_RESPONSE_TIMEOUT_SECS),
+        }
+    }
+}
+
+/// 服务端声明支持原地重新加载模型
+pub const SERVER_CAPABILITY_RELOAD_MODEL: &str = "reload_model";
+/// 服务端以 `language:<code>` 声明接受的识别语言提示
+const SERVER_CAPABILITY_LANGUAGE_PREFIX: &str = "language:";
+
 /// 语音转写的结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptionResult {
@@ -94,6 +131,20 @@ pub struct TranscriptionResult {
     pub error: Option<String>,
     /// 检测到的语言
     pub language: Option<String>,
+    /// 带时间戳的分段；开启说话人分离且引擎支持时带 speaker
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segments: Option<Vec<TranscriptionSegment>>,
+}
+
+/// 转写分段，字段与字幕导出的 `SubtitleCue` 一致，前端可以直接传回导出
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionSegment {
+    pub start_sec: f64,
+    pub end_sec: f64,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
 }
 
 /// FunASR 服务器的状态信息
@@ -111,6 +162,14 @@ pub struct FunASRStatus {
     pub gpu_name: Option<String>,
     /// GPU 总显存（GB）
     pub gpu_memory_total: Option<f64>,
+    /// 服务器上报的 CPU 推理线程数
+    pub cpu_threads: Option<u32>,
+    /// 最近几次最终转写的平均实时率（处理耗时 / 音频时长），越小越快
+    pub avg_rtf: Option<f64>,
+    /// 服务器实际生效的 ITN 设置；引擎不支持切换时为 None
+    pub itn: Option<bool>,
+    /// 服务器实际生效的标点设置
+    pub punctuation: Option<bool>,
     /// 状态描述信息
     pub message: String,
     /// 当前引擎
@@ -145,6 +204,34 @@ const VAD_REPO_ID: &str = "funasr/fsmn-vad";
 const WHISPER_REPO_ID: &str = "deepdml/faster-whisper-large-v3-turbo-ct2";
 const HF_COMPLETE_MANIFEST_NAME: &str = ".light_whisper_complete.json";
 
+/// Python 服务器在长转写过程中输出的中间进度行
+///
+/// 协议：`{"type": "progress", "request_id": N, "current": x, "total": y, "message": "..."}`。
+/// 不含 `success` 字段；`current`/`total` 单位由服务器决定（Whisper 用音频秒数），
+/// 宿主只关心两者的比值。
+#[derive(Debug, Clone, Deserialize)]
+struct ServerProgress {
+    #[serde(rename = "type")]
+    kind: String,
+    request_id: Option<u64>,
+    current: f64,
+    total: f64,
+    message: Option<String>,
+}
+
+impl ServerProgress {
+    fn fraction(&self) -> Option<f64> {
+        (self.total > 0.0 && self.current.is_finite())
+            .then(|| (self.current / self.total).clamp(0.0, 1.0))
+    }
+}
+
+fn parse_progress_line(line: &str) -> Option<ServerProgress> {
+    serde_json::from_str::<ServerProgress>(line)
+        .ok()
+        .filter(|progress| progress.kind == "progress")
+}
+
 /// Python 服务器的 JSON 响应
 ///
 /// 这个结构体对应 Python 服务器返回的 JSON 格式。
@@ -179,10 +266,55 @@ struct ServerResponse {
     gpu_name: Option<String>,
     /// GPU 总显存（GB）
     gpu_memory_total: Option<f64>,
+    /// CPU 推理线程数
+    cpu_threads: Option<u32>,
+    /// 生效的 ITN 设置
+    itn: Option<bool>,
+    /// 生效的标点设置
+    punctuation: Option<bool>,
     /// 当前引擎
     engine: Option<String>,
     /// 服务端实际采用的输入模式（memory/path）
     input_mode: Option<String>,
+    /// 初始化握手上报的协议版本
+    server_version: Option<String>,
+    /// 初始化握手上报的可选能力；旧脚本不上报
+    capabilities: Option<Vec<String>>,
+    /// 生效的说话人分离设置；None 表示引擎不支持
+    diarization: Option<bool>,
+    /// 分段结果
+    segments: Option<Vec<ServerSegment>>,
+}
+
+/// Python 返回的分段；speaker 可能是编号也可能是字符串。缺时间戳的分段
+/// 不能导出字幕，转换时丢弃，但不能让整条转写结果解析失败。
+#[derive(Debug, Deserialize)]
+struct ServerSegment {
+    #[serde(default)]
+    start: Option<f64>,
+    #[serde(default)]
+    end: Option<f64>,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    speaker: Option<serde_json::Value>,
+}
+
+impl ServerSegment {
+    fn into_segment(self) -> Option<TranscriptionSegment> {
+        let (start_sec, end_sec) = (self.start?, self.end?);
+        let speaker = match self.speaker {
+            Some(serde_json::Value::String(label)) => Some(label),
+            Some(serde_json::Value::Number(index)) => Some(index.to_string()),
+            _ => None,
+        };
+        Some(TranscriptionSegment {
+            start_sec,
+            end_sec,
+            text: self.text,
+            speaker,
+        })
+    }
 }
 
 /// Python status 返回的模型状态
@@ -193,6 +325,25 @@ struct ServerModelStatus {
     punc: Option<bool>,
 }
 
+/// stdout 上的协议消息必须带的判别字段。字段全是 Option 的响应结构几乎能
+/// 解析任何 JSON 对象，第三方库打到 stdout 的 `{"path": ...}` 之类日志光靠
+/// "能反序列化" 区分不出来。
+trait ProtocolMessage {
+    fn has_discriminator(&self) -> bool;
+}
+
+impl ProtocolMessage for ServerResponse {
+    /// 服务端的每条响应都带 success，新协议还会回传 request_id；旧版初始化
+    /// 握手可能只有 status / initialized / model_loaded。
+    fn has_discriminator(&self) -> bool {
+        self.success.is_some()
+            || self.request_id.is_some()
+            || self.status.is_some()
+            || self.initialized.is_some()
+            || self.model_loaded.is_some()
+    }
+}
+
 impl ServerResponse {
     fn is_model_loaded(&self) -> bool {
         self.model_loaded.unwrap_or_else(|| {
@@ -204,13 +355,32 @@ impl ServerResponse {
                 .unwrap_or(false)
         })
     }
+
+    /// 响应里显式带了模型状态时返回它，旧版或自定义脚本两个字段都不带时为 None。
+    fn reported_model_state(&self) -> Option<bool> {
+        (self.model_loaded.is_some() || self.models.is_some()).then(|| self.is_model_loaded())
+    }
+
+    /// 初始化握手后模型是否真正可用。只有响应完全没有模型状态时才退回
+    /// success / initialized / status 判断，否则 "进程起来了但模型没加载"
+    /// 会被当成就绪，第一次听写直接报错。
+    fn init_models_ready(&self) -> bool {
+        self.reported_model_state().unwrap_or_else(|| {
+            self.initialized.unwrap_or(false)
+                || self.success.unwrap_or(false)
+                || self.status.as_deref() == Some("ready")
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct StartTicket {
     owner: u64,
     generation: u64,
+    /// 本次实际启动的引擎；自动回退时与配置里的引擎不同
     engine: String,
+    /// 启动时读到的引擎配置，用来判断用户是否在启动途中切换了引擎
+    configured_engine: String,
 }
 
 #[derive(Clone)]
@@ -272,6 +442,10 @@ impl Drop for StartingFlagGuard<'_> {
 const SERVER_INIT_TIMEOUT_SECS: u64 = 120;
 const SERVER_RESPONSE_TIMEOUT_SECS: u64 = 60;
 const SERVER_EXIT_WRITE_TIMEOUT_MS: u64 = 300;
+/// 命令失败后判定进程崩溃前的确认窗口；写入失败时也在这之后重试一次
+pub const DEFAULT_SERVER_CRASH_GRACE_MS: u64 = 300;
+/// 窗口上限，避免真正的崩溃被拖太久才上报
+pub const MAX_SERVER_CRASH_GRACE_MS: u64 = 3000;
 const SERVER_EXIT_WAIT_TIMEOUT_SECS: u64 = 2;
 const INLINE_AUDIO_FORMAT_PCM_S16LE: &str = "pcm_s16le";
 const TARGET_SAMPLE_RATE_FOR_INLINE_AUDIO: u32 = 16_000;
@@ -291,7 +465,7 @@ fn next_server_start_owner() -> u64 {
 fn start_ticket_is_current(state: &AppState, ticket: &StartTicket) -> bool {
     state.engine.owns_funasr_start(ticket.owner)
         && state.engine.funasr_generation.load(Ordering::SeqCst) == ticket.generation
-        && paths::read_engine_config() == ticket.engine
+        && paths::read_engine_config() == ticket.configured_engine
 }
 
 fn take_starting_child(
@@ -324,6 +498,488 @@ fn to_normalized_path(path: &std::path::Path) -> String {
     paths::strip_win_prefix(&canonical)
 }
 
+const SERVER_LOG_LEVELS: [&str; 4] = ["debug", "info", "warning", "error"];
+
+fn normalize_server_log_level(level: &str) -> Result<&'static str, AppError> {
+    let level = level.trim().to_ascii_lowercase();
+    let level = if level == "warn" { "warning" } else { &level };
+    SERVER_LOG_LEVELS
+        .iter()
+        .find(|candidate| **candidate == level)
+        .copied()
+        .ok_or_else(|| {
+            AppError::Other(format!(
+                "未知的日志级别: {}，可选值: {}",
+                level,
+                SERVER_LOG_LEVELS.join(", ")
+            ))
+        })
+}
+
+/// 调整正在运行的本地引擎的日志级别，只对当前进程生效，重启后恢复 info。
+pub async fn set_server_log_level(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+    level: &str,
+) -> Result<String, AppError> {
+    let level = normalize_server_log_level(level)?;
+    if paths::is_online_engine(&paths::read_engine_config()) {
+        return Err(AppError::Other("在线引擎没有本地服务器日志".to_string()));
+    }
+    let response = send_command_to_server(
+        state,
+        &ServerCommand::Configure {
+            log_level: Some(level.to_string()),
+            itn: None,
+            punctuation: None,
+            diarization: None,
+            language: None,
+        },
+        Some(app_handle),
+    )
+    .await?;
+    if response.success == Some(false) {
+        return Err(AppError::Asr(
+            response
+                .error
+                .unwrap_or_else(|| "设置服务器日志级别失败".to_string()),
+        ));
+    }
+    log::info!("FunASR 服务器日志级别已切换为 {}", level);
+    Ok(format!("服务器日志级别已切换为 {}", level))
+}
+
+/// 构造下发输出格式的 configure 命令。`omit_defaults` 时省略与默认值相同的字段，
+/// 全是默认值就不发命令；用户在设置里改回默认时则需要显式下发。
+fn asr_formatting_command(
+    settings: AsrFormattingSettings,
+    omit_defaults: bool,
+) -> Option<ServerCommand> {
+    let defaults = AsrFormattingSettings::default();
+    let pick = |value: bool, default: bool| (!omit_defaults || value != default).then_some(value);
+    let itn = pick(settings.itn, defaults.itn);
+    let punctuation = pick(settings.punctuation, defaults.punctuation);
+    if itn.is_none() && punctuation.is_none() {
+        return None;
+    }
+    Some(ServerCommand::Configure {
+        log_level: None,
+        itn,
+        punctuation,
+        diarization: None,
+        language: None,
+    })
+}
+
+/// 把画像里的 ITN / 标点设置下发给正在运行的本地引擎；在线引擎直接跳过。
+pub async fn apply_asr_formatting(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+    omit_defaults: bool,
+) -> Result<(), AppError> {
+    if paths::is_online_engine(&paths::read_engine_config()) {
+        return Ok(());
+    }
+    let settings = state.with_profile(|profile| profile.asr_formatting);
+    let Some(command) = asr_formatting_command(settings, omit_defaults) else {
+        return Ok(());
+    };
+    let response = send_command_to_server(state, &command, Some(app_handle)).await?;
+    if response.success == Some(false) {
+        return Err(AppError::Asr(
+            response
+                .error
+                .unwrap_or_else(|| "设置输出格式失败".to_string()),
+        ));
+    }
+    log::info!(
+        "引擎输出格式已更新: ITN {:?}，标点 {:?}",
+        response.itn,
+        response.punctuation
+    );
+    Ok(())
+}
+
+/// 下发说话人分离开关，返回引擎实际生效的值；引擎不支持时返回 None，
+/// 分段里也不会出现说话人标签。
+pub async fn apply_diarization(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+) -> Result<Option<bool>, AppError> {
+    if paths::is_online_engine(&paths::read_engine_config()) {
+        return Ok(None);
+    }
+    let enabled = state.with_profile(|profile| profile.diarization_enabled);
+    let response = send_command_to_server(
+        state,
+        &ServerCommand::Configure {
+            log_level: None,
+            itn: None,
+            punctuation: None,
+            diarization: Some(enabled),
+            language: None,
+        },
+        Some(app_handle),
+    )
+    .await?;
+    if response.success == Some(false) {
+        return Err(AppError::Asr(
+            response
+                .error
+                .unwrap_or_else(|| "设置说话人分离失败".to_string()),
+        ));
+    }
+    if enabled && response.diarization.is_none() {
+        log::info!("当前引擎不支持说话人分离，已忽略该设置");
+    }
+    Ok(response.diarization)
+}
+
+fn languages_from_capabilities(capabilities: &[String]) -> Vec<String> {
+    capabilities
+        .iter()
+        .filter_map(|capability| capability.strip_prefix(SERVER_CAPABILITY_LANGUAGE_PREFIX))
+        .map(str::to_string)
+        .collect()
+}
+
+/// 正在运行的本地引擎在握手里声明的识别语言（ISO 639-1，粤语为 yue）。
+/// 引擎没在运行或旧脚本没有声明时为 None。
+pub fn server_supported_languages(state: &AppState) -> Option<Vec<String>> {
+    let languages = languages_from_capabilities(&state.engine.server_capabilities.lock());
+    (!languages.is_empty()).then_some(languages)
+}
+
+/// 语言代码的中文名，用于切换提示；None 为自动检测，不认识的代码原样返回。
+pub fn language_display_name(code: Option<&str>) -> String {
+    let Some(code) = code else {
+        return "自动检测".to_string();
+    };
+    match code {
+        "zh" => "中文",
+        "en" => "英语",
+        "yue" => "粤语",
+        "ja" => "日语",
+        "ko" => "韩语",
+        "de" => "德语",
+        "fr" => "法语",
+        "es" => "西班牙语",
+        "it" => "意大利语",
+        "pt" => "葡萄牙语",
+        "ru" => "俄语",
+        "ar" => "阿拉伯语",
+        "hi" => "印地语",
+        "th" => "泰语",
+        "vi" => "越南语",
+        "id" => "印尼语",
+        "ms" => "马来语",
+        "tr" => "土耳其语",
+        "nl" => "荷兰语",
+        "pl" => "波兰语",
+        "sv" => "瑞典语",
+        "uk" => "乌克兰语",
+        other => other,
+    }
+    .to_string()
+}
+
+/// 轮换列表里代表自动检测的项；语言提示里写作 None。
+pub const LANGUAGE_AUTO: &str = "auto";
+pub const MAX_LANGUAGE_CYCLE_LEN: usize = 8;
+
+/// 规范化并校验语言轮换列表：转小写、去重，每一项都必须是 `supported`（引擎
+/// 声明的语言）之一或 `auto`。至少两项才有轮换的意义。
+pub fn normalize_language_cycle(
+    engine: &str,
+    supported: Option<&[String]>,
+    languages: Vec<String>,
+) -> Result<Vec<String>, String> {
+    if paths::is_online_engine(engine) {
+        return Err(format!("引擎 {} 不支持指定识别语言", engine));
+    }
+    let Some(supported) = supported else {
+        return Err(format!(
+            "引擎 {} 尚未就绪或未声明支持的语言，请等引擎启动后再设置",
+            engine
+        ));
+    };
+    let mut normalized: Vec<String> = Vec::new();
+    for language in languages {
+        let language = language.trim().to_lowercase();
+        if language.is_empty() || normalized.contains(&language) {
+            continue;
+        }
+        if language != LANGUAGE_AUTO && !supported.contains(&language) {
+            return Err(format!(
+                "引擎 {} 不支持语言 {}（可选: {}, {}）",
+                engine,
+                language,
+                LANGUAGE_AUTO,
+                supported.join(", ")
+            ));
+        }
+        normalized.push(language);
+    }
+    if normalized.len() < 2 {
+        return Err("语言轮换列表至少需要两种语言".to_string());
+    }
+    if normalized.len() > MAX_LANGUAGE_CYCLE_LEN {
+        return Err(format!("语言轮换列表最多 {} 项", MAX_LANGUAGE_CYCLE_LEN));
+    }
+    Ok(normalized)
+}
+
+/// 轮换列表里当前语言的下一项；当前语言不在列表里时从第一项开始。
+/// 返回 None 表示切回自动检测。
+pub fn next_cycle_language(cycle: &[String], current: Option<&str>) -> Option<String> {
+    let current = current.unwrap_or(LANGUAGE_AUTO);
+    let next = cycle
+        .iter()
+        .position(|language| language == current)
+        .map_or(0, |index| (index + 1) % cycle.len());
+    cycle
+        .get(next)
+        .filter(|language| language.as_str() != LANGUAGE_AUTO)
+        .cloned()
+}
+
+/// 把语言提示下发给正在运行的本地引擎，返回实际生效的语言。引擎声明的语言里
+/// 没有它时（例如换了引擎后保存的提示失效）改为自动检测；在线引擎直接跳过。
+pub async fn apply_language_hint(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+    language: Option<&str>,
+) -> Result<Option<String>, AppError> {
+    let engine = paths::read_engine_config();
+    if paths::is_online_engine(&engine) {
+        return Ok(language.map(str::to_string));
+    }
+    let supported = server_supported_languages(state);
+    let language = language.filter(|language| {
+        let accepted = supported
+            .as_ref()
+            .is_none_or(|supported| supported.iter().any(|code| code == language));
+        if !accepted {
+            log::warn!("引擎 {} 不支持语言提示 {}，改为自动检测", engine, language);
+        }
+        accepted
+    });
+    let response = send_command_to_server(
+        state,
+        &ServerCommand::Configure {
+            log_level: None,
+            itn: None,
+            punctuation: None,
+            diarization: None,
+            language: Some(language.map(str::to_string)),
+        },
+        Some(app_handle),
+    )
+    .await?;
+    if response.success == Some(false) {
+        return Err(AppError::Asr(
+            response
+                .error
+                .unwrap_or_else(|| "设置识别语言失败".to_string()),
+        ));
+    }
+    log::info!("识别语言已设置为 {}", language_display_name(language));
+    Ok(language.map(str::to_string))
+}
+
+/// 切到轮换列表里的下一种语言：引擎运行中时立即下发，成功后保存并发出
+/// "language-changed" 事件供前端提示；引擎不支持而退回自动检测时，保存和提示的
+/// 都是实际生效的语言。录音中不切换，避免同一段话前后语言不一致。
+pub async fn cycle_language(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+) -> Result<Option<String>, AppError> {
+    if state.recording.recording.lock().is_some() {
+        return Err(AppError::Other(
+            "录音进行中，结束后再切换识别语言".to_string(),
+        ));
+    }
+    let (cycle, current) = state.with_profile(|profile| {
+        (
+            profile.language_cycle.clone(),
+            profile.language_hint.clone(),
+        )
+    });
+    if cycle.len() < 2 {
+        return Err(AppError::Other("尚未设置语言轮换列表".to_string()));
+    }
+    let mut next = next_cycle_language(&cycle, current.as_deref());
+    if state.is_funasr_ready() {
+        next = apply_language_hint(app_handle, state, next.as_deref()).await?;
+    }
+    crate::services::profile_service::update_profile_and_schedule(state, |profile| {
+        profile.language_hint = next.clone();
+    });
+
+    let name = language_display_name(next.as_deref());
+    log::info!("识别语言已切换为 {}", name);
+    let _ = app_handle.emit(
+        "language-changed",
+        serde_json::json!({
+            "language": next,
+            "name": name,
+            "message": format!("识别语言已切换为 {}", name),
+        }),
+    );
+    Ok(next)
+}
+
+/// 可用于推理的逻辑核心数，作为 `cpu_threads` 设置的上限
+pub fn available_cpu_threads() -> u32 {
+    std::thread::available_parallelism()
+        .map(|n| n.get() as u32)
+        .unwrap_or(1)
+}
+
+pub fn validate_cpu_threads(threads: u32) -> Result<(), AppError> {
+    let available = available_cpu_threads();
+    if threads == 0 || threads > available {
+        return Err(AppError::Other(format!(
+            "推理线程数需在 1-{} 之间",
+            available
+        )));
+    }
+    Ok(())
+}
+
+/// 显存预算可设置的范围（GB）
+pub const MIN_MAX_VRAM_GB: f64 = 0.5;
+pub const MAX_MAX_VRAM_GB: f64 = 128.0;
+
+/// 模型在某个设备/精度下的显存占用估算（GB，含推理时的激活余量）。
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelVramEstimate {
+    pub engine: &'static str,
+    pub model: &'static str,
+    pub device: &'static str,
+    pub compute_type: &'static str,
+    pub vram_gb: f64,
+}
+
+/// 每个引擎按占用从大到小排列，末尾的 CPU 档位不占显存、作为兜底。
+/// CUDA 档位必须与服务端脚本里的 `*_CUDA_VRAM_TIERS` 保持一致，
+/// 否则这里给出的建议和引擎实际选择会对不上。
+pub const MODEL_VRAM_ESTIMATES: &[ModelVramEstimate] = &[
+    ModelVramEstimate {
+        engine: "sensevoice",
+        model: "SenseVoiceSmall",
+        device: "cuda",
+        compute_type: "float32",
+        vram_gb: 1.2,
+    },
+    ModelVramEstimate {
+        engine: "sensevoice",
+        model: "SenseVoiceSmall",
+        device: "cpu",
+        compute_type: "float32",
+        vram_gb: 0.0,
+    },
+    ModelVramEstimate {
+        engine: "whisper",
+        model: "large-v3-turbo",
+        device: "cuda",
+        compute_type: "float16",
+        vram_gb: 1.7,
+    },
+    ModelVramEstimate {
+        engine: "whisper",
+        model: "large-v3-turbo",
+        device: "cuda",
+        compute_type: "int8_float16",
+        vram_gb: 1.0,
+    },
+    ModelVramEstimate {
+        engine: "whisper",
+        model: "large-v3-turbo",
+        device: "cpu",
+        compute_type: "int8",
+        vram_gb: 0.0,
+    },
+];
+
+pub fn validate_max_vram_gb(gb: f64) -> Result<(), AppError> {
+    if !gb.is_finite() || !(MIN_MAX_VRAM_GB..=MAX_MAX_VRAM_GB).contains(&gb) {
+        return Err(AppError::Other(format!(
+            "显存预算需在 {}-{} GB 之间",
+            MIN_MAX_VRAM_GB, MAX_MAX_VRAM_GB
+        )));
+    }
+    Ok(())
+}
+
+/// 选出预算内占用最大的档位；不限预算时取首档。未知引擎返回 None。
+pub fn select_model_for_vram(
+    engine: &str,
+    budget_gb: Option<f64>,
+) -> Option<&'static ModelVramEstimate> {
+    let mut tiers = MODEL_VRAM_ESTIMATES
+        .iter()
+        .filter(|estimate| estimate.engine == engine);
+    match budget_gb {
+        None => tiers.next(),
+        Some(budget) => tiers.find(|estimate| estimate.vram_gb <= budget),
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VramBudgetReport {
+    pub engine: String,
+    pub max_vram_gb: Option<f64>,
+    /// 引擎最近一次以 CUDA 初始化时上报的总显存；从未用过 GPU 时为 None
+    pub gpu_memory_total_gb: Option<f64>,
+    /// 不限预算时引擎会使用的档位
+    pub preferred: Option<ModelVramEstimate>,
+    /// 按预算实际会使用的档位
+    pub selected: Option<ModelVramEstimate>,
+    pub warning: Option<String>,
+}
+
+pub fn vram_budget_report(
+    engine: &str,
+    budget_gb: Option<f64>,
+    gpu_memory_total_gb: Option<f64>,
+) -> VramBudgetReport {
+    let preferred = select_model_for_vram(engine, None).copied();
+    let selected = select_model_for_vram(engine, budget_gb).copied();
+    let mut warnings = Vec::new();
+    if let (Some(preferred), Some(selected), Some(budget)) = (preferred, selected, budget_gb) {
+        if preferred != selected {
+            warnings.push(format!(
+                "{}（{}，约 {} GB）超出显存预算 {} GB，将改用 {} {}",
+                preferred.model,
+                preferred.compute_type,
+                preferred.vram_gb,
+                budget,
+                selected.device.to_uppercase(),
+                selected.compute_type
+            ));
+        }
+    }
+    if let (Some(budget), Some(total)) = (budget_gb, gpu_memory_total_gb) {
+        if budget > total {
+            warnings.push(format!(
+                "显存预算 {} GB 超过显卡总显存 {} GB，实际不起限制作用",
+                budget, total
+            ));
+        }
+    }
+    VramBudgetReport {
+        engine: engine.to_string(),
+        max_vram_gb: budget_gb,
+        gpu_memory_total_gb,
+        preferred,
+        selected,
+        warning: (!warnings.is_empty()).then(|| warnings.join("；")),
+    }
+}
+
 fn status_with_defaults(
     running: bool,
     ready: bool,
@@ -337,6 +993,10 @@ fn status_with_defaults(
         device: None,
         gpu_name: None,
         gpu_memory_total: None,
+        cpu_threads: None,
+        avg_rtf: None,
+        itn: None,
+        punctuation: None,
         message,
         engine: None,
         models_present: None,
@@ -382,23 +1042,40 @@ async fn read_json_response<T, R>(
     context: &str,
 ) -> Result<T, AppError>
 where
-    T: for<'de> Deserialize<'de>,
+    T: for<'de> Deserialize<'de> + ProtocolMessage,
     R: AsyncBufRead + Unpin,
 {
     read_json_response_matching(reader, timeout, context, |_| true).await
 }
 
 async fn read_json_response_matching<T, R>(
+    reader: &mut R,
+    timeout: Duration,
+    context: &str,
+    accept: impl FnMut(&T) -> bool,
+) -> Result<T, AppError>
+where
+    T: for<'de> Deserialize<'de> + ProtocolMessage,
+    R: AsyncBufRead + Unpin,
+{
+    read_json_response_with_progress(reader, timeout, context, accept, |_| {}).await
+}
+
+/// 与 `read_json_response_matching` 相同，但把中间进度行交给 `on_progress`，
+/// 而不是当作最终响应或噪音丢弃。每条进度行都说明服务器仍在工作，
+/// `timeout` 从收到它时重新计算，长文件转写因此不会被判超时。
+async fn read_json_response_with_progress<T, R>(
     reader: &mut R,
     timeout: Duration,
     context: &str,
     mut accept: impl FnMut(&T) -> bool,
+    mut on_progress: impl FnMut(ServerProgress),
 ) -> Result<T, AppError>
 where
-    T: for<'de> Deserialize<'de>,
+    T: for<'de> Deserialize<'de> + ProtocolMessage,
     R: AsyncBufRead + Unpin,
 {
-    let start_at = Instant::now();
+    let mut start_at = Instant::now();
     let mut line_bytes = Vec::new();
 
     loop {
@@ -418,12 +1095,14 @@ where
                 let line = match std::str::from_utf8(&line_bytes) {
                     Ok(line) => std::borrow::Cow::Borrowed(line),
                     Err(err) => {
+                        let lossy = String::from_utf8_lossy(&line_bytes);
                         log::warn!(
-                            "{}阶段收到非 UTF-8 输出，已按损坏文本容错处理: {}",
+                            "{}阶段收到非 UTF-8 输出，已按损坏文本容错处理 ({}): {}",
                             context,
-                            err
+                            err,
+                            lossy.trim().chars().take(120).collect::<String>()
                         );
-                        String::from_utf8_lossy(&line_bytes)
+                        lossy
                     }
                 };
 
@@ -432,7 +1111,23 @@ where
                     continue;
                 }
 
-                if let Ok(value) = serde_json::from_str::<T>(trimmed) {
+                // 进度行字段全是可选的响应结构也能解析，必须先于最终响应判断
+                if let Some(progress) = parse_progress_line(trimmed) {
+                    start_at = Instant::now();
+                    on_progress(progress);
+                    continue;
+                }
+
+                let parsed = serde_json::from_str::<T>(trimmed);
+                if matches!(&parsed, Ok(value) if !value.has_discriminator()) {
+                    log::warn!(
+                        "{}阶段跳过了缺少协议字段的 JSON 输出 ({}字符)",
+                        context,
+                        trimmed.chars().count()
+                    );
+                    continue;
+                }
+                if let Ok(value) = parsed {
                     if accept(&value) {
                         return Ok(value);
                     }
@@ -444,7 +1139,10 @@ where
                 // 尝试从首个 '{' 到末尾 '}' 提取有效 JSON，避免一次脏输出导致整次初始化失败。
                 if let (Some(start), Some(end)) = (trimmed.find('{'), trimmed.rfind('}')) {
                     if start < end {
-                        if let Ok(value) = serde_json::from_str::<T>(&trimmed[start..=end]) {
+                        if let Some(value) = serde_json::from_str::<T>(&trimmed[start..=end])
+                            .ok()
+                            .filter(|value| value.has_discriminator())
+                        {
                             if !accept(&value) {
                                 log::warn!("{}阶段丢弃了不匹配的旧 JSON 响应", context);
                                 continue;
@@ -843,8 +1541,219 @@ async fn find_python() -> Result<String, AppError> {
     ))
 }
 
+/// 子进程里与启动问题相关、但由宿主环境继承下来的变量
+const INHERITED_ENV_VARS: &[&str] = &[
+    "PATH",
+    "PYTHONHOME",
+    "PYTHONPATH",
+    "HF_HOME",
+    "HF_ENDPOINT",
+    "CUDA_VISIBLE_DEVICES",
+    "HTTPS_PROXY",
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerEnvVar {
+    pub name: String,
+    /// None 表示未设置（或被显式移除）
+    pub value: Option<String>,
+    /// "app"：启动时显式设置；"removed"：显式移除；"inherited"：继承自宿主
+    pub source: &'static str,
+}
+
+/// 本版本 Rust 端期望的服务端协议版本，对应 server_common.py 的 `SERVER_VERSION`
+pub const EXPECTED_SERVER_VERSION: &str = "2";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerVersionInfo {
+    pub expected_version: &'static str,
+    /// 运行中的引擎在握手里上报的版本
+    pub reported_version: Option<String>,
+    /// 磁盘上 server_common.py 声明的版本；打包版没有脚本文件时为 None
+    pub script_version: Option<String>,
+    pub script_path: Option<String>,
+    pub engine_running: bool,
+    /// 内存音频传输探测结果；None 表示尚未探测
+    pub inline_audio_transport: Option<bool>,
+    /// 两个来源都拿不到版本时为 None
+    pub compatible: Option<bool>,
+}
+
+/// 从脚本源码里找 `SERVER_VERSION = "x"`。
+fn parse_server_version_constant(source: &str) -> Option<String> {
+    source.lines().find_map(|line| {
+        let value = line.trim().strip_prefix("SERVER_VERSION")?.trim_start();
+        let value = value.strip_prefix('=')?.trim();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let inner = &value[1..];
+        let end = inner.find(quote)?;
+        Some(inner[..end].to_string())
+    })
+}
+
+pub async fn server_version_info(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+) -> ServerVersionInfo {
+    let reported_version = state.engine.server_version.lock().clone();
+    let engine_running = state.is_funasr_ready();
+    let engine = paths::read_engine_config();
+    let common_script = server_script_path(app_handle, &engine).with_file_name("server_common.py");
+    let script_version = tokio::fs::read_to_string(&common_script)
+        .await
+        .ok()
+        .and_then(|source| parse_server_version_constant(&source));
+    let compatible = reported_version
+        .as_deref()
+        .filter(|_| engine_running)
+        .or(script_version.as_deref())
+        .map(|version| version == EXPECTED_SERVER_VERSION);
+
+    ServerVersionInfo {
+        expected_version: EXPECTED_SERVER_VERSION,
+        reported_version,
+        script_path: script_version
+            .is_some()
+            .then(|| paths::strip_win_prefix(&common_script)),
+        script_version,
+        engine_running,
+        inline_audio_transport: state.inline_audio_transport(),
+        compatible,
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerEnvironmentReport {
+    pub engine: String,
+    /// "bundled" | "bundled_archive"（尚未解压）| "development" | "unavailable"
+    pub runtime: &'static str,
+    pub executable_path: Option<String>,
+    pub script_path: Option<String>,
+    pub script_exists: bool,
+    pub args: Vec<String>,
+    pub env: Vec<ServerEnvVar>,
+    /// 找不到运行时的原因
+    pub runtime_error: Option<String>,
+}
+
+/// 启动引擎子进程时显式写入的环境变量；值为 None 表示显式移除，不继承宿主设置。
+fn server_env_vars(state: &AppState) -> Vec<(&'static str, Option<String>)> {
+    let data_dir = paths::strip_win_prefix(paths::get_data_dir());
+    let models_dir = paths::strip_win_prefix(&paths::get_effective_models_dir());
+    let mut vars = vec![
+        ("PYTHONIOENCODING", Some("utf-8".to_string())),
+        ("PYTHONUTF8", Some("1".to_string())),
+        ("LIGHT_WHISPER_DATA_DIR", Some(data_dir)),
+        ("HF_HUB_CACHE", Some(models_dir)),
+    ];
+    // 自定义数据目录生效时 HF 缓存跟随数据目录，HF_HOME 也要一致，
+    // 否则 token 等其余 HF 文件仍落在默认位置
+    if let Some(hf_home) = paths::get_hf_home_override() {
+        vars.push(("HF_HOME", Some(paths::strip_win_prefix(&hf_home))));
+    }
+    let cpu_threads = state.with_profile(|profile| profile.cpu_threads);
+    vars.push((
+        "LIGHT_WHISPER_THREADS",
+        cpu_threads.map(|threads| threads.to_string()),
+    ));
+    let max_vram_gb = state.with_profile(|profile| profile.max_vram_gb);
+    vars.push((
+        "LIGHT_WHISPER_MAX_VRAM_GB",
+        max_vram_gb.map(|gb| gb.to_string()),
+    ));
+    vars
+}
+
+fn server_script_path(app_handle: &tauri::AppHandle, engine: &str) -> PathBuf {
+    if engine == "whisper" {
+        paths::get_whisper_server_path(app_handle)
+    } else {
+        paths::get_funasr_server_path(app_handle)
+    }
+}
+
+/// 只读诊断：汇总下一次启动引擎时子进程会拿到的可执行文件、参数和环境变量。
+/// 与 find_engine 不同，这里不会解压引擎归档，也不占用安装锁。
+pub async fn describe_server_environment(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+) -> ServerEnvironmentReport {
+    let engine = paths::read_engine_config();
+    let mut report = ServerEnvironmentReport {
+        engine: engine.clone(),
+        runtime: "unavailable",
+        executable_path: None,
+        script_path: None,
+        script_exists: false,
+        args: Vec::new(),
+        env: Vec::new(),
+        runtime_error: None,
+    };
+
+    let installed_exe = paths::get_engine_exe_path(app_handle).filter(|_| {
+        let installed_version =
+            std::fs::read_to_string(paths::get_engine_dir().join(".version")).unwrap_or_default();
+        engine_install_fingerprint_matches(
+            &installed_version,
+            &expected_engine_install_fingerprint(app_handle),
+        )
+    });
+    if let Some(exe_path) = installed_exe {
+        report.runtime = "bundled";
+        report.executable_path = Some(paths::strip_win_prefix(&exe_path));
+    } else if let Some(archive_path) = paths::get_engine_archive_path(app_handle) {
+        report.runtime = "bundled_archive";
+        report.executable_path = Some(paths::strip_win_prefix(&archive_path));
+    } else {
+        match find_python().await {
+            Ok(python_path) => {
+                report.runtime = "development";
+                report.executable_path = Some(python_path);
+            }
+            Err(err) => report.runtime_error = Some(err.to_string()),
+        }
+    }
+
+    if report.runtime == "development" {
+        let script = server_script_path(app_handle, &engine);
+        let script_str = paths::strip_win_prefix(&script);
+        report.script_exists = script.exists();
+        report.args = vec!["-X".into(), "utf8".into(), "-u".into(), script_str.clone()];
+        report.script_path = Some(script_str);
+    } else if report.runtime != "unavailable" {
+        report.args = vec!["serve".into(), "--engine".into(), engine];
+    }
+
+    let app_vars = server_env_vars(state);
+    for (name, value) in &app_vars {
+        report.env.push(ServerEnvVar {
+            name: name.to_string(),
+            source: if value.is_some() { "app" } else { "removed" },
+            value: value.clone(),
+        });
+    }
+    for name in INHERITED_ENV_VARS {
+        if app_vars.iter().any(|(app_name, _)| app_name == name) {
+            continue;
+        }
+        report.env.push(ServerEnvVar {
+            name: name.to_string(),
+            value: std::env::var(name).ok(),
+            source: "inherited",
+        });
+    }
+    report
+}
+
+/// 模型下载期间拒绝启动引擎时给前端的提示
+pub const WAITING_FOR_DOWNLOAD_MESSAGE: &str = "模型正在下载，下载完成后将自动启动引擎";
+
 /// 启动 FunASR Python 服务器
 pub async fn start_server(app_handle: &tauri::AppHandle, state: &AppState) -> Result<(), AppError> {
+    let startup_started = Instant::now();
     let (ticket, mut starting_guard) = {
         // 启动预检与 lifecycle 配置变更串行：engine snapshot、owner、generation
         // 以及首个 loading 状态必须作为一个不可分割的提交。
@@ -853,6 +1762,18 @@ pub async fn start_server(app_handle: &tauri::AppHandle, state: &AppState) -> Re
         if paths::is_online_engine(&engine) {
             return Ok(());
         }
+        // 下载中的模型文件可能只写了一半，此时加载会直接崩溃；下载完成后
+        // download_service 会自动启动引擎，这里只需拒绝并告知前端在等待下载。
+        if state.engine.download_task.lock().await.is_some() {
+            let _ = app_handle.emit(
+                "funasr-status",
+                serde_json::json!({
+                    "status": "waiting_download",
+                    "message": WAITING_FOR_DOWNLOAD_MESSAGE
+                }),
+            );
+            return Err(AppError::Asr(WAITING_FOR_DOWNLOAD_MESSAGE.to_string()));
+        }
 
         let process_guard = state.engine.funasr_process.lock().await;
         if process_guard.is_some() {
@@ -871,10 +1792,16 @@ pub async fn start_server(app_handle: &tauri::AppHandle, state: &AppState) -> Re
             return Ok(());
         }
 
+        let run_engine = if state.with_profile(|profile| profile.auto_fallback_engine) {
+            fallback_engine_for_run(app_handle, &engine)
+        } else {
+            engine.clone()
+        };
         let ticket = StartTicket {
             owner: start_owner,
             generation: state.engine.funasr_generation.load(Ordering::SeqCst),
-            engine,
+            engine: run_engine,
+            configured_engine: engine,
         };
         let starting_guard = StartingFlagGuard {
             engine: &state.engine,
@@ -911,7 +1838,6 @@ pub async fn start_server(app_handle: &tauri::AppHandle, state: &AppState) -> Re
     }
 
     // 构建子进程命令
-    let data_dir = paths::strip_win_prefix(paths::get_data_dir());
     let mut cmd = match &runtime {
         EngineRuntime::Bundled { exe_path } => {
             log::info!("使用打包引擎: {} (engine={})", exe_path, ticket.engine);
@@ -921,11 +1847,7 @@ pub async fn start_server(app_handle: &tauri::AppHandle, state: &AppState) -> Re
         }
         EngineRuntime::Development { python_path } => {
             log::info!("使用开发模式 Python: {}", python_path);
-            let server_script = if ticket.engine == "whisper" {
-                paths::get_whisper_server_path(app_handle)
-            } else {
-                paths::get_funasr_server_path(app_handle)
-            };
+            let server_script = server_script_path(app_handle, &ticket.engine);
             let server_script_str = paths::strip_win_prefix(&server_script);
             log::info!(
                 "语音识别脚本路径 (engine={}): {}",
@@ -946,15 +1868,17 @@ pub async fn start_server(app_handle: &tauri::AppHandle, state: &AppState) -> Re
         }
     };
 
-    let models_dir = paths::strip_win_prefix(&paths::get_effective_models_dir());
-    cmd.env("PYTHONIOENCODING", "utf-8")
-        .env("PYTHONUTF8", "1")
-        .env("LIGHT_WHISPER_DATA_DIR", &data_dir)
-        .env("HF_HUB_CACHE", &models_dir)
-        .stdin(std::process::Stdio::piped())
+    // 可见控制台只在 Windows 上有意义；此时 stderr 直接输出到控制台，
+    // 不再写 funasr_stderr.log。stdout 仍是与宿主通信的协议通道。
+    let show_console =
+        cfg!(target_os = "windows") && state.with_profile(|profile| profile.show_python_console);
+    cmd.stdin(std::process::Stdio::piped())
         .stdout(std::process::Stdio::piped())
-        .stderr({
-            let log_path = paths::get_data_dir().join("funasr_stderr.log");
+        .stderr(if show_console {
+            log::info!("已开启 Python 调试控制台，stderr 输出到可见窗口");
+            std::process::Stdio::inherit()
+        } else {
+            let log_path = server_stderr_log_path();
             match std::fs::File::create(&log_path) {
                 Ok(file) => {
                     log::info!("Python stderr 重定向到: {}", log_path.display());
@@ -966,14 +1890,29 @@ pub async fn start_server(app_handle: &tauri::AppHandle, state: &AppState) -> Re
                 }
             }
         });
-
-    // Windows 上隐藏控制台窗口
-    #[cfg(target_os = "windows")]
-    {
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
-        cmd.creation_flags(CREATE_NO_WINDOW);
+    if let Some(threads) = state.with_profile(|profile| profile.cpu_threads) {
+        log::info!("CPU 推理线程数: {}", threads);
     }
-
+    for (name, value) in server_env_vars(state) {
+        match value {
+            Some(value) => {
+                cmd.env(name, value);
+            }
+            None => {
+                cmd.env_remove(name);
+            }
+        }
+    }
+
+    // Windows 上默认隐藏控制台窗口
+    #[cfg(target_os = "windows")]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        if !show_console {
+            cmd.creation_flags(CREATE_NO_WINDOW);
+        }
+    }
+
     let (stdin, stdout, child_handle) = {
         // spawn 与 starting slot 登记在同一 lifecycle 临界区，stop 不会漏掉
         // “已生成但尚未写入正式 process slot”的子进程。
@@ -1011,6 +1950,7 @@ pub async fn start_server(app_handle: &tauri::AppHandle, state: &AppState) -> Re
         );
         (stdin, stdout, child_handle)
     };
+    let spawned_at = Instant::now();
 
     // 读取子进程初始化输出，跳过非 JSON 行，直到拿到有效响应
     let mut stdout_reader = BufReader::new(stdout);
@@ -1031,11 +1971,20 @@ pub async fn start_server(app_handle: &tauri::AppHandle, state: &AppState) -> Re
         }
     };
 
-    let model_loaded = response.is_model_loaded();
-    let initialized = response.initialized.unwrap_or(false)
-        || response.success.unwrap_or(false)
-        || response.status.as_deref() == Some("ready")
-        || model_loaded;
+    if response.server_version.is_none() {
+        log::warn!("引擎未上报协议版本，可能是旧版或被修改过的服务端脚本");
+    }
+    *state.engine.server_version.lock() = response.server_version.clone();
+    *state.engine.server_capabilities.lock() = response.capabilities.clone().unwrap_or_default();
+    // 预算迫使引擎改用 CPU 时不会上报显存，保留上一次的值
+    if response.gpu_memory_total.is_some() {
+        *state.engine.gpu_memory_total.lock() = response.gpu_memory_total;
+    }
+
+    let initialized = response.init_models_ready();
+    if !initialized && response.success.unwrap_or(false) {
+        log::warn!("引擎报告初始化成功但模型未加载，不标记为就绪");
+    }
 
     let error_message = response
         .error
@@ -1044,13 +1993,23 @@ pub async fn start_server(app_handle: &tauri::AppHandle, state: &AppState) -> Re
         .unwrap_or_else(|| "FunASR 初始化失败".to_string());
 
     if initialized {
+        let timing = EngineStartupTiming {
+            engine: ticket.engine.clone(),
+            total_ms: startup_started.elapsed().as_millis() as u64,
+            model_load_ms: spawned_at.elapsed().as_millis() as u64,
+            cold: state.engine.last_startup.lock().is_none(),
+            finished_at_ms: now_unix_ms() as u64,
+        };
         let ready_status = serde_json::json!({
             "status": "ready",
             "message": "FunASR 服务器已就绪",
             "device": response.device,
             "gpu_name": response.gpu_name,
+            "cpu_threads": response.cpu_threads,
             "models_present": true,
             "missing_models": [],
+            "startup_ms": timing.total_ms,
+            "cold_start": timing.cold,
         });
         let _lifecycle_guard = state.engine.funasr_lifecycle_op.lock().await;
         if !start_ticket_is_current(state, &ticket) {
@@ -1072,7 +2031,14 @@ pub async fn start_server(app_handle: &tauri::AppHandle, state: &AppState) -> Re
             stdout: stdout_reader,
         });
         starting_guard.release_child();
-        log::info!("FunASR 服务器初始化成功！");
+        log::info!(
+            "FunASR 服务器初始化成功！引擎 {} {}启动耗时 {}ms（模型加载 {}ms）",
+            timing.engine,
+            if timing.cold { "冷" } else { "热" },
+            timing.total_ms,
+            timing.model_load_ms
+        );
+        *state.engine.last_startup.lock() = Some(timing);
         state.set_funasr_ready(true);
         let _ = app_handle.emit("funasr-status", ready_status);
     } else {
@@ -1098,12 +2064,95 @@ pub async fn start_server(app_handle: &tauri::AppHandle, state: &AppState) -> Re
     }
 
     if initialized {
+        if let Err(err) = apply_asr_formatting(app_handle, state, true).await {
+            log::warn!("下发输出格式设置失败，沿用引擎默认: {}", err);
+        }
+        if state.with_profile(|profile| profile.diarization_enabled) {
+            if let Err(err) = apply_diarization(app_handle, state).await {
+                log::warn!("下发说话人分离设置失败: {}", err);
+            }
+        }
+        if let Some(language) = state.with_profile(|profile| profile.language_hint.clone()) {
+            if let Err(err) = apply_language_hint(app_handle, state, Some(&language)).await {
+                log::warn!("下发识别语言设置失败: {}", err);
+            }
+        }
         Ok(())
     } else {
         Err(AppError::Asr(error_message))
     }
 }
 
+/// 在正在运行的引擎进程里重新加载模型，省去重启进程和重新导入依赖的时间。
+/// 服务端没有声明 `reload_model` 能力时返回 `Ok(false)`，由调用方改为完整重启。
+pub async fn reload_model(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+) -> Result<bool, AppError> {
+    if !state
+        .engine
+        .server_capabilities
+        .lock()
+        .iter()
+        .any(|capability| capability == SERVER_CAPABILITY_RELOAD_MODEL)
+    {
+        return Ok(false);
+    }
+
+    log::info!("正在原地重新加载模型...");
+    state.set_funasr_ready(false);
+    let _ = app_handle.emit(
+        "funasr-status",
+        serde_json::json!({
+            "status": "loading",
+            "message": "正在重新加载语音识别模型..."
+        }),
+    );
+
+    let result = send_command_to_server(state, &ServerCommand::ReloadModel, Some(app_handle)).await;
+    let response = match result {
+        Ok(response) if response.init_models_ready() => response,
+        Ok(response) => {
+            let message = response
+                .error
+                .or(response.message)
+                .unwrap_or_else(|| "重新加载模型失败".to_string());
+            log::error!("重新加载模型失败: {}", message);
+            let _ = app_handle.emit(
+                "funasr-status",
+                serde_json::json!({ "status": "error", "message": &message }),
+            );
+            return Err(AppError::Asr(message));
+        }
+        Err(err) => {
+            let _ = app_handle.emit(
+                "funasr-status",
+                serde_json::json!({ "status": "error", "message": err.to_string() }),
+            );
+            return Err(err);
+        }
+    };
+
+    if response.gpu_memory_total.is_some() {
+        *state.engine.gpu_memory_total.lock() = response.gpu_memory_total;
+    }
+    state.set_funasr_ready(true);
+    log::info!("模型已重新加载");
+    let _ = app_handle.emit(
+        "funasr-status",
+        serde_json::json!({
+            "status": "ready",
+            "message": "语音识别模型已重新加载",
+            "device": response.device,
+            "gpu_name": response.gpu_name,
+            "cpu_threads": response.cpu_threads,
+            "models_present": true,
+            "missing_models": [],
+        }),
+    );
+    Ok(true)
+}
+
 /// 执行语音转写
 ///
 /// 将音频数据写入临时 WAV 文件，然后通过 stdin 发送转写命令给 Python 进程，
@@ -1121,9 +2170,21 @@ pub async fn transcribe(
     state: &AppState,
     audio_data: Vec<u8>,
     app_handle: &tauri::AppHandle,
+) -> Result<TranscriptionResult, AppError> {
+    transcribe_audio_file_bytes(state, audio_data, "wav", app_handle).await
+}
+
+/// 转写一段完整的音频文件内容。临时文件沿用原扩展名，mp3/flac 等压缩格式
+/// 交给 Python 端按实际格式解码。
+pub async fn transcribe_audio_file_bytes(
+    state: &AppState,
+    audio_data: Vec<u8>,
+    extension: &str,
+    app_handle: &tauri::AppHandle,
 ) -> Result<TranscriptionResult, AppError> {
     let hot_words = profile_hot_words(state);
-    transcribe_wav_bytes_via_path(state, audio_data, hot_words, app_handle).await
+    let temp_file = create_temp_audio_path().with_extension(extension);
+    transcribe_audio_bytes_via_path(state, audio_data, temp_file, hot_words, app_handle, None).await
 }
 
 pub async fn transcribe_pcm16(
@@ -1131,6 +2192,39 @@ pub async fn transcribe_pcm16(
     samples: &[i16],
     sample_rate: u32,
     app_handle: &tauri::AppHandle,
+) -> Result<TranscriptionResult, AppError> {
+    transcribe_pcm16_unless_stopped(state, samples, sample_rate, app_handle, None).await
+}
+
+/// 中间转写入口：`stop_flag` 在等进程锁期间被置位时放弃本轮，
+/// 返回 `success: false` 的空结果，不再占用引擎。
+pub async fn transcribe_interim_pcm16(
+    state: &AppState,
+    samples: &[i16],
+    sample_rate: u32,
+    app_handle: &tauri::AppHandle,
+    stop_flag: &AtomicBool,
+) -> Result<TranscriptionResult, AppError> {
+    transcribe_pcm16_unless_stopped(state, samples, sample_rate, app_handle, Some(stop_flag)).await
+}
+
+fn skipped_transcription_result() -> TranscriptionResult {
+    TranscriptionResult {
+        text: String::new(),
+        duration: None,
+        success: false,
+        error: Some("录音已停止，跳过本轮中间转写".to_string()),
+        language: None,
+        segments: None,
+    }
+}
+
+async fn transcribe_pcm16_unless_stopped(
+    state: &AppState,
+    samples: &[i16],
+    sample_rate: u32,
+    app_handle: &tauri::AppHandle,
+    stop: Option<&AtomicBool>,
 ) -> Result<TranscriptionResult, AppError> {
     // 检查服务器是否就绪
     if !state.is_funasr_ready() {
@@ -1162,14 +2256,16 @@ pub async fn transcribe_pcm16(
     // for duration accounting, while the ASR backends assume the in-memory array
     // is already at their expected rate. Preserve non-16k audio by sending WAV.
     if sample_rate != TARGET_SAMPLE_RATE_FOR_INLINE_AUDIO {
-        return transcribe_pcm16_via_path(state, samples, sample_rate, hot_words, app_handle).await;
+        return transcribe_pcm16_via_path(state, samples, sample_rate, hot_words, app_handle, stop)
+            .await;
     }
 
     if state.inline_audio_transport() == Some(false) {
-        return transcribe_pcm16_via_path(state, samples, sample_rate, hot_words, app_handle).await;
+        return transcribe_pcm16_via_path(state, samples, sample_rate, hot_words, app_handle, stop)
+            .await;
     }
 
-    let response = send_command_to_server(
+    let Some(response) = send_command_to_server_unless_stopped(
         state,
         &ServerCommand::Transcribe {
             audio_path: None,
@@ -1179,8 +2275,12 @@ pub async fn transcribe_pcm16(
             hot_words: hot_words.clone(),
         },
         Some(app_handle),
+        stop,
     )
-    .await?;
+    .await?
+    else {
+        return Ok(skipped_transcription_result());
+    };
 
     if response.input_mode.as_deref() == Some("memory") {
         state.set_inline_audio_transport(Some(true));
@@ -1190,7 +2290,8 @@ pub async fn transcribe_pcm16(
     if response_indicates_inline_unsupported(&response) {
         log::info!("当前 FunASR 运行时不支持内存音频，回退到临时 WAV 文件");
         state.set_inline_audio_transport(Some(false));
-        return transcribe_pcm16_via_path(state, samples, sample_rate, hot_words, app_handle).await;
+        return transcribe_pcm16_via_path(state, samples, sample_rate, hot_words, app_handle, stop)
+            .await;
     }
 
     state.set_inline_audio_transport(Some(true));
@@ -1217,17 +2318,219 @@ fn encode_wav_bytes(samples: &[i16], sample_rate: u32) -> Result<Vec<u8>, AppErr
     super::audio_service::encode_wav(samples, sample_rate)
 }
 
+const TEMP_AUDIO_PREFIX: &str = "light_whisper_audio_";
+
+/// 临时音频文件名 `light_whisper_audio_<pid>_<毫秒>_<序号>.wav`，由
+/// `parse_temp_audio_pid` 反向解析；两者放在一起，改格式时一并改。调用方可以用
+/// `with_extension` 换成实际格式，解析时不限扩展名。
 fn create_temp_audio_path() -> std::path::PathBuf {
     static TEMP_AUDIO_COUNTER: AtomicU64 = AtomicU64::new(1);
     let counter = TEMP_AUDIO_COUNTER.fetch_add(1, Ordering::Relaxed);
-    std::env::temp_dir().join(format!(
-        "light_whisper_audio_{}_{}_{}.wav",
+    paths::get_ipc_temp_dir().join(format!(
+        "{}{}_{}_{}.wav",
+        TEMP_AUDIO_PREFIX,
         std::process::id(),
         now_unix_ms(),
         counter,
     ))
 }
 
+/// 只认 `create_temp_audio_path` 生成的文件名（任意扩展名），返回写入它的进程 PID。
+fn parse_temp_audio_pid(file_name: &str) -> Option<u32> {
+    let (stem, extension) = file_name
+        .strip_prefix(TEMP_AUDIO_PREFIX)?
+        .rsplit_once('.')?;
+    if extension.is_empty() || !extension.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        return None;
+    }
+    let mut parts = stem.split('_');
+    let pid = parts.next()?.parse::<u32>().ok()?;
+    let rest: Vec<&str> = parts.collect();
+    let all_numeric = rest.len() == 2
+        && rest
+            .iter()
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()));
+    all_numeric.then_some(pid)
+}
+
+const SERVER_STDERR_LOG_FILE: &str = "funasr_stderr.log";
+pub const DEFAULT_SERVER_STDERR_LINES: usize = 200;
+pub const MAX_SERVER_STDERR_LINES: usize = 5_000;
+/// 只读日志末尾这么多字节，长时间运行的日志可能很大
+const SERVER_STDERR_READ_LIMIT_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerStderrTail {
+    pub path: String,
+    /// 日志文件是否存在（可见控制台模式下不写文件）
+    pub exists: bool,
+    pub lines: Vec<String>,
+    /// 文件里还有更早的行没有返回
+    pub truncated: bool,
+}
+
+pub fn server_stderr_log_path() -> PathBuf {
+    paths::get_data_dir().join(SERVER_STDERR_LOG_FILE)
+}
+
+/// 取最后 `count` 行，去掉行尾 `\r` 和末尾空行。`starts_mid_line` 表示
+/// 内容是从文件中间截出来的，第一行可能不完整，直接丢掉。
+fn tail_lines(content: &str, count: usize, starts_mid_line: bool) -> (Vec<String>, bool) {
+    let mut lines: Vec<&str> = content
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .collect();
+    if starts_mid_line && !lines.is_empty() {
+        lines.remove(0);
+    }
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    let skip = lines.len().saturating_sub(count);
+    let truncated = skip > 0 || starts_mid_line;
+    (
+        lines[skip..].iter().map(|line| line.to_string()).collect(),
+        truncated,
+    )
+}
+
+pub fn server_stderr_tail(count: usize) -> Result<ServerStderrTail, AppError> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let path = server_stderr_log_path();
+    let mut report = ServerStderrTail {
+        path: paths::strip_win_prefix(&path),
+        ..Default::default()
+    };
+    let mut file = match std::fs::File::open(&path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(report),
+        Err(err) => return Err(err.into()),
+    };
+    report.exists = true;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(SERVER_STDERR_READ_LIMIT_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::with_capacity((len - start) as usize);
+    file.read_to_end(&mut bytes)?;
+    let (lines, truncated) = tail_lines(&String::from_utf8_lossy(&bytes), count, start > 0);
+    report.lines = lines;
+    report.truncated = truncated;
+    Ok(report)
+}
+
+/// 最近一次引擎启动（start_server 开始到就绪）的耗时。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineStartupTiming {
+    pub engine: String,
+    pub total_ms: u64,
+    /// 子进程启动后到模型加载完成：Python 导入依赖 + 加载模型
+    pub model_load_ms: u64,
+    /// 本次应用运行中的第一次启动，依赖库和模型文件都从磁盘读；
+    /// 之后的重启通常命中系统文件缓存，明显更快
+    pub cold: bool,
+    pub finished_at_ms: u64,
+}
+
+/// 其它进程留下的临时文件至少这么旧才算孤儿，避免误删另一个实例正在用的文件
+const TEMP_AUDIO_ORPHAN_MIN_AGE: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TempAudioUsage {
+    pub directories: Vec<String>,
+    pub file_count: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TempAudioCleanupReport {
+    pub removed_count: u64,
+    pub removed_bytes: u64,
+    pub failed_count: u64,
+}
+
+fn temp_audio_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![paths::get_ipc_temp_dir().clone()];
+    let system_temp = std::env::temp_dir();
+    if !dirs.contains(&system_temp) {
+        dirs.push(system_temp);
+    }
+    dirs
+}
+
+/// 列出崩溃或异常退出遗留的临时音频。本进程的文件由转写路径自己清理，不算在内。
+fn find_orphaned_temp_audio(dirs: &[PathBuf]) -> Vec<(PathBuf, u64)> {
+    let current_pid = std::process::id();
+    let mut orphans = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(parse_temp_audio_pid) else {
+                continue;
+            };
+            if pid == current_pid {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            let old_enough = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_none_or(|age| age >= TEMP_AUDIO_ORPHAN_MIN_AGE);
+            if old_enough {
+                orphans.push((entry.path(), metadata.len()));
+            }
+        }
+    }
+    orphans
+}
+
+pub fn temp_audio_usage() -> TempAudioUsage {
+    let dirs = temp_audio_dirs();
+    let orphans = find_orphaned_temp_audio(&dirs);
+    TempAudioUsage {
+        directories: dirs.iter().map(|dir| dir.display().to_string()).collect(),
+        file_count: orphans.len() as u64,
+        total_bytes: orphans.iter().map(|(_, size)| size).sum(),
+    }
+}
+
+pub fn clear_temp_audio_files() -> TempAudioCleanupReport {
+    let mut report = TempAudioCleanupReport::default();
+    for (path, size) in find_orphaned_temp_audio(&temp_audio_dirs()) {
+        match std::fs::remove_file(&path) {
+            Ok(()) => {
+                report.removed_count += 1;
+                report.removed_bytes += size;
+            }
+            Err(e) => {
+                log::warn!("删除遗留临时音频失败 {}: {}", path.display(), e);
+                report.failed_count += 1;
+            }
+        }
+    }
+    if report.removed_count > 0 || report.failed_count > 0 {
+        log::info!(
+            "清理遗留临时音频：删除 {} 个（{} 字节），失败 {} 个",
+            report.removed_count,
+            report.removed_bytes,
+            report.failed_count
+        );
+    }
+    report
+}
+
 fn response_indicates_inline_unsupported(response: &ServerResponse) -> bool {
     if response.input_mode.as_deref() == Some("memory") {
         return false;
@@ -1253,6 +2556,12 @@ fn server_response_to_transcription_result(response: ServerResponse) -> Transcri
             success: true,
             error: None,
             language: response.language,
+            segments: response.segments.map(|segments| {
+                segments
+                    .into_iter()
+                    .filter_map(ServerSegment::into_segment)
+                    .collect()
+            }),
         }
     } else {
         let error_msg = response
@@ -1264,23 +2573,24 @@ fn server_response_to_transcription_result(response: ServerResponse) -> Transcri
             success: false,
             error: Some(error_msg),
             language: None,
+            segments: None,
         }
     }
 }
 
-async fn transcribe_wav_bytes_via_path(
+async fn transcribe_audio_bytes_via_path(
     state: &AppState,
     audio_data: Vec<u8>,
+    temp_file: std::path::PathBuf,
     hot_words: Option<Vec<String>>,
     app_handle: &tauri::AppHandle,
+    stop: Option<&AtomicBool>,
 ) -> Result<TranscriptionResult, AppError> {
-    let temp_file = create_temp_audio_path();
-
     tokio::fs::write(&temp_file, &audio_data)
         .await
         .map_err(|e| AppError::Asr(format!("写入临时音频文件失败: {}", e)))?;
 
-    let response = send_command_to_server(
+    let response = send_command_to_server_unless_stopped(
         state,
         &ServerCommand::Transcribe {
             audio_path: Some(temp_file.to_string_lossy().to_string()),
@@ -1290,11 +2600,16 @@ async fn transcribe_wav_bytes_via_path(
             hot_words,
         },
         Some(app_handle),
+        stop,
     )
     .await;
 
     let _ = tokio::fs::remove_file(&temp_file).await;
-    response.map(server_response_to_transcription_result)
+    response.map(|response| {
+        response
+            .map(server_response_to_transcription_result)
+            .unwrap_or_else(skipped_transcription_result)
+    })
 }
 
 async fn transcribe_pcm16_via_path(
@@ -1303,9 +2618,18 @@ async fn transcribe_pcm16_via_path(
     sample_rate: u32,
     hot_words: Option<Vec<String>>,
     app_handle: &tauri::AppHandle,
+    stop: Option<&AtomicBool>,
 ) -> Result<TranscriptionResult, AppError> {
     let wav_bytes = encode_wav_bytes(samples, sample_rate)?;
-    transcribe_wav_bytes_via_path(state, wav_bytes, hot_words, app_handle).await
+    transcribe_audio_bytes_via_path(
+        state,
+        wav_bytes,
+        create_temp_audio_path(),
+        hot_words,
+        app_handle,
+        stop,
+    )
+    .await
 }
 
 /// 向 Python 服务器发送命令并读取响应
@@ -1330,31 +2654,109 @@ async fn send_command_to_server(
     command: &ServerCommand,
     app_handle: Option<&tauri::AppHandle>,
 ) -> Result<ServerResponse, AppError> {
-    let mut guard = state.engine.funasr_process.lock().await;
+    let guard = state.engine.funasr_process.lock().await;
+    send_command_with_guard(state, guard, command, app_handle).await
+}
+
+/// 拿到进程锁后若 `stop` 已置位就不再发送、直接让出锁，返回 `Ok(None)`。
+/// 中间转写用它避免松键后再插队一轮，让最终识别不必排在后面。
+async fn send_command_to_server_unless_stopped(
+    state: &AppState,
+    command: &ServerCommand,
+    app_handle: Option<&tauri::AppHandle>,
+    stop: Option<&AtomicBool>,
+) -> Result<Option<ServerResponse>, AppError> {
+    let guard = state.engine.funasr_process.lock().await;
+    if stop.is_some_and(|flag| flag.load(Ordering::Acquire)) {
+        return Ok(None);
+    }
+    send_command_with_guard(state, guard, command, app_handle)
+        .await
+        .map(Some)
+}
+
+/// 命令失败的阶段：写入 stdin 失败说明命令没有送达，重发不会让服务端重复执行；
+/// 其余（序列化、读取响应）失败不重试。
+enum CommandFailure {
+    Write(AppError),
+    Other(AppError),
+}
 
-    let result = {
+impl CommandFailure {
+    fn into_error(self) -> AppError {
+        match self {
+            Self::Write(err) | Self::Other(err) => err,
+        }
+    }
+}
+
+/// 用户设置的确认窗口，0 表示立即判定且不重试。
+pub fn effective_crash_grace_ms(configured: Option<u64>) -> u64 {
+    configured
+        .unwrap_or(DEFAULT_SERVER_CRASH_GRACE_MS)
+        .min(MAX_SERVER_CRASH_GRACE_MS)
+}
+
+fn exited_status(
+    guard: &mut tokio::sync::MutexGuard<'_, Option<FunasrProcess>>,
+) -> Option<std::process::ExitStatus> {
+    guard
+        .as_mut()
+        .and_then(|process| process.child.try_wait().ok().flatten())
+}
+
+async fn send_command_with_guard(
+    state: &AppState,
+    mut guard: tokio::sync::MutexGuard<'_, Option<FunasrProcess>>,
+    command: &ServerCommand,
+    app_handle: Option<&tauri::AppHandle>,
+) -> Result<ServerResponse, AppError> {
+    let grace = Duration::from_millis(effective_crash_grace_ms(
+        state.with_profile(|profile| profile.server_crash_grace_ms),
+    ));
+    let mut result = {
         let process = guard
             .as_mut()
             .ok_or_else(|| AppError::Asr("FunASR 进程未运行".to_string()))?;
-        send_command_impl(process, command).await
+        send_command_impl(process, command, app_handle).await
     };
+    // 管道瞬时错误：进程还活着时稍等再发一次
+    if let Err(CommandFailure::Write(err)) = &result {
+        if !grace.is_zero() && exited_status(&mut guard).is_none() {
+            log::warn!(
+                "写入 FunASR 命令失败，{}ms 后重试一次: {}",
+                grace.as_millis(),
+                err
+            );
+            tokio::time::sleep(grace).await;
+            if let Some(process) = guard.as_mut() {
+                result = send_command_impl(process, command, app_handle).await;
+            }
+        }
+    }
+    let result = result.map_err(CommandFailure::into_error);
 
     if result.is_err() {
-        if let Some(process) = guard.as_mut() {
-            if let Ok(Some(status)) = process.child.try_wait() {
-                log::warn!("FunASR 进程已退出，状态码: {}", status);
-                state.set_funasr_ready(false);
-                *guard = None;
-                // 主动通知前端进程已崩溃
-                if let Some(handle) = app_handle {
-                    let _ = handle.emit(
-                        "funasr-status",
-                        serde_json::json!({
-                            "status": "crashed",
-                            "message": format!("FunASR 进程异常退出（状态码: {}），正在准备重启...", status)
-                        }),
-                    );
-                }
+        // 进程可能正在退出、还没被回收：等一个确认窗口再看，既不把瞬时错误
+        // 当成崩溃，也能发现 stdout 关闭后才退出的进程
+        let mut exited = exited_status(&mut guard);
+        if exited.is_none() && !grace.is_zero() {
+            tokio::time::sleep(grace).await;
+            exited = exited_status(&mut guard);
+        }
+        if let Some(status) = exited {
+            log::warn!("FunASR 进程已退出，状态码: {}", status);
+            state.set_funasr_ready(false);
+            *guard = None;
+            // 主动通知前端进程已崩溃
+            if let Some(handle) = app_handle {
+                let _ = handle.emit(
+                    "funasr-status",
+                    serde_json::json!({
+                        "status": "crashed",
+                        "message": format!("FunASR 进程异常退出（状态码: {}），正在准备重启...", status)
+                    }),
+                );
             }
         }
     }
@@ -1369,10 +2771,11 @@ async fn send_command_to_server(
 async fn send_command_impl(
     process: &mut FunasrProcess,
     command: &ServerCommand,
-) -> Result<ServerResponse, AppError> {
+    app_handle: Option<&tauri::AppHandle>,
+) -> Result<ServerResponse, CommandFailure> {
     let request_id = NEXT_SERVER_REQUEST_ID.fetch_add(1, Ordering::Relaxed);
     let mut command_value = serde_json::to_value(command)
-        .map_err(|e| AppError::Asr(format!("序列化命令失败: {}", e)))?;
+        .map_err(|e| CommandFailure::Other(AppError::Asr(format!("序列化命令失败: {}", e))))?;
     if let Some(map) = command_value.as_object_mut() {
         map.insert(
             "request_id".to_string(),
@@ -1381,7 +2784,7 @@ async fn send_command_impl(
     }
     // 序列化命令为 Python 端期望的扁平 JSON 格式
     let command_json = serde_json::to_string(&command_value)
-        .map_err(|e| AppError::Asr(format!("序列化命令失败: {}", e)))?;
+        .map_err(|e| CommandFailure::Other(AppError::Asr(format!("序列化命令失败: {}", e))))?;
 
     // 写入命令到 stdin
     // `write_all` 确保所有字节都被写入
@@ -1389,19 +2792,19 @@ async fn send_command_impl(
         .stdin
         .write_all(format!("{}\n", command_json).as_bytes())
         .await
-        .map_err(|e| AppError::Asr(format!("写入命令到 FunASR 失败: {}", e)))?;
+        .map_err(|e| {
+            CommandFailure::Write(AppError::Asr(format!("写入命令到 FunASR 失败: {}", e)))
+        })?;
 
     // `flush` 确保缓冲区的数据被立即发送
-    process
-        .stdin
-        .flush()
-        .await
-        .map_err(|e| AppError::Asr(format!("刷新 stdin 缓冲区失败: {}", e)))?;
+    process.stdin.flush().await.map_err(|e| {
+        CommandFailure::Write(AppError::Asr(format!("刷新 stdin 缓冲区失败: {}", e)))
+    })?;
 
-    // 从 stdout 读取响应（允许跳过非 JSON 行）
-    read_json_response_matching(
+    // 从 stdout 读取响应（允许跳过非 JSON 行），中间进度行转发为 transcription-progress
+    read_json_response_with_progress(
         &mut process.stdout,
-        Duration::from_secs(SERVER_RESPONSE_TIMEOUT_SECS),
+        command.response_timeout(),
         "等待 FunASR 响应",
         |response: &ServerResponse| match response.request_id {
             Some(actual) => actual == request_id,
@@ -1410,8 +2813,27 @@ async fn send_command_impl(
                 true
             }
         },
+        |progress| {
+            if progress.request_id.is_some_and(|id| id != request_id) {
+                return;
+            }
+            let (Some(handle), Some(fraction)) = (app_handle, progress.fraction()) else {
+                return;
+            };
+            let _ = handle.emit(
+                "transcription-progress",
+                serde_json::json!({
+                    "requestId": request_id,
+                    "current": progress.current,
+                    "total": progress.total,
+                    "fraction": fraction,
+                    "message": progress.message,
+                }),
+            );
+        },
     )
     .await
+    .map_err(CommandFailure::Other)
 }
 
 async fn try_send_exit_command(process: &mut FunasrProcess) -> Result<(), AppError> {
@@ -1466,6 +2888,10 @@ pub async fn check_status(
                 device: Some("cloud".into()),
                 gpu_name: None,
                 gpu_memory_total: None,
+                cpu_threads: None,
+                avg_rtf: state.engine.average_rtf(),
+                itn: None,
+                punctuation: None,
                 message: if has_key {
                     format!("{} 在线服务就绪", label)
                 } else {
@@ -1505,12 +2931,16 @@ pub async fn check_status(
         Ok(response) => {
             let model_loaded = response.is_model_loaded();
 
-            let initialized = response.initialized.unwrap_or(false) || model_loaded;
-            if initialized {
+            let ready = response
+                .reported_model_state()
+                .unwrap_or_else(|| response.initialized.unwrap_or(false));
+            if ready {
                 state.set_funasr_ready(true);
+            } else if state.is_funasr_ready() && response.reported_model_state() == Some(false) {
+                log::warn!("引擎报告模型未加载，暂停接受录音");
+                state.set_funasr_ready(false);
             }
-
-            let ready = state.is_funasr_ready() || initialized;
+            let ready = ready || state.is_funasr_ready();
             let message = response
                 .message
                 .or(response.error)
@@ -1523,6 +2953,10 @@ pub async fn check_status(
                 device: response.device,
                 gpu_name: response.gpu_name,
                 gpu_memory_total: response.gpu_memory_total,
+                cpu_threads: response.cpu_threads,
+                avg_rtf: state.engine.average_rtf(),
+                itn: response.itn,
+                punctuation: response.punctuation,
                 message,
                 engine: response.engine,
                 models_present: Some(true),
@@ -1603,6 +3037,7 @@ pub async fn stop_server(state: &AppState) -> Result<(), AppError> {
     // 更新状态
     state.set_funasr_ready(false);
     state.set_inline_audio_transport(None);
+    state.engine.server_capabilities.lock().clear();
 
     log::info!("FunASR 服务器已停止");
     Ok(())
@@ -1768,7 +3203,7 @@ fn snapshot_matches_legacy_weight_check(snapshot_path: &std::path::Path) -> bool
 /// - `FunAudioLLM/SenseVoiceSmall` + `funasr/fsmn-vad`
 ///
 /// 注：SenseVoiceSmall 内置 ITN 标点恢复，不再需要独立的 ct-punc 模型
-fn inspect_model_files_for_engine(engine: &str) -> ModelCheckResult {
+pub(crate) fn inspect_model_files_for_engine(engine: &str) -> ModelCheckResult {
     if paths::is_online_engine(engine) {
         return ModelCheckResult {
             all_present: true,
@@ -1821,6 +3256,56 @@ fn inspect_model_files_for_engine(engine: &str) -> ModelCheckResult {
     }
 }
 
+/// 本地引擎互为兜底；在线引擎不参与（需要 API Key，不能静默切过去）。
+fn alternate_local_engine(engine: &str) -> Option<&'static str> {
+    match engine {
+        "sensevoice" => Some("whisper"),
+        "whisper" => Some("sensevoice"),
+        _ => None,
+    }
+}
+
+/// 配置的本地引擎缺模型、另一个本地引擎模型齐全时，返回应改用的引擎和缺失的模型。
+fn pick_fallback_engine(
+    engine: &str,
+    inspect: impl Fn(&str) -> ModelCheckResult,
+) -> Option<(&'static str, Vec<String>)> {
+    let alternate = alternate_local_engine(engine)?;
+    let current = inspect(engine);
+    if current.all_present || !inspect(alternate).all_present {
+        return None;
+    }
+    Some((alternate, current.missing_models))
+}
+
+/// 选出本次启动实际使用的引擎。只影响这一次启动，不改写引擎配置，
+/// 补齐模型后下次启动会回到用户选的引擎。
+fn fallback_engine_for_run(app_handle: &tauri::AppHandle, engine: &str) -> String {
+    if paths::engine_env_override().is_some() {
+        return engine.to_string();
+    }
+    let Some((fallback, missing_models)) =
+        pick_fallback_engine(engine, inspect_model_files_for_engine)
+    else {
+        return engine.to_string();
+    };
+    log::warn!(
+        "引擎 {} 缺少模型 {:?}，本次启动临时使用模型齐全的 {}",
+        engine,
+        missing_models,
+        fallback
+    );
+    let _ = app_handle.emit(
+        "engine-fallback",
+        serde_json::json!({
+            "from": engine,
+            "to": fallback,
+            "missingModels": missing_models,
+        }),
+    );
+    fallback.to_string()
+}
+
 pub async fn check_model_files() -> Result<ModelCheckResult, AppError> {
     Ok(inspect_model_files_for_engine(&paths::read_engine_config()))
 }
@@ -1831,8 +3316,15 @@ use tauri::Emitter;
 #[cfg(test)]
 mod tests {
     use super::{
-        engine_install_fingerprint_matches, read_json_response, read_json_response_matching,
-        EngineProgressGate, ServerResponse, StartingFlagGuard, ENGINE_ARCHIVE_FINGERPRINT,
+        asr_formatting_command, effective_crash_grace_ms, engine_install_fingerprint_matches,
+        languages_from_capabilities, next_cycle_language, normalize_language_cycle,
+        normalize_server_log_level, parse_progress_line, parse_server_version_constant,
+        pick_fallback_engine, read_json_response, read_json_response_matching,
+        read_json_response_with_progress, select_model_for_vram,
+        server_response_to_transcription_result, tail_lines, validate_max_vram_gb,
+        vram_budget_report, AsrFormattingSettings, EngineProgressGate, ModelCheckResult,
+        ServerCommand, ServerResponse, StartingFlagGuard, DEFAULT_SERVER_CRASH_GRACE_MS,
+        ENGINE_ARCHIVE_FINGERPRINT, MAX_SERVER_CRASH_GRACE_MS,
     };
     use std::sync::atomic::{AtomicU64, Ordering};
     use std::sync::Arc;
@@ -1933,6 +3425,18 @@ mod tests {
         assert_eq!(response.message.as_deref(), Some("ok"));
     }
 
+    #[tokio::test]
+    async fn read_json_response_skips_invalid_utf8_line_before_json() {
+        let response = read_response_from_chunks(&[
+            b"Traceback \xb4\xed\xce\xf3: \xff\n",
+            br#"{"success":true,"text":"\u4f60\u597d"}\n"#,
+        ])
+        .await;
+
+        assert_eq!(response.success, Some(true));
+        assert_eq!(response.text.as_deref(), Some("你好"));
+    }
+
     #[tokio::test]
     async fn read_json_response_recovers_json_from_mixed_line() {
         let response =
@@ -1942,6 +3446,23 @@ mod tests {
         assert_eq!(response.message.as_deref(), Some("ok"));
     }
 
+    #[tokio::test]
+    async fn read_json_response_skips_log_lines_that_merely_contain_json() {
+        let response = read_response_from_chunks(&[
+            b"INFO: loaded {model}\n",
+            br#"INFO: loaded {"model": "SenseVoiceSmall", "path": "/tmp/m"}
+"#,
+            br#"{"level": "warning", "message": "not a response"}
+"#,
+            br#"{"success":true,"message":"real"}
+"#,
+        ])
+        .await;
+
+        assert_eq!(response.success, Some(true));
+        assert_eq!(response.message.as_deref(), Some("real"));
+    }
+
     #[tokio::test]
     async fn read_json_response_skips_python_dict_noise_and_reads_next_json() {
         let response = read_response_from_chunks(&[
@@ -2011,6 +3532,178 @@ mod tests {
         assert_eq!(response.message.as_deref(), Some("legacy"));
     }
 
+    #[tokio::test]
+    async fn read_json_response_forwards_progress_lines_before_final_response() {
+        let (mut writer, reader) = tokio::io::duplex(1024);
+        writer
+            .write_all(
+                br#"{"type":"progress","request_id":3,"current":15,"total":60,"message":"seg"}
+{"type":"progress","request_id":3,"current":60,"total":60}
+{"request_id":3,"success":true,"text":"done"}
+"#,
+            )
+            .await
+            .unwrap();
+        writer.shutdown().await.unwrap();
+        let mut reader = BufReader::new(reader);
+        let mut fractions = Vec::new();
+        let response = read_json_response_with_progress(
+            &mut reader,
+            Duration::from_secs(1),
+            "test",
+            |response: &ServerResponse| response.request_id == Some(3),
+            |progress| fractions.push(progress.fraction()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.text.as_deref(), Some("done"));
+        assert_eq!(fractions, vec![Some(0.25), Some(1.0)]);
+    }
+
+    #[tokio::test]
+    async fn progress_lines_reset_the_response_deadline() {
+        let (mut writer, reader) = tokio::io::duplex(1024);
+        tokio::spawn(async move {
+            for line in [
+                &br#"{"type":"progress","current":0,"total":60}"#[..],
+                br#"{"type":"progress","current":0,"total":60}"#,
+                br#"{"request_id":4,"success":true,"text":"long"}"#,
+            ] {
+                tokio::time::sleep(Duration::from_millis(250)).await;
+                writer.write_all(line).await.unwrap();
+                writer.write_all(b"\n").await.unwrap();
+            }
+        });
+        let mut reader = BufReader::new(reader);
+        // 总耗时约 750ms，超过 400ms 的超时，但每两行之间都不到 400ms
+        let response = read_json_response_with_progress(
+            &mut reader,
+            Duration::from_millis(400),
+            "test",
+            |response: &ServerResponse| response.request_id == Some(4),
+            |_| {},
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.text.as_deref(), Some("long"));
+    }
+
+    #[test]
+    fn transcribe_command_keeps_cjk_audio_path_as_utf8() {
+        let path = r"C:\Users\张三\AppData\Local\Temp\light_whisper_audio_1.wav";
+        let command = ServerCommand::Transcribe {
+            audio_path: Some(crate::utils::paths::strip_win_prefix(std::path::Path::new(
+                &format!(r"\\?\{}", path),
+            ))),
+            audio_base64: None,
+            audio_format: None,
+            sample_rate: None,
+            hot_words: None,
+        };
+        let json = serde_json::to_string(&command).unwrap();
+        assert!(
+            json.contains("张三"),
+            "path must be sent as raw UTF-8: {json}"
+        );
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["audio_path"].as_str(), Some(path));
+    }
+
+    #[test]
+    fn server_log_level_is_normalized_and_validated() {
+        assert_eq!(normalize_server_log_level(" DEBUG ").unwrap(), "debug");
+        assert_eq!(normalize_server_log_level("warn").unwrap(), "warning");
+        assert!(normalize_server_log_level("trace").is_err());
+
+        let json = serde_json::to_string(&ServerCommand::Configure {
+            log_level: Some("debug".into()),
+            itn: None,
+            punctuation: None,
+            diarization: None,
+            language: None,
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"action":"configure","log_level":"debug"}"#);
+    }
+
+    #[test]
+    fn language_cycle_is_validated_against_engine_and_rotates() {
+        let capabilities: Vec<String> = ["reload_model", "language:zh", "language:en"]
+            .map(String::from)
+            .to_vec();
+        let supported = languages_from_capabilities(&capabilities);
+        assert_eq!(supported, vec!["zh", "en"]);
+        let cycle = normalize_language_cycle(
+            "sensevoice",
+            Some(&supported),
+            vec![" ZH ".into(), "en".into(), "zh".into(), "auto".into()],
+        )
+        .unwrap();
+        assert_eq!(cycle, vec!["zh", "en", "auto"]);
+        let two = |a: &str, b: &str| vec![a.to_string(), b.to_string()];
+        assert!(normalize_language_cycle("sensevoice", Some(&supported), two("zh", "fr")).is_err());
+        assert!(normalize_language_cycle("whisper", Some(&supported), vec!["en".into()]).is_err());
+        assert!(normalize_language_cycle("whisper", None, two("zh", "en")).is_err());
+        assert!(normalize_language_cycle("glm-asr", Some(&supported), two("zh", "en")).is_err());
+
+        assert_eq!(
+            next_cycle_language(&cycle, Some("zh")).as_deref(),
+            Some("en")
+        );
+        assert_eq!(next_cycle_language(&cycle, Some("en")), None);
+        assert_eq!(next_cycle_language(&cycle, None).as_deref(), Some("zh"));
+        assert_eq!(
+            next_cycle_language(&cycle, Some("ja")).as_deref(),
+            Some("zh")
+        );
+
+        let json = serde_json::to_string(&ServerCommand::Configure {
+            log_level: None,
+            itn: None,
+            punctuation: None,
+            diarization: None,
+            language: Some(None),
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"action":"configure","language":null}"#);
+    }
+
+    #[test]
+    fn asr_formatting_command_omits_defaults_on_startup() {
+        let defaults = AsrFormattingSettings::default();
+        assert!(asr_formatting_command(defaults, true).is_none());
+
+        let raw = AsrFormattingSettings {
+            itn: false,
+            punctuation: true,
+        };
+        let json = serde_json::to_string(&asr_formatting_command(raw, true).unwrap()).unwrap();
+        assert_eq!(json, r#"{"action":"configure","itn":false}"#);
+
+        // 改回默认值时必须显式下发，否则运行中的引擎会保留之前的设置
+        let json =
+            serde_json::to_string(&asr_formatting_command(defaults, false).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"action":"configure","itn":true,"punctuation":true}"#
+        );
+    }
+
+    #[test]
+    fn progress_line_requires_progress_type_and_counts() {
+        assert!(
+            parse_progress_line(r#"{"type":"progress","current":1,"total":0}"#)
+                .is_some_and(|p| p.fraction().is_none())
+        );
+        assert!(parse_progress_line(
+            r#"{"success":false,"type":"transcription_error","error":"x"}"#
+        )
+        .is_none());
+        assert!(parse_progress_line(r#"{"type":"progress"}"#).is_none());
+    }
+
     #[test]
     fn engine_extraction_preserves_existing_engine_until_archive_succeeds() {
         let root = std::env::temp_dir().join(format!(
@@ -2033,4 +3726,263 @@ mod tests {
         );
         let _ = std::fs::remove_dir_all(root);
     }
+
+    #[test]
+    fn temp_audio_names_must_match_app_pattern() {
+        assert_eq!(
+            super::parse_temp_audio_pid("light_whisper_audio_4321_1700000000000_7.wav"),
+            Some(4321)
+        );
+        assert_eq!(
+            super::parse_temp_audio_pid("light_whisper_audio_4321_1700000000000.wav"),
+            None
+        );
+        assert_eq!(
+            super::parse_temp_audio_pid("light_whisper_audio_x_1_2.wav"),
+            None
+        );
+        assert_eq!(
+            super::parse_temp_audio_pid("light_whisper_audio_1_2_3.ogg"),
+            Some(1)
+        );
+        assert_eq!(
+            super::parse_temp_audio_pid("light_whisper_audio_1_2_3"),
+            None
+        );
+        assert_eq!(
+            super::parse_temp_audio_pid("light_whisper_audio_1_2_3."),
+            None
+        );
+        assert_eq!(super::parse_temp_audio_pid("other_1_2_3.wav"), None);
+        for extension in ["wav", "mp3", "flac"] {
+            assert_eq!(
+                super::create_temp_audio_path()
+                    .with_extension(extension)
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(super::parse_temp_audio_pid),
+                Some(std::process::id())
+            );
+        }
+    }
+
+    #[test]
+    fn orphaned_temp_audio_includes_non_wav_files() {
+        let dir = std::env::temp_dir().join(format!("lw_temp_orphans_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let foreign_pid = std::process::id().wrapping_add(1);
+        let orphan = dir.join(format!(
+            "light_whisper_audio_{foreign_pid}_1700000000000_3.mp3"
+        ));
+        std::fs::write(&orphan, b"mp3").unwrap();
+        let old = std::time::SystemTime::now() - super::TEMP_AUDIO_ORPHAN_MIN_AGE * 2;
+        std::fs::File::options()
+            .write(true)
+            .open(&orphan)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        let orphans = super::find_orphaned_temp_audio(std::slice::from_ref(&dir));
+        assert_eq!(orphans, vec![(orphan, 3)]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    fn model_check(engine: &str, present: bool) -> ModelCheckResult {
+        ModelCheckResult {
+            all_present: present,
+            asr_model: present,
+            vad_model: true,
+            punc_model: true,
+            engine: engine.to_string(),
+            cache_path: String::new(),
+            missing_models: if present {
+                Vec::new()
+            } else {
+                vec![format!("{} ASR", engine)]
+            },
+        }
+    }
+
+    #[test]
+    fn engine_fallback_only_when_alternate_models_are_complete() {
+        let only_whisper = |engine: &str| model_check(engine, engine == "whisper");
+        assert_eq!(
+            pick_fallback_engine("sensevoice", only_whisper),
+            Some(("whisper", vec!["sensevoice ASR".to_string()]))
+        );
+        assert_eq!(pick_fallback_engine("whisper", only_whisper), None);
+        assert_eq!(
+            pick_fallback_engine("sensevoice", |engine| model_check(engine, false)),
+            None
+        );
+        assert_eq!(
+            pick_fallback_engine("glm-asr", |engine| model_check(engine, false)),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn segments_keep_speaker_labels_from_numbers_or_strings() {
+        let response = read_response_from_chunks(&[br#"{"success":true,"text":"hi","segments":[{"start":0.0,"end":1.2,"text":"hi","speaker":0},{"start":1.2,"end":2.0,"text":"yo","speaker":"SPEAKER_01"},{"start":2.0,"end":2.5,"text":"ok"}]}
+"#])
+        .await;
+        let segments = server_response_to_transcription_result(response)
+            .segments
+            .expect("segments");
+        let speakers: Vec<_> = segments.iter().map(|s| s.speaker.as_deref()).collect();
+        assert_eq!(speakers, vec![Some("0"), Some("SPEAKER_01"), None]);
+        assert_eq!(segments[1].start_sec, 1.2);
+    }
+
+    #[test]
+    fn segments_without_timestamps_are_dropped_instead_of_failing_the_result() {
+        let response = init_response(serde_json::json!({
+            "success": true,
+            "text": "hi yo",
+            "segments": [{"text": "hi"}, {"start": 1.0, "end": 2.0, "text": "yo"}],
+        }));
+        let result = server_response_to_transcription_result(response);
+        assert!(result.success);
+        let segments = result.segments.expect("segments");
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].text, "yo");
+    }
+
+    #[test]
+    fn server_version_constant_is_parsed_from_script_source() {
+        let source = "import json\n\n# 协议版本\nSERVER_VERSION = \"2\"\nCLEANUP_EVERY_N = 20\n";
+        assert_eq!(parse_server_version_constant(source).as_deref(), Some("2"));
+        assert_eq!(
+            parse_server_version_constant("SERVER_VERSION='3-dev'  # local").as_deref(),
+            Some("3-dev")
+        );
+        assert_eq!(
+            parse_server_version_constant("SERVER_VERSION_NOTE = 1"),
+            None
+        );
+        assert_eq!(parse_server_version_constant("x = 1"), None);
+    }
+
+    fn init_response(value: serde_json::Value) -> ServerResponse {
+        serde_json::from_value(value).expect("server response")
+    }
+
+    #[test]
+    fn init_readiness_requires_loaded_models_when_reported() {
+        let ready = |value| init_response(value).init_models_ready();
+        assert!(ready(
+            serde_json::json!({"success": true, "model_loaded": true})
+        ));
+        assert!(!ready(
+            serde_json::json!({"success": true, "model_loaded": false})
+        ));
+        assert!(!ready(
+            serde_json::json!({"status": "ready", "initialized": true, "model_loaded": false})
+        ));
+        assert!(!ready(serde_json::json!({
+            "success": true,
+            "models": {"asr": true, "vad": true, "punc": false},
+        })));
+        assert!(ready(serde_json::json!({
+            "success": true,
+            "models": {"asr": true, "vad": true, "punc": true},
+        })));
+        // 完全不带模型状态的旧脚本沿用原来的判断
+        assert!(ready(serde_json::json!({"success": true})));
+        assert!(ready(serde_json::json!({"status": "ready"})));
+        assert!(!ready(
+            serde_json::json!({"success": false, "error": "boom"})
+        ));
+    }
+
+    #[test]
+    fn reload_command_serializes_and_waits_like_init() {
+        assert_eq!(
+            serde_json::to_string(&ServerCommand::ReloadModel).unwrap(),
+            r#"{"action":"reload_model"}"#
+        );
+        assert_eq!(
+            ServerCommand::ReloadModel.response_timeout(),
+            Duration::from_secs(SERVER_INIT_TIMEOUT_SECS)
+        );
+        assert_eq!(
+            ServerCommand::Status.response_timeout(),
+            Duration::from_secs(SERVER_RESPONSE_TIMEOUT_SECS)
+        );
+        let response = init_response(serde_json::json!({
+            "success": true,
+            "model_loaded": true,
+            "capabilities": [SERVER_CAPABILITY_RELOAD_MODEL],
+        }));
+        assert_eq!(
+            response.capabilities.as_deref(),
+            Some(&[SERVER_CAPABILITY_RELOAD_MODEL.to_string()][..])
+        );
+    }
+
+    #[test]
+    fn crash_grace_defaults_and_is_capped() {
+        assert_eq!(
+            effective_crash_grace_ms(None),
+            DEFAULT_SERVER_CRASH_GRACE_MS
+        );
+        assert_eq!(effective_crash_grace_ms(Some(0)), 0);
+        assert_eq!(
+            effective_crash_grace_ms(Some(60_000)),
+            MAX_SERVER_CRASH_GRACE_MS
+        );
+    }
+
+    #[test]
+    fn vram_budget_picks_largest_tier_that_fits() {
+        let tier = |budget| select_model_for_vram("whisper", budget).map(|t| t.compute_type);
+        assert_eq!(tier(None), Some("float16"));
+        assert_eq!(tier(Some(8.0)), Some("float16"));
+        assert_eq!(tier(Some(1.5)), Some("int8_float16"));
+        assert_eq!(
+            select_model_for_vram("whisper", Some(0.5)).map(|t| t.device),
+            Some("cpu")
+        );
+        assert!(select_model_for_vram("unknown", None).is_none());
+        assert!(validate_max_vram_gb(f64::NAN).is_err());
+        assert!(validate_max_vram_gb(0.1).is_err());
+        assert!(validate_max_vram_gb(4.0).is_ok());
+    }
+
+    #[test]
+    fn vram_budget_report_warns_on_downgrade_and_oversized_budget() {
+        let report = vram_budget_report("whisper", Some(1.5), Some(8.0));
+        assert_eq!(
+            report.selected.map(|t| t.compute_type),
+            Some("int8_float16")
+        );
+        assert!(report.warning.unwrap().contains("large-v3-turbo"));
+
+        let report = vram_budget_report("sensevoice", Some(16.0), Some(8.0));
+        assert_eq!(report.selected, report.preferred);
+        assert!(report.warning.unwrap().contains("超过显卡总显存"));
+
+        assert!(vram_budget_report("whisper", None, Some(8.0))
+            .warning
+            .is_none());
+    }
+
+    #[test]
+    fn stderr_tail_keeps_last_lines_and_drops_partial_first_line() {
+        let (lines, truncated) = tail_lines("a\r\nb\nc\n\n", 2, false);
+        assert_eq!(lines, vec!["b", "c"]);
+        assert!(truncated);
+
+        let (lines, truncated) = tail_lines("a\nb\n", 10, false);
+        assert_eq!(lines, vec!["a", "b"]);
+        assert!(!truncated);
+
+        let (lines, truncated) = tail_lines("rtial\nTraceback\n", 10, true);
+        assert_eq!(lines, vec!["Traceback"]);
+        assert!(truncated);
+
+        assert_eq!(tail_lines("", 5, false), (Vec::new(), false));
+    }
 }
@@ -115,6 +115,7 @@ pub async fn transcribe(
                 success: false,
                 error: parsed.message.or(Some(format!("GLM-ASR 错误码: {}", code))),
                 language: None,
+                segments: None,
             });
         }
     }
@@ -125,6 +126,7 @@ pub async fn transcribe(
         success: true,
         error: None,
         language: None,
+        segments: None,
     })
 }
 
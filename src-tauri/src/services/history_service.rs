@@ -148,8 +148,8 @@ fn safe_audio_path(file_name: &str) -> Option<PathBuf> {
 }
 
 fn open_connection() -> Result<Connection, String> {
-    std::fs::create_dir_all(paths::get_data_dir())
-        .map_err(|error| format!("创建历史数据目录失败: {error}"))?;
+    let data_dir = paths::try_get_data_dir().map_err(|error| error.to_string())?;
+    std::fs::create_dir_all(data_dir).map_err(|error| format!("创建历史数据目录失败: {error}"))?;
     let connection = Connection::open(history_db_path())
         .map_err(|error| format!("打开历史数据库失败: {error}"))?;
     configure_runtime_connection(&connection)?;
@@ -302,15 +302,20 @@ pub async fn initialize() -> Result<(), String> {
         .map(|_| ())
 }
 
-pub async fn save_audio(session_id: u64, wav: Vec<u8>) -> Result<String, String> {
+/// `extension` 取 "wav" 或 "ogg"，由 `HistorySettings::recording_format` 决定。
+pub async fn save_audio(
+    session_id: u64,
+    audio: Vec<u8>,
+    extension: &'static str,
+) -> Result<String, String> {
     initialize().await?;
     tokio::task::spawn_blocking(move || {
         let directory = history_audio_dir();
         std::fs::create_dir_all(&directory)
             .map_err(|error| format!("创建历史音频目录失败: {error}"))?;
-        let file_name = format!("{}-{session_id}.wav", now_millis());
+        let file_name = format!("{}-{session_id}.{extension}", now_millis());
         let path = directory.join(&file_name);
-        paths::atomic_write(&path, &wav).map_err(|error| format!("保存历史音频失败: {error}"))?;
+        paths::atomic_write(&path, &audio).map_err(|error| format!("保存历史音频失败: {error}"))?;
         Ok(file_name)
     })
     .await
@@ -778,6 +783,32 @@ pub async fn delete(id: i64) -> Result<bool, String> {
     .map_err(|error| format!("删除转写历史任务失败: {error}"))?
 }
 
+/// 修正已保存记录的最终文本；`original_text` 保留原始 ASR 不动。
+fn update_text_with_connection(
+    connection: &Connection,
+    id: i64,
+    text: &str,
+    updated_at: i64,
+) -> Result<bool, String> {
+    connection
+        .execute(
+            "UPDATE transcription_history SET text = ?2, updated_at = ?3 WHERE id = ?1",
+            params![id, text, updated_at],
+        )
+        .map(|changed| changed > 0)
+        .map_err(|error| format!("更新转写历史失败: {error}"))
+}
+
+pub async fn update_text(id: i64, text: String) -> Result<bool, String> {
+    initialize().await?;
+    tokio::task::spawn_blocking(move || {
+        let connection = open_connection()?;
+        update_text_with_connection(&connection, id, &text, now_millis())
+    })
+    .await
+    .map_err(|error| format!("更新转写历史任务失败: {error}"))?
+}
+
 fn cleanup_expired_with_connection(
     connection: &mut Connection,
     retention_days: u32,
@@ -854,7 +885,8 @@ mod tests {
     use super::{
         cleanup_expired_with_connection, configure_connection, get_for_reprocess_with_connection,
         latency_stats, map_stored_record, now_millis, release_audio_lease_with_connection,
-        safe_audio_path, table_has_column, HISTORY_COLUMNS, HISTORY_SCHEMA_VERSION,
+        safe_audio_path, table_has_column, update_text_with_connection, HISTORY_COLUMNS,
+        HISTORY_SCHEMA_VERSION,
     };
 
     #[test]
@@ -923,6 +955,36 @@ mod tests {
         assert_eq!(remaining, 0);
     }
 
+    #[test]
+    fn editing_history_text_keeps_original_asr() {
+        let connection = Connection::open_in_memory().expect("open in-memory history database");
+        configure_connection(&connection).expect("initialize history schema");
+        connection
+            .execute(
+                r#"
+                INSERT INTO transcription_history (
+                    session_id, created_at, updated_at, mode, status, text, original_text, engine
+                ) VALUES (7, 1, 1, 'dictation', 'success', '今天天气很号', '今天天气很号', 'sensevoice')
+                "#,
+                [],
+            )
+            .expect("insert history fixture");
+        let id = connection.last_insert_rowid();
+
+        assert!(update_text_with_connection(&connection, id, "今天天气很好", 99).expect("update"));
+        assert!(!update_text_with_connection(&connection, id + 1, "x", 99).expect("update missing"));
+        let stored = connection
+            .query_row(
+                &format!("SELECT {HISTORY_COLUMNS} FROM transcription_history WHERE id = ?1"),
+                params![id],
+                map_stored_record,
+            )
+            .expect("read updated history");
+        assert_eq!(stored.record.text, "今天天气很好");
+        assert_eq!(stored.record.original_text, "今天天气很号");
+        assert_eq!(stored.record.updated_at, 99);
+    }
+
     #[test]
     fn audio_lease_survives_source_row_deletion_until_reprocess_finishes() {
         let mut connection = Connection::open_in_memory().expect("open in-memory history database");
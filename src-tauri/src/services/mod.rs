@@ -3,6 +3,7 @@ pub mod alibaba_asr_service;
 pub mod assistant_service;
 pub mod audio_service;
 pub mod codex_oauth_service;
+pub mod config_bundle_service;
 pub mod download_service;
 pub mod funasr_service;
 pub mod glm_asr_service;
@@ -10,8 +11,13 @@ pub mod history_service;
 pub mod llm_client;
 pub mod llm_provider;
 pub mod profile_service;
+pub mod recording_archive_service;
+pub mod remote_asr_service;
+pub mod resource_service;
 pub mod screen_capture_service;
 pub mod selection_service;
+pub mod subtitle_export_service;
+pub mod usage_stats_service;
 pub mod web_search_service;
 
 #[cfg(test)]
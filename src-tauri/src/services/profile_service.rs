@@ -14,6 +14,8 @@ const MAX_SEGMENT_CHARS: usize = 12;
 const MAX_HOT_WORD_CHARS: usize = 24;
 const MAX_USER_HOT_WORD_CHARS: usize = 80;
 pub const MAX_APP_PROFILE_RULES: usize = 100;
+pub const MIN_INTERIM_COST_MS: u64 = 20;
+pub const MAX_INTERIM_COST_MS: u64 = 5000;
 const PROFILE_SAVE_DEBOUNCE_MS: u64 = 350;
 
 // ============================================================
@@ -130,7 +132,9 @@ fn take_pending_profile_save_if(
 }
 
 async fn write_profile_async(profile: &UserProfile) -> Result<(), String> {
-    let path = paths::get_data_dir().join("user_profile.json");
+    let path = paths::try_get_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("user_profile.json");
     let data = serialize_profile(profile)?;
     tokio::task::spawn_blocking(move || paths::atomic_write(&path, data.as_bytes()))
         .await
@@ -195,6 +199,47 @@ pub struct ProfileCleanupStats {
 
 pub fn cleanup_profile(profile: &mut UserProfile) -> ProfileCleanupStats {
     sanitize_history_settings(profile);
+    sanitize_interim_cost_settings(profile);
+    sanitize_download_retry_settings(profile);
+    sanitize_loudness_settings(profile);
+    sanitize_output_template(profile);
+    sanitize_post_hook(profile);
+    profile.strip_prefix = crate::services::audio_service::normalize_strip_prefixes(
+        std::mem::take(&mut profile.strip_prefix),
+    );
+    if profile.pending_paste_limit.is_some_and(|limit| {
+        limit == 0 || limit > crate::services::audio_service::MAX_PENDING_PASTE_LIMIT
+    }) {
+        profile.pending_paste_limit = None;
+    }
+    if profile.min_audio_duration_ms.is_some_and(|ms| {
+        crate::commands::audio::validate_min_audio_duration(ms as f64 / 1000.0).is_err()
+    }) {
+        profile.min_audio_duration_ms = None;
+    }
+    if let Some(methods) = profile.input_method_fallback.as_mut() {
+        methods.retain(|method| crate::commands::audio::validate_input_method(method).is_ok());
+        let mut seen = std::collections::HashSet::new();
+        methods.retain(|method| seen.insert(method.clone()));
+    }
+    if profile.cpu_threads.is_some_and(|threads| {
+        crate::services::funasr_service::validate_cpu_threads(threads).is_err()
+    }) {
+        // 换到核心更少的机器上时回退为自动
+        profile.cpu_threads = None;
+    }
+    profile.device_sample_rates.retain(|name, rate| {
+        !name.trim().is_empty()
+            && (crate::commands::audio::MIN_DEVICE_SAMPLE_RATE
+                ..=crate::commands::audio::MAX_DEVICE_SAMPLE_RATE)
+                .contains(rate)
+    });
+    if profile
+        .max_vram_gb
+        .is_some_and(|gb| crate::services::funasr_service::validate_max_vram_gb(gb).is_err())
+    {
+        profile.max_vram_gb = None;
+    }
     sanitize_app_profile_rules(profile);
     sanitize_blocked_hot_words(profile);
     let removed_hot_words = sanitize_hot_words(profile);
@@ -218,6 +263,107 @@ fn sanitize_history_settings(profile: &mut UserProfile) {
     if profile.history_settings.retention_days > 3650 {
         profile.history_settings.retention_days = 3650;
     }
+    if !matches!(
+        profile.history_settings.recording_format.as_str(),
+        "wav" | "opus"
+    ) {
+        profile.history_settings.recording_format = "wav".to_string();
+    }
+}
+
+pub fn validate_interim_cost_thresholds(
+    heavy_cost_ms: u64,
+    light_cost_ms: u64,
+) -> Result<(), String> {
+    let range = MIN_INTERIM_COST_MS..=MAX_INTERIM_COST_MS;
+    if !range.contains(&heavy_cost_ms) || !range.contains(&light_cost_ms) {
+        return Err(format!(
+            "中间转写耗时阈值需在 {}-{} 毫秒之间",
+            MIN_INTERIM_COST_MS, MAX_INTERIM_COST_MS
+        ));
+    }
+    if light_cost_ms >= heavy_cost_ms {
+        return Err("轻载阈值必须小于重载阈值".into());
+    }
+    Ok(())
+}
+
+fn sanitize_interim_cost_settings(profile: &mut UserProfile) {
+    let settings = &mut profile.interim_cost_settings;
+    if validate_interim_cost_thresholds(settings.heavy_cost_ms, settings.light_cost_ms).is_err() {
+        let auto_calibrate = settings.auto_calibrate;
+        *settings = InterimCostSettings {
+            auto_calibrate,
+            ..Default::default()
+        };
+    }
+}
+
+pub fn validate_download_retry_settings(
+    max_attempts: u32,
+    stall_timeout_secs: u64,
+) -> Result<(), String> {
+    use crate::services::download_service::{
+        MAX_DOWNLOAD_ATTEMPTS, MAX_DOWNLOAD_STALL_TIMEOUT_SECS,
+    };
+    if !(1..=MAX_DOWNLOAD_ATTEMPTS).contains(&max_attempts) {
+        return Err(format!("下载尝试次数需在 1-{} 之间", MAX_DOWNLOAD_ATTEMPTS));
+    }
+    if stall_timeout_secs > MAX_DOWNLOAD_STALL_TIMEOUT_SECS {
+        return Err(format!(
+            "下载无响应超时不能超过 {} 秒",
+            MAX_DOWNLOAD_STALL_TIMEOUT_SECS
+        ));
+    }
+    Ok(())
+}
+
+fn sanitize_download_retry_settings(profile: &mut UserProfile) {
+    let settings = &profile.download_retry_settings;
+    if validate_download_retry_settings(settings.max_attempts, settings.stall_timeout_secs).is_err()
+    {
+        profile.download_retry_settings = DownloadRetrySettings::default();
+    }
+}
+
+pub fn validate_loudness_target(target_lufs: f64) -> Result<(), String> {
+    use crate::services::audio_service::{MAX_LOUDNESS_TARGET_LUFS, MIN_LOUDNESS_TARGET_LUFS};
+    if !(MIN_LOUDNESS_TARGET_LUFS..=MAX_LOUDNESS_TARGET_LUFS).contains(&target_lufs) {
+        return Err(format!(
+            "目标响度需在 {} 到 {} LUFS 之间",
+            MIN_LOUDNESS_TARGET_LUFS, MAX_LOUDNESS_TARGET_LUFS
+        ));
+    }
+    Ok(())
+}
+
+fn sanitize_loudness_settings(profile: &mut UserProfile) {
+    if validate_loudness_target(profile.loudness_settings.target_lufs).is_err() {
+        profile.loudness_settings.target_lufs = LoudnessSettings::default().target_lufs;
+    }
+}
+
+fn sanitize_output_template(profile: &mut UserProfile) {
+    use crate::services::audio_service::{validate_output_template, DEFAULT_OUTPUT_TEMPLATE};
+    // 与默认值相同或不合法（缺少 {text}）时回退为 None，粘贴时按原文处理
+    if profile.output_template.as_deref().is_some_and(|template| {
+        template == DEFAULT_OUTPUT_TEMPLATE || validate_output_template(template).is_err()
+    }) {
+        profile.output_template = None;
+    }
+}
+
+fn sanitize_post_hook(profile: &mut UserProfile) {
+    use crate::services::audio_service::validate_post_hook;
+    // 手改配置文件写坏时直接关闭钩子，而不是带着非法超时去启动程序
+    if let Some(err) = profile
+        .post_hook
+        .as_ref()
+        .and_then(|hook| validate_post_hook(hook).err())
+    {
+        log::warn!("后置钩子配置无效，已关闭: {}", err);
+        profile.post_hook = None;
+    }
 }
 
 pub fn sanitize_app_profile_rules(profile: &mut UserProfile) {
@@ -837,7 +983,27 @@ fn extract_diff_segments(original: &str, polished: &str) -> Vec<(String, String)
 
 #[cfg(test)]
 mod tests {
-    use super::collect_diff_correction_pairs;
+    use super::{
+        collect_diff_correction_pairs, sanitize_interim_cost_settings,
+        validate_interim_cost_thresholds,
+    };
+    use crate::state::user_profile::UserProfile;
+
+    #[test]
+    fn interim_cost_thresholds_are_validated_and_sanitized() {
+        assert!(validate_interim_cost_thresholds(420, 180).is_ok());
+        assert!(validate_interim_cost_thresholds(180, 180).is_err());
+        assert!(validate_interim_cost_thresholds(9000, 180).is_err());
+
+        let mut profile = UserProfile::default();
+        profile.interim_cost_settings.heavy_cost_ms = 100;
+        profile.interim_cost_settings.light_cost_ms = 300;
+        profile.interim_cost_settings.auto_calibrate = true;
+        sanitize_interim_cost_settings(&mut profile);
+        assert_eq!(profile.interim_cost_settings.heavy_cost_ms, 420);
+        assert_eq!(profile.interim_cost_settings.light_cost_ms, 180);
+        assert!(profile.interim_cost_settings.auto_calibrate);
+    }
 
     #[test]
     fn collect_diff_correction_pairs_merges_and_dedupes_baselines() {
@@ -0,0 +1,128 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::utils::paths;
+
+// ---------- 录音存档 ----------
+//
+// 开关打开时把每次听写的 WAV 和转写文本另存到数据目录下的 recordings/，
+// 文件名 `{时间}_{session_id}.wav` / `.txt`，用于事后复核和纠错。识别失败时
+// `.txt` 记错误原因，没识别到内容时为空。与历史记录里的音频互不影响；写盘
+// 失败只记日志，不影响粘贴。
+
+const RECORDINGS_DIR: &str = "recordings";
+const TEXT_PREVIEW_CHARS: usize = 80;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedRecording {
+    pub file_name: String,
+    /// WAV 头里读出的时长；文件损坏时为 None
+    pub duration_sec: Option<f64>,
+    pub text_preview: String,
+}
+
+fn recordings_dir() -> PathBuf {
+    paths::get_data_dir().join(RECORDINGS_DIR)
+}
+
+fn recording_stem(session_id: u64) -> String {
+    format!(
+        "{}_{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        session_id
+    )
+}
+
+fn save_recording_to(dir: &Path, stem: &str, wav: &[u8], text: &str) -> std::io::Result<()> {
+    paths::atomic_write(&dir.join(format!("{stem}.wav")), wav)?;
+    paths::atomic_write(&dir.join(format!("{stem}.txt")), text.as_bytes())
+}
+
+/// 后台保存一次录音，立即返回。
+pub fn spawn_save(session_id: u64, wav: Vec<u8>, text: String) {
+    tokio::task::spawn_blocking(move || {
+        let stem = recording_stem(session_id);
+        match save_recording_to(&recordings_dir(), &stem, &wav, &text) {
+            Ok(()) => log::info!("录音已存档: {}.wav", stem),
+            Err(err) => log::warn!("保存录音存档失败 ({}): {}", stem, err),
+        }
+    });
+}
+
+fn wav_duration_sec(path: &Path) -> Option<f64> {
+    let reader = hound::WavReader::open(path).ok()?;
+    let spec = reader.spec();
+    let frames = reader.duration() as f64;
+    (spec.sample_rate > 0).then(|| frames / spec.sample_rate as f64)
+}
+
+fn text_preview(path: &Path) -> String {
+    let text = std::fs::read_to_string(path).unwrap_or_default();
+    let trimmed = text.trim();
+    let mut preview: String = trimmed.chars().take(TEXT_PREVIEW_CHARS).collect();
+    if trimmed.chars().count() > TEXT_PREVIEW_CHARS {
+        preview.push('…');
+    }
+    preview
+}
+
+fn list_recordings_in(dir: &Path) -> Result<Vec<SavedRecording>, String> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("读取录音存档目录失败: {}", err)),
+    };
+    let mut recordings: Vec<SavedRecording> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "wav"))
+        .filter_map(|path| {
+            let file_name = path.file_name()?.to_str()?.to_string();
+            Some(SavedRecording {
+                duration_sec: wav_duration_sec(&path),
+                text_preview: text_preview(&path.with_extension("txt")),
+                file_name,
+            })
+        })
+        .collect();
+    // 文件名以时间开头，倒序即最新在前
+    recordings.sort_by(|a, b| b.file_name.cmp(&a.file_name));
+    Ok(recordings)
+}
+
+pub async fn list() -> Result<Vec<SavedRecording>, String> {
+    tokio::task::spawn_blocking(|| list_recordings_in(&recordings_dir()))
+        .await
+        .map_err(|e| format!("读取录音存档失败: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{list_recordings_in, save_recording_to, TEXT_PREVIEW_CHARS};
+    use crate::services::audio_service::encode_wav;
+
+    #[test]
+    fn saved_recordings_are_listed_newest_first_with_duration_and_preview() {
+        let dir = std::env::temp_dir().join(format!("lw_recordings_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(list_recordings_in(&dir).unwrap().is_empty());
+
+        let wav = encode_wav(&[0i16; 8000], 16_000).unwrap();
+        save_recording_to(&dir, "20260301-090000_1", &wav, "  你好 ").unwrap();
+        let long_text = "a".repeat(TEXT_PREVIEW_CHARS + 5);
+        save_recording_to(&dir, "20260301-100000_2", &wav, &long_text).unwrap();
+
+        let recordings = list_recordings_in(&dir).unwrap();
+        assert_eq!(recordings.len(), 2);
+        assert_eq!(recordings[0].file_name, "20260301-100000_2.wav");
+        assert_eq!(
+            recordings[0].text_preview.chars().count(),
+            TEXT_PREVIEW_CHARS + 1
+        );
+        assert_eq!(recordings[1].text_preview, "你好");
+        assert_eq!(recordings[1].duration_sec, Some(0.5));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,233 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::services::audio_service;
+use crate::state::AppState;
+use crate::utils::AppError;
+
+// ---------- OpenAI 兼容转写接口（/audio/transcriptions） ----------
+//
+// 目前只用于切换引擎前的连通性测试：发一段 1 秒的合成音频，按 HTTP 状态和
+// 响应体区分鉴权、网络、格式错误，不保存任何设置。
+
+const TRANSCRIPTIONS_PATH: &str = "/audio/transcriptions";
+const DEFAULT_REMOTE_ASR_MODEL: &str = "whisper-1";
+const TEST_REQUEST_TIMEOUT_SECS: u64 = 20;
+const TEST_SAMPLE_DURATION_SECS: f64 = 1.0;
+/// HTTP 错误响应体只截取这么多字符回给前端
+const ERROR_BODY_PREVIEW_CHARS: usize = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteEndpointErrorKind {
+    InvalidUrl,
+    Network,
+    Timeout,
+    Auth,
+    Http,
+    Format,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteEndpointTestReport {
+    pub success: bool,
+    /// 实际请求的完整地址
+    pub url: String,
+    pub latency_ms: u64,
+    pub http_status: Option<u16>,
+    pub error_kind: Option<RemoteEndpointErrorKind>,
+    pub error: Option<String>,
+    /// 接口对合成音频返回的文本，通常为空，只用来确认格式
+    pub text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TranscriptionResponse {
+    text: Option<String>,
+}
+
+/// 接受基础地址（`https://host/v1`）或完整的 `/audio/transcriptions` 地址。
+pub fn transcriptions_url(url: &str) -> Result<String, String> {
+    let url = url.trim().trim_end_matches('/');
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return Err("接口地址必须以 http:// 或 https:// 开头".to_string());
+    }
+    if url.ends_with(TRANSCRIPTIONS_PATH) {
+        Ok(url.to_string())
+    } else {
+        Ok(format!("{}{}", url, TRANSCRIPTIONS_PATH))
+    }
+}
+
+/// 按状态码和响应体判定测试结果；成功时返回接口给出的文本。
+fn classify_response(status: u16, body: &str) -> Result<String, (RemoteEndpointErrorKind, String)> {
+    if status == 401 || status == 403 {
+        return Err((
+            RemoteEndpointErrorKind::Auth,
+            format!("API Key 无效或没有权限 (HTTP {})", status),
+        ));
+    }
+    if !(200..300).contains(&status) {
+        let preview: String = body.chars().take(ERROR_BODY_PREVIEW_CHARS).collect();
+        return Err((
+            RemoteEndpointErrorKind::Http,
+            format!("接口返回 HTTP {}: {}", status, preview.trim()),
+        ));
+    }
+    match serde_json::from_str::<TranscriptionResponse>(body) {
+        Ok(TranscriptionResponse { text: Some(text) }) => Ok(text),
+        _ => Err((
+            RemoteEndpointErrorKind::Format,
+            "响应不是 OpenAI 兼容格式（缺少 text 字段）".to_string(),
+        )),
+    }
+}
+
+pub async fn test_endpoint(
+    state: &AppState,
+    url: &str,
+    api_key: &str,
+    model: Option<&str>,
+) -> Result<RemoteEndpointTestReport, AppError> {
+    let failed = |url: String, kind, error: String, latency_ms, http_status| {
+        log::warn!("远程转写接口测试失败 ({}): {}", url, error);
+        RemoteEndpointTestReport {
+            success: false,
+            url,
+            latency_ms,
+            http_status,
+            error_kind: Some(kind),
+            error: Some(error),
+            text: None,
+        }
+    };
+
+    let url = match transcriptions_url(url) {
+        Ok(url) => url,
+        Err(error) => {
+            return Ok(failed(
+                url.to_string(),
+                RemoteEndpointErrorKind::InvalidUrl,
+                error,
+                0,
+                None,
+            ))
+        }
+    };
+    let audio = audio_service::synthetic_sample_wav(TEST_SAMPLE_DURATION_SECS)?;
+    let file_part = reqwest::multipart::Part::bytes(audio)
+        .file_name("audio.wav")
+        .mime_str("audio/wav")
+        .map_err(|e| AppError::Asr(format!("构建 multipart 失败: {}", e)))?;
+    let form = reqwest::multipart::Form::new()
+        .part("file", file_part)
+        .text(
+            "model",
+            model
+                .map(str::trim)
+                .filter(|model| !model.is_empty())
+                .unwrap_or(DEFAULT_REMOTE_ASR_MODEL)
+                .to_string(),
+        );
+
+    let mut request = state
+        .http_client
+        .post(&url)
+        .multipart(form)
+        .timeout(Duration::from_secs(TEST_REQUEST_TIMEOUT_SECS));
+    if !api_key.trim().is_empty() {
+        request = request.bearer_auth(api_key.trim());
+    }
+
+    let started = Instant::now();
+    let response = match request.send().await {
+        Ok(response) => response,
+        Err(err) => {
+            let latency_ms = started.elapsed().as_millis() as u64;
+            let (kind, error) = if err.is_timeout() {
+                (
+                    RemoteEndpointErrorKind::Timeout,
+                    format!("请求超时（{} 秒）", TEST_REQUEST_TIMEOUT_SECS),
+                )
+            } else {
+                (
+                    RemoteEndpointErrorKind::Network,
+                    format!("无法连接到接口: {}", err),
+                )
+            };
+            return Ok(failed(url, kind, error, latency_ms, None));
+        }
+    };
+    let status = response.status().as_u16();
+    let body = response.text().await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    let body = match body {
+        Ok(body) => body,
+        Err(err) => {
+            return Ok(failed(
+                url,
+                RemoteEndpointErrorKind::Network,
+                format!("读取响应失败: {}", err),
+                latency_ms,
+                Some(status),
+            ))
+        }
+    };
+
+    match classify_response(status, &body) {
+        Ok(text) => {
+            log::info!("远程转写接口测试成功 ({})，耗时 {}ms", url, latency_ms);
+            Ok(RemoteEndpointTestReport {
+                success: true,
+                url,
+                latency_ms,
+                http_status: Some(status),
+                error_kind: None,
+                error: None,
+                text: Some(text),
+            })
+        }
+        Err((kind, error)) => Ok(failed(url, kind, error, latency_ms, Some(status))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{classify_response, transcriptions_url, RemoteEndpointErrorKind};
+
+    #[test]
+    fn url_accepts_base_or_full_transcriptions_path() {
+        assert_eq!(
+            transcriptions_url(" https://api.example.com/v1/ ").unwrap(),
+            "https://api.example.com/v1/audio/transcriptions"
+        );
+        assert_eq!(
+            transcriptions_url("http://localhost:8000/v1/audio/transcriptions").unwrap(),
+            "http://localhost:8000/v1/audio/transcriptions"
+        );
+        assert!(transcriptions_url("api.example.com/v1").is_err());
+    }
+
+    #[test]
+    fn responses_are_classified_by_status_and_shape() {
+        assert_eq!(classify_response(200, r#"{"text":""}"#), Ok(String::new()));
+        assert_eq!(
+            classify_response(401, "unauthorized").unwrap_err().0,
+            RemoteEndpointErrorKind::Auth
+        );
+        assert_eq!(
+            classify_response(404, "not found").unwrap_err().0,
+            RemoteEndpointErrorKind::Http
+        );
+        assert_eq!(
+            classify_response(200, "<html></html>").unwrap_err().0,
+            RemoteEndpointErrorKind::Format
+        );
+        assert_eq!(
+            classify_response(200, r#"{"result":"ok"}"#).unwrap_err().0,
+            RemoteEndpointErrorKind::Format
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::state::AppState;
+
+// ---------- 进程内存占用（诊断用） ----------
+//
+// 统一报告常驻内存（RSS / Windows 工作集）。Linux 读 /proc/<pid>/status，
+// Windows 调 GetProcessMemoryInfo，macOS 借助系统自带的 ps，避免为此引入新依赖。
+
+/// 引擎进程锁被转写占用时最多等这么久，拿不到就只报告进程在运行
+const ENGINE_LOCK_WAIT: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceUsage {
+    pub app_pid: u32,
+    pub app_rss_bytes: Option<u64>,
+    pub engine_running: bool,
+    pub engine_pid: Option<u32>,
+    pub engine_rss_bytes: Option<u64>,
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn parse_vm_rss_bytes(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kib = line
+        .trim_start_matches("VmRSS:")
+        .split_whitespace()
+        .next()?
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(target_os = "linux")]
+fn process_rss_bytes(pid: u32) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    parse_vm_rss_bytes(&status)
+}
+
+#[cfg(target_os = "windows")]
+fn process_rss_bytes(pid: u32) -> Option<u64> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::ProcessStatus::{
+        GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS,
+    };
+    use windows_sys::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return None;
+        }
+        let mut counters: PROCESS_MEMORY_COUNTERS = std::mem::zeroed();
+        counters.cb = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+        let ok = GetProcessMemoryInfo(handle, &mut counters, counters.cb);
+        CloseHandle(handle);
+        (ok != 0).then_some(counters.WorkingSetSize as u64)
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn process_rss_bytes(pid: u32) -> Option<u64> {
+    let output = std::process::Command::new("ps")
+        .args(["-o", "rss=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let kib = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn process_rss_bytes(_pid: u32) -> Option<u64> {
+    None
+}
+
+pub async fn resource_usage(state: &AppState) -> ResourceUsage {
+    let app_pid = std::process::id();
+    let (engine_running, engine_pid) =
+        match tokio::time::timeout(ENGINE_LOCK_WAIT, state.engine.funasr_process.lock()).await {
+            Ok(guard) => (
+                guard.is_some(),
+                guard.as_ref().and_then(|process| process.child.id()),
+            ),
+            Err(_) => {
+                log::debug!("引擎进程正忙，跳过读取其内存占用");
+                (true, None)
+            }
+        };
+    let (app_rss_bytes, engine_rss_bytes) = tokio::task::spawn_blocking(move || {
+        (
+            process_rss_bytes(app_pid),
+            engine_pid.and_then(process_rss_bytes),
+        )
+    })
+    .await
+    .unwrap_or((None, None));
+
+    ResourceUsage {
+        app_pid,
+        app_rss_bytes,
+        engine_running,
+        engine_pid,
+        engine_rss_bytes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_vm_rss_bytes;
+
+    #[test]
+    fn parses_vm_rss_from_proc_status() {
+        let status = "Name:\tlight-whisper\nVmPeak:\t  900000 kB\nVmRSS:\t  123456 kB\n";
+        assert_eq!(parse_vm_rss_bytes(status), Some(123456 * 1024));
+        assert_eq!(parse_vm_rss_bytes("Name:\tx\n"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn reads_own_rss_on_linux() {
+        assert!(super::process_rss_bytes(std::process::id()).is_some_and(|bytes| bytes > 0));
+    }
+}
@@ -0,0 +1,214 @@
+use serde::Deserialize;
+
+// ---------- SRT / WebVTT 字幕导出 ----------
+//
+// 识别结果若带分段时间戳，每段输出一条字幕；没有分段时整段文本作为一条字幕，
+// 覆盖整个音频时长。时间一律以秒为单位传入，格式化时四舍五入到毫秒。
+
+/// 没有时长信息时，按大约每秒 4 个字估算，至少 1 秒。
+const FALLBACK_CHARS_PER_SEC: f64 = 4.0;
+const MIN_FALLBACK_DURATION_SEC: f64 = 1.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+}
+
+impl SubtitleFormat {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "srt" => Ok(Self::Srt),
+            "vtt" | "webvtt" => Ok(Self::Vtt),
+            _ => Err("字幕导出格式仅支持 SRT 或 VTT".into()),
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Srt => "srt",
+            Self::Vtt => "vtt",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Srt => "SubRip",
+            Self::Vtt => "WebVTT",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubtitleCue {
+    pub start_sec: f64,
+    pub end_sec: f64,
+    pub text: String,
+    /// 说话人分离给出的标签；有值时正文前加 `[Speaker N]`
+    #[serde(default)]
+    pub speaker: Option<String>,
+}
+
+/// 没有分段时的兜底：整段文本一条字幕，时长取音频时长或按字数估算。
+pub fn single_cue(text: &str, duration_sec: Option<f64>) -> SubtitleCue {
+    let text = text.trim();
+    let duration = duration_sec
+        .filter(|value| value.is_finite() && *value > 0.0)
+        .unwrap_or_else(|| {
+            (text.chars().count() as f64 / FALLBACK_CHARS_PER_SEC).max(MIN_FALLBACK_DURATION_SEC)
+        });
+    SubtitleCue {
+        start_sec: 0.0,
+        end_sec: duration,
+        text: text.to_string(),
+        speaker: None,
+    }
+}
+
+/// 引擎给的说话人标签可能是从 0 开始的编号，也可能是 "SPEAKER_01" 之类的名字；
+/// 纯数字按 1 起始显示，其余原样保留。
+fn speaker_label(speaker: &str) -> Option<String> {
+    let speaker = speaker.trim();
+    if speaker.is_empty() {
+        return None;
+    }
+    Some(match speaker.parse::<u32>() {
+        Ok(index) => format!("Speaker {}", index.saturating_add(1)),
+        Err(_) => speaker.to_string(),
+    })
+}
+
+fn format_timestamp(seconds: f64, millis_separator: char) -> String {
+    let total_ms = if seconds.is_finite() && seconds > 0.0 {
+        (seconds * 1000.0).round() as u64
+    } else {
+        0
+    };
+    let hours = total_ms / 3_600_000;
+    let minutes = (total_ms / 60_000) % 60;
+    let secs = (total_ms / 1000) % 60;
+    let millis = total_ms % 1000;
+    format!("{hours:02}:{minutes:02}:{secs:02}{millis_separator}{millis:03}")
+}
+
+/// 字幕正文里的空行会被播放器当成字幕结束，这里把连续换行压成单个换行。
+fn cue_body(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn render(format: SubtitleFormat, cues: &[SubtitleCue]) -> String {
+    let mut output = String::new();
+    let separator = match format {
+        SubtitleFormat::Srt => ',',
+        SubtitleFormat::Vtt => {
+            output.push_str("WEBVTT\n\n");
+            '.'
+        }
+    };
+    let mut index = 0;
+    for cue in cues {
+        let mut body = cue_body(&cue.text);
+        if body.is_empty() {
+            continue;
+        }
+        if let Some(label) = cue.speaker.as_deref().and_then(speaker_label) {
+            body = format!("[{}] {}", label, body);
+        }
+        index += 1;
+        let end_sec = cue.end_sec.max(cue.start_sec);
+        if format == SubtitleFormat::Srt {
+            output.push_str(&format!("{index}\n"));
+        }
+        output.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_timestamp(cue.start_sec, separator),
+            format_timestamp(end_sec, separator),
+            body
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_timestamp, render, single_cue, SubtitleCue, SubtitleFormat};
+
+    fn cue(start_sec: f64, end_sec: f64, text: &str) -> SubtitleCue {
+        SubtitleCue {
+            start_sec,
+            end_sec,
+            text: text.into(),
+            speaker: None,
+        }
+    }
+
+    #[test]
+    fn speaker_labels_prefix_cue_text() {
+        let mut first = cue(0.0, 1.0, "你好");
+        first.speaker = Some("0".into());
+        let mut second = cue(1.0, 2.0, "hi");
+        second.speaker = Some("SPEAKER_B".into());
+        let mut blank = cue(2.0, 3.0, "ok");
+        blank.speaker = Some(" ".into());
+        let output = render(SubtitleFormat::Vtt, &[first, second, blank]);
+        assert_eq!(
+            output,
+            "WEBVTT\n\n00:00:00.000 --> 00:00:01.000\n[Speaker 1] 你好\n\n\
+             00:00:01.000 --> 00:00:02.000\n[SPEAKER_B] hi\n\n\
+             00:00:02.000 --> 00:00:03.000\nok\n\n"
+        );
+    }
+
+    #[test]
+    fn timestamps_use_format_specific_millis_separator() {
+        assert_eq!(format_timestamp(0.0, ','), "00:00:00,000");
+        assert_eq!(format_timestamp(3723.4567, ','), "01:02:03,457");
+        assert_eq!(format_timestamp(59.9996, '.'), "00:01:00.000");
+        assert_eq!(format_timestamp(f64::NAN, ','), "00:00:00,000");
+    }
+
+    #[test]
+    fn srt_numbers_cues_and_skips_empty_text() {
+        let output = render(
+            SubtitleFormat::Srt,
+            &[
+                cue(0.0, 1.5, "你好"),
+                cue(1.5, 2.0, "  "),
+                cue(2.0, 4.25, "第二句\n\n换行"),
+            ],
+        );
+        assert_eq!(
+            output,
+            "1\n00:00:00,000 --> 00:00:01,500\n你好\n\n\
+             2\n00:00:02,000 --> 00:00:04,250\n第二句\n换行\n\n"
+        );
+    }
+
+    #[test]
+    fn vtt_has_header_and_dot_separator() {
+        let output = render(SubtitleFormat::Vtt, &[cue(1.0, 0.5, "hello")]);
+        assert_eq!(output, "WEBVTT\n\n00:00:01.000 --> 00:00:01.000\nhello\n\n");
+    }
+
+    #[test]
+    fn single_cue_spans_duration_or_estimates_from_text() {
+        assert_eq!(single_cue(" 你好 ", Some(3.2)), cue(0.0, 3.2, "你好"));
+        assert_eq!(single_cue("一二三四五六七八", None).end_sec, 2.0);
+        assert_eq!(single_cue("短", Some(0.0)).end_sec, 1.0);
+    }
+
+    #[test]
+    fn format_parsing_accepts_aliases() {
+        assert_eq!(SubtitleFormat::parse(" SRT ").unwrap(), SubtitleFormat::Srt);
+        assert_eq!(
+            SubtitleFormat::parse("webvtt").unwrap(),
+            SubtitleFormat::Vtt
+        );
+        assert!(SubtitleFormat::parse("ass").is_err());
+    }
+}
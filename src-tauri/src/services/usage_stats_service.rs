@@ -0,0 +1,257 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::paths;
+
+// ---------- 使用统计 ----------
+//
+// 只累计几个总数（次数、音频秒数、字数、识别耗时），按引擎分开记，存成一个
+// 很小的 JSON 文件，和详细的历史记录库分开，清空历史不影响统计。
+// 更新在后台线程里做，听写流程只负责丢进去，不等待写盘。
+
+const USAGE_STATS_FILE: &str = "usage_stats.json";
+
+static USAGE_STATS: OnceLock<parking_lot::Mutex<StoredUsageStats>> = OnceLock::new();
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct UsageCounters {
+    transcriptions: u64,
+    audio_secs: f64,
+    asr_secs: f64,
+    /// 测到识别耗时的那部分音频时长，RTF 只按这部分算
+    timed_audio_secs: f64,
+    chars: u64,
+}
+
+impl UsageCounters {
+    fn add(&mut self, audio_secs: f64, asr_secs: Option<f64>, chars: u64) {
+        self.transcriptions += 1;
+        self.audio_secs += audio_secs.max(0.0);
+        if let Some(asr_secs) = asr_secs {
+            self.asr_secs += asr_secs.max(0.0);
+            self.timed_audio_secs += audio_secs.max(0.0);
+        }
+        self.chars += chars;
+    }
+
+    /// 识别耗时 / 音频时长；还没有测过耗时时为 None
+    fn average_rtf(&self) -> Option<f64> {
+        (self.timed_audio_secs > 0.0).then(|| self.asr_secs / self.timed_audio_secs)
+    }
+
+    /// 旧文件没有 timed_audio_secs，当时每次都测了耗时
+    fn migrate(&mut self) {
+        if self.timed_audio_secs == 0.0 && self.asr_secs > 0.0 {
+            self.timed_audio_secs = self.audio_secs;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct StoredUsageStats {
+    /// 开始统计（或上次重置）的时间，毫秒时间戳
+    since_ms: u64,
+    totals: UsageCounters,
+    engines: BTreeMap<String, UsageCounters>,
+}
+
+impl StoredUsageStats {
+    fn fresh() -> Self {
+        Self {
+            since_ms: now_ms(),
+            ..Default::default()
+        }
+    }
+
+    fn record(&mut self, engine: &str, audio_secs: f64, asr_secs: Option<f64>, chars: u64) {
+        self.totals.add(audio_secs, asr_secs, chars);
+        self.engines
+            .entry(engine.to_string())
+            .or_default()
+            .add(audio_secs, asr_secs, chars);
+    }
+
+    fn report(&self) -> UsageStatsReport {
+        UsageStatsReport {
+            since_ms: self.since_ms,
+            totals: UsageSummary::from(&self.totals),
+            engines: self
+                .engines
+                .iter()
+                .map(|(engine, counters)| EngineUsageSummary {
+                    engine: engine.clone(),
+                    usage: UsageSummary::from(counters),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageSummary {
+    pub transcriptions: u64,
+    pub audio_secs: f64,
+    pub chars: u64,
+    pub average_rtf: Option<f64>,
+}
+
+impl From<&UsageCounters> for UsageSummary {
+    fn from(counters: &UsageCounters) -> Self {
+        Self {
+            transcriptions: counters.transcriptions,
+            audio_secs: counters.audio_secs,
+            chars: counters.chars,
+            average_rtf: counters.average_rtf(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineUsageSummary {
+    pub engine: String,
+    #[serde(flatten)]
+    pub usage: UsageSummary,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageStatsReport {
+    pub since_ms: u64,
+    pub totals: UsageSummary,
+    pub engines: Vec<EngineUsageSummary>,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn stats_path() -> PathBuf {
+    paths::get_data_dir().join(USAGE_STATS_FILE)
+}
+
+fn load_stored_stats() -> StoredUsageStats {
+    let path = stats_path();
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return StoredUsageStats::fresh(),
+        Err(err) => {
+            log::warn!("读取使用统计失败，重新开始统计: {}", err);
+            return StoredUsageStats::fresh();
+        }
+    };
+    let mut stored: StoredUsageStats = serde_json::from_str(&content).unwrap_or_else(|err| {
+        log::warn!("使用统计文件损坏，重新开始统计: {}", err);
+        StoredUsageStats::fresh()
+    });
+    stored.totals.migrate();
+    stored.engines.values_mut().for_each(UsageCounters::migrate);
+    stored
+}
+
+fn stats() -> &'static parking_lot::Mutex<StoredUsageStats> {
+    USAGE_STATS.get_or_init(|| parking_lot::Mutex::new(load_stored_stats()))
+}
+
+/// 持有锁时写盘，保证并发更新按顺序落到文件里
+fn save_stored_stats(stored: &StoredUsageStats) -> Result<(), String> {
+    let json = serde_json::to_vec(stored).map_err(|e| format!("序列化使用统计失败: {}", e))?;
+    paths::atomic_write(&stats_path(), &json).map_err(|e| format!("保存使用统计失败: {}", e))
+}
+
+/// 记一次成功的识别，`chars` 是最终产出文本的字数；复用中间结果、没测到识别
+/// 耗时时 `asr_secs` 为 None。立即返回，读写文件都在后台线程里完成。
+pub fn record_transcription(engine: &str, audio_secs: f64, asr_secs: Option<f64>, chars: u64) {
+    let engine = engine.to_string();
+    tokio::task::spawn_blocking(move || {
+        let mut stored = stats().lock();
+        stored.record(&engine, audio_secs, asr_secs, chars);
+        if let Err(err) = save_stored_stats(&stored) {
+            log::warn!("{}", err);
+        }
+    });
+}
+
+pub async fn report() -> Result<UsageStatsReport, String> {
+    tokio::task::spawn_blocking(|| stats().lock().report())
+        .await
+        .map_err(|e| format!("读取使用统计失败: {}", e))
+}
+
+pub async fn reset() -> Result<UsageStatsReport, String> {
+    tokio::task::spawn_blocking(|| {
+        let mut stored = stats().lock();
+        *stored = StoredUsageStats::fresh();
+        save_stored_stats(&stored)?;
+        log::info!("使用统计已重置");
+        Ok(stored.report())
+    })
+    .await
+    .map_err(|e| format!("重置使用统计失败: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StoredUsageStats, UsageCounters};
+
+    #[test]
+    fn record_accumulates_totals_and_per_engine_rtf() {
+        let mut stored = StoredUsageStats::default();
+        stored.record("sensevoice", 10.0, Some(1.0), 20);
+        stored.record("sensevoice", 10.0, Some(3.0), 30);
+        stored.record("glm-asr", 5.0, Some(5.0), 10);
+        // 复用中间结果：计入次数、时长和字数，不拉低 RTF
+        stored.record("sensevoice", 4.0, None, 5);
+
+        let report = stored.report();
+        assert_eq!(report.totals.transcriptions, 4);
+        assert_eq!(report.totals.chars, 65);
+        assert_eq!(report.totals.audio_secs, 29.0);
+        assert_eq!(report.totals.average_rtf, Some(9.0 / 25.0));
+
+        let engines: Vec<_> = report
+            .engines
+            .iter()
+            .map(|e| {
+                (
+                    e.engine.as_str(),
+                    e.usage.transcriptions,
+                    e.usage.average_rtf,
+                )
+            })
+            .collect();
+        assert_eq!(
+            engines,
+            vec![("glm-asr", 1, Some(1.0)), ("sensevoice", 3, Some(0.2))]
+        );
+    }
+
+    #[test]
+    fn empty_stats_have_no_rtf_and_old_files_still_parse() {
+        assert_eq!(
+            StoredUsageStats::default().report().totals.average_rtf,
+            None
+        );
+        let parsed: StoredUsageStats =
+            serde_json::from_str(r#"{"since_ms":5,"totals":{"transcriptions":2}}"#).unwrap();
+        assert_eq!(parsed.since_ms, 5);
+        assert_eq!(parsed.totals.transcriptions, 2);
+        assert!(parsed.engines.is_empty());
+
+        let mut old: UsageCounters =
+            serde_json::from_str(r#"{"transcriptions":1,"audio_secs":4.0,"asr_secs":1.0}"#)
+                .unwrap();
+        old.migrate();
+        assert_eq!(old.average_rtf(), Some(0.25));
+    }
+}
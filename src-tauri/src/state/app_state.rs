@@ -10,7 +10,7 @@ use tokio::process::{Child, ChildStdin, ChildStdout};
 use tokio::sync::oneshot;
 use tokio::sync::Mutex;
 
-use super::user_profile::{LlmProviderConfig, UserProfile};
+use super::user_profile::{InputSampleFormat, LlmProviderConfig, UserProfile};
 use crate::services::codex_oauth_service::OpenaiCodexOauthSession;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, serde::Deserialize)]
@@ -51,6 +51,9 @@ pub struct RecordingSnapshot {
     pub outcome: Option<RecordingOutcomeKind>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub detail: Option<String>,
+    /// 实际采集的设备名；开始采集后才有，同一会话后续阶段沿用
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_name: Option<String>,
 }
 
 impl RecordingSnapshot {
@@ -62,6 +65,7 @@ impl RecordingSnapshot {
             mode,
             outcome: None,
             detail: None,
+            device_name: None,
         }
     }
 
@@ -79,6 +83,7 @@ impl RecordingSnapshot {
             mode,
             outcome: Some(outcome),
             detail: detail.map(str::to_owned),
+            device_name: None,
         }
     }
 }
@@ -128,6 +133,15 @@ pub struct InterimCache {
     pub language: Option<String>,
 }
 
+/// 等待用户确认的听写结果（review_before_paste）。`id` 即录音的 session id。
+#[derive(Debug, Clone)]
+pub struct PendingPasteReview {
+    pub id: u64,
+    pub text: String,
+    /// 挂起时的前台窗口；确认通常在本应用窗口里点，粘贴前要切回去
+    pub target_window: Option<crate::utils::foreground::WindowHandle>,
+}
+
 pub struct RecordingSession {
     pub session_id: u64,
     pub subtitle_show_gen: u64,
@@ -136,6 +150,8 @@ pub struct RecordingSession {
     pub stop_notify: Arc<tokio::sync::Notify>,
     pub samples: Arc<parking_lot::Mutex<Vec<i16>>>,
     pub sample_rate: u32,
+    /// 实际采集的设备名；指定设备不可用时是回退后的默认设备
+    pub device_name: String,
     pub audio_thread: Option<JoinHandle<()>>,
     pub interim_task: Option<tokio::task::JoinHandle<()>>,
     pub interim_cache: Arc<parking_lot::Mutex<Option<InterimCache>>>,
@@ -145,6 +161,60 @@ pub struct RecordingSession {
     /// 热键按下时并行抓取的选中文本任务。与会话同生同死，避免全局共享导致的
     /// 跨会话污染（finalize_N 读到 hotkey_{N+1} 的 grab）。
     pub edit_grab: Option<tokio::task::JoinHandle<Option<String>>>,
+    pub pause: RecordingPause,
+}
+
+/// 录音暂停状态。暂停期间采集回调丢弃输入、缓冲不增长，因此最终时长和录音
+/// 上限（MAX_RECORD_SAMPLES）都只计实际采到的音频；中间转写见不到新样本也会
+/// 自然停下。停止录音不要求先恢复，暂停中停止就按已采到的部分收尾。
+#[derive(Default)]
+pub struct RecordingPause {
+    pub flag: Arc<AtomicBool>,
+    paused_since: Option<std::time::Instant>,
+    paused_total: std::time::Duration,
+}
+
+impl RecordingPause {
+    pub fn with_flag(flag: Arc<AtomicBool>) -> Self {
+        Self {
+            flag,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_since.is_some()
+    }
+
+    /// 已经暂停时返回 false（幂等）。
+    pub fn pause(&mut self, now: std::time::Instant) -> bool {
+        if self.paused_since.is_some() {
+            return false;
+        }
+        self.paused_since = Some(now);
+        self.flag.store(true, Ordering::Release);
+        true
+    }
+
+    /// 未暂停时返回 false（幂等）。
+    pub fn resume(&mut self, now: std::time::Instant) -> bool {
+        let Some(since) = self.paused_since.take() else {
+            return false;
+        };
+        self.paused_total += now.saturating_duration_since(since);
+        self.flag.store(false, Ordering::Release);
+        true
+    }
+
+    /// 累计暂停时长，包含正在进行的这一段。
+    pub fn total_paused(&self, now: std::time::Instant) -> std::time::Duration {
+        self.paused_total
+            + self
+                .paused_since
+                .map_or(std::time::Duration::ZERO, |since| {
+                    now.saturating_duration_since(since)
+                })
+    }
 }
 
 #[derive(Clone)]
@@ -184,11 +254,14 @@ impl RecordingSlot {
     }
 
     pub fn snapshot(&self, revision: u64) -> RecordingSnapshot {
-        let phase = match self {
-            Self::Starting(_) => RecordingPhase::Starting,
-            Self::Active(_) => RecordingPhase::Recording,
+        let (phase, device_name) = match self {
+            Self::Starting(_) => (RecordingPhase::Starting, None),
+            Self::Active(s) => (RecordingPhase::Recording, Some(s.device_name.clone())),
         };
-        RecordingSnapshot::new(self.session_id(), revision, phase, self.trigger().mode())
+        RecordingSnapshot {
+            device_name,
+            ..RecordingSnapshot::new(self.session_id(), revision, phase, self.trigger().mode())
+        }
     }
 }
 
@@ -234,6 +307,15 @@ impl Default for HotkeyDiagnosticState {
     }
 }
 
+/// `set_next_input_method` 设置的一次性注入方式，超时未用则作废。
+pub const NEXT_INPUT_METHOD_TTL: std::time::Duration = std::time::Duration::from_secs(120);
+
+#[derive(Debug, Clone)]
+pub struct NextInputMethod {
+    pub method: String,
+    pub expires_at: std::time::Instant,
+}
+
 // ---------- AppState 按领域分组的子结构 ----------
 
 /// ASR 引擎生命周期 + 下载 + 传输能力探测
@@ -256,8 +338,24 @@ pub struct EngineState {
     pub download_task: Arc<Mutex<Option<DownloadTask>>>,
     /// 内存音频传输支持状态：0=未知, 1=支持, 2=不支持
     pub inline_audio_transport: AtomicU8,
+    /// 当前引擎进程在初始化握手里上报的协议版本；旧脚本不上报时为 None
+    pub server_version: parking_lot::Mutex<Option<String>>,
+    /// 当前引擎进程在初始化握手里声明的可选能力（如 `reload_model`）
+    pub server_capabilities: parking_lot::Mutex<Vec<String>>,
+    /// 最近一次以 CUDA 初始化时上报的 GPU 总显存（GB），用于核对显存预算
+    pub gpu_memory_total: parking_lot::Mutex<Option<f64>>,
+    /// 最近几次最终转写的实时率（处理耗时 / 音频时长）
+    pub recent_rtf: parking_lot::Mutex<std::collections::VecDeque<f64>>,
+    /// 最近一次引擎启动耗时；本次运行还没启动成功过时为 None
+    pub last_startup:
+        parking_lot::Mutex<Option<crate::services::funasr_service::EngineStartupTiming>>,
+    /// 引擎对比进行中：期间会来回切换引擎，拒绝开始录音
+    pub comparison_active: AtomicBool,
 }
 
+/// 滚动 RTF 只保留最近这么多次转写
+pub const RTF_WINDOW_SIZE: usize = 10;
+
 impl Default for EngineState {
     fn default() -> Self {
         Self {
@@ -271,6 +369,12 @@ impl Default for EngineState {
             funasr_generation: Arc::new(AtomicU64::new(0)),
             download_task: Default::default(),
             inline_audio_transport: AtomicU8::new(0),
+            server_version: Default::default(),
+            server_capabilities: Default::default(),
+            gpu_memory_total: Default::default(),
+            recent_rtf: Default::default(),
+            last_startup: Default::default(),
+            comparison_active: AtomicBool::new(false),
         }
     }
 }
@@ -282,10 +386,27 @@ pub struct RecordingState {
     snapshot_revision: AtomicU64,
     pub subtitle_window_op: Mutex<()>,
     pub session_counter: AtomicU64,
+    /// 短于这个时长（毫秒）的录音直接跳过转写
+    pub min_audio_duration_ms: AtomicU64,
+    /// 打开时把每次听写的 WAV 和转写文本存到数据目录的 recordings/
+    pub save_recordings: Arc<AtomicBool>,
     pub pending_paste: Arc<parking_lot::Mutex<Vec<String>>>,
     pub selected_input_device_name: Arc<parking_lot::Mutex<Option<String>>>,
     pub microphone_level_monitor: Arc<parking_lot::Mutex<Option<MicrophoneLevelMonitor>>>,
     pub subtitle_show_gen: AtomicU64,
+    /// 字幕窗口连续创建失败次数；达到上限后本次运行不再尝试创建
+    pub subtitle_create_failures: AtomicU8,
+    /// 最近一次显示字幕窗口时的 show generation 和时刻，hide 据此保证最短可见时长
+    pub subtitle_shown_at: parking_lot::Mutex<Option<(u64, std::time::Instant)>>,
+    /// 已停止、正在等待合并窗口的听写会话。窗口内再次开始录音会接管它的音频，
+    /// 否则到期后由 stop_recording_inner 派出的计时任务正常收尾。
+    pub merge_candidate: Arc<parking_lot::Mutex<Option<RecordingSession>>>,
+    /// 最近一次成功注入的文本，供 repaste_last 重新粘贴
+    pub last_pasted_text: Arc<parking_lot::Mutex<Option<String>>>,
+    /// 最近一次注入时的前台窗口；从主窗口发起重贴时先切回这里
+    pub last_paste_target: Arc<parking_lot::Mutex<Option<crate::utils::foreground::WindowHandle>>>,
+    /// 等待确认的听写结果，同一时间只保留最新一条
+    pub pending_review: Arc<parking_lot::Mutex<Option<PendingPasteReview>>>,
 }
 
 impl Default for RecordingState {
@@ -296,10 +417,20 @@ impl Default for RecordingState {
             snapshot_revision: AtomicU64::new(0),
             subtitle_window_op: Default::default(),
             session_counter: AtomicU64::new(0),
+            min_audio_duration_ms: AtomicU64::new(
+                (crate::services::audio_service::MIN_AUDIO_DURATION_SEC * 1000.0) as u64,
+            ),
+            save_recordings: Arc::new(AtomicBool::new(false)),
             pending_paste: Default::default(),
             selected_input_device_name: Default::default(),
             microphone_level_monitor: Default::default(),
             subtitle_show_gen: AtomicU64::new(0),
+            subtitle_create_failures: AtomicU8::new(0),
+            subtitle_shown_at: Default::default(),
+            merge_candidate: Default::default(),
+            last_pasted_text: Default::default(),
+            last_paste_target: Default::default(),
+            pending_review: Default::default(),
         }
     }
 }
@@ -319,6 +450,22 @@ impl RecordingState {
         mode: RecordingMode,
         outcome: Option<RecordingOutcomeKind>,
         detail: Option<&str>,
+    ) -> Option<RecordingSnapshot> {
+        self.transition_snapshot_with_device_while_recording_locked(
+            session_id, phase, mode, outcome, detail, None,
+        )
+    }
+
+    /// 同 `transition_snapshot_while_recording_locked`，`device_name` 为 None 时
+    /// 沿用同一会话上一个快照里的设备名。
+    pub fn transition_snapshot_with_device_while_recording_locked(
+        &self,
+        session_id: u64,
+        phase: RecordingPhase,
+        mode: RecordingMode,
+        outcome: Option<RecordingOutcomeKind>,
+        detail: Option<&str>,
+        device_name: Option<String>,
     ) -> Option<RecordingSnapshot> {
         if self.session_counter.load(Ordering::Acquire) != session_id {
             return None;
@@ -330,7 +477,18 @@ impl RecordingState {
             }
             _ => RecordingSnapshot::new(session_id, revision, phase, mode),
         };
-        *self.recording_snapshot.lock() = Some(snapshot.clone());
+        let mut current = self.recording_snapshot.lock();
+        let device_name = device_name.or_else(|| {
+            current
+                .as_ref()
+                .filter(|previous| previous.session_id == session_id)
+                .and_then(|previous| previous.device_name.clone())
+        });
+        let snapshot = RecordingSnapshot {
+            device_name,
+            ..snapshot
+        };
+        *current = Some(snapshot.clone());
         Some(snapshot)
     }
 
@@ -385,12 +543,18 @@ pub struct ProfileState {
 /// UI / 交互类偏好 + 诊断
 pub struct UiState {
     pub input_method: Arc<parking_lot::Mutex<String>>,
+    /// 只对下一次注入生效的输入方式，用后即清
+    pub next_input_method: Arc<parking_lot::Mutex<Option<NextInputMethod>>>,
     pub sound_enabled: Arc<AtomicBool>,
     pub hotkey_diagnostic: Arc<parking_lot::Mutex<HotkeyDiagnosticState>>,
     pub assistant_chat_generation: AtomicU64,
     pub assistant_chat_cancel: Arc<parking_lot::Mutex<Option<AssistantChatTask>>>,
     pub selection_generation: AtomicU64,
     pub selection_cancel: Arc<parking_lot::Mutex<Option<SelectionTask>>>,
+    /// 注入测试窗口回报文本框内容的通道；仅在 test_injection 运行期间存在。
+    /// 值为 None 表示窗口尚未挂载。
+    pub injection_test_probe:
+        Arc<parking_lot::Mutex<Option<tokio::sync::watch::Sender<Option<String>>>>>,
 }
 
 pub struct AssistantChatTask {
@@ -407,17 +571,36 @@ impl Default for UiState {
     fn default() -> Self {
         Self {
             input_method: Arc::new(parking_lot::Mutex::new("sendInput".into())),
+            next_input_method: Default::default(),
             sound_enabled: Arc::new(AtomicBool::new(true)),
             hotkey_diagnostic: Default::default(),
             assistant_chat_generation: AtomicU64::new(0),
             assistant_chat_cancel: Default::default(),
             selection_generation: AtomicU64::new(0),
             selection_cancel: Default::default(),
+            injection_test_probe: Default::default(),
         }
     }
 }
 
 impl EngineState {
+    /// 记录一次转写的耗时；音频过短（<0.3s）时 RTF 噪声太大，直接忽略。
+    pub fn record_transcription_rtf(&self, processing_secs: f64, audio_secs: f64) {
+        if !processing_secs.is_finite() || !audio_secs.is_finite() || audio_secs < 0.3 {
+            return;
+        }
+        let mut recent = self.recent_rtf.lock();
+        if recent.len() >= RTF_WINDOW_SIZE {
+            recent.pop_front();
+        }
+        recent.push_back(processing_secs.max(0.0) / audio_secs);
+    }
+
+    pub fn average_rtf(&self) -> Option<f64> {
+        let recent = self.recent_rtf.lock();
+        (!recent.is_empty()).then(|| recent.iter().sum::<f64>() / recent.len() as f64)
+    }
+
     pub fn is_funasr_starting(&self) -> bool {
         self.funasr_starting_owner.load(Ordering::SeqCst) != 0
     }
@@ -479,6 +662,31 @@ impl Default for AppState {
     }
 }
 
+/// `get_task_status` 返回的后台任务快照，只读原子量和各个 Option 槽位，
+/// 不会等待任何长时间持有的锁。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskStatusSnapshot {
+    pub recording: Option<RecordingSnapshot>,
+    pub recording_slot: Option<&'static str>,
+    pub audio_capture_active: bool,
+    pub interim_task_active: bool,
+    pub edit_grab_pending: bool,
+    pub recording_paused: bool,
+    pub pending_paste_count: usize,
+    pub funasr_ready: bool,
+    pub funasr_starting: bool,
+    pub funasr_starting_child: bool,
+    /// None 表示进程槽正被某个请求占用（正在转写/状态查询），无法立即判断
+    pub funasr_process_running: Option<bool>,
+    pub funasr_generation: u64,
+    /// None 表示下载槽正被占用，无法立即判断
+    pub download_in_progress: Option<bool>,
+    pub microphone_monitor_active: bool,
+    pub assistant_chat_active: bool,
+    pub selection_task_active: bool,
+}
+
 pub struct DownloadTask {
     pub id: u64,
     pub cancel: Option<oneshot::Sender<()>>,
@@ -655,6 +863,99 @@ impl AppState {
         });
     }
 
+    pub fn min_audio_duration_sec(&self) -> f64 {
+        self.recording.min_audio_duration_ms.load(Ordering::Relaxed) as f64 / 1000.0
+    }
+
+    /// 用于采集配置选择的按设备采样率偏好快照
+    pub fn device_sample_rates(&self) -> HashMap<String, u32> {
+        self.with_profile(|profile| profile.device_sample_rates.clone())
+    }
+
+    /// 用于采集配置选择的首选采样格式
+    pub fn preferred_sample_format(&self) -> Option<InputSampleFormat> {
+        self.with_profile(|profile| profile.preferred_sample_format)
+    }
+
+    /// 按识别结果语种挑选注入方式：有语种覆盖就用覆盖值，否则用全局设置。
+    pub fn input_method_for_language(&self, is_cjk: bool) -> String {
+        let overrides = self.with_profile(|profile| profile.language_input_methods.clone());
+        let language_override = if is_cjk {
+            overrides.cjk
+        } else {
+            overrides.latin
+        };
+        language_override.unwrap_or_else(|| self.ui.input_method.lock().clone())
+    }
+
+    /// 取出并清掉一次性注入方式；已过期的同样清掉，但返回 None。
+    pub fn take_next_input_method(&self, now: std::time::Instant) -> Option<String> {
+        let next = self.ui.next_input_method.lock().take()?;
+        if now >= next.expires_at {
+            log::info!("一次性输入方式 {} 已过期，沿用默认设置", next.method);
+            return None;
+        }
+        Some(next.method)
+    }
+
+    pub fn task_status_snapshot(&self) -> TaskStatusSnapshot {
+        let (
+            recording_slot,
+            audio_capture_active,
+            interim_task_active,
+            edit_grab_pending,
+            recording_paused,
+        ) = match self.recording.recording.lock().as_ref() {
+            None => (None, false, false, false, false),
+            Some(RecordingSlot::Starting(_)) => (Some("starting"), false, false, false, false),
+            Some(RecordingSlot::Active(session)) => (
+                Some("active"),
+                session
+                    .audio_thread
+                    .as_ref()
+                    .is_some_and(|handle| !handle.is_finished()),
+                session
+                    .interim_task
+                    .as_ref()
+                    .is_some_and(|task| !task.is_finished()),
+                session
+                    .edit_grab
+                    .as_ref()
+                    .is_some_and(|task| !task.is_finished()),
+                session.pause.is_paused(),
+            ),
+        };
+
+        TaskStatusSnapshot {
+            recording: self.recording.snapshot(),
+            recording_slot,
+            audio_capture_active,
+            interim_task_active,
+            edit_grab_pending,
+            recording_paused,
+            pending_paste_count: self.recording.pending_paste.lock().len(),
+            funasr_ready: self.is_funasr_ready(),
+            funasr_starting: self.engine.is_funasr_starting(),
+            funasr_starting_child: self.engine.funasr_starting_process.lock().is_some(),
+            funasr_process_running: self
+                .engine
+                .funasr_process
+                .try_lock()
+                .ok()
+                .map(|guard| guard.is_some()),
+            funasr_generation: self.engine.funasr_generation.load(Ordering::Acquire),
+            download_in_progress: self
+                .engine
+                .download_task
+                .try_lock()
+                .ok()
+                .map(|guard| guard.is_some()),
+            microphone_monitor_active: self.recording.microphone_level_monitor.lock().is_some(),
+            assistant_chat_active: self.ui.assistant_chat_cancel.lock().is_some(),
+            selection_task_active: self.ui.selection_cancel.lock().is_some(),
+        }
+    }
+
     pub fn hotkey_diagnostic_snapshot(&self) -> HotkeyDiagnosticState {
         self.ui.hotkey_diagnostic.lock().clone()
     }
@@ -692,14 +993,73 @@ mod recording_snapshot_tests {
             stop_notify: Arc::new(tokio::sync::Notify::new()),
             samples: Arc::new(parking_lot::Mutex::new(Vec::new())),
             sample_rate: 16_000,
+            device_name: "Test Microphone".to_string(),
             audio_thread: None,
             interim_task: None,
             interim_cache: Arc::new(parking_lot::Mutex::new(None)),
             foreground_app: None,
             edit_grab: None,
+            pause: RecordingPause::default(),
         })
     }
 
+    #[test]
+    fn next_input_method_is_consumed_once_and_expires() {
+        let state = AppState::new();
+        let now = std::time::Instant::now();
+        *state.ui.next_input_method.lock() = Some(NextInputMethod {
+            method: "clipboard".into(),
+            expires_at: now + NEXT_INPUT_METHOD_TTL,
+        });
+        assert_eq!(
+            state.take_next_input_method(now).as_deref(),
+            Some("clipboard")
+        );
+        assert_eq!(state.take_next_input_method(now), None);
+
+        *state.ui.next_input_method.lock() = Some(NextInputMethod {
+            method: "clipboard".into(),
+            expires_at: now,
+        });
+        assert_eq!(state.take_next_input_method(now), None);
+        assert!(state.ui.next_input_method.lock().is_none());
+    }
+
+    #[test]
+    fn recording_pause_accumulates_paused_intervals() {
+        let start = std::time::Instant::now();
+        let secs = std::time::Duration::from_secs;
+        let mut pause = RecordingPause::default();
+        assert!(!pause.resume(start));
+        assert!(pause.pause(start));
+        assert!(!pause.pause(start + secs(1)));
+        assert!(pause.flag.load(Ordering::Acquire));
+        assert_eq!(pause.total_paused(start + secs(2)), secs(2));
+
+        assert!(pause.resume(start + secs(3)));
+        assert!(!pause.is_paused());
+        assert!(!pause.flag.load(Ordering::Acquire));
+        assert!(pause.pause(start + secs(10)));
+        assert!(pause.resume(start + secs(11)));
+        assert_eq!(pause.total_paused(start + secs(20)), secs(4));
+    }
+
+    #[test]
+    fn rolling_rtf_keeps_only_recent_transcriptions() {
+        let engine = EngineState::default();
+        assert_eq!(engine.average_rtf(), None);
+        engine.record_transcription_rtf(1.0, 0.1);
+        assert_eq!(engine.average_rtf(), None);
+
+        for _ in 0..RTF_WINDOW_SIZE {
+            engine.record_transcription_rtf(4.0, 2.0);
+        }
+        engine.record_transcription_rtf(0.5, 1.0);
+        assert_eq!(engine.recent_rtf.lock().len(), RTF_WINDOW_SIZE);
+        let expected = (2.0 * (RTF_WINDOW_SIZE - 1) as f64 + 0.5) / RTF_WINDOW_SIZE as f64;
+        assert!((engine.average_rtf().unwrap() - expected).abs() < 1e-9);
+    }
+
     #[test]
     fn recording_snapshot_serializes_as_frontend_contract() {
         let value = serde_json::to_value(RecordingSnapshot::outcome(
@@ -735,6 +1095,43 @@ mod recording_snapshot_tests {
         let active = active_slot(8, RecordingTrigger::Assistant).snapshot(2);
         assert_eq!(active.phase, RecordingPhase::Recording);
         assert_eq!(active.mode, RecordingMode::Assistant);
+        assert_eq!(active.device_name.as_deref(), Some("Test Microphone"));
+    }
+
+    #[test]
+    fn recording_snapshot_keeps_device_name_for_the_same_session() {
+        let state = RecordingState::default();
+        state.session_counter.store(5, Ordering::Release);
+        state.transition_snapshot_with_device_while_recording_locked(
+            5,
+            RecordingPhase::Recording,
+            RecordingMode::Dictation,
+            None,
+            None,
+            Some("USB Mic".into()),
+        );
+        let processing = state
+            .transition_snapshot_if_current(
+                5,
+                RecordingPhase::Processing,
+                RecordingMode::Dictation,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(processing.device_name.as_deref(), Some("USB Mic"));
+
+        state.session_counter.store(6, Ordering::Release);
+        let next = state
+            .transition_snapshot_if_current(
+                6,
+                RecordingPhase::Starting,
+                RecordingMode::Dictation,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(next.device_name, None);
     }
 
     #[test]
@@ -2,7 +2,9 @@ pub mod app_state;
 pub mod user_profile;
 pub use app_state::{
     AppState, DictationOutputMode, DownloadTask, EngineState, FunasrProcess, HotkeyDiagnosticState,
-    InterimCache, MicrophoneLevelMonitor, PendingRecordingSession, RecordingMode,
-    RecordingOutcomeKind, RecordingPhase, RecordingSession, RecordingSlot, RecordingSnapshot,
-    RecordingTrigger, SelectionTask, StartingFunasrProcess,
+    InterimCache, MicrophoneLevelMonitor, NextInputMethod, PendingPasteReview,
+    PendingRecordingSession, RecordingMode, RecordingOutcomeKind, RecordingPause, RecordingPhase,
+    RecordingSession, RecordingSlot, RecordingSnapshot, RecordingTrigger, SelectionTask,
+    StartingFunasrProcess, TaskStatusSnapshot,
 };
+pub use user_profile::LanguageInputMethods;
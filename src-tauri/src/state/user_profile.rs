@@ -71,6 +71,12 @@ pub struct UserProfile {
     /// 翻译目标语言（None = 关闭翻译，非空 = 开启并翻译为该语言）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub translation_target: Option<String>,
+    /// 听写热键；None 时启动注册默认的 F2
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dictation_hotkey: Option<String>,
+    /// 关闭听写热键，启动时不再注册任何听写热键
+    #[serde(default)]
+    pub dictation_hotkey_disabled: bool,
     /// 翻译模式独立热键
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub translation_hotkey: Option<String>,
@@ -110,6 +116,218 @@ pub struct UserProfile {
     /// 按前台应用匹配的听写覆盖规则；顺序即优先级，首个匹配项生效
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub app_profile_rules: Vec<AppProfileRule>,
+    /// 听写停止后在这段时间（毫秒）内重新开始，就把两段录音合并成一次转写；0 = 关闭
+    #[serde(default)]
+    pub recording_merge_window_ms: u64,
+    /// 中间转写自适应间隔的耗时阈值
+    #[serde(default)]
+    pub interim_cost_settings: InterimCostSettings,
+    /// 调试用：Windows 上给 Python 引擎进程保留可见控制台，实时查看 stderr 输出
+    #[serde(default)]
+    pub show_python_console: bool,
+    /// 模型下载失败（网络类错误或长时间无输出）后的自动重试策略
+    #[serde(default)]
+    pub download_retry_settings: DownloadRetrySettings,
+    /// 送入识别前的响度归一化
+    #[serde(default)]
+    pub loudness_settings: LoudnessSettings,
+    /// 最终识别前在音频开头补的静音毫秒数，缓解首字被吞；None 或 0 关闭
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leading_silence_ms: Option<u32>,
+    /// 中间与最终识别结果统一的文本清理
+    #[serde(default)]
+    pub output_cleanup: OutputCleanupSettings,
+    /// 录音缓冲区预分配时长（秒）；None 为 30 秒
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recording_buffer_prealloc_secs: Option<u64>,
+    /// 批量（默认，每轮送整段窗口）或流式（更早出字，可能牺牲准确率和标点）
+    #[serde(default)]
+    pub transcription_mode: TranscriptionMode,
+    /// 听写结果先等用户确认（confirm_paste / discard_paste）再粘贴
+    #[serde(default)]
+    pub review_before_paste: bool,
+    /// 听写结果在字幕上显示哪个版本
+    #[serde(default)]
+    pub subtitle_text_variant: TextVariant,
+    /// 听写结果粘贴哪个版本
+    #[serde(default)]
+    pub paste_text_variant: TextVariant,
+    /// 从识别结果开头去掉的引导语（如唤醒词），忽略大小写
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub strip_prefix: Vec<String>,
+    /// 引导语允许少量识别错字
+    #[serde(default)]
+    pub strip_prefix_fuzzy: bool,
+    /// CPU 推理线程数；None 表示由引擎按核心数自动决定，重启引擎后生效
+    #[serde(default)]
+    pub cpu_threads: Option<u32>,
+    /// 录音时推送 min/max 波形数据流（"audio-waveform" 事件），供前端画滚动波形
+    #[serde(default)]
+    pub waveform_stream_enabled: bool,
+    /// 启动时预热录音链路（会短暂打开麦克风），减少第一次听写的启动延迟
+    #[serde(default)]
+    pub audio_prewarm_enabled: bool,
+    /// cpal 音频后端（如 WASAPI、ASIO、ALSA、JACK）；None 使用平台默认。
+    /// 有些设备只在特定后端下可见
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_host: Option<String>,
+    /// 按输入设备名记住的采集采样率（Hz）；设备不支持时自动选择
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub device_sample_rates: HashMap<String, u32>,
+    /// 排在最前面的采集采样格式；None 使用默认顺序，设备不提供该格式时自动回退
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_sample_format: Option<InputSampleFormat>,
+    /// 引擎命令失败后判定崩溃前的确认窗口（毫秒）；None 使用默认值，0 立即判定且不重试
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_crash_grace_ms: Option<u64>,
+    /// 输入第一个字符（或发送 Ctrl+V）前的额外停顿（毫秒）；None 使用默认值，0 不等待
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_char_delay_ms: Option<u64>,
+    /// 写剪贴板失败后的重试次数；None 使用默认值，0 不重试
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clipboard_write_retries: Option<u32>,
+    /// 显存预算（GB）；None 不限制。引擎按预算选择精度，放不下时改用 CPU，重启引擎后生效
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_vram_gb: Option<f64>,
+    /// 用户在应用里选择的开机自启动；None 表示从未通过应用设置过
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub autostart_enabled: Option<bool>,
+    /// 字幕窗口显示在哪块显示器上
+    #[serde(default)]
+    pub subtitle_display: SubtitleDisplay,
+    /// 字幕窗口显示后至少保持可见的毫秒数；None 使用默认值，0 关闭
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_subtitle_visible_ms: Option<u64>,
+    /// 字幕窗口最多显示的宽度（按半角字符计，全角/CJK 算 2）；超出时只显示
+    /// 末尾部分。None 使用默认值，0 不截断
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtitle_max_chars: Option<u32>,
+    /// 字幕窗口常驻显示：启动即显示，录音结束后不再自动隐藏
+    #[serde(default)]
+    pub subtitle_persistent: bool,
+    /// 关闭录音期间的字幕窗口定期重新置顶（默认开启，防止被其他置顶窗口盖住）
+    #[serde(default)]
+    pub subtitle_keep_on_top_disabled: bool,
+    /// 字幕窗口可点击、可拖动：关闭鼠标穿透，窗口缩成字幕条大小
+    #[serde(default)]
+    pub subtitle_interactive: bool,
+    /// 可交互模式下用户拖动后的字幕窗口左上角（物理像素）；None 为底部居中
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtitle_position: Option<SubtitlePosition>,
+    /// 听写结果粘贴前套用的输出模板；None 等同于 `{text}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_template: Option<String>,
+    /// 听写完成后执行的外部命令；None 表示关闭（默认）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_hook: Option<PostHookSettings>,
+    /// 录音期间待粘贴队列最多保留的段数；None 使用默认值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_paste_limit: Option<u32>,
+    /// 短于这个时长的录音视为误按、不转写；None 使用默认值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_audio_duration_ms: Option<u64>,
+    /// 每次听写另存 WAV 和转写文本到数据目录的 recordings/
+    #[serde(default)]
+    pub save_recordings: bool,
+    /// 主输入方式失败后依次尝试的输入方式；None 使用平台默认
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_method_fallback: Option<Vec<String>>,
+    /// 本地引擎的 ITN / 标点开关，引擎启动后通过 configure 下发
+    #[serde(default)]
+    pub asr_formatting: AsrFormattingSettings,
+    /// 配置的本地引擎缺模型、另一个本地引擎模型齐全时自动切过去
+    #[serde(default)]
+    pub auto_fallback_engine: bool,
+    /// 说话人分离：引擎支持时分段带说话人标签，字幕导出加 `[Speaker N]` 前缀
+    #[serde(default)]
+    pub diarization_enabled: bool,
+    /// 本地引擎的识别语言提示；None 自动检测，引擎启动后通过 configure 下发
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language_hint: Option<String>,
+    /// 语言切换热键依次轮换的语言，`auto` 表示自动检测
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub language_cycle: Vec<String>,
+    /// 语言切换热键，按下时切到 `language_cycle` 的下一项
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language_cycle_hotkey: Option<String>,
+    /// 显示/隐藏主窗口的热键
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toggle_window_hotkey: Option<String>,
+    /// 暂停/恢复录音的热键
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pause_hotkey: Option<String>,
+    /// 丢弃当前录音的热键
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cancel_hotkey: Option<String>,
+    /// 切换本地引擎的热键
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cycle_engine_hotkey: Option<String>,
+    /// 立即粘贴待粘贴队列的热键
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flush_paste_hotkey: Option<String>,
+    /// 序列热键相邻两步之间允许的最长间隔（毫秒）；None 使用默认值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hotkey_sequence_timeout_ms: Option<u64>,
+    /// 按识别结果语种覆盖注入方式
+    #[serde(default)]
+    pub language_input_methods: LanguageInputMethods,
+}
+
+/// 按识别结果的语种覆盖注入方式；None 表示沿用全局 `input_method`。
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageInputMethods {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cjk: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latin: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SubtitlePosition {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// 转写文本交给后置钩子的方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PostHookInput {
+    #[default]
+    Stdin,
+    /// 追加为最后一个命令行参数
+    Argument,
+}
+
+fn default_post_hook_timeout_ms() -> u64 {
+    crate::services::audio_service::DEFAULT_POST_HOOK_TIMEOUT_MS
+}
+
+/// 听写完成后执行的外部命令。`program` 直接启动，不经过 shell，文本不会被
+/// 当成命令解析；但程序本身拿到的是完整转写内容并以本应用权限运行，
+/// 只应配置自己信任的程序。`replace_output` 打开时用其 stdout 替换要粘贴的文本。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PostHookSettings {
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub input: PostHookInput,
+    #[serde(default)]
+    pub replace_output: bool,
+    #[serde(default = "default_post_hook_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+/// 多显示器下字幕窗口的目标显示器
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SubtitleDisplay {
+    /// 光标所在显示器，取不到时退回主窗口所在显示器
+    #[default]
+    Cursor,
+    Primary,
+    MainWindow,
 }
 
 fn default_history_enabled() -> bool {
@@ -120,6 +338,10 @@ fn default_history_retention_days() -> u32 {
     90
 }
 
+fn default_history_recording_format() -> String {
+    "wav".to_string()
+}
+
 /// 本地历史默认仅保存文本；音频需要用户主动开启。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistorySettings {
@@ -130,6 +352,9 @@ pub struct HistorySettings {
     /// 0 表示永久保留，否则按天清理文本和音频。
     #[serde(default = "default_history_retention_days")]
     pub retention_days: u32,
+    /// 历史音频落盘格式："wav" 或 "opus"（Ogg/Opus，体积约为 WAV 的 1/10）。
+    #[serde(default = "default_history_recording_format")]
+    pub recording_format: String,
 }
 
 impl Default for HistorySettings {
@@ -138,6 +363,174 @@ impl Default for HistorySettings {
             enabled: false,
             save_audio: false,
             retention_days: default_history_retention_days(),
+            recording_format: default_history_recording_format(),
+        }
+    }
+}
+
+fn default_interim_heavy_cost_ms() -> u64 {
+    crate::services::audio_service::INTERIM_HEAVY_COST_MS
+}
+
+fn default_interim_light_cost_ms() -> u64 {
+    crate::services::audio_service::INTERIM_LIGHT_COST_MS
+}
+
+/// 单次中间转写耗时 ≥ heavy 时放慢节奏，≤ light 时加快。
+/// `auto_calibrate` 打开后，每次录音改用前几次实测耗时推算阈值和间隔上下限。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterimCostSettings {
+    #[serde(default = "default_interim_heavy_cost_ms")]
+    pub heavy_cost_ms: u64,
+    #[serde(default = "default_interim_light_cost_ms")]
+    pub light_cost_ms: u64,
+    #[serde(default)]
+    pub auto_calibrate: bool,
+}
+
+impl Default for InterimCostSettings {
+    fn default() -> Self {
+        Self {
+            heavy_cost_ms: default_interim_heavy_cost_ms(),
+            light_cost_ms: default_interim_light_cost_ms(),
+            auto_calibrate: false,
+        }
+    }
+}
+
+fn default_download_max_attempts() -> u32 {
+    crate::services::download_service::DEFAULT_DOWNLOAD_MAX_ATTEMPTS
+}
+
+fn default_download_stall_timeout_secs() -> u64 {
+    crate::services::download_service::DEFAULT_DOWNLOAD_STALL_TIMEOUT_SECS
+}
+
+/// `max_attempts` 含首次下载；`stall_timeout_secs` 为 0 表示不检测卡死。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadRetrySettings {
+    #[serde(default = "default_download_max_attempts")]
+    pub max_attempts: u32,
+    #[serde(default = "default_download_stall_timeout_secs")]
+    pub stall_timeout_secs: u64,
+}
+
+impl Default for DownloadRetrySettings {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_download_max_attempts(),
+            stall_timeout_secs: default_download_stall_timeout_secs(),
+        }
+    }
+}
+
+fn default_loudness_target_lufs() -> f64 {
+    crate::services::audio_service::DEFAULT_LOUDNESS_TARGET_LUFS
+}
+
+/// 打开后最终识别前把录音响度调到 `target_lufs`（近似值，未做 K 加权）。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoudnessSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_loudness_target_lufs")]
+    pub target_lufs: f64,
+}
+
+impl Default for LoudnessSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_lufs: default_loudness_target_lufs(),
+        }
+    }
+}
+
+/// 识别结果的大小写转换
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputCase {
+    #[default]
+    Preserve,
+    Lowercase,
+    Uppercase,
+}
+
+/// 转写模式。
+///
+/// - `Batch`：松键后对整段录音重新识别（录音落在中间转写窗口内且尾部
+///   几乎全被覆盖时才复用中间结果），准确率和标点最好。
+/// - `Streaming`：中间转写从最快节奏起步，松键时只要中间结果覆盖到
+///   最后一秒内就直接采用，省掉一次完整识别；代价是尾音和句末标点
+///   可能缺失。引擎支持分块协议前，流式也仍是对滑动窗口的重复识别。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionMode {
+    #[default]
+    Batch,
+    Streaming,
+}
+
+/// 听写结果的版本：`Formatted` 走完 AI 润色（粘贴时再套输出模板），
+/// `Raw` 是清理过空白和引导语的识别原文，不等润色。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TextVariant {
+    #[default]
+    Formatted,
+    Raw,
+}
+
+/// 采集配置的首选采样格式。默认顺序是 I16 > F32 > U16；个别设备的 I16
+/// 输出只是驱动内部从浮点有损转换来的，改成 F32 优先可能更干净。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InputSampleFormat {
+    I16,
+    F32,
+    U16,
+}
+
+fn default_collapse_spaces() -> bool {
+    true
+}
+
+/// 首尾空白总是去掉；连续空格合并默认打开，大小写默认不动。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OutputCleanupSettings {
+    #[serde(default = "default_collapse_spaces")]
+    pub collapse_spaces: bool,
+    #[serde(default)]
+    pub case: OutputCase,
+}
+
+impl Default for OutputCleanupSettings {
+    fn default() -> Self {
+        Self {
+            collapse_spaces: default_collapse_spaces(),
+            case: OutputCase::Preserve,
+        }
+    }
+}
+
+fn default_asr_formatting_enabled() -> bool {
+    true
+}
+
+/// 本地引擎的输出格式：逆文本正则化与标点。默认与模型内置行为一致（都开）。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AsrFormattingSettings {
+    #[serde(default = "default_asr_formatting_enabled")]
+    pub itn: bool,
+    #[serde(default = "default_asr_formatting_enabled")]
+    pub punctuation: bool,
+}
+
+impl Default for AsrFormattingSettings {
+    fn default() -> Self {
+        Self {
+            itn: true,
+            punctuation: true,
         }
     }
 }
@@ -861,6 +1254,15 @@ mod tests {
         assert!(!settings.enabled);
         assert!(!settings.save_audio);
         assert_eq!(settings.retention_days, 90);
+        assert_eq!(settings.recording_format, "wav");
+    }
+
+    #[test]
+    fn history_settings_without_recording_format_default_to_wav() {
+        let settings: HistorySettings =
+            serde_json::from_str(r#"{"enabled":true,"save_audio":true,"retention_days":30}"#)
+                .expect("legacy history settings");
+        assert_eq!(settings.recording_format, "wav");
     }
 
     #[test]
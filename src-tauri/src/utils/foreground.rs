@@ -175,9 +175,262 @@ pub fn get_foreground_app() -> Option<ForegroundApp> {
     None
 }
 
+/// 当前前台窗口能否作为粘贴目标：存在且不属于本进程（主窗口/字幕窗口）。
+/// 返回 None 表示该平台无法查询焦点，调用方应退回固定延迟。
+#[cfg(target_os = "windows")]
+pub fn is_foreground_paste_target_ready() -> Option<bool> {
+    use windows_sys::Win32::System::Threading::GetCurrentProcessId;
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowThreadProcessId,
+    };
+
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_null() {
+            // 焦点切换过程中会短暂没有前台窗口
+            return Some(false);
+        }
+        let mut pid: u32 = 0;
+        GetWindowThreadProcessId(hwnd, &mut pid);
+        Some(pid != 0 && pid != GetCurrentProcessId())
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn is_foreground_paste_target_ready() -> Option<bool> {
+    None
+}
+
+/// 记下的目标窗口，之后可以重新激活。Windows 下保存 HWND 的数值，便于跨线程传递。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub struct WindowHandle(isize);
+
+/// 记下当前前台窗口；前台是本程序自己的窗口或没有前台窗口时返回 None。
+#[cfg(target_os = "windows")]
+pub fn capture_foreground_window() -> Option<WindowHandle> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::GetForegroundWindow;
+
+    if is_foreground_paste_target_ready() != Some(true) {
+        return None;
+    }
+    let hwnd = unsafe { GetForegroundWindow() };
+    (!hwnd.is_null()).then(|| WindowHandle(hwnd as isize))
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn capture_foreground_window() -> Option<WindowHandle> {
+    None
+}
+
+/// 把记下的窗口切回前台（最小化的先还原）。窗口已关闭或系统拒绝切换时返回 false。
+#[cfg(target_os = "windows")]
+pub fn restore_foreground_window(handle: WindowHandle) -> bool {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        IsIconic, IsWindow, SetForegroundWindow, ShowWindow, SW_RESTORE,
+    };
+
+    let hwnd = handle.0 as HWND;
+    unsafe {
+        if IsWindow(hwnd) == 0 {
+            return false;
+        }
+        if IsIconic(hwnd) != 0 {
+            ShowWindow(hwnd, SW_RESTORE);
+        }
+        SetForegroundWindow(hwnd) != 0
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn restore_foreground_window(_handle: WindowHandle) -> bool {
+    false
+}
+
+// ---------- 前台窗口可注入性 ----------
+//
+// Windows UIPI 不允许低完整性级别的进程向高完整性级别的窗口发送输入：普通权限运行时，
+// 以管理员身份运行的应用会静默丢弃 SendInput / 粘贴。安全桌面（UAC、锁屏）上
+// 没有可用的前台窗口，同样无法注入。
+
+#[cfg(any(target_os = "windows", test))]
+const SECURITY_MANDATORY_MEDIUM_RID: u32 = 0x2000;
+#[cfg(any(target_os = "windows", test))]
+const SECURITY_MANDATORY_HIGH_RID: u32 = 0x3000;
+#[cfg(any(target_os = "windows", test))]
+const SECURITY_MANDATORY_SYSTEM_RID: u32 = 0x4000;
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InjectTargetCheck {
+    pub injectable: bool,
+    pub reason: Option<String>,
+    pub process_name: Option<String>,
+    pub target_integrity: Option<String>,
+    pub own_integrity: Option<String>,
+}
+
+#[cfg(any(target_os = "windows", test))]
+fn integrity_label(rid: u32) -> &'static str {
+    match rid {
+        r if r >= SECURITY_MANDATORY_SYSTEM_RID => "system",
+        r if r >= SECURITY_MANDATORY_HIGH_RID => "high",
+        r if r >= SECURITY_MANDATORY_MEDIUM_RID => "medium",
+        _ => "low",
+    }
+}
+
+#[cfg(any(target_os = "windows", test))]
+/// 按完整性级别判断能否注入；任一方未知时按可注入处理，只在确定会被拦截时报警。
+fn integrity_verdict(own_rid: Option<u32>, target_rid: Option<u32>) -> (bool, Option<String>) {
+    match (own_rid, target_rid) {
+        (Some(own), Some(target)) if target > own => {
+            let reason = if target >= SECURITY_MANDATORY_SYSTEM_RID {
+                "目标窗口属于系统级进程，无法向其输入文字".to_string()
+            } else {
+                "目标应用以管理员身份运行，请同样以管理员身份运行轻语 Whisper".to_string()
+            };
+            (false, Some(reason))
+        }
+        (Some(_), Some(_)) => (true, None),
+        _ => (
+            true,
+            Some("无法读取进程权限级别，未能确认能否注入".to_string()),
+        ),
+    }
+}
+
+#[cfg(target_os = "windows")]
+unsafe fn process_integrity_rid(process: windows_sys::Win32::Foundation::HANDLE) -> Option<u32> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::Security::{
+        GetSidSubAuthority, GetSidSubAuthorityCount, GetTokenInformation, TokenIntegrityLevel,
+        TOKEN_MANDATORY_LABEL, TOKEN_QUERY,
+    };
+    use windows_sys::Win32::System::Threading::OpenProcessToken;
+
+    let mut token = std::ptr::null_mut();
+    if OpenProcessToken(process, TOKEN_QUERY, &mut token) == 0 {
+        return None;
+    }
+    let mut len = 0u32;
+    GetTokenInformation(
+        token,
+        TokenIntegrityLevel,
+        std::ptr::null_mut(),
+        0,
+        &mut len,
+    );
+    // u64 缓冲保证 TOKEN_MANDATORY_LABEL 的对齐
+    let mut buf = vec![0u64; (len as usize).div_ceil(8).max(1)];
+    let ok = GetTokenInformation(
+        token,
+        TokenIntegrityLevel,
+        buf.as_mut_ptr().cast(),
+        (buf.len() * 8) as u32,
+        &mut len,
+    );
+    CloseHandle(token);
+    if ok == 0 {
+        return None;
+    }
+    let label = &*(buf.as_ptr() as *const TOKEN_MANDATORY_LABEL);
+    let sid = label.Label.Sid;
+    let count = *GetSidSubAuthorityCount(sid);
+    if count == 0 {
+        return None;
+    }
+    Some(*GetSidSubAuthority(sid, (count - 1) as u32))
+}
+
+#[cfg(target_os = "windows")]
+pub fn check_foreground_inject_target() -> InjectTargetCheck {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        GetCurrentProcess, GetCurrentProcessId, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowThreadProcessId,
+    };
+
+    unsafe {
+        let own_rid = process_integrity_rid(GetCurrentProcess());
+        let own_integrity = own_rid.map(|rid| integrity_label(rid).to_string());
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_null() {
+            return InjectTargetCheck {
+                injectable: false,
+                reason: Some("当前没有前台窗口（可能处于锁屏或 UAC 安全桌面）".to_string()),
+                process_name: None,
+                target_integrity: None,
+                own_integrity,
+            };
+        }
+        let process_name = Some(get_process_name(hwnd)).filter(|name| !name.is_empty());
+        let mut pid: u32 = 0;
+        GetWindowThreadProcessId(hwnd, &mut pid);
+        if pid == GetCurrentProcessId() {
+            return InjectTargetCheck {
+                injectable: false,
+                reason: Some("前台是本应用自己的窗口".to_string()),
+                process_name,
+                target_integrity: own_integrity.clone(),
+                own_integrity,
+            };
+        }
+        let target_rid = if pid == 0 {
+            None
+        } else {
+            let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+            if handle.is_null() {
+                None
+            } else {
+                let rid = process_integrity_rid(handle);
+                CloseHandle(handle);
+                rid
+            }
+        };
+        let (injectable, reason) = integrity_verdict(own_rid, target_rid);
+        InjectTargetCheck {
+            injectable,
+            reason,
+            process_name,
+            target_integrity: target_rid.map(|rid| integrity_label(rid).to_string()),
+            own_integrity,
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn check_foreground_inject_target() -> InjectTargetCheck {
+    InjectTargetCheck {
+        injectable: true,
+        reason: Some("当前平台无法检测前台窗口权限".to_string()),
+        process_name: None,
+        target_integrity: None,
+        own_integrity: None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{format_prompt_context, wrap_xml_cdata, ForegroundApp};
+    use super::{
+        format_prompt_context, integrity_label, integrity_verdict, wrap_xml_cdata, ForegroundApp,
+    };
+
+    #[test]
+    fn elevated_targets_are_reported_as_not_injectable() {
+        assert_eq!(integrity_label(0x2000), "medium");
+        assert_eq!(integrity_label(0x3000), "high");
+        assert_eq!(integrity_label(0x4000), "system");
+
+        let (injectable, reason) = integrity_verdict(Some(0x2000), Some(0x3000));
+        assert!(!injectable);
+        assert!(reason.unwrap().contains("管理员"));
+        assert_eq!(integrity_verdict(Some(0x3000), Some(0x3000)), (true, None));
+        assert_eq!(integrity_verdict(Some(0x3000), Some(0x2000)), (true, None));
+        assert!(integrity_verdict(Some(0x2000), None).0);
+    }
 
     #[test]
     fn shortens_editor_window_titles_for_prompt_context() {
@@ -4,23 +4,219 @@ use std::sync::{
     OnceLock,
 };
 
+use serde::Serialize;
 use tauri::Manager;
 
+use crate::utils::AppError;
+
 const APP_IDENTIFIER: &str = "com.light-whisper.app";
 
-pub fn get_data_dir() -> &'static PathBuf {
-    static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+const BOOTSTRAP_CONFIG_FILE: &str = "bootstrap.json";
+/// 判断目录里是否已有应用数据时参考的文件
+const DATA_DIR_MARKERS: [&str; 3] = [
+    "user_profile.json",
+    "engine.json",
+    "transcription_history.sqlite3",
+];
+
+/// 默认数据目录（不考虑环境变量和 bootstrap 配置）
+pub fn get_default_data_dir() -> PathBuf {
+    let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from(".light-whisper"));
+    base.join(APP_IDENTIFIER)
+}
+
+/// bootstrap 配置放在系统配置目录，不随数据目录迁移，启动早期即可读取
+pub fn get_bootstrap_config_path() -> PathBuf {
+    let base = dirs::config_dir().unwrap_or_else(|| PathBuf::from(".light-whisper"));
+    base.join(APP_IDENTIFIER).join(BOOTSTRAP_CONFIG_FILE)
+}
+
+fn parse_bootstrap_data_dir(content: &str) -> Option<PathBuf> {
+    serde_json::from_str::<serde_json::Value>(content)
+        .ok()?
+        .get("data_dir")?
+        .as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(PathBuf::from)
+}
+
+/// 读取用户自定义的数据目录（None 表示使用默认目录）
+pub fn read_custom_data_dir() -> Option<PathBuf> {
+    let content = std::fs::read_to_string(get_bootstrap_config_path()).ok()?;
+    parse_bootstrap_data_dir(&content)
+}
+
+/// 写入自定义数据目录（None 表示恢复默认），重启后生效
+pub fn write_custom_data_dir(dir: Option<&Path>) -> Result<(), std::io::Error> {
+    let path = get_bootstrap_config_path();
+    match dir {
+        Some(dir) => {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let obj = serde_json::json!({ "data_dir": dir.to_string_lossy() });
+            let data = serde_json::to_string_pretty(&obj).map_err(std::io::Error::other)?;
+            atomic_write(&path, data.as_bytes())
+        }
+        None => match std::fs::remove_file(&path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        },
+    }
+}
+
+/// 目录里是否已有应用数据（设置、引擎配置或历史记录）
+pub fn dir_has_app_data(dir: &Path) -> bool {
+    DATA_DIR_MARKERS
+        .iter()
+        .any(|marker| dir.join(marker).exists())
+}
+
+struct DataDirInfo {
+    path: PathBuf,
+    /// 是否来自 bootstrap 自定义配置
+    from_bootstrap: bool,
+    issue: Option<DataDirIssue>,
+}
+
+/// 启动时数据目录创建失败的记录，用于提示用户
+#[derive(Debug, Clone)]
+pub struct DataDirIssue {
+    /// 原本要使用的目录
+    pub requested: PathBuf,
+    pub error: String,
+    /// 实际改用的临时目录；None 表示临时目录也不可用
+    pub fallback: Option<PathBuf>,
+}
+
+/// 目标目录创建失败时退到系统临时目录，保证本次运行还能读写设置和日志。
+fn resolve_usable_data_dir(
+    requested: PathBuf,
+    fallback: PathBuf,
+) -> (PathBuf, Option<DataDirIssue>) {
+    let err = match std::fs::create_dir_all(&requested) {
+        Ok(()) => return (requested, None),
+        Err(err) => err,
+    };
+    log::error!("创建应用数据目录失败 {}: {}", requested.display(), err);
+    if fallback != requested {
+        match std::fs::create_dir_all(&fallback) {
+            Ok(()) => {
+                log::warn!("改用临时数据目录: {}", fallback.display());
+                return (
+                    fallback.clone(),
+                    Some(DataDirIssue {
+                        requested,
+                        error: err.to_string(),
+                        fallback: Some(fallback),
+                    }),
+                );
+            }
+            Err(fallback_err) => {
+                log::error!(
+                    "临时数据目录同样无法创建 {}: {}",
+                    fallback.display(),
+                    fallback_err
+                );
+            }
+        }
+    }
+    let issue = DataDirIssue {
+        requested: requested.clone(),
+        error: err.to_string(),
+        fallback: None,
+    };
+    (requested, Some(issue))
+}
+
+fn data_dir_info() -> &'static DataDirInfo {
+    static DATA_DIR: OnceLock<DataDirInfo> = OnceLock::new();
     DATA_DIR.get_or_init(|| {
-        let app_dir = std::env::var_os("LIGHT_WHISPER_DATA_DIR")
-            .map(PathBuf::from)
-            .unwrap_or_else(|| {
-                let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from(".light-whisper"));
-                base.join(APP_IDENTIFIER)
-            });
-        if let Err(err) = std::fs::create_dir_all(&app_dir) {
-            log::warn!("创建应用数据目录失败 {}: {}", app_dir.display(), err);
+        let (path, from_bootstrap) = match std::env::var_os("LIGHT_WHISPER_DATA_DIR") {
+            Some(dir) => (PathBuf::from(dir), false),
+            None => match read_custom_data_dir() {
+                Some(dir) => (dir, true),
+                None => (get_default_data_dir(), false),
+            },
+        };
+        let (path, issue) =
+            resolve_usable_data_dir(path, std::env::temp_dir().join(APP_IDENTIFIER));
+        DataDirInfo {
+            path,
+            from_bootstrap,
+            issue,
+        }
+    })
+}
+
+/// 环境变量 LIGHT_WHISPER_DATA_DIR > bootstrap 自定义目录 > 默认目录。
+/// 进程内只解析一次，修改 bootstrap 配置后需重启才生效。
+pub fn get_data_dir() -> &'static PathBuf {
+    &data_dir_info().path
+}
+
+/// 与 `get_data_dir` 相同，但目录（含临时目录兜底）都不可用时返回错误。
+/// 写设置、历史这类必须落盘的路径走这里，错误能直接报给用户。
+pub fn try_get_data_dir() -> Result<PathBuf, AppError> {
+    let info = data_dir_info();
+    match &info.issue {
+        Some(issue) if issue.fallback.is_none() => {
+            Err(AppError::Io(std::io::Error::other(format!(
+                "无法创建应用数据目录 {}: {}",
+                issue.requested.display(),
+                issue.error
+            ))))
+        }
+        _ => Ok(info.path.clone()),
+    }
+}
+
+/// 启动时数据目录创建失败的情况；正常时为 None
+pub fn data_dir_issue() -> Option<&'static DataDirIssue> {
+    data_dir_info().issue.as_ref()
+}
+
+/// 非 ASCII 路径（中文用户名下的 %TEMP% 很常见）交给 Python 端时，部分原生库
+/// 仍按 ANSI 代码页打开文件而失败，即便已设置 PYTHONUTF8。按顺序挑第一个
+/// 纯 ASCII 且可创建的目录，都不满足时退回系统临时目录。
+fn pick_ascii_temp_dir(candidates: &[PathBuf]) -> Option<&PathBuf> {
+    candidates.iter().find(|dir| {
+        dir.to_str().is_some_and(|s| s.is_ascii()) && std::fs::create_dir_all(dir).is_ok()
+    })
+}
+
+/// 与 Python 子进程交换临时音频文件用的目录
+pub fn get_ipc_temp_dir() -> &'static PathBuf {
+    static IPC_TEMP_DIR: OnceLock<PathBuf> = OnceLock::new();
+    IPC_TEMP_DIR.get_or_init(|| {
+        let system_temp = std::env::temp_dir();
+        let mut candidates = vec![system_temp.clone(), get_data_dir().join("tmp")];
+        if let Some(program_data) = std::env::var_os("ProgramData") {
+            candidates.push(
+                PathBuf::from(program_data)
+                    .join("light-whisper")
+                    .join("tmp"),
+            );
+        }
+        match pick_ascii_temp_dir(&candidates) {
+            Some(dir) => {
+                if *dir != system_temp {
+                    log::info!(
+                        "系统临时目录包含非 ASCII 字符，临时音频改用: {}",
+                        dir.display()
+                    );
+                }
+                dir.clone()
+            }
+            None => {
+                log::warn!(
+                    "未找到纯 ASCII 临时目录，继续使用: {}",
+                    system_temp.display()
+                );
+                system_temp
+            }
         }
-        app_dir
     })
 }
 
@@ -47,6 +243,77 @@ pub fn get_download_script_path(app: &tauri::AppHandle) -> PathBuf {
     get_resource_script_path(app, "download_models.py")
 }
 
+/// 启动自检覆盖的打包脚本
+const RESOURCE_SCRIPTS: [&str; 3] = [
+    "funasr_server.py",
+    "whisper_server.py",
+    "download_models.py",
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceScriptCheck {
+    pub name: String,
+    /// 实际解析到的路径，便于排查打包布局
+    pub path: String,
+    pub readable: bool,
+    pub error: Option<String>,
+}
+
+fn check_resource_script(name: &str, path: &Path) -> ResourceScriptCheck {
+    let error = match std::fs::File::open(path) {
+        Ok(_) => None,
+        Err(err) => Some(err.to_string()),
+    };
+    ResourceScriptCheck {
+        name: name.to_string(),
+        path: strip_win_prefix(path),
+        readable: error.is_none(),
+        error,
+    }
+}
+
+/// 逐个确认打包的 Python 脚本能解析并打开。
+pub fn check_resource_scripts(app: &tauri::AppHandle) -> Vec<ResourceScriptCheck> {
+    RESOURCE_SCRIPTS
+        .iter()
+        .map(|name| check_resource_script(name, &get_resource_script_path(app, name)))
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedPath {
+    pub name: String,
+    /// 解析失败（例如拿不到系统日志目录）时为 None
+    pub path: Option<String>,
+    pub exists: bool,
+}
+
+fn resolved_path(name: &str, path: Option<PathBuf>) -> ResolvedPath {
+    ResolvedPath {
+        name: name.to_string(),
+        exists: path.as_deref().is_some_and(Path::exists),
+        path: path.as_deref().map(strip_win_prefix),
+    }
+}
+
+/// 汇总当前实际生效的各个路径，排查模型位置或脚本找不到时用。
+pub fn resolved_paths(app: &tauri::AppHandle) -> Vec<ResolvedPath> {
+    vec![
+        resolved_path("dataDir", Some(get_data_dir().clone())),
+        resolved_path("bootstrapConfig", Some(get_bootstrap_config_path())),
+        resolved_path("engineConfig", Some(get_engine_config_path())),
+        resolved_path("hfCache", Some(get_effective_models_dir())),
+        resolved_path("engineDir", Some(get_engine_dir())),
+        resolved_path("funasrServer", Some(get_funasr_server_path(app))),
+        resolved_path("whisperServer", Some(get_whisper_server_path(app))),
+        resolved_path("downloadScript", Some(get_download_script_path(app))),
+        resolved_path("logDir", app.path().app_log_dir().ok()),
+        resolved_path("tempDir", Some(get_ipc_temp_dir().clone())),
+    ]
+}
+
 pub fn strip_win_prefix(path: &std::path::Path) -> String {
     let s = path.to_string_lossy().to_string();
     s.strip_prefix(r"\\?\").unwrap_or(&s).to_string()
@@ -56,11 +323,59 @@ pub fn get_engine_config_path() -> PathBuf {
     get_data_dir().join("engine.json")
 }
 
+pub const SUPPORTED_ENGINES: &[&str] = &["sensevoice", "whisper", "glm-asr", "alibaba-asr"];
+/// 脚本/自动化测试用：设置后覆盖 engine.json 里的引擎选择
+pub const ENGINE_ENV_VAR: &str = "LIGHT_WHISPER_ENGINE";
+
+fn parse_engine_override(value: Option<&str>) -> Result<Option<String>, String> {
+    let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    let engine = value.to_ascii_lowercase();
+    if SUPPORTED_ENGINES.contains(&engine.as_str()) {
+        Ok(Some(engine))
+    } else {
+        Err(format!(
+            "环境变量 {} 的值 {} 不是支持的引擎（可选: {}），改用 engine.json",
+            ENGINE_ENV_VAR,
+            value,
+            SUPPORTED_ENGINES.join(", ")
+        ))
+    }
+}
+
+/// 进程启动时解析一次的引擎环境变量覆盖；无效值只记录警告后忽略。
+pub fn engine_env_override() -> Option<&'static str> {
+    static ENGINE_OVERRIDE: OnceLock<Option<String>> = OnceLock::new();
+    ENGINE_OVERRIDE
+        .get_or_init(|| {
+            let value = std::env::var(ENGINE_ENV_VAR).ok();
+            match parse_engine_override(value.as_deref()) {
+                Ok(Some(engine)) => {
+                    log::info!("引擎由环境变量 {} 指定: {}", ENGINE_ENV_VAR, engine);
+                    Some(engine)
+                }
+                Ok(None) => {
+                    log::info!("引擎使用 engine.json 中的配置");
+                    None
+                }
+                Err(err) => {
+                    log::warn!("{}", err);
+                    None
+                }
+            }
+        })
+        .as_deref()
+}
+
+/// 环境变量 LIGHT_WHISPER_ENGINE > engine.json > 默认 sensevoice。
 pub fn read_engine_config() -> String {
+    if let Some(engine) = engine_env_override() {
+        return engine.to_string();
+    }
     if let Some(engine) = read_engine_json().get("engine").and_then(|v| v.as_str()) {
-        match engine {
-            "whisper" | "sensevoice" | "glm-asr" | "alibaba-asr" => return engine.to_string(),
-            _ => {}
+        if SUPPORTED_ENGINES.contains(&engine) {
+            return engine.to_string();
         }
     }
     "sensevoice".to_string()
@@ -191,6 +506,33 @@ fn replace_file_atomic(from: &Path, to: &Path) -> Result<(), std::io::Error> {
     std::fs::rename(from, to)
 }
 
+/// `path` 所在磁盘对当前用户可用的剩余空间（字节）。目录还不存在时按最近
+/// 一级已存在的父目录查询；查不到时返回 None。
+#[cfg(target_os = "windows")]
+pub fn available_disk_space(path: &Path) -> Option<u64> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let existing = path.ancestors().find(|dir| dir.exists())?;
+    let mut wide: Vec<u16> = existing.as_os_str().encode_wide().collect();
+    wide.push(0);
+    let mut free_bytes: u64 = 0;
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(
+            wide.as_ptr(),
+            &mut free_bytes,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    (ok != 0).then_some(free_bytes)
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn available_disk_space(_path: &Path) -> Option<u64> {
+    None
+}
+
 pub fn atomic_write(path: &Path, data: &[u8]) -> Result<(), std::io::Error> {
     use std::io::Write;
 
@@ -418,13 +760,51 @@ pub fn write_models_dir(dir: Option<&str>) -> Result<(), std::io::Error> {
     write_engine_json(&obj)
 }
 
+fn legacy_hf_hub_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_default()
+        .join(".cache")
+        .join("huggingface")
+        .join("hub")
+}
+
+/// hub 目录下是否已有下载过的模型仓库（`models--*`）
+fn hub_has_models(hub: &Path) -> bool {
+    std::fs::read_dir(hub).is_ok_and(|entries| {
+        entries
+            .filter_map(Result::ok)
+            .any(|entry| entry.file_name().to_string_lossy().starts_with("models--"))
+    })
+}
+
+/// 数据目录下的 HF 缓存还是空的、旧的默认缓存里已有模型时，继续用旧缓存，
+/// 避免切换数据目录后把模型重新下载一遍。
+fn pick_hf_home(data_hf_home: PathBuf, legacy_hub: &Path) -> Option<PathBuf> {
+    if !hub_has_models(&data_hf_home.join("hub")) && hub_has_models(legacy_hub) {
+        return None;
+    }
+    Some(data_hf_home)
+}
+
+/// 自定义数据目录生效且用户没有设置 HF_HOME 时，HF 缓存跟随数据目录，
+/// 子进程通过 HF_HOME 拿到同一位置；旧的默认缓存里已有模型时沿用旧缓存
+pub fn get_hf_home_override() -> Option<PathBuf> {
+    if std::env::var_os("HF_HOME").is_some() || !data_dir_info().from_bootstrap {
+        return None;
+    }
+    pick_hf_home(get_data_dir().join("huggingface"), &legacy_hf_hub_dir())
+}
+
 /// 默认 HF 缓存根目录（不考虑自定义配置）
 pub fn get_default_models_dir() -> PathBuf {
     if let Ok(hf_home) = std::env::var("HF_HOME") {
         return PathBuf::from(hf_home).join("hub");
     }
-    if let Some(home) = dirs::home_dir() {
-        return home.join(".cache").join("huggingface").join("hub");
+    if let Some(hf_home) = get_hf_home_override() {
+        return hf_home.join("hub");
+    }
+    if dirs::home_dir().is_some() {
+        return legacy_hf_hub_dir();
     }
     PathBuf::from(".cache").join("huggingface").join("hub")
 }
@@ -439,7 +819,113 @@ pub fn get_effective_models_dir() -> PathBuf {
 
 #[cfg(test)]
 mod tests {
-    use super::engine_json_object_or_empty;
+    use super::{
+        check_resource_script, engine_json_object_or_empty, parse_bootstrap_data_dir,
+        parse_engine_override, pick_ascii_temp_dir, pick_hf_home, resolve_usable_data_dir,
+    };
+    use std::path::PathBuf;
+
+    #[test]
+    fn engine_override_accepts_only_supported_engines() {
+        assert_eq!(parse_engine_override(None), Ok(None));
+        assert_eq!(parse_engine_override(Some("  ")), Ok(None));
+        assert_eq!(
+            parse_engine_override(Some(" Whisper ")),
+            Ok(Some("whisper".to_string()))
+        );
+        assert!(parse_engine_override(Some("paraformer")).is_err());
+    }
+
+    #[test]
+    fn ascii_temp_dir_skips_cjk_candidates() {
+        let base = std::env::temp_dir().join(format!("lw_ascii_tmp_{}", std::process::id()));
+        let cjk = base.join("用户").join("临时");
+        let ascii = base.join("fallback");
+        let candidates = [cjk.clone(), ascii.clone()];
+        if base.to_str().is_some_and(|s| s.is_ascii()) {
+            assert_eq!(pick_ascii_temp_dir(&candidates), Some(&ascii));
+        }
+        assert_eq!(pick_ascii_temp_dir(&[cjk]), None);
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn unusable_data_dir_falls_back_to_temp() {
+        let base = std::env::temp_dir().join(format!("lw_data_dir_{}", std::process::id()));
+        std::fs::create_dir_all(&base).unwrap();
+        // 父路径是普通文件，目录必然创建失败
+        let blocker = base.join("blocker");
+        std::fs::write(&blocker, b"x").unwrap();
+        let requested = blocker.join("data");
+        let fallback = base.join("fallback");
+
+        let (path, issue) = resolve_usable_data_dir(requested.clone(), fallback.clone());
+        assert_eq!(path, fallback);
+        let issue = issue.expect("issue recorded");
+        assert_eq!(issue.requested, requested);
+        assert_eq!(issue.fallback.as_ref(), Some(&fallback));
+
+        let (path, issue) = resolve_usable_data_dir(requested.clone(), blocker.join("tmp"));
+        assert_eq!(path, requested);
+        assert!(issue.expect("issue recorded").fallback.is_none());
+
+        let (path, issue) = resolve_usable_data_dir(fallback.clone(), base.join("unused"));
+        assert_eq!(path, fallback);
+        assert!(issue.is_none());
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn resource_script_check_reports_missing_files() {
+        let dir = std::env::temp_dir().join(format!("lw_scripts_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let present = dir.join("funasr_server.py");
+        std::fs::write(&present, b"# ok").unwrap();
+
+        let ok = check_resource_script("funasr_server.py", &present);
+        assert!(ok.readable);
+        assert!(ok.error.is_none());
+
+        let missing = check_resource_script("whisper_server.py", &dir.join("whisper_server.py"));
+        assert!(!missing.readable);
+        assert!(missing.error.is_some());
+        assert!(missing.path.ends_with("whisper_server.py"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn hf_home_keeps_legacy_cache_until_data_dir_has_models() {
+        let base = std::env::temp_dir().join(format!("lw_hf_home_{}", std::process::id()));
+        let data_hf_home = base.join("data").join("huggingface");
+        let legacy_hub = base.join("legacy").join("hub");
+
+        // 两边都没有模型：跟随数据目录
+        assert_eq!(
+            pick_hf_home(data_hf_home.clone(), &legacy_hub),
+            Some(data_hf_home.clone())
+        );
+
+        std::fs::create_dir_all(legacy_hub.join("models--FunAudioLLM--SenseVoiceSmall")).unwrap();
+        assert_eq!(pick_hf_home(data_hf_home.clone(), &legacy_hub), None);
+
+        std::fs::create_dir_all(data_hf_home.join("hub").join("models--openai--whisper")).unwrap();
+        assert_eq!(
+            pick_hf_home(data_hf_home.clone(), &legacy_hub),
+            Some(data_hf_home)
+        );
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn bootstrap_data_dir_parses_trimmed_path() {
+        assert_eq!(
+            parse_bootstrap_data_dir(r#"{"data_dir": "  /mnt/data/lw  "}"#),
+            Some(PathBuf::from("/mnt/data/lw"))
+        );
+        assert_eq!(parse_bootstrap_data_dir(r#"{"data_dir": ""}"#), None);
+        assert_eq!(parse_bootstrap_data_dir(r#"{"data_dir": 1}"#), None);
+        assert_eq!(parse_bootstrap_data_dir("not json"), None);
+    }
 
     #[test]
     fn engine_json_string_normalizes_to_empty_object() {
@@ -9,23 +9,60 @@ import type {
   ApiFormat,
   AppProfileRule,
   AppUpdateInfo,
+  AudioHostListPayload,
+  AudioPipelineBenchmark,
+  AudioPrewarmReport,
+  AudioSubsystemResetPayload,
+  ConfigInputSettings,
   AiModelListPayload,
+  DeviceCapabilitiesPayload,
+  DictationHotkeySetting,
+  DownloadSpaceReport,
+  EngineComparison,
+  EngineStartupTiming,
   FunASRStatus,
+  HistoryRecordingFormat,
+  HotkeyAction,
+  HotkeyBinding,
   HotkeyDiagnostic,
+  HotkeyValidation,
+  InjectTargetCheck,
+  InjectionTestResult,
   InputDeviceListPayload,
+  InputMethod,
+  InputSampleFormat,
+  LanguageInputMethods,
   LlmReasoningMode,
   LlmReasoningSupport,
+  MicrophoneInfoPayload,
   ModelCheckResult,
+  NoiseFloorReport,
   OpenaiAuthMode,
   OpenaiCodexOauthDeviceCodeChallenge,
   OpenaiCodexOauthStatus,
+  OutputCleanupSettings,
   PersistentHistoryFilter,
   PersistentHistoryPage,
   PersistentHistoryRecord,
   PersistentHistoryStats,
+  PostHookSettings,
   RecordingMode,
+  ResourceUsage,
+  SavedRecording,
+  ServerEnvironmentReport,
+  ServerStderrTail,
+  ServerVersionInfo,
+  SubtitleCue,
+  SubtitleDisplay,
+  SubtitleExportFormat,
+  TempAudioCleanupReport,
+  TempAudioUsage,
+  TextVariant,
+  TranscriptionMode,
   TranscriptionResult,
+  UsageStatsReport,
   UserProfile,
+  VramBudgetReport,
   WebSearchProvider,
 } from "@/types";
 
@@ -45,6 +82,38 @@ export interface RecordingSnapshot {
   mode: RecordingMode;
   outcome?: RecordingOutcomeKind;
   detail?: string;
+  /** 实际采集的设备名；开始采集后才有 */
+  deviceName?: string;
+}
+
+/** 拉取式录音计时；elapsedSec 按样本数计算，rmsLevel 归一化到 0..1 */
+export interface RecordingStats {
+  recording: boolean;
+  sessionId: number | null;
+  paused: boolean;
+  sampleRate: number;
+  sampleCount: number;
+  elapsedSec: number;
+  rmsLevel: number;
+}
+
+export interface TaskStatusSnapshot {
+  recording: RecordingSnapshot | null;
+  recordingSlot: "starting" | "active" | null;
+  audioCaptureActive: boolean;
+  interimTaskActive: boolean;
+  editGrabPending: boolean;
+  recordingPaused: boolean;
+  pendingPasteCount: number;
+  funasrReady: boolean;
+  funasrStarting: boolean;
+  funasrStartingChild: boolean;
+  funasrProcessRunning: boolean | null;
+  funasrGeneration: number;
+  downloadInProgress: boolean | null;
+  microphoneMonitorActive: boolean;
+  assistantChatActive: boolean;
+  selectionTaskActive: boolean;
 }
 
 export class IpcError extends Error {
@@ -114,25 +183,84 @@ export function transcribeAudio(audioBase64: string): Promise<TranscriptionResul
   return invokeCommand<TranscriptionResult>("transcribe_audio", { audioBase64 });
 }
 
+export function transcribePcm(
+  samples: Int16Array | number[],
+  sampleRate: number
+): Promise<TranscriptionResult> {
+  return invokeCommand<TranscriptionResult>("transcribe_pcm", {
+    samples: Array.from(samples),
+    sampleRate,
+  });
+}
+
+export const transcribeClipboardFile = createNoArgCommand<TranscriptionResult>("transcribe_clipboard_file");
+
 export function openAppReleasePage(url?: string | null): Promise<string> {
   return invokeCommand<string>("open_app_release_page", { url: url ?? null });
 }
 
 export const checkFunASRStatus = createNoArgCommand<FunASRStatus>("check_funasr_status");
+/** 诊断用：引擎子进程会拿到的可执行文件、参数和环境变量。 */
+export const getServerEnvironment = createNoArgCommand<ServerEnvironmentReport>("get_server_environment");
+export const getServerVersion = createNoArgCommand<ServerVersionInfo>("get_server_version");
+export const getLastStartupTime = createNoArgCommand<EngineStartupTiming | null>("get_last_startup_time");
+export function getServerStderr(lines?: number): Promise<ServerStderrTail> {
+  return invokeCommand<ServerStderrTail>("get_server_stderr", { lines: lines ?? null });
+}
+export const getTempUsage = createNoArgCommand<TempAudioUsage>("get_temp_usage");
+export const clearTempAudio = createNoArgCommand<TempAudioCleanupReport>("clear_temp_audio");
+export const getResourceUsage = createNoArgCommand<ResourceUsage>("get_resource_usage");
 export const checkModelFiles = createNoArgCommand<ModelCheckResult>("check_model_files");
 export const downloadModels = createNoArgCommand<string>("download_models");
+export const checkDownloadSpace = createNoArgCommand<DownloadSpaceReport>("check_download_space");
 export const cancelModelDownload = createNoArgCommand<string>("cancel_model_download");
 export const restartFunASR = createNoArgCommand<string>("restart_funasr");
+export const reloadModel = createNoArgCommand<string>("reload_model");
 export const getEngine = createNoArgCommand<string>("get_engine");
 
 export function setEngine(engine: string): Promise<string> {
   return invokeCommand<string>("set_engine", { engine });
 }
 
+export function compareEngines(samplePath: string | null): Promise<EngineComparison> {
+  return invokeCommand<EngineComparison>("compare_engines", { samplePath });
+}
+
+export function setDownloadRetrySettings(maxAttempts: number, stallTimeoutSecs: number): Promise<void> {
+  return invokeCommand<void>("set_download_retry_settings", { maxAttempts, stallTimeoutSecs });
+}
+
 export function copyToClipboard(text: string): Promise<string> {
   return invokeCommand<string>("copy_to_clipboard", { text });
 }
 
+export function testInjection(
+  text: string,
+  method?: InputMethod
+): Promise<InjectionTestResult> {
+  return invokeCommand<InjectionTestResult>("test_injection", { text, method: method ?? null });
+}
+
+export const canInjectHere = createNoArgCommand<InjectTargetCheck>("can_inject_here");
+
+export function reportInjectionTestValue(value: string): Promise<void> {
+  return invokeCommand<void>("report_injection_test_value", { value });
+}
+
+export const repasteLast = createNoArgCommand<boolean>("repaste_last");
+
+export function confirmPaste(id?: number): Promise<boolean> {
+  return invokeCommand<boolean>("confirm_paste", { id: id ?? null });
+}
+
+export function discardPaste(id?: number): Promise<boolean> {
+  return invokeCommand<boolean>("discard_paste", { id: id ?? null });
+}
+
+export function setReviewBeforePaste(enabled: boolean): Promise<void> {
+  return invokeCommand<void>("set_review_before_paste", { enabled });
+}
+
 export function pasteText(
   text: string,
   method?: "sendInput" | "clipboard"
@@ -140,9 +268,47 @@ export function pasteText(
   return invokeCommand<string>("paste_text", { text, method });
 }
 
+export function setFirstCharDelayMs(ms: number | null): Promise<number> {
+  return invokeCommand<number>("set_first_char_delay_ms", { ms });
+}
+
+export function setClipboardWriteRetries(retries: number | null): Promise<number> {
+  return invokeCommand<number>("set_clipboard_write_retries", { retries });
+}
+
 export const hideMainWindow = createNoArgCommand<string>("hide_main_window");
 export const showSubtitleWindow = createNoArgCommand<string>("show_subtitle_window");
 export const hideSubtitleWindow = createNoArgCommand<string>("hide_subtitle_window");
+
+export function setSubtitleDisplay(display: SubtitleDisplay): Promise<void> {
+  return invokeCommand<void>("set_subtitle_display", { display });
+}
+
+export function setSubtitlePersistent(enabled: boolean): Promise<void> {
+  return invokeCommand<void>("set_subtitle_persistent", { enabled });
+}
+
+export function setSubtitleKeepOnTop(enabled: boolean): Promise<void> {
+  return invokeCommand<void>("set_subtitle_keep_on_top", { enabled });
+}
+
+/** 字幕显示后的最短可见时长（毫秒），返回生效值；null 恢复默认 600ms。 */
+export function setMinSubtitleVisibleMs(ms: number | null): Promise<number> {
+  return invokeCommand<number>("set_min_subtitle_visible_ms", { ms });
+}
+
+/** 字幕最多显示的宽度（全角算 2），超出只显示末尾；0 不截断，null 恢复默认 60。 */
+export function setSubtitleMaxChars(maxChars: number | null): Promise<number> {
+  return invokeCommand<number>("set_subtitle_max_chars", { maxChars });
+}
+
+export function setSubtitleInteractive(enabled: boolean): Promise<void> {
+  return invokeCommand<void>("set_subtitle_interactive", { enabled });
+}
+
+export const resetSubtitlePosition = createNoArgCommand<void>("reset_subtitle_position");
+export const startSubtitleDrag = createNoArgCommand<void>("start_subtitle_drag");
+
 export const getOpenaiCodexOauthStatus = createNoArgCommand<OpenaiCodexOauthStatus>("get_openai_codex_oauth_status");
 export const loginOpenaiCodexOauth = createNoArgCommand<OpenaiCodexOauthStatus>("login_openai_codex_oauth");
 export const startOpenaiCodexOauthDeviceCode = createNoArgCommand<OpenaiCodexOauthDeviceCodeChallenge>("start_openai_codex_oauth_device_code");
@@ -159,30 +325,228 @@ export function registerCustomHotkey(shortcut: string): Promise<string> {
   return invokeCommand<string>("register_custom_hotkey", { shortcut });
 }
 
+export const getDictationHotkeySetting = createNoArgCommand<DictationHotkeySetting>("get_dictation_hotkey_setting");
+export const disableDictationHotkey = createNoArgCommand<void>("disable_dictation_hotkey");
+
+export function validateHotkey(shortcut: string): Promise<HotkeyValidation> {
+  return invokeCommand<HotkeyValidation>("validate_hotkey", { shortcut });
+}
+
 export function registerAssistantHotkey(shortcut: string): Promise<string> {
   return invokeCommand<string>("register_assistant_hotkey", { shortcut });
 }
 
-export const startRecording = createNoArgCommand<number>("start_recording");
+export function registerLanguageCycleHotkey(shortcut: string): Promise<string> {
+  return invokeCommand<string>("register_language_cycle_hotkey", { shortcut });
+}
+
+export const getHotkeyBindings = createNoArgCommand<HotkeyBinding[]>("get_hotkey_bindings");
+
+export function setHotkeyBinding(action: HotkeyAction, shortcut: string | null): Promise<HotkeyBinding[]> {
+  return invokeCommand<HotkeyBinding[]>("set_hotkey_binding", { action, shortcut });
+}
+
+/** `deviceName` 只对本次录音生效；省略时使用已保存的麦克风选择。 */
+export function startRecording(deviceName?: string | null): Promise<number> {
+  return invokeCommand<number>("start_recording", { deviceName: deviceName ?? null });
+}
 export const stopRecording = createNoArgCommand<void>("stop_recording");
+export const forceStopRecording = createNoArgCommand<number | null>("force_stop_recording");
+export const pauseRecording = createNoArgCommand<number>("pause_recording");
+export const resumeRecording = createNoArgCommand<number>("resume_recording");
 export const getRecordingSnapshot = createNoArgCommand<RecordingSnapshot | null>("get_recording_snapshot");
+export const getRecordingStats = createNoArgCommand<RecordingStats>("get_recording_stats");
+export const getTaskStatus = createNoArgCommand<TaskStatusSnapshot>("get_task_status");
 export const testMicrophone = createNoArgCommand<string>("test_microphone");
+/** 建一条输入流后立即关闭，让第一次录音更快开始；会短暂打开麦克风。 */
+export const prewarmAudio = createNoArgCommand<AudioPrewarmReport>("prewarm_audio");
+
+export const listAudioHosts = createNoArgCommand<AudioHostListPayload>("list_audio_hosts");
+
+/** 选择录音使用的音频后端，null 恢复平台默认；返回规范化后的后端名。 */
+export function setAudioHost(host: string | null): Promise<string | null> {
+  return invokeCommand<string | null>("set_audio_host", { host });
+}
+
+export function setAudioPrewarmEnabled(enabled: boolean): Promise<void> {
+  return invokeCommand<void>("set_audio_prewarm_enabled", { enabled });
+}
 export const listInputDevices = createNoArgCommand<InputDeviceListPayload>("list_input_devices");
+/** 热插拔音频设备后重置录音子系统，返回重新枚举到的设备。 */
+export const resetAudioSubsystem = createNoArgCommand<AudioSubsystemResetPayload>("reset_audio_subsystem");
+export const getDeviceCapabilities = createNoArgCommand<DeviceCapabilitiesPayload>("get_device_capabilities");
+export const getMicrophoneInfo = createNoArgCommand<MicrophoneInfoPayload>("get_microphone_info");
 export const startMicrophoneLevelMonitor = createNoArgCommand<string>("start_microphone_level_monitor");
 export const stopMicrophoneLevelMonitor = createNoArgCommand<void>("stop_microphone_level_monitor");
 
+export function benchmarkAudioPipeline(
+  durationSecs: number,
+  sourceSampleRate?: number
+): Promise<AudioPipelineBenchmark> {
+  return invokeCommand<AudioPipelineBenchmark>("benchmark_audio_pipeline", {
+    durationSecs,
+    sourceSampleRate: sourceSampleRate ?? null,
+  });
+}
+
+export function measureNoiseFloor(secs: number): Promise<NoiseFloorReport> {
+  return invokeCommand<NoiseFloorReport>("measure_noise_floor", { secs });
+}
+
 export function setInputDevice(name?: string | null): Promise<void> {
   return invokeCommand<void>("set_input_device", { name: name ?? null });
 }
 
+export function setWaveformStreamEnabled(enabled: boolean): Promise<void> {
+  return invokeCommand<void>("set_waveform_stream_enabled", { enabled });
+}
+
+export const getDeviceSampleRates =
+  createNoArgCommand<Record<string, number>>("get_device_sample_rates");
+
+export function setDeviceSampleRate(deviceName: string, sampleRate: number | null): Promise<void> {
+  return invokeCommand<void>("set_device_sample_rate", { deviceName, sampleRate });
+}
+
+export function setPreferredSampleFormat(format: InputSampleFormat | null): Promise<void> {
+  return invokeCommand<void>("set_preferred_sample_format", { format });
+}
+
 export function setInputMethodCommand(method: string): Promise<void> {
   return invokeCommand<void>("set_input_method", { method });
 }
 
+export function setInputMethodFallback(
+  methods: InputMethod[] | null
+): Promise<InputMethod[]> {
+  return invokeCommand<InputMethod[]>("set_input_method_fallback", { methods });
+}
+
+/** 仅对下一次注入生效，用后或 2 分钟后恢复默认；传 null 取消。 */
+export function setNextInputMethod(method: InputMethod | null): Promise<void> {
+  return invokeCommand<void>("set_next_input_method", { method });
+}
+
+export function setLanguageInputMethod(
+  language: "cjk" | "latin",
+  method: InputMethod | null
+): Promise<void> {
+  return invokeCommand<void>("set_language_input_method", { language, method });
+}
+
+export const getLanguageInputMethods = createNoArgCommand<LanguageInputMethods>("get_language_input_methods");
+
+export function setRecordingMergeWindow(windowMs: number): Promise<void> {
+  return invokeCommand<void>("set_recording_merge_window", { windowMs });
+}
+
+/** 录音期间待粘贴队列的段数上限；null 恢复默认（50）。 */
+export function setPendingPasteLimit(limit: number | null): Promise<void> {
+  return invokeCommand<void>("set_pending_paste_limit", { limit });
+}
+
+export function setInterimCostSettings(
+  heavyCostMs: number,
+  lightCostMs: number,
+  autoCalibrate: boolean
+): Promise<void> {
+  return invokeCommand<void>("set_interim_cost_settings", {
+    heavyCostMs,
+    lightCostMs,
+    autoCalibrate,
+  });
+}
+
+export function setLoudnessNormalization(enabled: boolean, targetLufs?: number | null): Promise<void> {
+  return invokeCommand<void>("set_loudness_normalization", { enabled, targetLufs: targetLufs ?? null });
+}
+
+export function setLeadingSilencePad(padMs: number | null): Promise<void> {
+  return invokeCommand<void>("set_leading_silence_pad", { padMs });
+}
+
+export function setOutputCleanup(settings: OutputCleanupSettings): Promise<void> {
+  return invokeCommand<void>("set_output_cleanup", { settings });
+}
+
+export function setRecordingBufferPreallocSecs(secs: number | null): Promise<number> {
+  return invokeCommand<number>("set_recording_buffer_prealloc_secs", { secs });
+}
+
+export function setTranscriptionMode(mode: TranscriptionMode): Promise<void> {
+  return invokeCommand<void>("set_transcription_mode", { mode });
+}
+
+export function setTextVariants(subtitle: TextVariant, paste: TextVariant): Promise<void> {
+  return invokeCommand<void>("set_text_variants", { subtitle, paste });
+}
+
+export function setStripPrefix(phrases: string[], fuzzy: boolean): Promise<string[]> {
+  return invokeCommand<string[]>("set_strip_prefix", { phrases, fuzzy });
+}
+
+export type ServerLogLevel = "debug" | "info" | "warning" | "error";
+
+export function setServerLogLevel(level: ServerLogLevel): Promise<string> {
+  return invokeCommand<string>("set_server_log_level", { level });
+}
+
+/** 本地引擎的逆文本正则化 / 标点开关；引擎运行中立即生效。 */
+export function setAsrFormatting(itn: boolean, punctuation: boolean): Promise<void> {
+  return invokeCommand<void>("set_asr_formatting", { itn, punctuation });
+}
+
+/** 返回引擎实际生效的值；null 表示引擎未运行或不支持。 */
+export function setDiarization(enabled: boolean): Promise<boolean | null> {
+  return invokeCommand<boolean | null>("set_diarization", { enabled });
+}
+
+/** 保存语言轮换列表，返回按当前引擎校验、去重后的结果。 */
+export function setLanguageCycle(languages: string[]): Promise<string[]> {
+  return invokeCommand<string[]>("set_language_cycle", { languages });
+}
+
+/** 切到轮换列表的下一种语言；null 表示自动检测。 */
+export const cycleLanguage = createNoArgCommand<string | null>("cycle_language");
+
+export function setAutoFallbackEngine(enabled: boolean): Promise<void> {
+  return invokeCommand<void>("set_auto_fallback_engine", { enabled });
+}
+
+export interface CpuThreadsInfo {
+  configured: number | null;
+  available: number;
+}
+
+export const getCpuThreads = createNoArgCommand<CpuThreadsInfo>("get_cpu_threads");
+
+export function setCpuThreads(threads: number | null): Promise<string> {
+  return invokeCommand<string>("set_cpu_threads", { threads });
+}
+
+export function setServerCrashGraceMs(ms: number | null): Promise<number> {
+  return invokeCommand<number>("set_server_crash_grace_ms", { ms });
+}
+
+export const getVramBudget = createNoArgCommand<VramBudgetReport>("get_vram_budget");
+
+export function setMaxVramGb(maxVramGb: number | null): Promise<VramBudgetReport> {
+  return invokeCommand<VramBudgetReport>("set_max_vram_gb", { maxVramGb });
+}
+
+export function setShowPythonConsole(enabled: boolean): Promise<void> {
+  return invokeCommand<void>("set_show_python_console", { enabled });
+}
+
 export function setSoundEnabled(enabled: boolean): Promise<void> {
   return invokeCommand<void>("set_sound_enabled", { enabled });
 }
 
+/** 最短录音时长（秒，0.1-5.0），更短的录音不转写 */
+export function setMinAudioDuration(secs: number): Promise<void> {
+  return invokeCommand<void>("set_min_audio_duration", { secs });
+}
+
 export function setAiPolishConfig(enabled: boolean, apiKey: string): Promise<void> {
   return invokeCommand<void>("set_ai_polish_config", { enabled, apiKey });
 }
@@ -223,8 +587,15 @@ export function setHistorySettings(
   enabled: boolean,
   saveAudio: boolean,
   retentionDays: number,
+  recordingFormat?: HistoryRecordingFormat,
 ): Promise<void> {
-  return invokeCommand<void>("set_history_settings", { enabled, saveAudio, retentionDays });
+  // 省略 recordingFormat 时后端沿用已保存的格式
+  return invokeCommand<void>("set_history_settings", {
+    enabled,
+    saveAudio,
+    retentionDays,
+    ...(recordingFormat ? { recordingFormat } : {}),
+  });
 }
 
 export function setAppProfileRules(rules: AppProfileRule[]): Promise<void> {
@@ -241,14 +612,49 @@ export const getTranscriptionHistoryStats = createNoArgCommand<PersistentHistory
   "get_transcription_history_stats",
 );
 
+export const getUsageStats = createNoArgCommand<UsageStatsReport>("get_usage_stats");
+
+export const resetUsageStats = createNoArgCommand<UsageStatsReport>("reset_usage_stats");
+
+export function setSaveRecordings(enabled: boolean): Promise<void> {
+  return invokeCommand<void>("set_save_recordings", { enabled });
+}
+
+export const listRecordings = createNoArgCommand<SavedRecording[]>("list_recordings");
+
 export function deleteTranscriptionHistory(id: number): Promise<boolean> {
   return invokeCommand<boolean>("delete_transcription_history", { id });
 }
 
+export function repasteHistory(id: number): Promise<void> {
+  return invokeCommand<void>("repaste_history", { id });
+}
+
+export function copyHistory(id: number): Promise<void> {
+  return invokeCommand<void>("copy_history", { id });
+}
+
+export function updateHistory(id: number, text: string): Promise<boolean> {
+  return invokeCommand<boolean>("update_history", { id, text });
+}
+
 export function exportTranscriptionHistory(format: "json" | "markdown"): Promise<string | null> {
   return invokeCommand<string | null>("export_transcription_history", { format });
 }
 
+export function exportTranscription(
+  id: number,
+  format: SubtitleExportFormat,
+  options: { path?: string; segments?: SubtitleCue[] } = {}
+): Promise<string | null> {
+  return invokeCommand<string | null>("export_transcription", {
+    id,
+    format,
+    path: options.path ?? null,
+    segments: options.segments ?? null,
+  });
+}
+
 export function reprocessTranscriptionHistory(
   id: number,
   kind: "polish" | "asr",
@@ -323,6 +729,19 @@ export function importUserProfile(jsonData: string): Promise<void> {
   return invokeCommand<void>("import_user_profile", { jsonData });
 }
 
+/** 导出整机配置（画像 + 引擎 + 输入设置，不含 API Key 和本机路径），返回写入路径。 */
+export function exportConfig(path: string): Promise<string> {
+  return invokeCommand<string>("export_config", { path });
+}
+
+/**
+ * 导入 exportConfig 生成的文件；会重新注册热键、切换输入设备和注入方式，
+ * 必要时切换或重启引擎。返回的输入设置需由调用方写回 localStorage。
+ */
+export function importConfig(path: string): Promise<ConfigInputSettings> {
+  return invokeCommand<ConfigInputSettings>("import_config", { path });
+}
+
 export function submitUserCorrection(original: string, corrected: string, rawOriginal?: string | null): Promise<void> {
   return invokeCommand<void>("submit_user_correction", {
     original,
@@ -335,6 +754,11 @@ export function setRecordingMode(toggle: boolean): Promise<void> {
   return invokeCommand<void>("set_recording_mode", { toggle });
 }
 
+/** 序列热键（如 "Ctrl+K, D"）相邻两步的最长间隔，200-5000ms。 */
+export function setHotkeySequenceTimeout(timeoutMs: number): Promise<void> {
+  return invokeCommand<void>("set_hotkey_sequence_timeout", { timeoutMs });
+}
+
 /** 设置翻译目标语言。返回是否自动开启了 AI 润色。 */
 export function setTranslationTarget(target: string | null): Promise<boolean> {
   return invokeCommand<boolean>("set_translation_target", { target });
@@ -348,6 +772,15 @@ export function setCustomPrompt(prompt: string | null): Promise<void> {
   return invokeCommand<void>("set_custom_prompt", { prompt });
 }
 
+/** 设置输出模板，支持 {text} {time} {date} {duration}；null 恢复原文粘贴。 */
+export function setOutputTemplate(template: string | null): Promise<void> {
+  return invokeCommand<void>("set_output_template", { template });
+}
+
+export function setPostHook(hook: PostHookSettings | null): Promise<void> {
+  return invokeCommand<void>("set_post_hook", { hook });
+}
+
 export function setOpenaiFastMode(enabled: boolean): Promise<void> {
   return invokeCommand<void>("set_openai_fast_mode", { enabled });
 }
@@ -565,6 +998,27 @@ export function setOnlineAsrEndpoint(region: string): Promise<{ region: string;
   return invokeCommand<{ region: string; url: string }>("set_online_asr_endpoint", { region });
 }
 
+export type RemoteEndpointErrorKind = "invalid_url" | "network" | "timeout" | "auth" | "http" | "format";
+
+export interface RemoteEndpointTestReport {
+  success: boolean;
+  url: string;
+  latencyMs: number;
+  httpStatus: number | null;
+  errorKind: RemoteEndpointErrorKind | null;
+  error: string | null;
+  text: string | null;
+}
+
+/** 测试 OpenAI 兼容转写接口，不保存设置；url 可以是基础地址或完整的 /audio/transcriptions */
+export function testRemoteEndpoint(
+  url: string,
+  apiKey: string,
+  model?: string | null
+): Promise<RemoteEndpointTestReport> {
+  return invokeCommand<RemoteEndpointTestReport>("test_remote_endpoint", { url, apiKey, model: model ?? null });
+}
+
 export interface AlibabaAsrConfig {
   region: string;
   url: string;
@@ -606,4 +1060,66 @@ export function setModelsDir(path: string | null, migrate: boolean): Promise<Mod
   return invokeCommand<ModelsDirUpdateResult>("set_models_dir", { path, migrate });
 }
 
+export interface DataDirInfo {
+  path: string;
+  configured: string | null;
+  is_custom: boolean;
+  env_override: boolean;
+  /** 启动时数据目录创建失败的原因 */
+  startup_error: string | null;
+  /** 本次运行改用了系统临时目录 */
+  using_fallback: boolean;
+}
+
+export function getDataDir(): Promise<DataDirInfo> {
+  return invokeCommand<DataDirInfo>("get_data_dir");
+}
+
+export type ResolvedPathName =
+  | "dataDir"
+  | "bootstrapConfig"
+  | "engineConfig"
+  | "hfCache"
+  | "engineDir"
+  | "funasrServer"
+  | "whisperServer"
+  | "downloadScript"
+  | "logDir"
+  | "tempDir";
+
+export interface ResolvedPath {
+  name: ResolvedPathName;
+  /** 解析失败时为 null */
+  path: string | null;
+  exists: boolean;
+}
+
+export const getPaths = createNoArgCommand<ResolvedPath[]>("get_paths");
+
+export interface DataDirUpdateResult {
+  message: string;
+  restartRequired: boolean;
+  currentHasData: boolean;
+  targetHasData: boolean;
+}
+
+export function setDataDir(path: string | null): Promise<DataDirUpdateResult> {
+  return invokeCommand<DataDirUpdateResult>("set_data_dir", { path });
+}
+
 export { enableAutostart, disableAutostart, isAutostartEnabled };
+
+export interface AutostartStatus {
+  /** 系统里是否注册了自启动；查询失败时为 null */
+  osEnabled: boolean | null;
+  /** 用户最后一次在应用里的选择；从未设置过时为 null */
+  intended: boolean | null;
+  inSync: boolean;
+  error: string | null;
+}
+
+export const getAutostartStatus = createNoArgCommand<AutostartStatus>("get_autostart_status");
+
+export function setAutostart(enabled: boolean): Promise<AutostartStatus> {
+  return invokeCommand<AutostartStatus>("set_autostart", { enabled });
+}
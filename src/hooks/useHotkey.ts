@@ -1,6 +1,8 @@
 import { listen } from "@tauri-apps/api/event";
 import { useState, useEffect, useCallback, useRef } from "react";
 import {
+  disableDictationHotkey,
+  getDictationHotkeySetting,
   getHotkeyDiagnostic,
   registerCustomHotkey,
 } from "@/api/tauri";
@@ -14,6 +16,10 @@ interface UseHotkeyReturn {
   hotkeyDisplay: string;
   /** Update hotkey, persist it locally and re-register immediately. */
   setHotkey: (shortcut: string) => Promise<void>;
+  /** Whether the user turned the dictation hotkey off entirely. */
+  disabled: boolean;
+  /** Unregister the dictation hotkey and keep it off across restarts. */
+  disableHotkey: () => Promise<void>;
   /** Error message if registration failed. */
   error: string | null;
   /** Runtime diagnostic state emitted by the backend hotkey layer. */
@@ -40,6 +46,7 @@ export function useHotkey(): UseHotkeyReturn {
   const [error, setError] = useState<string | null>(null);
   const [hotkeyRaw, setHotkeyRaw] = useState<string>(() => readStoredHotkey());
   const [diagnostic, setDiagnostic] = useState<HotkeyDiagnostic | null>(null);
+  const [disabled, setDisabled] = useState(false);
   const hotkeyDisplay = formatHotkeyForDisplay(hotkeyRaw);
 
   const mountedRef = useRef(true);
@@ -52,6 +59,7 @@ export function useHotkey(): UseHotkeyReturn {
     await registerCustomHotkey(normalized);
     if (!mountedRef.current) return normalized;
     setError(null);
+    setDisabled(false);
     setHotkeyRaw(normalized);
     hotkeyRef.current = normalized;
     writeStoredHotkey(normalized);
@@ -61,7 +69,17 @@ export function useHotkey(): UseHotkeyReturn {
   const register = useCallback(async () => {
     try {
       setError(null);
-      await registerShortcut(hotkeyRef.current || DEFAULT_HOTKEY);
+      // The backend saves the hotkey in the profile and registers it at
+      // startup; older installs only have it in localStorage, so fall back.
+      const setting = await getDictationHotkeySetting();
+      if (!mountedRef.current) return;
+      if (setting.disabled) {
+        setDisabled(true);
+        return;
+      }
+      await registerShortcut(
+        setting.saved ? setting.shortcut : hotkeyRef.current || DEFAULT_HOTKEY
+      );
     } catch (err) {
       const message =
         err instanceof Error ? err.message : String(err);
@@ -84,6 +102,13 @@ export function useHotkey(): UseHotkeyReturn {
     }
   }, [registerShortcut]);
 
+  const disableHotkey = useCallback(async () => {
+    await disableDictationHotkey();
+    if (!mountedRef.current) return;
+    setError(null);
+    setDisabled(true);
+  }, []);
+
   // Register on mount. Do not unregister all hotkeys on cleanup because
   // translation/assistant hotkeys are owned by other settings flows.
   useEffect(() => {
@@ -128,6 +153,8 @@ export function useHotkey(): UseHotkeyReturn {
   return {
     hotkeyDisplay,
     setHotkey,
+    disabled,
+    disableHotkey,
     error,
     diagnostic,
   };
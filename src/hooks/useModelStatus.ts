@@ -293,6 +293,10 @@ export function useModelStatus(): UseModelStatusReturn {
               return;
             }
             enterErrorState(message ?? getEngineStartFallbackMessage());
+          } else if (status === "waiting_download") {
+            setStage("downloading");
+            setError(null);
+            setDownloadMessage(message ?? null);
           } else if (status === "crashed") {
             setStage("loading");
             setError(null);
@@ -346,14 +350,23 @@ export function useModelStatus(): UseModelStatusReturn {
                 setDownloadMessage(message ?? null);
                 break;
               }
+              case "retrying": {
+                // 后端在退避等待，刷新看门狗，保留已有进度
+                lastDownloadEventAtRef.current = Date.now();
+                startDownloadWatchdog();
+                setDownloadingState(true);
+                setStage("downloading");
+                setDownloadMessage(message ?? null);
+                break;
+              }
               case "completed": {
                 clearDownloadWatchdog();
                 autoDownloadRetryRef.current = 0;
                 setDownloadingState(false);
                 setDownloadProgress(100);
                 setDownloadMessage(message ?? null);
+                // 后端在下载完成后会自行启动引擎（见 models-ready），这里只切换展示状态
                 setStage("loading");
-                restartFunASR().catch(() => {});
                 break;
               }
               case "cancelled": {
@@ -191,6 +191,10 @@ const en: TranslationSchema = {
       unchanged: "raw unchanged",
     },
   },
+  injectionTest: {
+    title: "Injection test",
+    hint: "Testing text injection. Don't type or switch windows; this window closes automatically when done.",
+  },
   toast: {
     correctionRecorded: "Correction preference recorded",
     correctionFailed: "Failed to record correction",
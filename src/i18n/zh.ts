@@ -189,6 +189,10 @@ const zh = {
       unchanged: "raw 未变化",
     },
   },
+  injectionTest: {
+    title: "注入测试",
+    hint: "正在测试文本注入，请勿操作键盘或切换窗口，测试结束后窗口会自动关闭。",
+  },
   toast: {
     correctionRecorded: "已记录修改偏好",
     correctionFailed: "记录修改偏好失败",
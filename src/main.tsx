@@ -151,6 +151,18 @@ if (windowLabel === "subtitle") {
     .catch((error) => {
       console.error("Subtitle window load failed:", error);
     });
+} else if (windowLabel === "injection-test") {
+  import("./pages/InjectionTestOverlay")
+    .then(({ default: InjectionTestOverlay }) => {
+      ReactDOM.createRoot(document.getElementById("root")!).render(
+        <React.StrictMode>
+          <InjectionTestOverlay />
+        </React.StrictMode>
+      );
+    })
+    .catch((error) => {
+      console.error("Injection test window load failed:", error);
+    });
 } else if (windowLabel === "selection-toolbar") {
   import("./pages/SelectionOverlay")
     .then(({ default: SelectionOverlay }) => {
@@ -0,0 +1,55 @@
+import { useEffect, useRef, useState, type ChangeEvent } from "react";
+import { useTranslation } from "react-i18next";
+import { reportInjectionTestValue } from "@/api/tauri";
+import { useTheme } from "@/hooks/useTheme";
+import "@/i18n";
+import "../styles/theme.css";
+
+export default function InjectionTestOverlay() {
+  const { t } = useTranslation();
+  const [value, setValue] = useState("");
+  const inputRef = useRef<HTMLTextAreaElement>(null);
+  useTheme();
+
+  useEffect(() => {
+    inputRef.current?.focus();
+    // 空串表示窗口已挂载，后端收到后才开始注入
+    void reportInjectionTestValue("").catch((error) => {
+      console.error("Injection test ready report failed:", error);
+    });
+  }, []);
+
+  const handleChange = (event: ChangeEvent<HTMLTextAreaElement>) => {
+    const next = event.target.value;
+    setValue(next);
+    void reportInjectionTestValue(next).catch((error) => {
+      console.error("Injection test value report failed:", error);
+    });
+  };
+
+  return (
+    <div
+      style={{
+        height: "100%",
+        display: "flex",
+        flexDirection: "column",
+        gap: 8,
+        padding: 12,
+        boxSizing: "border-box",
+        color: "var(--color-text-primary)",
+        background: "var(--color-bg-primary)",
+      }}
+    >
+      <p style={{ margin: 0, fontSize: 12, color: "var(--color-text-secondary)" }}>
+        {t("injectionTest.hint")}
+      </p>
+      <textarea
+        ref={inputRef}
+        aria-label={t("injectionTest.title")}
+        value={value}
+        onChange={handleChange}
+        style={{ flex: 1, resize: "none", fontSize: 14 }}
+      />
+    </div>
+  );
+}
@@ -17,13 +17,16 @@ import {
   continueAssistantConversation,
   copyToClipboard,
   getRecordingSnapshot,
+  getUserProfile,
   hideSubtitleWindow,
   openAssistantSource,
   retryAssistantRequest,
+  startSubtitleDrag,
   type AssistantConversationTurn,
   type RecordingOutcomeKind,
   type RecordingSnapshot,
 } from "@/api/tauri";
+import type { SubtitleTextPayload } from "@/types";
 import { readLocalStorage } from "@/lib/storage";
 import { THEME_STORAGE_KEY, LANGUAGE_STORAGE_KEY } from "@/lib/constants";
 import { useSmoothText, segmentGraphemes } from "@/hooks/useSmoothText";
@@ -174,9 +177,15 @@ export default function SubtitleOverlay() {
   const [conversationBusy, setConversationBusy] = useState(false);
   const [conversationDraft, setConversationDraft] = useState("");
   const [conversationError, setConversationError] = useState<string | null>(null);
+  const [subtitleDisplay, setSubtitleDisplay] = useState<Pick<SubtitleTextPayload, "text" | "displayText"> | null>(null);
+  // 听写时优先显示后端截断后的文本；助手回答不截断。只在它对应当前文本时使用，
+  // 避免上一段的截断结果盖住新文本。
+  const shownText = mode !== "assistant" && subtitleDisplay && subtitleDisplay.text.trim() === text.trim()
+    ? subtitleDisplay.displayText
+    : text;
   // Smoothly drain the streaming source so chunks never snap in.
   // Works for both assistant streaming (polishing phase) and interim dictation.
-  const smoothText = useSmoothText(text);
+  const smoothText = useSmoothText(shownText);
   const latestSessionIdRef = useRef(0);
   const latestRevisionRef = useRef(-1);
   const pairedOutcomeRevisionRef = useRef<{ sessionId: number; revision: number } | null>(null);
@@ -184,6 +193,8 @@ export default function SubtitleOverlay() {
   const phaseRef = useRef<Phase>("idle");
   const fadeTimerRef = useRef<ReturnType<typeof setTimeout> | null>(null);
   const cleanupTimerRef = useRef<ReturnType<typeof setTimeout> | null>(null);
+  // 常驻字幕：最终结果保留到下一次录音，不淡出也不清空
+  const persistentRef = useRef(false);
   const assistantTextRef = useRef<HTMLDivElement | null>(null);
   const conversationListRef = useRef<HTMLDivElement | null>(null);
   const conversationInputRef = useRef<HTMLTextAreaElement | null>(null);
@@ -541,6 +552,37 @@ export default function SubtitleOverlay() {
     };
   }, [applyRecordingOutcome, applyRecordingState, clearFadeTimer, hydrateRecordingSnapshot]);
 
+  // 常驻字幕开关：挂载时读画像，之后跟随 subtitle-persistent 事件
+  useEffect(() => {
+    let disposed = false;
+    let unlisten: (() => void) | null = null;
+
+    void getUserProfile()
+      .then((profile) => {
+        if (!disposed) persistentRef.current = !!profile?.subtitle_persistent;
+      })
+      .catch(() => undefined);
+
+    void (async () => {
+      try {
+        unlisten = await listen<{ enabled: boolean }>("subtitle-persistent", (event) => {
+          persistentRef.current = !!event.payload.enabled;
+        });
+        if (disposed && unlisten) {
+          unlisten();
+          unlisten = null;
+        }
+      } catch {
+        // ignore
+      }
+    })();
+
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
+
   // 监听 AI 润色状态（含流式进度）
   useEffect(() => {
     let disposed = false;
@@ -877,7 +919,7 @@ export default function SubtitleOverlay() {
             return;
           }
 
-          if (event.payload.mode !== "assistant") {
+          if (event.payload.mode !== "assistant" && !persistentRef.current) {
             const expectedSessionId = latestSessionIdRef.current;
             fadeTimerRef.current = setTimeout(() => {
               if (latestSessionIdRef.current !== expectedSessionId) return;
@@ -919,7 +961,36 @@ export default function SubtitleOverlay() {
     };
   }, [clearFadeTimer, updatePhase]);
 
-  const isStreaming = text.length > 0 && smoothText.length < text.length;
+  // 监听字幕显示文本（超出最大宽度时只保留末尾）
+  useEffect(() => {
+    let disposed = false;
+    let unlisten: (() => void) | null = null;
+
+    void (async () => {
+      try {
+        unlisten = await listen<SubtitleTextPayload>("subtitle-text", (event) => {
+          const { sessionId, text: fullText, displayText } = event.payload;
+          if (sessionId === terminalSessionIdRef.current) return;
+          if (sessionId < latestSessionIdRef.current) return;
+          setSubtitleDisplay({ text: fullText, displayText });
+        });
+
+        if (disposed && unlisten) {
+          unlisten();
+          unlisten = null;
+        }
+      } catch {
+        // 忽略事件监听初始化失败
+      }
+    })();
+
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
+
+  const isStreaming = shownText.length > 0 && smoothText.length < shownText.length;
   const hasText = smoothText.length > 0;
   const rawFirstLabelKey = rawFirstStatus === "preview_only" && resultStage === "polished"
     ? "polished_preview"
@@ -1156,6 +1227,14 @@ export default function SubtitleOverlay() {
 
   const assistantOverlayDismissible = interactiveAssistantResult || conversationOpen;
 
+  // Only reaches the webview when the user enabled the interactive subtitle
+  // setting (otherwise the window is click-through); the backend no-ops otherwise.
+  const handleCapsuleMouseDown = (event: MouseEvent<HTMLDivElement>) => {
+    if (event.button !== 0 || assistantInteractive) return;
+    if ((event.target as HTMLElement).closest("button, a, input, textarea")) return;
+    void startSubtitleDrag().catch(() => undefined);
+  };
+
   const renderSources = (sources: AssistantSource[] | undefined) => {
     if (!sources?.length) return null;
     return (
@@ -1188,6 +1267,7 @@ export default function SubtitleOverlay() {
         }
         role="presentation"
         onClick={assistantInteractive ? (event) => event.stopPropagation() : undefined}
+        onMouseDown={handleCapsuleMouseDown}
       >
         {conversationOpen ? (
           <section
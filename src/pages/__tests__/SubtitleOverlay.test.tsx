@@ -55,6 +55,7 @@ const tauriApiMocks = vi.hoisted(() => ({
   cancelAssistantConversation: vi.fn(),
   continueAssistantConversation: vi.fn(),
   getRecordingSnapshot: vi.fn(),
+  getUserProfile: vi.fn(),
   openAssistantSource: vi.fn(),
   retryAssistantRequest: vi.fn(),
 }));
@@ -68,9 +69,11 @@ vi.mock("@/api/tauri", () => ({
   continueAssistantConversation: tauriApiMocks.continueAssistantConversation,
   copyToClipboard: vi.fn(async () => undefined),
   getRecordingSnapshot: tauriApiMocks.getRecordingSnapshot,
+  getUserProfile: tauriApiMocks.getUserProfile,
   hideSubtitleWindow: vi.fn(async () => undefined),
   openAssistantSource: tauriApiMocks.openAssistantSource,
   retryAssistantRequest: tauriApiMocks.retryAssistantRequest,
+  startSubtitleDrag: vi.fn(async () => undefined),
 }));
 
 vi.mock("react-i18next", () => {
@@ -106,6 +109,8 @@ beforeEach(() => {
   tauriEvents.reset();
   tauriApiMocks.getRecordingSnapshot.mockReset();
   tauriApiMocks.getRecordingSnapshot.mockResolvedValue(null);
+  tauriApiMocks.getUserProfile.mockReset();
+  tauriApiMocks.getUserProfile.mockResolvedValue({});
   tauriApiMocks.cancelAssistantConversation.mockReset();
   tauriApiMocks.cancelAssistantConversation.mockResolvedValue(false);
   tauriApiMocks.continueAssistantConversation.mockReset();
@@ -1393,4 +1398,85 @@ describe("SubtitleOverlay stale-flash cleanup", () => {
     expect(polishingSlot).toBe(waveformSlot);
     expect(polishingSlot?.querySelector(".subtitle-dot-polishing")).not.toBeNull();
   });
+
+  it("AG. keeps the final result on screen while the subtitle is persistent", async () => {
+    tauriApiMocks.getUserProfile.mockResolvedValue({ subtitle_persistent: true });
+    const { container } = render(<SubtitleOverlay />);
+    await flushAsyncListeners();
+
+    await act(async () => {
+      tauriEvents.emit("recording-state", {
+        sessionId: 109,
+        isRecording: true,
+        isProcessing: false,
+      });
+    });
+    await act(async () => {
+      tauriEvents.emit("transcription-result", {
+        sessionId: 109,
+        text: "stays visible",
+        interim: false,
+      });
+    });
+
+    await advance(5000);
+
+    expect(readSubtitleText(container)).toContain("stays visible");
+    const capsule = container.querySelector(".subtitle-capsule");
+    expect(capsule?.classList.contains("subtitle-fade-out")).toBe(false);
+
+    // 关闭常驻后，下一条结果恢复正常淡出
+    await act(async () => {
+      tauriEvents.emit("subtitle-persistent", { enabled: false });
+      tauriEvents.emit("transcription-result", {
+        sessionId: 109,
+        text: "fades again",
+        interim: false,
+      });
+    });
+    await advance(5000);
+
+    expect(readSubtitleText(container)).not.toContain("fades again");
+  });
+
+  it("AH. shows the backend-truncated subtitle text while keeping newer text intact", async () => {
+    const { container } = render(<SubtitleOverlay />);
+    await flushAsyncListeners();
+
+    await act(async () => {
+      tauriEvents.emit("recording-state", {
+        sessionId: 110,
+        isRecording: true,
+        isProcessing: false,
+      });
+    });
+    await act(async () => {
+      tauriEvents.emit("transcription-result", {
+        sessionId: 110,
+        text: "a very long interim sentence",
+        interim: true,
+      });
+      tauriEvents.emit("subtitle-text", {
+        sessionId: 110,
+        text: "a very long interim sentence",
+        displayText: "…interim sentence",
+        truncated: true,
+        interim: true,
+      });
+    });
+
+    expect(readSubtitleText(container)).toContain("…interim sentence");
+    expect(readSubtitleText(container)).not.toContain("a very long");
+
+    // 新文本到达而截断结果还没跟上时，显示完整新文本而不是旧的截断结果
+    await act(async () => {
+      tauriEvents.emit("transcription-result", {
+        sessionId: 110,
+        text: "a very long interim sentence grows",
+        interim: true,
+      });
+    });
+
+    expect(readSubtitleText(container)).toContain("a very long interim sentence grows");
+  });
 });
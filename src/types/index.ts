@@ -4,12 +4,23 @@ export interface TranscriptionResult {
   duration?: number;
   success: boolean;
   error?: string;
+  /** 与 SubtitleCue 同构，可直接传给字幕导出 */
+  segments?: SubtitleCue[];
 }
 
 export type RecordingMode = "dictation" | "assistant";
 export type EditGrabStatus = "ok" | "timeout" | "empty" | "unsupported";
 export type TranscriptionResultStage = "raw" | "polished";
 
+// transcription-progress 事件：长转写时 Python 服务器输出的中间进度
+export interface TranscriptionProgressPayload {
+  requestId: number;
+  current: number;
+  total: number;
+  fraction: number;
+  message?: string | null;
+}
+
 export interface TranscriptionTiming {
   asrMs?: number;
   polishMs?: number;
@@ -33,12 +44,166 @@ export interface FunASRStatus {
   device?: string;
   gpu_name?: string;
   gpu_memory_total?: number;
+  cpu_threads?: number | null;
+  avg_rtf?: number | null;
+  itn?: boolean | null;
+  punctuation?: boolean | null;
   message: string;
   engine?: string;
   models_present?: boolean;
   missing_models?: string[];
 }
 
+// 与 Rust 后端 funasr_service::ServerEnvironmentReport 对应
+export interface ServerEnvVar {
+  name: string;
+  value?: string | null;
+  source: "app" | "removed" | "inherited";
+}
+
+/** 最近一次引擎启动耗时；cold 表示本次运行中的第一次启动 */
+export interface EngineStartupTiming {
+  engine: string;
+  totalMs: number;
+  modelLoadMs: number;
+  cold: boolean;
+  finishedAtMs: number;
+}
+
+/** 引擎 stderr 日志末尾；truncated 表示文件比读取上限更长，只取了末尾 */
+export interface ServerStderrTail {
+  path: string;
+  exists: boolean;
+  lines: string[];
+  truncated: boolean;
+}
+
+/** 服务端协议版本诊断；compatible 为 null 表示两个来源都拿不到版本 */
+export interface ServerVersionInfo {
+  expectedVersion: string;
+  reportedVersion?: string | null;
+  scriptVersion?: string | null;
+  scriptPath?: string | null;
+  engineRunning: boolean;
+  inlineAudioTransport?: boolean | null;
+  compatible?: boolean | null;
+}
+
+/** "audio-waveform" 事件：每个点是归一化到 -1..1 的 [min, max] */
+export interface AudioWaveformPayload {
+  sessionId: number;
+  pointsPerSec: number;
+  peaks: [number, number][];
+}
+
+export interface EngineComparisonEntry {
+  engine: string;
+  text?: string | null;
+  latencyMs?: number | null;
+  startupMs?: number | null;
+  missingModels: string[];
+  error?: string | null;
+}
+
+/** 两个本地引擎对同一段音频的转写结果；synthetic 样本只适合比较延迟 */
+export interface EngineComparison {
+  source: "file" | "synthetic";
+  results: EngineComparisonEntry[];
+  restoredEngine: string;
+  restoreError?: string | null;
+}
+
+export interface ModelVramEstimate {
+  engine: string;
+  model: string;
+  device: "cuda" | "cpu";
+  computeType: string;
+  vramGb: number;
+}
+
+/** 显存预算核对结果；warning 非空表示当前模型超出预算会被降级 */
+export interface VramBudgetReport {
+  engine: string;
+  maxVramGb?: number | null;
+  gpuMemoryTotalGb?: number | null;
+  preferred?: ModelVramEstimate | null;
+  selected?: ModelVramEstimate | null;
+  warning?: string | null;
+}
+
+export interface ServerEnvironmentReport {
+  engine: string;
+  runtime: "bundled" | "bundled_archive" | "development" | "unavailable";
+  executablePath?: string | null;
+  scriptPath?: string | null;
+  scriptExists: boolean;
+  args: string[];
+  env: ServerEnvVar[];
+  runtimeError?: string | null;
+}
+
+export interface EngineFallbackPayload {
+  from: string;
+  to: string;
+  missingModels: string[];
+}
+
+export interface ResourceScriptCheck {
+  name: string;
+  path: string;
+  readable: boolean;
+  error?: string | null;
+}
+
+/** "startup-error" 事件：启动阶段的致命或降级问题 */
+export type StartupErrorPayload =
+  | {
+      kind: "data_dir";
+      message: string;
+      error: string;
+      requestedPath: string;
+      fallbackPath?: string | null;
+    }
+  | {
+      kind: "resource_scripts";
+      message: string;
+      scripts: ResourceScriptCheck[];
+    };
+
+/** "subtitle-text" 事件：字幕窗口显示 displayText，text 是完整文本 */
+export interface SubtitleTextPayload {
+  sessionId: number;
+  text: string;
+  displayText: string;
+  truncated: boolean;
+  interim: boolean;
+}
+
+/** "second-instance" 事件：又启动了一个进程，已由当前实例接管 */
+export interface SecondInstancePayload {
+  fromAutostart: boolean;
+}
+
+export interface ResourceUsage {
+  appPid: number;
+  appRssBytes?: number | null;
+  engineRunning: boolean;
+  enginePid?: number | null;
+  engineRssBytes?: number | null;
+}
+
+export interface TempAudioUsage {
+  directories: string[];
+  fileCount: number;
+  totalBytes: number;
+}
+
+export interface TempAudioCleanupReport {
+  removedCount: number;
+  removedBytes: number;
+  failedCount: number;
+}
+
 // 与 Rust 后端 funasr_service::ModelCheckResult 对应
 export interface ModelCheckResult {
   all_present: boolean;
@@ -50,6 +215,16 @@ export interface ModelCheckResult {
   missing_models: string[];
 }
 
+/** 下载前的磁盘空间检查；availableBytes 为 null 时无法查询，按足够处理 */
+export interface DownloadSpaceReport {
+  engine: string;
+  cachePath: string;
+  requiredBytes: number;
+  availableBytes: number | null;
+  sufficient: boolean;
+  missingModels: string[];
+}
+
 // 转录历史记录
 export interface HistoryItem {
   id: string;
@@ -72,6 +247,182 @@ export interface InputDeviceListPayload {
   selectedDeviceName?: string | null;
 }
 
+/** "recording-device"：录音开始时实际使用的麦克风 */
+export interface RecordingDevicePayload {
+  sessionId: number;
+  deviceName: string;
+  requestedDeviceName: string | null;
+  /** 指定设备不可用，已回退到默认设备 */
+  fallback: boolean;
+}
+
+/** "audio-level"：录音中每 50ms 一次的输入电平，均归一化到 0..1 */
+export interface AudioLevelPayload {
+  sessionId: number;
+  rms: number;
+  peak: number;
+}
+
+/** "audio-device-lost"：录音中麦克风断开 */
+export interface AudioDeviceLostPayload {
+  sessionId: number;
+  deviceName: string;
+}
+
+export interface AudioSubsystemResetPayload {
+  stoppedSessionId?: number | null;
+  monitorStopped: boolean;
+  clearedDeviceName?: string | null;
+  hosts: string[];
+  devices: InputDeviceInfo[];
+}
+
+export type SubtitleExportFormat = "srt" | "vtt";
+
+/** 字幕窗口连续创建失败后发出，之后本次运行不再尝试显示字幕 */
+export interface SubtitleDisabledPayload {
+  failures: number;
+  error: string;
+}
+
+export interface SubtitleCue {
+  startSec: number;
+  endSec: number;
+  text: string;
+  /** 说话人分离标签，导出时加 [Speaker N] 前缀 */
+  speaker?: string | null;
+}
+
+export interface InjectionTestResult {
+  method: InputMethod;
+  expected: string;
+  received: string;
+  matched: boolean;
+  elapsedMs: number;
+}
+
+// 与 Rust 后端 foreground::InjectTargetCheck 对应
+export interface InjectTargetCheck {
+  injectable: boolean;
+  reason?: string | null;
+  processName?: string | null;
+  targetIntegrity?: string | null;
+  ownIntegrity?: string | null;
+}
+
+export interface InputConfigRangeInfo {
+  sampleFormat: string;
+  channels: number;
+  minSampleRate: number;
+  maxSampleRate: number;
+}
+
+export interface ChosenInputConfigInfo {
+  rangeIndex: number;
+  sampleFormat: string;
+  channels: number;
+  sampleRate: number;
+  needsResample: boolean;
+  tier: "devicePreference" | "native16k" | "maxRate" | "firstRange";
+  reason: string;
+}
+
+export interface DeviceCapabilitiesPayload {
+  deviceName: string;
+  isDefault: boolean;
+  requestedDeviceName?: string | null;
+  configs: InputConfigRangeInfo[];
+  chosen?: ChosenInputConfigInfo | null;
+}
+
+export interface MicrophoneDefaultConfig {
+  sampleFormat: string;
+  channels: number;
+  sampleRate: number;
+}
+
+export interface MicrophoneInfoPayload {
+  deviceName: string;
+  isDefault: boolean;
+  sampleRates: number[];
+  channelCounts: number[];
+  defaultConfig?: MicrophoneDefaultConfig | null;
+  supports16kMono: boolean;
+  captureSampleRate?: number | null;
+  needsResample: boolean;
+}
+
+export interface AudioHostListPayload {
+  hosts: string[];
+  defaultHost: string;
+  /** null 表示使用默认后端 */
+  selectedHost: string | null;
+}
+
+/** prewarm_audio 的结果：预热时协商出的配置，第一次录音会复用 */
+export interface AudioPrewarmReport {
+  deviceName: string;
+  sampleFormat: string;
+  channels: number;
+  sampleRate: number;
+  elapsedMs: number;
+}
+
+export interface AudioPipelineBenchmark {
+  durationSecs: number;
+  sourceSampleRate: number;
+  targetSampleRate: number;
+  iterations: number;
+  resampleMsAvg: number;
+  encodeMsAvg: number;
+  totalMsAvg: number;
+  samplesPerSec: number | null;
+  realTimeFactor: number;
+}
+
+export interface NoiseFloorReport {
+  deviceName: string;
+  durationSecs: number;
+  sampleRate: number;
+  rmsDbfs: number;
+  peakDbfs: number;
+  noiseFloorDbfs: number;
+  recommendedThresholdDbfs: number;
+  recommendedThresholdAmplitude: number;
+}
+
+export type InputMethod = "sendInput" | "clipboard" | "copyOnly";
+
+export interface PasteMethodUsedPayload {
+  method: InputMethod;
+  fallback: boolean;
+  failedMethods: InputMethod[];
+}
+
+/**
+ * "paste-review" 事件：review_before_paste 打开时听写结果先挂起。
+ * pending 带文本和有效期；expired / replaced 表示未注入就被丢弃；
+ * failed 表示确认后粘贴失败，结果仍在等待，可以再次确认。
+ */
+export interface PasteReviewPayload {
+  status: "pending" | "confirmed" | "discarded" | "expired" | "replaced" | "failed";
+  id: number;
+  text?: string;
+  expiresInMs?: number;
+  error?: string;
+}
+
+export interface LanguageInputMethods {
+  cjk?: InputMethod | null;
+  latin?: InputMethod | null;
+}
+
+/** 配置文件里的输入设置（importConfig 返回实际生效的值）。 */
+export interface ConfigInputSettings {
+  inputDevice?: string;
+  inputMethod?: InputMethod;
+}
+
 export interface AppUpdateInfo {
   available: boolean;
   currentVersion: string;
@@ -81,6 +432,35 @@ export interface AppUpdateInfo {
   releaseUrl?: string | null;
 }
 
+export interface DictationHotkeySetting {
+  shortcut: string;
+  saved: boolean;
+  disabled: boolean;
+}
+
+export type HotkeyAction =
+  | "dictation"
+  | "translation"
+  | "assistant"
+  | "languageCycle"
+  | "toggleWindow"
+  | "pause"
+  | "cancel"
+  | "cycleEngine"
+  | "flushPaste";
+
+/** shortcut 为 null 表示该动作未绑定 */
+export interface HotkeyBinding {
+  action: HotkeyAction;
+  shortcut?: string | null;
+}
+
+export interface HotkeyValidation {
+  normalized: string;
+  kind: "standard" | "modifierOnly" | "sequence";
+  warning?: string | null;
+}
+
 export interface HotkeyDiagnostic {
   shortcut: string;
   registered: boolean;
@@ -228,12 +608,125 @@ export interface UserProfile {
   last_correction_validation?: number;
   history_settings?: HistorySettings;
   app_profile_rules?: AppProfileRule[];
+  recording_merge_window_ms?: number;
+  interim_cost_settings?: InterimCostSettings;
+  show_python_console?: boolean;
+  download_retry_settings?: DownloadRetrySettings;
+  loudness_settings?: LoudnessSettings;
+  leading_silence_ms?: number | null;
+  output_cleanup?: OutputCleanupSettings;
+  recording_buffer_prealloc_secs?: number | null;
+  transcription_mode?: TranscriptionMode;
+  review_before_paste?: boolean;
+  subtitle_text_variant?: TextVariant;
+  paste_text_variant?: TextVariant;
+  strip_prefix?: string[];
+  strip_prefix_fuzzy?: boolean;
+  cpu_threads?: number | null;
+  waveform_stream_enabled?: boolean;
+  audio_prewarm_enabled?: boolean;
+  audio_host?: string | null;
+  device_sample_rates?: Record<string, number>;
+  preferred_sample_format?: InputSampleFormat | null;
+  server_crash_grace_ms?: number | null;
+  first_char_delay_ms?: number | null;
+  clipboard_write_retries?: number | null;
+  max_vram_gb?: number | null;
+  subtitle_display?: SubtitleDisplay;
+  autostart_enabled?: boolean | null;
+  subtitle_persistent?: boolean;
+  subtitle_keep_on_top_disabled?: boolean;
+  min_subtitle_visible_ms?: number | null;
+  subtitle_max_chars?: number | null;
+  subtitle_interactive?: boolean;
+  subtitle_position?: { x: number; y: number } | null;
+  output_template?: string | null;
+  post_hook?: PostHookSettings | null;
+  pending_paste_limit?: number | null;
+  min_audio_duration_ms?: number | null;
+  save_recordings?: boolean;
+  input_method_fallback?: InputMethod[] | null;
+  dictation_hotkey?: string | null;
+  dictation_hotkey_disabled?: boolean;
+  asr_formatting?: AsrFormattingSettings;
+  auto_fallback_engine?: boolean;
+  diarization_enabled?: boolean;
+  /** 本地引擎的识别语言；null 为自动检测 */
+  language_hint?: string | null;
+  /** 语言切换热键依次轮换的语言代码，"auto" 表示自动检测 */
+  language_cycle?: string[];
+  language_cycle_hotkey?: string | null;
+  toggle_window_hotkey?: string | null;
+  pause_hotkey?: string | null;
+  cancel_hotkey?: string | null;
+  cycle_engine_hotkey?: string | null;
+  flush_paste_hotkey?: string | null;
+  hotkey_sequence_timeout_ms?: number | null;
+  language_input_methods?: LanguageInputMethods;
+}
+
+/** "language-changed" 事件；language 为 null 表示切回自动检测 */
+export interface LanguageChangedPayload {
+  language: string | null;
+  name: string;
+  message: string;
+}
+
+export type SubtitleDisplay = "cursor" | "primary" | "main_window";
+
+export interface AsrFormattingSettings {
+  itn: boolean;
+  punctuation: boolean;
+}
+
+/** 听写完成后执行的外部程序；不经过 shell，但会拿到完整转写文本 */
+export interface PostHookSettings {
+  program: string;
+  args?: string[];
+  input?: "stdin" | "argument";
+  replace_output?: boolean;
+  timeout_ms?: number;
+}
+
+export interface LoudnessSettings {
+  enabled: boolean;
+  target_lufs: number;
+}
+
+export type OutputCase = "preserve" | "lowercase" | "uppercase";
+
+/** batch：整段重新识别，准确率高；streaming：更早出字，尾音和标点可能缺失 */
+export type TranscriptionMode = "batch" | "streaming";
+
+/** 采集首选采样格式；设备不提供时按默认 i16 > f32 > u16 */
+export type InputSampleFormat = "i16" | "f32" | "u16";
+
+/** formatted：AI 润色后的文本；raw：识别原文，不等润色 */
+export type TextVariant = "formatted" | "raw";
+
+export interface OutputCleanupSettings {
+  collapse_spaces: boolean;
+  case: OutputCase;
 }
 
+export interface DownloadRetrySettings {
+  max_attempts: number;
+  stall_timeout_secs: number;
+}
+
+export interface InterimCostSettings {
+  heavy_cost_ms: number;
+  light_cost_ms: number;
+  auto_calibrate: boolean;
+}
+
+export type HistoryRecordingFormat = "wav" | "opus";
+
 export interface HistorySettings {
   enabled: boolean;
   save_audio: boolean;
   retention_days: number;
+  recording_format?: HistoryRecordingFormat;
 }
 
 export type AppRuleOverride = "inherit" | "enabled" | "disabled";
@@ -316,6 +809,29 @@ export interface PersistentHistoryStats {
   totalLatency: LatencyStats;
 }
 
+export interface SavedRecording {
+  fileName: string;
+  durationSec: number | null;
+  textPreview: string;
+}
+
+export interface UsageSummary {
+  transcriptions: number;
+  audioSecs: number;
+  chars: number;
+  averageRtf: number | null;
+}
+
+export interface EngineUsageSummary extends UsageSummary {
+  engine: string;
+}
+
+export interface UsageStatsReport {
+  sinceMs: number;
+  totals: UsageSummary;
+  engines: EngineUsageSummary[];
+}
+
 export interface SelectionAssistantConfig {
   enabled: boolean;
   auto_screenshot?: boolean;